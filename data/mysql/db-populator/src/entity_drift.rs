@@ -0,0 +1,598 @@
+//! Entity drift check
+//!
+//! Compares the tables and views of a database built from the SQL scripts with
+//! the SeaORM entities in `web/api/entities`. A column renamed in the DDL but
+//! not in its entity, or a table nobody wrote an entity for, fails here
+//! instead of at query time in the API.
+
+use anyhow::{Context, Result};
+use entities::tanahpedia;
+use sea_orm::sea_query::ColumnType;
+use sea_orm::{ColumnTrait, EntityTrait, IdenStatic, Iterable};
+use sqlx_core::query::query;
+use sqlx_core::row::Row;
+use sqlx_mysql::{MySql, MySqlConnection};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// A column as MySQL reports it in `information_schema`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DbColumn {
+    pub(crate) table: String,
+    pub(crate) is_view: bool,
+    pub(crate) column: String,
+    /// `DATA_TYPE`, e.g. `varchar`.
+    pub(crate) data_type: String,
+    /// `COLUMN_TYPE`, e.g. `varchar(255)` or `enum('A','B')`.
+    pub(crate) column_type: String,
+    pub(crate) nullable: bool,
+}
+
+/// The columns an entity declares for its table.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EntityTable {
+    pub(crate) table: String,
+    pub(crate) columns: Vec<EntityColumn>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EntityColumn {
+    pub(crate) name: String,
+    pub(crate) column_type: ColumnType,
+    pub(crate) nullable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Drift {
+    MissingEntity {
+        table: String,
+    },
+    MissingTable {
+        table: String,
+    },
+    MissingField {
+        table: String,
+        column: String,
+        column_type: String,
+    },
+    MissingColumn {
+        table: String,
+        column: String,
+    },
+    TypeMismatch {
+        table: String,
+        column: String,
+        column_type: String,
+        entity_type: String,
+    },
+    Nullability {
+        table: String,
+        column: String,
+        column_nullable: bool,
+    },
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Drift::MissingEntity { table } => write!(f, "{}: table has no entity", table),
+            Drift::MissingTable { table } => {
+                write!(f, "{}: entity has no table or view", table)
+            }
+            Drift::MissingField {
+                table,
+                column,
+                column_type,
+            } => write!(
+                f,
+                "{}.{}: column ({}) has no entity field",
+                table, column, column_type
+            ),
+            Drift::MissingColumn { table, column } => {
+                write!(f, "{}.{}: entity field has no column", table, column)
+            }
+            Drift::TypeMismatch {
+                table,
+                column,
+                column_type,
+                entity_type,
+            } => write!(
+                f,
+                "{}.{}: column is {} but entity field is {}",
+                table, column, column_type, entity_type
+            ),
+            Drift::Nullability {
+                table,
+                column,
+                column_nullable: true,
+            } => write!(
+                f,
+                "{}.{}: column is NULL but entity field is not an Option",
+                table, column
+            ),
+            Drift::Nullability {
+                table,
+                column,
+                column_nullable: false,
+            } => write!(
+                f,
+                "{}.{}: column is NOT NULL but entity field is an Option",
+                table, column
+            ),
+        }
+    }
+}
+
+/// Introspects the connected database and diffs it against every entity.
+pub(crate) async fn check(conn: &mut MySqlConnection) -> Result<Vec<Drift>> {
+    let db_columns = load_db_columns(conn).await?;
+    Ok(diff(&db_columns, &entity_tables()))
+}
+
+/// One line per drift, grouped by table.
+pub(crate) fn report(drift: &[Drift]) -> String {
+    drift
+        .iter()
+        .map(|item| format!("  - {}", item))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+async fn load_db_columns(conn: &mut MySqlConnection) -> Result<Vec<DbColumn>> {
+    let rows = query::<MySql>(
+        "SELECT c.TABLE_NAME AS table_name, t.TABLE_TYPE AS table_type, \
+                c.COLUMN_NAME AS column_name, c.DATA_TYPE AS data_type, \
+                c.COLUMN_TYPE AS column_type, c.IS_NULLABLE AS is_nullable \
+         FROM information_schema.COLUMNS c \
+         INNER JOIN information_schema.TABLES t \
+            ON t.TABLE_SCHEMA = c.TABLE_SCHEMA AND t.TABLE_NAME = c.TABLE_NAME \
+         WHERE c.TABLE_SCHEMA = DATABASE() \
+         ORDER BY c.TABLE_NAME, c.ORDINAL_POSITION",
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .context("Failed to introspect information_schema columns")?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(DbColumn {
+                table: row.try_get("table_name")?,
+                is_view: row.try_get::<String, _>("table_type")? == "VIEW",
+                column: row.try_get("column_name")?,
+                data_type: row.try_get("data_type")?,
+                column_type: row.try_get("column_type")?,
+                nullable: row.try_get::<String, _>("is_nullable")? == "YES",
+            })
+        })
+        .collect()
+}
+
+fn entity_table<E: EntityTrait>() -> EntityTable {
+    EntityTable {
+        table: E::default().table_name().to_string(),
+        columns: E::Column::iter()
+            .map(|column| {
+                let def = column.def();
+                EntityColumn {
+                    name: column.as_str().to_string(),
+                    column_type: def.get_column_type().clone(),
+                    nullable: def.is_null(),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// db-populator bookkeeping the API never reads.
+const UNMAPPED_TABLES: &[&str] = &["schema_migrations"];
+
+/// Every entity of the `entities` crate. A table whose entity is missing here
+/// is reported as having none, so new entities must be added to this list.
+pub(crate) fn entity_tables() -> Vec<EntityTable> {
+    vec![
+        entity_table::<entities::additional::Entity>(),
+        entity_table::<entities::article::Entity>(),
+        entity_table::<entities::article_dedication::Entity>(),
+        entity_table::<entities::author::Entity>(),
+        entity_table::<entities::dedication::Entity>(),
+        entity_table::<entities::dedication_dedication_type::Entity>(),
+        entity_table::<entities::dedication_type::Entity>(),
+        entity_table::<entities::helek::Entity>(),
+        entity_table::<entities::perek::Entity>(),
+        entity_table::<entities::perek_date::Entity>(),
+        entity_table::<entities::perek_dedication::Entity>(),
+        entity_table::<entities::perushim::note::Entity>(),
+        entity_table::<entities::perushim::parshan::Entity>(),
+        entity_table::<entities::perushim::perush::Entity>(),
+        entity_table::<entities::sefer::Entity>(),
+        entity_table::<entities::system_message::Entity>(),
+        entity_table::<entities::tanah_perek::Entity>(),
+        entity_table::<tanahpedia::alt_group::Entity>(),
+        entity_table::<tanahpedia::animal::Entity>(),
+        entity_table::<tanahpedia::animal_kind::Entity>(),
+        entity_table::<tanahpedia::animal_purity::Entity>(),
+        entity_table::<tanahpedia::astronomical_object::Entity>(),
+        entity_table::<tanahpedia::astronomical_object_creation_day::Entity>(),
+        entity_table::<tanahpedia::category_homepage::Entity>(),
+        entity_table::<tanahpedia::entity::Entity>(),
+        entity_table::<tanahpedia::entity_tanah_source::Entity>(),
+        entity_table::<tanahpedia::entry::Entity>(),
+        entity_table::<tanahpedia::entry_entity::Entity>(),
+        entity_table::<tanahpedia::entry_link::Entity>(),
+        entity_table::<tanahpedia::entry_revision::Entity>(),
+        entity_table::<tanahpedia::entry_synonym::Entity>(),
+        entity_table::<tanahpedia::entry_synonym_disambiguation::Entity>(),
+        entity_table::<tanahpedia::event::Entity>(),
+        entity_table::<tanahpedia::event_date_range::Entity>(),
+        entity_table::<tanahpedia::event_place::Entity>(),
+        entity_table::<tanahpedia::god::Entity>(),
+        entity_table::<tanahpedia::king_reign::Entity>(),
+        entity_table::<tanahpedia::lookup_name_type::Entity>(),
+        entity_table::<tanahpedia::lookup_parent_child_type::Entity>(),
+        entity_table::<tanahpedia::lookup_parent_role::Entity>(),
+        entity_table::<tanahpedia::lookup_person_nation_role::Entity>(),
+        entity_table::<tanahpedia::lookup_union_end_reason::Entity>(),
+        entity_table::<tanahpedia::lookup_union_type::Entity>(),
+        entity_table::<tanahpedia::nation::Entity>(),
+        entity_table::<tanahpedia::nation_source_nation::Entity>(),
+        entity_table::<tanahpedia::nation_territory::Entity>(),
+        entity_table::<tanahpedia::non_tanah_source::Entity>(),
+        entity_table::<tanahpedia::object::Entity>(),
+        entity_table::<tanahpedia::person::Entity>(),
+        entity_table::<tanahpedia::person_birth_date::Entity>(),
+        entity_table::<tanahpedia::person_birth_place::Entity>(),
+        entity_table::<tanahpedia::person_death_cause::Entity>(),
+        entity_table::<tanahpedia::person_death_date::Entity>(),
+        entity_table::<tanahpedia::person_name::Entity>(),
+        entity_table::<tanahpedia::person_name_giver_god::Entity>(),
+        entity_table::<tanahpedia::person_name_giver_person::Entity>(),
+        entity_table::<tanahpedia::person_nation::Entity>(),
+        entity_table::<tanahpedia::person_parent_child::Entity>(),
+        entity_table::<tanahpedia::person_role_king::Entity>(),
+        entity_table::<tanahpedia::person_role_prophet::Entity>(),
+        entity_table::<tanahpedia::person_sex::Entity>(),
+        entity_table::<tanahpedia::person_union::Entity>(),
+        entity_table::<tanahpedia::place::Entity>(),
+        entity_table::<tanahpedia::place_identification::Entity>(),
+        entity_table::<tanahpedia::plant::Entity>(),
+        entity_table::<tanahpedia::plant_creation_day::Entity>(),
+        entity_table::<tanahpedia::prophecy::Entity>(),
+        entity_table::<tanahpedia::prophecy_is_good::Entity>(),
+        entity_table::<tanahpedia::prophecy_prophet::Entity>(),
+        entity_table::<tanahpedia::prophecy_recipient_nation::Entity>(),
+        entity_table::<tanahpedia::prophecy_recipient_person::Entity>(),
+        entity_table::<tanahpedia::saying::Entity>(),
+        entity_table::<tanahpedia::saying_audience_nation::Entity>(),
+        entity_table::<tanahpedia::saying_audience_person::Entity>(),
+        entity_table::<tanahpedia::saying_location::Entity>(),
+        entity_table::<tanahpedia::saying_speaker_god::Entity>(),
+        entity_table::<tanahpedia::saying_speaker_nation::Entity>(),
+        entity_table::<tanahpedia::saying_speaker_person::Entity>(),
+        entity_table::<tanahpedia::sefer_tanah_match::Entity>(),
+        entity_table::<tanahpedia::source_group::Entity>(),
+        entity_table::<tanahpedia::tanah_sefer::Entity>(),
+        entity_table::<tanahpedia::tanah_source::Entity>(),
+        entity_table::<tanahpedia::tanahpedia_sefer::Entity>(),
+        entity_table::<tanahpedia::temple_tool::Entity>(),
+        entity_table::<tanahpedia::three_d_model::Entity>(),
+        entity_table::<tanahpedia::war::Entity>(),
+        entity_table::<tanahpedia::war_side::Entity>(),
+        entity_table::<tanahpedia::war_side_participant_nation::Entity>(),
+        entity_table::<tanahpedia::war_side_participant_person::Entity>(),
+    ]
+}
+
+/// Diffs introspected columns against entity definitions, table by table in
+/// name order.
+pub(crate) fn diff(db_columns: &[DbColumn], entity_tables: &[EntityTable]) -> Vec<Drift> {
+    let mut db_tables = BTreeMap::<&str, Vec<&DbColumn>>::new();
+    for column in db_columns {
+        db_tables.entry(&column.table).or_default().push(column);
+    }
+    let entities_by_table = entity_tables
+        .iter()
+        .map(|entity| (entity.table.as_str(), entity))
+        .collect::<BTreeMap<_, _>>();
+    let table_names = db_tables
+        .keys()
+        .chain(entities_by_table.keys())
+        .copied()
+        .collect::<BTreeSet<_>>();
+
+    let mut drift = Vec::new();
+    for table in table_names {
+        match (db_tables.get(table), entities_by_table.get(table)) {
+            (Some(columns), Some(entity)) => diff_table(table, columns, entity, &mut drift),
+            (Some(columns), None) => {
+                // Views are read models; only the ones the API reads need an entity.
+                if !columns[0].is_view && !UNMAPPED_TABLES.contains(&table) {
+                    drift.push(Drift::MissingEntity {
+                        table: table.to_string(),
+                    });
+                }
+            }
+            (None, _) => drift.push(Drift::MissingTable {
+                table: table.to_string(),
+            }),
+        }
+    }
+    drift
+}
+
+fn diff_table(table: &str, columns: &[&DbColumn], entity: &EntityTable, drift: &mut Vec<Drift>) {
+    for column in columns {
+        let Some(field) = entity
+            .columns
+            .iter()
+            .find(|field| field.name == column.column)
+        else {
+            drift.push(Drift::MissingField {
+                table: table.to_string(),
+                column: column.column.clone(),
+                column_type: column.column_type.clone(),
+            });
+            continue;
+        };
+        if !type_matches(column, &field.column_type) {
+            drift.push(Drift::TypeMismatch {
+                table: table.to_string(),
+                column: column.column.clone(),
+                column_type: column.column_type.clone(),
+                entity_type: format!("{:?}", field.column_type),
+            });
+        }
+        // MySQL infers view nullability from the expression, so an entity may
+        // safely be more lenient than a view says.
+        if column.nullable != field.nullable && !(column.is_view && field.nullable) {
+            drift.push(Drift::Nullability {
+                table: table.to_string(),
+                column: column.column.clone(),
+                column_nullable: column.nullable,
+            });
+        }
+    }
+    for field in &entity.columns {
+        if !columns.iter().any(|column| column.column == field.name) {
+            drift.push(Drift::MissingColumn {
+                table: table.to_string(),
+                column: field.name.clone(),
+            });
+        }
+    }
+}
+
+/// Whether the entity type can hold every value the column can. Integer fields
+/// may be wider than their column; view integers are only checked for being
+/// integers because MySQL widens arithmetic in a view to `bigint`.
+fn type_matches(column: &DbColumn, entity_type: &ColumnType) -> bool {
+    use ColumnType as T;
+
+    if column.is_view && integer_width(&column.data_type).is_some() {
+        return entity_integer_width(entity_type).is_some();
+    }
+    match (column.data_type.as_str(), entity_type) {
+        ("tinyint", T::Boolean) => column.column_type == "tinyint(1)",
+        (
+            "char" | "varchar" | "tinytext" | "text" | "mediumtext" | "longtext" | "enum",
+            T::String(_) | T::Char(_) | T::Text,
+        ) => true,
+        ("enum", T::Enum { variants, .. }) => {
+            enum_values(&column.column_type)
+                == variants
+                    .iter()
+                    .map(|variant| variant.to_string())
+                    .collect::<BTreeSet<_>>()
+        }
+        ("date", T::Date)
+        | ("time", T::Time)
+        | ("datetime" | "timestamp", T::DateTime | T::Timestamp)
+        | ("decimal", T::Decimal(_))
+        | ("float", T::Float | T::Double)
+        | ("double", T::Double)
+        | ("json", T::Json | T::JsonBinary) => true,
+        (data_type, entity_type) => {
+            match (integer_width(data_type), entity_integer_width(entity_type)) {
+                (Some(column_width), Some(entity_width)) => entity_width >= column_width,
+                _ => false,
+            }
+        }
+    }
+}
+
+fn integer_width(data_type: &str) -> Option<u8> {
+    match data_type {
+        "tinyint" => Some(8),
+        "smallint" => Some(16),
+        "mediumint" => Some(24),
+        "int" => Some(32),
+        "bigint" => Some(64),
+        _ => None,
+    }
+}
+
+fn entity_integer_width(column_type: &ColumnType) -> Option<u8> {
+    match column_type {
+        ColumnType::TinyInteger => Some(8),
+        ColumnType::SmallInteger => Some(16),
+        ColumnType::Integer => Some(32),
+        ColumnType::BigInteger => Some(64),
+        _ => None,
+    }
+}
+
+/// `enum('A','B')` to `{"A", "B"}`.
+fn enum_values(column_type: &str) -> BTreeSet<String> {
+    column_type
+        .strip_prefix("enum(")
+        .and_then(|values| values.strip_suffix(')'))
+        .unwrap_or_default()
+        .split(',')
+        .map(|value| value.trim().trim_matches('\'').replace("''", "'"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::sea_query::StringLen;
+
+    fn db_column(table: &str, column: &str, column_type: &str, nullable: bool) -> DbColumn {
+        DbColumn {
+            table: table.to_string(),
+            is_view: false,
+            column: column.to_string(),
+            data_type: column_type
+                .split('(')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            column_type: column_type.to_string(),
+            nullable,
+        }
+    }
+
+    fn field(name: &str, column_type: ColumnType, nullable: bool) -> EntityColumn {
+        EntityColumn {
+            name: name.to_string(),
+            column_type,
+            nullable,
+        }
+    }
+
+    fn string() -> ColumnType {
+        ColumnType::String(StringLen::None)
+    }
+
+    #[test]
+    fn diff_reports_renamed_columns_and_tables_without_entities() {
+        let db_columns = vec![
+            db_column("tanahpedia_sefer", "id", "char(36)", false),
+            db_column("tanahpedia_sefer", "entity_id", "char(36)", false),
+            db_column("tanahpedia_king_reign", "id", "char(36)", false),
+        ];
+        let entities = vec![EntityTable {
+            table: "tanahpedia_sefer".to_string(),
+            columns: vec![field("id", string(), false), field("name", string(), false)],
+        }];
+
+        let drift = diff(&db_columns, &entities);
+
+        assert_eq!(
+            drift,
+            vec![
+                Drift::MissingEntity {
+                    table: "tanahpedia_king_reign".to_string(),
+                },
+                Drift::MissingField {
+                    table: "tanahpedia_sefer".to_string(),
+                    column: "entity_id".to_string(),
+                    column_type: "char(36)".to_string(),
+                },
+                Drift::MissingColumn {
+                    table: "tanahpedia_sefer".to_string(),
+                    column: "name".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            report(&drift),
+            "  - tanahpedia_king_reign: table has no entity\n  \
+             - tanahpedia_sefer.entity_id: column (char(36)) has no entity field\n  \
+             - tanahpedia_sefer.name: entity field has no column"
+        );
+    }
+
+    #[test]
+    fn diff_reports_type_and_nullability_mismatches() {
+        let db_columns = vec![
+            db_column("tanah_sefer", "id", "int", false),
+            db_column("tanah_sefer", "name", "varchar(19)", true),
+            db_column("tanah_author", "id", "smallint", false),
+        ];
+        let entities = vec![
+            EntityTable {
+                table: "tanah_sefer".to_string(),
+                columns: vec![
+                    field("id", ColumnType::SmallInteger, false),
+                    field("name", string(), false),
+                ],
+            },
+            EntityTable {
+                table: "tanah_author".to_string(),
+                columns: vec![field("id", ColumnType::Integer, false)],
+            },
+        ];
+
+        assert_eq!(
+            diff(&db_columns, &entities),
+            vec![
+                Drift::TypeMismatch {
+                    table: "tanah_sefer".to_string(),
+                    column: "id".to_string(),
+                    column_type: "int".to_string(),
+                    entity_type: "SmallInteger".to_string(),
+                },
+                Drift::Nullability {
+                    table: "tanah_sefer".to_string(),
+                    column: "name".to_string(),
+                    column_nullable: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_ignores_views_without_entities_and_lenient_view_fields() {
+        let mut perek_in_context =
+            db_column("tanah_perek_view", "perek_in_context", "bigint", false);
+        perek_in_context.is_view = true;
+        let entities = vec![EntityTable {
+            table: "tanah_perek_view".to_string(),
+            columns: vec![field("perek_in_context", ColumnType::Integer, true)],
+        }];
+        let mut other_view = db_column("some_report_view", "id", "int", false);
+        other_view.is_view = true;
+
+        assert!(diff(&[perek_in_context, other_view], &entities).is_empty());
+    }
+
+    #[test]
+    fn type_matches_accepts_wider_integers_booleans_and_strings() {
+        assert!(type_matches(
+            &db_column("t", "c", "tinyint(1)", false),
+            &ColumnType::Boolean
+        ));
+        assert!(!type_matches(
+            &db_column("t", "c", "tinyint", false),
+            &ColumnType::Boolean
+        ));
+        assert!(type_matches(
+            &db_column("t", "c", "mediumint", false),
+            &ColumnType::Integer
+        ));
+        assert!(!type_matches(
+            &db_column("t", "c", "mediumint", false),
+            &ColumnType::SmallInteger
+        ));
+        assert!(type_matches(
+            &db_column("t", "c", "mediumtext", false),
+            &ColumnType::Text
+        ));
+        assert!(type_matches(
+            &db_column("t", "c", "enum('PERSON','PLACE')", false),
+            &string()
+        ));
+        assert!(!type_matches(
+            &db_column("t", "c", "varchar(10)", false),
+            &ColumnType::Integer
+        ));
+    }
+
+    #[test]
+    fn enum_values_parses_column_type() {
+        assert_eq!(
+            enum_values("enum('TAHOR','TAMEH')"),
+            BTreeSet::from(["TAHOR".to_string(), "TAMEH".to_string()])
+        );
+    }
+}
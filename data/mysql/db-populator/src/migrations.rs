@@ -0,0 +1,590 @@
+//! Versioned schema migrations
+//!
+//! Each migration is an idempotent SQL script in `data/mysql`, numbered by its
+//! position in [`MIGRATIONS`]. The `schema_migrations` table records which
+//! versions a database has applied together with the SHA-256 of the script at
+//! that time, so a script edited after it shipped fails `migrate verify`
+//! instead of silently diverging between databases.
+//!
+//! The production data-deploy Lambda still runs the same scripts on every
+//! deploy (`devops/deploy/data-deploy/sql-files.json`), so they must stay
+//! idempotent even though this runner applies each one only once.
+
+use anyhow::{Context, Result, bail};
+use clap::Subcommand;
+use sha2::{Digest, Sha256};
+use sqlx_core::connection::Connection;
+use sqlx_core::query::query;
+use sqlx_core::query_scalar::query_scalar;
+use sqlx_core::raw_sql::raw_sql;
+use sqlx_core::row::Row;
+use sqlx_core::sql_str::AssertSqlSafe;
+use sqlx_mysql::{MySql, MySqlConnection};
+use std::fmt;
+use std::path::Path;
+
+pub(crate) struct Migration {
+    pub(crate) version: i32,
+    pub(crate) name: &'static str,
+    /// File name relative to the migrations directory.
+    pub(crate) script: &'static str,
+}
+
+/// Every migration, in the order it must be applied. Append only: never
+/// renumber, reorder or edit a migration that has shipped.
+pub(crate) const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "tanahpedia_source_citation",
+        script: "tanahpedia_alter_source_citation.sql",
+    },
+    Migration {
+        version: 2,
+        name: "tanahpedia_person_source_citation",
+        script: "tanahpedia_alter_person_source_citation.sql",
+    },
+    Migration {
+        version: 3,
+        name: "tanahpedia_animal_classification_alternatives",
+        script: "tanahpedia_alter_animal_classification_alternatives.sql",
+    },
+    Migration {
+        version: 4,
+        name: "tanahpedia_alt_group",
+        script: "tanahpedia_alter_alt_group.sql",
+    },
+    Migration {
+        version: 5,
+        name: "tanahpedia_name_giver_source_citation",
+        script: "tanahpedia_alter_name_giver_source_citation.sql",
+    },
+    Migration {
+        version: 6,
+        name: "tanahpedia_person_nation",
+        script: "tanahpedia_alter_person_nation.sql",
+    },
+    Migration {
+        version: 7,
+        name: "tanahpedia_non_tanah_source_reference",
+        script: "tanahpedia_alter_non_tanah_source_reference.sql",
+    },
+    Migration {
+        version: 8,
+        name: "tanahpedia_entry_link",
+        script: "tanahpedia_alter_entry_link.sql",
+    },
+    Migration {
+        version: 9,
+        name: "tanahpedia_war_side_number",
+        script: "tanahpedia_alter_war_side_number.sql",
+    },
+    Migration {
+        version: 10,
+        name: "tanahpedia_incremental_lookups",
+        script: "tanahpedia_incremental_lookups.sql",
+    },
+];
+
+#[derive(Subcommand, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MigrateAction {
+    /// Apply every pending migration in order
+    Up,
+    /// List every migration with its state in the database
+    Status,
+    /// Fail unless every migration is applied and unchanged since
+    Verify,
+}
+
+/// A `schema_migrations` row.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AppliedMigration {
+    pub(crate) version: i32,
+    pub(crate) name: String,
+    pub(crate) checksum: String,
+    pub(crate) applied_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum MigrationState {
+    Applied {
+        applied_at: String,
+    },
+    Pending,
+    /// Applied, but the script changed since.
+    Modified {
+        applied_at: String,
+    },
+    /// Recorded in the database but missing from [`MIGRATIONS`].
+    Unknown {
+        applied_at: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MigrationStatus {
+    pub(crate) version: i32,
+    pub(crate) name: String,
+    pub(crate) state: MigrationState,
+}
+
+impl fmt::Display for MigrationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match &self.state {
+            MigrationState::Applied { applied_at } => format!("applied {}", applied_at),
+            MigrationState::Pending => "pending".to_string(),
+            MigrationState::Modified { applied_at } => {
+                format!("MODIFIED since it was applied {}", applied_at)
+            }
+            MigrationState::Unknown { applied_at } => {
+                format!("UNKNOWN to this db-populator, applied {}", applied_at)
+            }
+        };
+        write!(f, "{:04} {:<50} {}", self.version, self.name, state)
+    }
+}
+
+pub(crate) async fn run(
+    conn: &mut MySqlConnection,
+    migrations_dir: &Path,
+    action: MigrateAction,
+) -> Result<()> {
+    match action {
+        MigrateAction::Up => {
+            let applied = up(conn, migrations_dir).await?;
+            println!("Applied {} migration(s)", applied);
+            Ok(())
+        }
+        MigrateAction::Status => {
+            for migration in status(conn, migrations_dir).await? {
+                println!("{}", migration);
+            }
+            Ok(())
+        }
+        MigrateAction::Verify => {
+            verify(conn, migrations_dir).await?;
+            println!(
+                "All {} migrations are applied and unchanged",
+                MIGRATIONS.len()
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Applies pending migrations in version order and returns how many ran.
+/// Refuses to run anything while an applied migration is modified or unknown.
+pub(crate) async fn up(conn: &mut MySqlConnection, migrations_dir: &Path) -> Result<usize> {
+    ensure_table(conn).await?;
+    let statuses = status(conn, migrations_dir).await?;
+    let broken = statuses
+        .iter()
+        .filter(|migration| {
+            matches!(
+                migration.state,
+                MigrationState::Modified { .. } | MigrationState::Unknown { .. }
+            )
+        })
+        .collect::<Vec<_>>();
+    if !broken.is_empty() {
+        bail!(
+            "Refusing to migrate; applied migrations do not match this db-populator:\n{}",
+            report(broken)
+        );
+    }
+
+    let mut applied = 0;
+    for migration in MIGRATIONS {
+        let is_pending = statuses.iter().any(|status| {
+            status.version == migration.version && status.state == MigrationState::Pending
+        });
+        if is_pending {
+            apply(conn, migrations_dir, migration).await?;
+            applied += 1;
+        }
+    }
+    Ok(applied)
+}
+
+pub(crate) async fn status(
+    conn: &mut MySqlConnection,
+    migrations_dir: &Path,
+) -> Result<Vec<MigrationStatus>> {
+    let mut checksums = Vec::with_capacity(MIGRATIONS.len());
+    for migration in MIGRATIONS {
+        let (_, checksum) = read_script(migrations_dir, migration)?;
+        checksums.push(checksum);
+    }
+    let applied = if table_exists(conn).await? {
+        load_applied(conn).await?
+    } else {
+        Vec::new()
+    };
+    Ok(plan(MIGRATIONS, &checksums, &applied))
+}
+
+pub(crate) async fn verify(conn: &mut MySqlConnection, migrations_dir: &Path) -> Result<()> {
+    let statuses = status(conn, migrations_dir).await?;
+    let outstanding = statuses
+        .iter()
+        .filter(|migration| !matches!(migration.state, MigrationState::Applied { .. }))
+        .collect::<Vec<_>>();
+    if !outstanding.is_empty() {
+        bail!(
+            "Schema migrations are not in sync:\n{}",
+            report(outstanding)
+        );
+    }
+    Ok(())
+}
+
+/// Forgets every applied migration. Used by the destructive rebuild, whose
+/// freshly created tables carry none of the migrations recorded for the old
+/// ones.
+pub(crate) async fn reset(conn: &mut MySqlConnection) -> Result<()> {
+    raw_sql("DROP TABLE IF EXISTS schema_migrations")
+        .execute(&mut *conn)
+        .await
+        .context("Failed to reset schema_migrations")?;
+    Ok(())
+}
+
+/// Pairs the known migrations (with their current checksums) with the
+/// recorded ones, in version order.
+pub(crate) fn plan(
+    migrations: &[Migration],
+    checksums: &[String],
+    applied: &[AppliedMigration],
+) -> Vec<MigrationStatus> {
+    let mut statuses = migrations
+        .iter()
+        .zip(checksums)
+        .map(|(migration, checksum)| {
+            let state = match applied.iter().find(|row| row.version == migration.version) {
+                None => MigrationState::Pending,
+                Some(row) if &row.checksum == checksum => MigrationState::Applied {
+                    applied_at: row.applied_at.clone(),
+                },
+                Some(row) => MigrationState::Modified {
+                    applied_at: row.applied_at.clone(),
+                },
+            };
+            MigrationStatus {
+                version: migration.version,
+                name: migration.name.to_string(),
+                state,
+            }
+        })
+        .collect::<Vec<_>>();
+    statuses.extend(
+        applied
+            .iter()
+            .filter(|row| {
+                !migrations
+                    .iter()
+                    .any(|migration| migration.version == row.version)
+            })
+            .map(|row| MigrationStatus {
+                version: row.version,
+                name: row.name.clone(),
+                state: MigrationState::Unknown {
+                    applied_at: row.applied_at.clone(),
+                },
+            }),
+    );
+    statuses.sort_by_key(|migration| migration.version);
+    statuses
+}
+
+fn report(statuses: Vec<&MigrationStatus>) -> String {
+    statuses
+        .iter()
+        .map(|migration| format!("  - {}", migration))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Line endings are normalized so a Windows checkout hashes like a Unix one.
+pub(crate) fn checksum(script: &str) -> String {
+    Sha256::digest(script.replace("\r\n", "\n").as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// MySQL commits implicitly around DDL, so only scripts without any can run
+/// in a transaction. Errs towards no transaction: a `PREPARE` may hide DDL.
+pub(crate) fn is_transactional(script: &str) -> bool {
+    let script = script.to_uppercase();
+    ![
+        "ALTER ",
+        "CREATE ",
+        "DROP ",
+        "RENAME ",
+        "TRUNCATE ",
+        "PREPARE ",
+    ]
+    .iter()
+    .any(|keyword| script.contains(keyword))
+}
+
+fn read_script(migrations_dir: &Path, migration: &Migration) -> Result<(String, String)> {
+    let path = migrations_dir.join(migration.script);
+    let script = std::fs::read_to_string(&path).with_context(|| {
+        format!(
+            "Failed to read migration {:04} script: {:?}",
+            migration.version, path
+        )
+    })?;
+    let checksum = checksum(&script);
+    Ok((script, checksum))
+}
+
+async fn apply(
+    conn: &mut MySqlConnection,
+    migrations_dir: &Path,
+    migration: &Migration,
+) -> Result<()> {
+    println!(
+        "Applying migration {:04} {}...",
+        migration.version, migration.name
+    );
+    let (script, checksum) = read_script(migrations_dir, migration)?;
+    let sql = crate::filter_use_statements(&script);
+
+    if is_transactional(&sql) {
+        let mut transaction = conn
+            .begin()
+            .await
+            .context("Failed to start migration transaction")?;
+        execute(&mut transaction, migration, sql).await?;
+        record(&mut transaction, migration, &checksum).await?;
+        transaction
+            .commit()
+            .await
+            .with_context(|| format!("Failed to commit migration {:04}", migration.version))?;
+    } else {
+        // A failure part-way leaves the migration unrecorded; the script is
+        // idempotent, so the next `migrate up` simply runs it again.
+        execute(conn, migration, sql).await?;
+        record(conn, migration, &checksum).await?;
+    }
+    Ok(())
+}
+
+async fn execute(conn: &mut MySqlConnection, migration: &Migration, sql: String) -> Result<()> {
+    raw_sql(AssertSqlSafe(sql))
+        .execute(&mut *conn)
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to apply migration {:04} {}",
+                migration.version, migration.name
+            )
+        })?;
+    Ok(())
+}
+
+async fn record(conn: &mut MySqlConnection, migration: &Migration, checksum: &str) -> Result<()> {
+    query::<MySql>("INSERT INTO schema_migrations (version, name, checksum) VALUES (?, ?, ?)")
+        .bind(migration.version)
+        .bind(migration.name)
+        .bind(checksum)
+        .execute(&mut *conn)
+        .await
+        .with_context(|| format!("Failed to record migration {:04}", migration.version))?;
+    Ok(())
+}
+
+async fn ensure_table(conn: &mut MySqlConnection) -> Result<()> {
+    raw_sql(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (\
+             version int NOT NULL, \
+             name varchar(200) NOT NULL, \
+             checksum char(64) NOT NULL, \
+             applied_at datetime NOT NULL DEFAULT CURRENT_TIMESTAMP, \
+             PRIMARY KEY (version)\
+         ) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4",
+    )
+    .execute(&mut *conn)
+    .await
+    .context("Failed to create schema_migrations")?;
+    Ok(())
+}
+
+async fn table_exists(conn: &mut MySqlConnection) -> Result<bool> {
+    let count: i64 = query_scalar::<MySql, i64>(
+        "SELECT COUNT(*) \
+         FROM information_schema.TABLES \
+         WHERE TABLE_SCHEMA = DATABASE() \
+           AND TABLE_NAME = 'schema_migrations'",
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .context("Failed to check for schema_migrations")?;
+    Ok(count > 0)
+}
+
+async fn load_applied(conn: &mut MySqlConnection) -> Result<Vec<AppliedMigration>> {
+    let rows = query::<MySql>(
+        "SELECT version, name, checksum, \
+                DATE_FORMAT(applied_at, '%Y-%m-%d %H:%i:%s') AS applied_at \
+         FROM schema_migrations \
+         ORDER BY version",
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .context("Failed to load schema_migrations")?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(AppliedMigration {
+                version: row.try_get("version")?,
+                name: row.try_get("name")?,
+                checksum: row.try_get("checksum")?,
+                applied_at: row.try_get("applied_at")?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn migrations_dir() -> &'static Path {
+        Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+    }
+
+    fn applied(version: i32, checksum: &str) -> AppliedMigration {
+        AppliedMigration {
+            version,
+            name: format!("migration_{}", version),
+            checksum: checksum.to_string(),
+            applied_at: "2026-01-01 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn migrations_are_numbered_consecutively_and_their_scripts_exist() {
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, index as i32 + 1, "{}", migration.name);
+            assert!(
+                migrations_dir().join(migration.script).exists(),
+                "{}",
+                migration.script
+            );
+        }
+        let names = MIGRATIONS
+            .iter()
+            .map(|migration| migration.name)
+            .collect::<HashSet<_>>();
+        assert_eq!(names.len(), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn every_alter_script_is_a_migration_deployed_by_the_lambda() {
+        let manifest = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../../devops/deploy/data-deploy/sql-files.json"
+        ))
+        .expect("Lambda manifest should be readable");
+        for entry in std::fs::read_dir(migrations_dir()).expect("data/mysql should be readable") {
+            let file_name = entry.unwrap().file_name().to_string_lossy().to_string();
+            if file_name.starts_with("tanahpedia_alter_") && file_name.ends_with(".sql") {
+                assert!(
+                    MIGRATIONS
+                        .iter()
+                        .any(|migration| migration.script == file_name),
+                    "{} is not registered in MIGRATIONS",
+                    file_name
+                );
+            }
+        }
+        for migration in MIGRATIONS {
+            assert!(
+                manifest.contains(&format!("\"{}\"", migration.script)),
+                "{} is missing from sql-files.json",
+                migration.script
+            );
+        }
+    }
+
+    #[test]
+    fn plan_marks_pending_modified_and_unknown_migrations() {
+        let migrations = [
+            Migration {
+                version: 1,
+                name: "first",
+                script: "first.sql",
+            },
+            Migration {
+                version: 2,
+                name: "second",
+                script: "second.sql",
+            },
+            Migration {
+                version: 3,
+                name: "third",
+                script: "third.sql",
+            },
+        ];
+        let checksums = ["a".to_string(), "b".to_string(), "c".to_string()];
+
+        let statuses = plan(
+            &migrations,
+            &checksums,
+            &[applied(1, "a"), applied(2, "changed"), applied(7, "z")],
+        );
+
+        assert_eq!(
+            statuses
+                .iter()
+                .map(|migration| (migration.version, migration.state.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    1,
+                    MigrationState::Applied {
+                        applied_at: "2026-01-01 00:00:00".to_string()
+                    }
+                ),
+                (
+                    2,
+                    MigrationState::Modified {
+                        applied_at: "2026-01-01 00:00:00".to_string()
+                    }
+                ),
+                (3, MigrationState::Pending),
+                (
+                    7,
+                    MigrationState::Unknown {
+                        applied_at: "2026-01-01 00:00:00".to_string()
+                    }
+                ),
+            ]
+        );
+        assert_eq!(statuses[3].name, "migration_7");
+    }
+
+    #[test]
+    fn checksum_ignores_line_ending_style() {
+        assert_eq!(
+            checksum("SELECT 1;\r\nSELECT 2;\r\n"),
+            checksum("SELECT 1;\nSELECT 2;\n")
+        );
+        assert_ne!(checksum("SELECT 1;"), checksum("SELECT 2;"));
+        assert_eq!(checksum("").len(), 64);
+    }
+
+    #[test]
+    fn only_scripts_without_ddl_run_in_a_transaction() {
+        assert!(is_transactional(
+            "INSERT IGNORE INTO tanahpedia_lookup_union_type (id, name) VALUES ('x', 'y');"
+        ));
+        assert!(!is_transactional(
+            "ALTER TABLE tanahpedia_person_union ADD COLUMN note varchar(10) NULL;"
+        ));
+        assert!(!is_transactional("PREPARE stmt FROM @preparedStatement;"));
+    }
+}
@@ -0,0 +1,127 @@
+//! Tanahpedia Knowledge-Graph Exporter
+//!
+//! Downloads every Tanahpedia entity and its relations as schema.org JSON-LD
+//! or as RDF Turtle with the Tanahpedia vocabulary (prophecies, sayings and
+//! `alt_group_id` opinions), for loading into a triple store. The graph is
+//! built by the API's `GET /tanahpedia/graph`, so the IRIs and terms have a
+//! single definition in `web/api/src/services/tanahpedia_graph_service.rs`.
+
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+#[derive(Parser)]
+#[command(name = "graph-exporter")]
+#[command(about = "Export the Tanahpedia knowledge graph as JSON-LD or RDF Turtle")]
+struct Cli {
+    /// Root URL of the API
+    #[arg(long, env = "API_URL", default_value = "http://127.0.0.1:3003/")]
+    api_url: String,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "jsonld")]
+    format: Format,
+
+    /// Output file; defaults to stdout
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Jsonld,
+    Turtle,
+}
+
+impl Format {
+    fn as_query(self) -> &'static str {
+        match self {
+            Self::Jsonld => "jsonld",
+            Self::Turtle => "turtle",
+        }
+    }
+}
+
+/// The graph endpoint under `api_url`, with or without its trailing slash.
+fn graph_url(api_url: &str, format: Format) -> String {
+    format!(
+        "{}/tanahpedia/graph?format={}",
+        api_url.trim_end_matches('/'),
+        format.as_query()
+    )
+}
+
+/// The API sends compact JSON-LD; files are easier to diff pretty-printed.
+fn render(format: Format, body: String) -> Result<String> {
+    match format {
+        Format::Jsonld => {
+            let graph: Value =
+                serde_json::from_str(&body).context("The API returned invalid JSON-LD")?;
+            Ok(serde_json::to_string_pretty(&graph)?)
+        }
+        Format::Turtle => Ok(body),
+    }
+}
+
+fn main() -> Result<()> {
+    let env_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../.dev.env");
+    if env_path.exists() {
+        dotenvy::from_path(&env_path).ok();
+    }
+
+    let cli = Cli::parse();
+    let url = graph_url(&cli.api_url, cli.format);
+    let body = reqwest::blocking::get(&url)
+        .with_context(|| format!("Failed to reach the API at {url}"))?
+        .error_for_status()
+        .context("The API rejected the request")?
+        .text()
+        .context("Failed to read the API response")?;
+    let output = render(cli.format, body)?;
+
+    match &cli.out {
+        Some(path) => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            std::fs::write(path, output)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!("Wrote {}", path.display());
+        }
+        None => println!("{}", output),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_url_joins_the_api_root() {
+        assert_eq!(
+            graph_url("http://127.0.0.1:3003/", Format::Turtle),
+            "http://127.0.0.1:3003/tanahpedia/graph?format=turtle"
+        );
+        assert_eq!(
+            graph_url("https://api.example", Format::Jsonld),
+            "https://api.example/tanahpedia/graph?format=jsonld"
+        );
+    }
+
+    #[test]
+    fn render_pretty_prints_json_ld_and_keeps_turtle() {
+        assert_eq!(
+            render(Format::Jsonld, r#"{"@graph":[]}"#.to_string()).unwrap(),
+            "{\n  \"@graph\": []\n}"
+        );
+        assert_eq!(
+            render(Format::Turtle, "@prefix tp: <x> .".to_string()).unwrap(),
+            "@prefix tp: <x> ."
+        );
+        assert!(render(Format::Jsonld, "<html>".to_string()).is_err());
+    }
+}
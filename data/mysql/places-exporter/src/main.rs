@@ -0,0 +1,311 @@
+//! Tanahpedia Places Exporter
+//!
+//! Downloads every located place identification as GeoJSON or KML for the
+//! map layer. The features come from the API's `tanahpediaPlacesGeoJson`
+//! query, so the shape, the alternative identifications and their opinion
+//! grouping have a single definition in
+//! `web/api/src/services/tanahpedia_places_service.rs`; KML is rendered from
+//! those features.
+
+use anyhow::{Context, Result, bail};
+use clap::{Parser, ValueEnum};
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+
+/// Selects every field, so the result is a complete GeoJSON document.
+const PLACES_QUERY: &str = r#"query Places($bbox: [Float!]) {
+  tanahpediaPlacesGeoJson(bbox: $bbox) {
+    type
+    features {
+      type
+      id
+      geometry { type coordinates }
+      properties {
+        placeId
+        entityId
+        displayName
+        identificationId
+        modernName
+        altGroupId
+        identifications { id modernName latitude longitude altGroupId }
+        identificationOpinions {
+          preferredAltGroupId
+          isDisputed
+          opinions {
+            altGroupId
+            label
+            isPreferred
+            sources {
+              tanah { perushId perekId pasukNumber }
+              nonTanah { sourceText }
+            }
+            facts { id modernName latitude longitude altGroupId }
+          }
+        }
+        events { linkId eventId displayName altGroupId }
+        births { linkId personId displayName altGroupId }
+      }
+    }
+  }
+}"#;
+
+#[derive(Parser)]
+#[command(name = "places-exporter")]
+#[command(about = "Export Tanahpedia place identifications to GeoJSON or KML")]
+struct Cli {
+    /// GraphQL endpoint of the API
+    #[arg(long, env = "API_URL", default_value = "http://127.0.0.1:3003/")]
+    api_url: String,
+
+    /// Revision API key; only needed when the API accepts registered
+    /// operations only (PERSISTED_QUERIES_ALLOW_LIST)
+    #[arg(long, env = "TANAHPEDIA_REVISION_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "geojson")]
+    format: Format,
+
+    /// Output file; defaults to stdout
+    #[arg(long)]
+    out: Option<PathBuf>,
+
+    /// Only export points inside minLongitude,minLatitude,maxLongitude,maxLatitude
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
+    bbox: Option<Vec<f64>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Geojson,
+    Kml,
+}
+
+/// The feature collection, or the GraphQL errors as one message.
+fn feature_collection(response: Value) -> Result<Value> {
+    if let Some(errors) = response.get("errors").and_then(Value::as_array) {
+        let messages: Vec<&str> = errors
+            .iter()
+            .filter_map(|error| error.get("message").and_then(Value::as_str))
+            .collect();
+        bail!("Export failed: {}", messages.join("; "));
+    }
+    response
+        .pointer("/data/tanahpediaPlacesGeoJson")
+        .cloned()
+        .context("The API response has no tanahpediaPlacesGeoJson result")
+}
+
+fn main() -> Result<()> {
+    let env_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../.dev.env");
+    if env_path.exists() {
+        dotenvy::from_path(&env_path).ok();
+    }
+
+    let cli = Cli::parse();
+    let mut request = reqwest::blocking::Client::new()
+        .post(&cli.api_url)
+        .json(&json!({ "query": PLACES_QUERY, "variables": { "bbox": cli.bbox } }));
+    if let Some(api_key) = &cli.api_key {
+        request = request.bearer_auth(api_key);
+    }
+    let response: Value = request
+        .send()
+        .with_context(|| format!("Failed to reach the API at {}", cli.api_url))?
+        .error_for_status()
+        .context("The API rejected the request")?
+        .json()
+        .context("Failed to parse the API response")?;
+    let collection = feature_collection(response)?;
+    let features = collection["features"].as_array().map_or(0, Vec::len);
+    let output = match cli.format {
+        Format::Geojson => serde_json::to_string_pretty(&collection)?,
+        Format::Kml => to_kml(&collection),
+    };
+
+    match &cli.out {
+        Some(path) => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            std::fs::write(path, output)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!("Wrote {} points to {}", features, path.display());
+        }
+        None => println!("{}", output),
+    }
+
+    Ok(())
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn kml_data(name: &str, value: &str) -> String {
+    format!(
+        "        <Data name=\"{}\"><value>{}</value></Data>\n",
+        name,
+        escape_xml(value)
+    )
+}
+
+/// The `displayName` of every object in `links`.
+fn display_names(links: &Value) -> Vec<&str> {
+    links
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|link| link["displayName"].as_str())
+        .collect()
+}
+
+/// Builds a KML document with one `Placemark` per feature. Alternatives,
+/// events and births go into the description so they show in the map's info
+/// balloon; stable ids go into `ExtendedData`.
+fn to_kml(collection: &Value) -> String {
+    let mut kml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <kml xmlns=\"http://www.opengis.net/kml/2.2\">\n\
+         <Document>\n\
+         <name>Tanahpedia places</name>\n",
+    );
+
+    for feature in collection["features"].as_array().into_iter().flatten() {
+        let properties = &feature["properties"];
+        let (Some(lon), Some(lat)) = (
+            feature["geometry"]["coordinates"][0].as_f64(),
+            feature["geometry"]["coordinates"][1].as_f64(),
+        ) else {
+            continue;
+        };
+        let text = |key: &str| properties[key].as_str().unwrap_or_default();
+
+        let mut description = Vec::new();
+        let identifications = properties["identifications"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if identifications.len() > 1 {
+            let alternatives = identifications
+                .iter()
+                .filter_map(|ident| ident["modernName"].as_str().or(ident["id"].as_str()))
+                .collect::<Vec<_>>();
+            description.push(format!("זיהויים: {}", alternatives.join(", ")));
+        }
+        let events = display_names(&properties["events"]);
+        if !events.is_empty() {
+            description.push(format!("אירועים: {}", events.join(", ")));
+        }
+        let births = display_names(&properties["births"]);
+        if !births.is_empty() {
+            description.push(format!("נולדו כאן: {}", births.join(", ")));
+        }
+
+        let name = match properties["modernName"].as_str() {
+            Some(modern_name) => format!("{} ({})", text("displayName"), modern_name),
+            None => text("displayName").to_string(),
+        };
+        kml.push_str("<Placemark>\n");
+        kml.push_str(&format!("    <name>{}</name>\n", escape_xml(&name)));
+        if !description.is_empty() {
+            kml.push_str(&format!(
+                "    <description>{}</description>\n",
+                escape_xml(&description.join("\n"))
+            ));
+        }
+        kml.push_str("    <ExtendedData>\n");
+        kml.push_str(&kml_data("placeId", text("placeId")));
+        kml.push_str(&kml_data("entityId", text("entityId")));
+        kml.push_str(&kml_data("identificationId", text("identificationId")));
+        if let Some(alt_group_id) = properties["altGroupId"].as_str() {
+            kml.push_str(&kml_data("altGroupId", alt_group_id));
+        }
+        kml.push_str("    </ExtendedData>\n");
+        kml.push_str(&format!(
+            "    <Point><coordinates>{},{}</coordinates></Point>\n",
+            lon, lat
+        ));
+        kml.push_str("</Placemark>\n");
+    }
+
+    kml.push_str("</Document>\n</kml>\n");
+    kml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hebron() -> Value {
+        json!({
+            "type": "FeatureCollection",
+            "features": [{
+                "type": "Feature",
+                "id": "ident-a",
+                "geometry": { "type": "Point", "coordinates": [35.1036, 31.5247] },
+                "properties": {
+                    "placeId": "place-1",
+                    "entityId": "entity-1",
+                    "displayName": "חברון",
+                    "identificationId": "ident-a",
+                    "modernName": "Tel Rumeida",
+                    "altGroupId": "alt-a",
+                    "identifications": [
+                        { "id": "ident-a", "modernName": "Tel Rumeida", "altGroupId": "alt-a" },
+                        { "id": "ident-b", "modernName": "Unknown & <lost>", "altGroupId": "alt-b" }
+                    ],
+                    "events": [{ "linkId": "link-1", "eventId": "event-1", "displayName": "קבורת שרה" }],
+                    "births": []
+                }
+            }]
+        })
+    }
+
+    #[test]
+    fn cli_parses_format_and_bbox() {
+        let cli = Cli::parse_from([
+            "places-exporter",
+            "--api-url",
+            "http://localhost:3003/",
+            "--format",
+            "kml",
+            "--bbox",
+            "34,31,36,33",
+        ]);
+
+        assert_eq!(cli.format, Format::Kml);
+        assert_eq!(cli.bbox, Some(vec![34.0, 31.0, 36.0, 33.0]));
+    }
+
+    #[test]
+    fn feature_collection_surfaces_graphql_errors() {
+        assert_eq!(
+            feature_collection(json!({ "data": { "tanahpediaPlacesGeoJson": hebron() } })).unwrap(),
+            hebron()
+        );
+        let err = feature_collection(json!({
+            "errors": [{ "message": "bbox minimums must not exceed its maximums" }]
+        }))
+        .unwrap_err();
+        assert!(err.to_string().contains("bbox minimums"));
+    }
+
+    #[test]
+    fn kml_escapes_text_and_uses_lon_lat_order() {
+        let kml = to_kml(&hebron());
+
+        assert_eq!(kml.matches("<Placemark>").count(), 1);
+        assert!(kml.contains("<name>חברון (Tel Rumeida)</name>"));
+        assert!(kml.contains("<coordinates>35.1036,31.5247</coordinates>"));
+        assert!(kml.contains("Unknown &amp; &lt;lost&gt;"));
+        assert!(kml.contains("אירועים: קבורת שרה"));
+        assert!(kml.contains("<Data name=\"altGroupId\"><value>alt-a</value></Data>"));
+    }
+}
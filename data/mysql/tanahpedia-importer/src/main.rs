@@ -0,0 +1,152 @@
+//! Tanahpedia Content Importer
+//!
+//! Sends a YAML or JSON content document to the API's
+//! `importTanahpediaContent` mutation, which resolves its keys to ids,
+//! validates it and writes it in one transaction. The document format is the
+//! mutation's `TanahpediaImportInput`; see `examples/` and
+//! docs/tanahpedia/external-revision-api.md.
+
+use anyhow::{Context, Result, bail};
+use clap::Parser;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+
+const IMPORT_MUTATION: &str = r#"mutation Import($input: TanahpediaImportInput!, $dryRun: Boolean!) {
+  importTanahpediaContent(input: $input, dryRun: $dryRun) {
+    dryRun
+    entities { key entityId created }
+    created
+    updated
+    unchanged
+  }
+}"#;
+
+#[derive(Parser)]
+#[command(name = "tanahpedia-importer")]
+#[command(about = "Import Tanahpedia content described in YAML or JSON")]
+struct Cli {
+    /// Content document (.yaml, .yml or .json)
+    file: PathBuf,
+
+    /// GraphQL endpoint of the API
+    #[arg(long, env = "API_URL", default_value = "http://127.0.0.1:3003/")]
+    api_url: String,
+
+    /// Revision API key of the API
+    #[arg(long, env = "TANAHPEDIA_REVISION_API_KEY", hide_env_values = true)]
+    api_key: String,
+
+    /// Validate and report what would change without writing
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Parses `text` as JSON for `.json` files and as YAML otherwise.
+fn parse_document(path: &Path, text: &str) -> Result<Value> {
+    let is_json = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    let document: Value = if is_json {
+        serde_json::from_str(text).context("Failed to parse JSON document")?
+    } else {
+        serde_yaml::from_str(text).context("Failed to parse YAML document")?
+    };
+    if !document.is_object() {
+        bail!("The document must be a mapping with entities, parentChild and unions");
+    }
+    Ok(document)
+}
+
+/// The mutation result, or the GraphQL errors as one message.
+fn import_result(response: Value) -> Result<Value> {
+    if let Some(errors) = response.get("errors").and_then(Value::as_array) {
+        let messages: Vec<&str> = errors
+            .iter()
+            .filter_map(|error| error.get("message").and_then(Value::as_str))
+            .collect();
+        bail!("Import failed: {}", messages.join("; "));
+    }
+    response
+        .pointer("/data/importTanahpediaContent")
+        .cloned()
+        .context("The API response has no importTanahpediaContent result")
+}
+
+fn main() -> Result<()> {
+    let env_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../.dev.env");
+    if env_path.exists() {
+        dotenvy::from_path(&env_path).ok();
+    }
+
+    let cli = Cli::parse();
+    let text = std::fs::read_to_string(&cli.file)
+        .with_context(|| format!("Failed to read {}", cli.file.display()))?;
+    let document = parse_document(&cli.file, &text)?;
+
+    let response: Value = reqwest::blocking::Client::new()
+        .post(&cli.api_url)
+        .bearer_auth(&cli.api_key)
+        .json(&json!({
+            "query": IMPORT_MUTATION,
+            "variables": { "input": document, "dryRun": cli.dry_run },
+        }))
+        .send()
+        .with_context(|| format!("Failed to reach the API at {}", cli.api_url))?
+        .error_for_status()
+        .context("The API rejected the request")?
+        .json()
+        .context("Failed to parse the API response")?;
+    let result = import_result(response)?;
+
+    println!("{}", serde_json::to_string_pretty(&result)?);
+    if cli.dry_run {
+        eprintln!("Dry run: nothing was written.");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_document_reads_yaml_and_json_alike() {
+        let yaml = "entities:\n  - key: manoah\n    entityType: PERSON\n    name: מנוח\n";
+        let json = r#"{"entities":[{"key":"manoah","entityType":"PERSON","name":"מנוח"}]}"#;
+
+        assert_eq!(
+            parse_document(Path::new("family.yaml"), yaml).unwrap(),
+            parse_document(Path::new("family.JSON"), json).unwrap()
+        );
+        assert!(parse_document(Path::new("list.yaml"), "- a\n- b\n").is_err());
+    }
+
+    #[test]
+    fn parse_document_accepts_the_examples() {
+        let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+        for entry in std::fs::read_dir(examples).unwrap() {
+            let path = entry.unwrap().path();
+            let text = std::fs::read_to_string(&path).unwrap();
+            let document = parse_document(&path, &text).unwrap();
+            assert!(document["entities"].is_array(), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn import_result_surfaces_graphql_errors() {
+        let ok = json!({ "data": { "importTanahpediaContent": { "created": 2 } } });
+        assert_eq!(import_result(ok).unwrap(), json!({ "created": 2 }));
+
+        let failed = json!({
+            "data": null,
+            "errors": [
+                { "message": "unknown parentRole UNCLE" },
+                { "message": "second" }
+            ]
+        });
+        assert_eq!(
+            import_result(failed).unwrap_err().to_string(),
+            "Import failed: unknown parentRole UNCLE; second"
+        );
+    }
+}
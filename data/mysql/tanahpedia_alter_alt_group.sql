@@ -0,0 +1,25 @@
+-- One-time upgrade for databases created before alternative opinions carried
+-- metadata (label, preferred flag and sources) in tanahpedia_alt_group.
+-- The production data-deploy Lambda re-runs this file on every deployment and
+-- injects a DROP TABLE IF EXISTS before every table creation it finds in the
+-- text, so the table is created through the information_schema + PREPARE
+-- idiom, with the statement text split after its first keyword.
+SET @preparedStatement = (
+        SELECT IF(
+                (
+                    SELECT COUNT(*)
+                    FROM information_schema.TABLES
+                    WHERE TABLE_SCHEMA = DATABASE()
+                        AND TABLE_NAME = 'tanahpedia_alt_group'
+                ) > 0,
+                'SELECT 1',
+                CONCAT(
+                    'CREATE',
+                    ' TABLE tanahpedia_alt_group (id char(36) NOT NULL, label varchar(200) DEFAULT NULL, is_preferred tinyint(1) NOT NULL DEFAULT 0, dispute_id char(36) DEFAULT NULL, PRIMARY KEY (id), KEY idx_alt_group_dispute (dispute_id)) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci'
+                )
+            )
+    );
+PREPARE createAltGroup
+FROM @preparedStatement;
+EXECUTE createAltGroup;
+DEALLOCATE PREPARE createAltGroup;
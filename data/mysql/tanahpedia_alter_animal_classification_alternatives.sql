@@ -0,0 +1,44 @@
+-- One-time upgrade for databases created while tanahpedia_animal_kind and
+-- tanahpedia_animal_purity still had a UNIQUE key on animal_id. That key allowed
+-- a single classification per animal, so alternative opinions (e.g. a disputed
+-- kashrut status, told apart by alt_group_id) could not be stored. The unique
+-- key is replaced by a plain index (the FK on animal_id keeps an index either way).
+--
+-- Plain MySQL has no DROP INDEX IF EXISTS clause, so this uses the standard
+-- prepared-statement idiom to check information_schema first. This makes the
+-- script safe to execute unconditionally on every deploy (production
+-- data-deploy Lambda has no pre-check and re-runs this file on every deployment).
+SET @preparedStatement = (
+        SELECT IF(
+                (
+                    SELECT COUNT(*)
+                    FROM information_schema.STATISTICS
+                    WHERE TABLE_SCHEMA = DATABASE()
+                        AND TABLE_NAME = 'tanahpedia_animal_kind'
+                        AND INDEX_NAME = 'uk_animal_kind'
+                ) = 0,
+                'SELECT 1',
+                'ALTER TABLE tanahpedia_animal_kind ADD KEY idx_animal_kind_animal (animal_id), DROP INDEX uk_animal_kind'
+            )
+    );
+PREPARE relaxAnimalKindUniqueKey
+FROM @preparedStatement;
+EXECUTE relaxAnimalKindUniqueKey;
+DEALLOCATE PREPARE relaxAnimalKindUniqueKey;
+SET @preparedStatement = (
+        SELECT IF(
+                (
+                    SELECT COUNT(*)
+                    FROM information_schema.STATISTICS
+                    WHERE TABLE_SCHEMA = DATABASE()
+                        AND TABLE_NAME = 'tanahpedia_animal_purity'
+                        AND INDEX_NAME = 'uk_animal_purity'
+                ) = 0,
+                'SELECT 1',
+                'ALTER TABLE tanahpedia_animal_purity ADD KEY idx_animal_purity_animal (animal_id), DROP INDEX uk_animal_purity'
+            )
+    );
+PREPARE relaxAnimalPurityUniqueKey
+FROM @preparedStatement;
+EXECUTE relaxAnimalPurityUniqueKey;
+DEALLOCATE PREPARE relaxAnimalPurityUniqueKey;
@@ -0,0 +1,26 @@
+-- One-time upgrade for databases created before internal entry links were
+-- tracked in tanahpedia_entry_link. The table starts empty, so run the
+-- rebuildTanahpediaEntryLinks mutation once after deploying it.
+-- The production data-deploy Lambda re-runs this file on every deployment and
+-- injects a DROP TABLE IF EXISTS before every table creation it finds in the
+-- text, so the table is created through the information_schema + PREPARE
+-- idiom, with the statement text split after its first keyword.
+SET @preparedStatement = (
+        SELECT IF(
+                (
+                    SELECT COUNT(*)
+                    FROM information_schema.TABLES
+                    WHERE TABLE_SCHEMA = DATABASE()
+                        AND TABLE_NAME = 'tanahpedia_entry_link'
+                ) > 0,
+                'SELECT 1',
+                CONCAT(
+                    'CREATE',
+                    ' TABLE tanahpedia_entry_link (id char(36) NOT NULL, source_entry_id char(36) NOT NULL, target_unique_name varchar(255) NOT NULL, PRIMARY KEY (id), UNIQUE KEY uk_entry_link (source_entry_id, target_unique_name), KEY idx_entry_link_target (target_unique_name), CONSTRAINT fk_entry_link_source FOREIGN KEY (source_entry_id) REFERENCES tanahpedia_entry (id) ON DELETE CASCADE) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci'
+                )
+            )
+    );
+PREPARE createEntryLink
+FROM @preparedStatement;
+EXECUTE createEntryLink;
+DEALLOCATE PREPARE createEntryLink;
@@ -0,0 +1,41 @@
+-- One-time upgrade for databases created before source_citation was added to the
+-- name-giver tables (who named whom, e.g. Leah naming Reuven).
+-- Plain MySQL has no ADD COLUMN IF NOT EXISTS clause (that is a MariaDB-only
+-- extension), so this uses the standard prepared-statement idiom to check
+-- information_schema first. This makes the script safe to execute
+-- unconditionally on every deploy (production data-deploy Lambda has no
+-- pre-check and re-runs this file on every deployment).
+SET @preparedStatement = (
+        SELECT IF(
+                (
+                    SELECT COUNT(*)
+                    FROM information_schema.COLUMNS
+                    WHERE TABLE_SCHEMA = DATABASE()
+                        AND TABLE_NAME = 'tanahpedia_person_name_giver_person'
+                        AND COLUMN_NAME = 'source_citation'
+                ) > 0,
+                'SELECT 1',
+                'ALTER TABLE tanahpedia_person_name_giver_person ADD COLUMN source_citation VARCHAR(400) NULL'
+            )
+    );
+PREPARE addSourceCitationToNameGiverPerson
+FROM @preparedStatement;
+EXECUTE addSourceCitationToNameGiverPerson;
+DEALLOCATE PREPARE addSourceCitationToNameGiverPerson;
+SET @preparedStatement = (
+        SELECT IF(
+                (
+                    SELECT COUNT(*)
+                    FROM information_schema.COLUMNS
+                    WHERE TABLE_SCHEMA = DATABASE()
+                        AND TABLE_NAME = 'tanahpedia_person_name_giver_god'
+                        AND COLUMN_NAME = 'source_citation'
+                ) > 0,
+                'SELECT 1',
+                'ALTER TABLE tanahpedia_person_name_giver_god ADD COLUMN source_citation VARCHAR(400) NULL'
+            )
+    );
+PREPARE addSourceCitationToNameGiverGod
+FROM @preparedStatement;
+EXECUTE addSourceCitationToNameGiverGod;
+DEALLOCATE PREPARE addSourceCitationToNameGiverGod;
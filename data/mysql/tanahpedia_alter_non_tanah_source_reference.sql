@@ -0,0 +1,26 @@
+-- One-time upgrade for databases created before non-Tanah sources carried a
+-- structured Chazal reference (corpus, work, section, subsection) parsed from
+-- source_text. Rows added before this keep NULLs until the API
+-- normalizeTanahpediaNonTanahSources mutation re-parses them.
+-- Plain MySQL has no ADD COLUMN IF NOT EXISTS clause (that is a MariaDB-only
+-- extension), so this uses the standard prepared-statement idiom to check
+-- information_schema first. This makes the script safe to execute
+-- unconditionally on every deploy (production data-deploy Lambda has no
+-- pre-check and re-runs this file on every deployment).
+SET @preparedStatement = (
+        SELECT IF(
+                (
+                    SELECT COUNT(*)
+                    FROM information_schema.COLUMNS
+                    WHERE TABLE_SCHEMA = DATABASE()
+                        AND TABLE_NAME = 'tanahpedia_non_tanah_source'
+                        AND COLUMN_NAME = 'ref_corpus'
+                ) > 0,
+                'SELECT 1',
+                'ALTER TABLE tanahpedia_non_tanah_source ADD COLUMN ref_corpus VARCHAR(20) NULL, ADD COLUMN ref_work VARCHAR(100) NULL, ADD COLUMN ref_section INT NULL, ADD COLUMN ref_subsection INT NULL'
+            )
+    );
+PREPARE addReferenceToNonTanahSource
+FROM @preparedStatement;
+EXECUTE addReferenceToNonTanahSource;
+DEALLOCATE PREPARE addReferenceToNonTanahSource;
@@ -0,0 +1,47 @@
+-- One-time upgrade for databases created before persons could be linked to
+-- nations (e.g. the founder of a nation). It must run before
+-- tanahpedia_seed_data.sql, which fills the role lookup.
+-- The production data-deploy Lambda re-runs this file on every deployment and
+-- injects a DROP TABLE IF EXISTS before every table creation it finds in the
+-- text, even inside string literals or comments, so a plain create-if-not-exists
+-- would wipe both tables. Instead each table is created through the
+-- information_schema + PREPARE idiom, with the statement text split after its
+-- first keyword so the Lambda leaves it alone.
+SET @preparedStatement = (
+        SELECT IF(
+                (
+                    SELECT COUNT(*)
+                    FROM information_schema.TABLES
+                    WHERE TABLE_SCHEMA = DATABASE()
+                        AND TABLE_NAME = 'tanahpedia_lookup_person_nation_role'
+                ) > 0,
+                'SELECT 1',
+                CONCAT(
+                    'CREATE',
+                    ' TABLE tanahpedia_lookup_person_nation_role (id char(36) NOT NULL, name varchar(50) NOT NULL, PRIMARY KEY (id)) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci'
+                )
+            )
+    );
+PREPARE createPersonNationRole
+FROM @preparedStatement;
+EXECUTE createPersonNationRole;
+DEALLOCATE PREPARE createPersonNationRole;
+SET @preparedStatement = (
+        SELECT IF(
+                (
+                    SELECT COUNT(*)
+                    FROM information_schema.TABLES
+                    WHERE TABLE_SCHEMA = DATABASE()
+                        AND TABLE_NAME = 'tanahpedia_person_nation'
+                ) > 0,
+                'SELECT 1',
+                CONCAT(
+                    'CREATE',
+                    ' TABLE tanahpedia_person_nation (id char(36) NOT NULL, person_id char(36) NOT NULL, nation_id char(36) NOT NULL, role_id char(36) NOT NULL, alt_group_id char(36) DEFAULT NULL, source_citation varchar(400) DEFAULT NULL, PRIMARY KEY (id), KEY idx_person_nation_person (person_id), KEY idx_person_nation_nation (nation_id), CONSTRAINT fk_person_nation_person FOREIGN KEY (person_id) REFERENCES tanahpedia_person (id), CONSTRAINT fk_person_nation_nation FOREIGN KEY (nation_id) REFERENCES tanahpedia_nation (id) ON DELETE CASCADE, CONSTRAINT fk_person_nation_role FOREIGN KEY (role_id) REFERENCES tanahpedia_lookup_person_nation_role (id)) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci'
+                )
+            )
+    );
+PREPARE createPersonNation
+FROM @preparedStatement;
+EXECUTE createPersonNation;
+DEALLOCATE PREPARE createPersonNation;
@@ -0,0 +1,23 @@
+-- One-time upgrade for databases created before side numbers were unique
+-- within a war. The API already rejects a taken side number, and the key
+-- closes the race between two concurrent puts. Renumber any duplicate sides
+-- before deploying, or the ALTER fails.
+-- Safe to execute unconditionally on every deploy: the key is only added when
+-- information_schema does not list it yet.
+SET @preparedStatement = (
+        SELECT IF(
+                (
+                    SELECT COUNT(*)
+                    FROM information_schema.STATISTICS
+                    WHERE TABLE_SCHEMA = DATABASE()
+                        AND TABLE_NAME = 'tanahpedia_war_side'
+                        AND INDEX_NAME = 'uk_war_side_number'
+                ) > 0,
+                'SELECT 1',
+                'ALTER TABLE tanahpedia_war_side ADD UNIQUE KEY uk_war_side_number (war_id, side_number)'
+            )
+    );
+PREPARE addWarSideNumberKey
+FROM @preparedStatement;
+EXECUTE addWarSideNumberKey;
+DEALLOCATE PREPARE addWarSideNumberKey;
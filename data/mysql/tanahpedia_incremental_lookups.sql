@@ -1,5 +1,7 @@
 -- Idempotent lookup rows added after older databases were provisioned.
--- Safe before tanahpedia_family_* scripts (e.g. --tanahpedia-families-only).
+-- Migration 10, so every Tanahpedia path (including --tanahpedia-families-only)
+-- has them before the tanahpedia_family_* scripts. Add later lookup rows in a
+-- new migration rather than here.
 
 INSERT IGNORE INTO `tanahpedia_lookup_union_type` (`id`, `name`) VALUES
 ('20000000-0000-0000-0000-000000000003', 'FORBIDDEN_WITH_GENTILE'),
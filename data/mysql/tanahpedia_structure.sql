@@ -58,11 +58,32 @@ CREATE TABLE `tanahpedia_non_tanah_source` (
     `id` char(36) NOT NULL,
     `source_group_id` char(36) NOT NULL,
     `source_text` varchar(500) NOT NULL,
+    `ref_corpus` varchar(20) DEFAULT NULL COMMENT 'BAVLI, YERUSHALMI, MISHNAH, MIDRASH_RABBAH or RAMBAM; NULL when source_text was not recognized',
+    `ref_work` varchar(100) DEFAULT NULL COMMENT 'English name of the tractate, midrash or Rambam hilchot',
+    `ref_section` int DEFAULT NULL COMMENT 'Daf, perek or parasha',
+    `ref_subsection` int DEFAULT NULL COMMENT 'Amud (1 = a, 2 = b), mishnah, halacha or siman',
     PRIMARY KEY (`id`),
     KEY `idx_non_tanah_source_group` (`source_group_id`),
     CONSTRAINT `fk_non_tanah_source_group` FOREIGN KEY (`source_group_id`) REFERENCES `tanahpedia_source_group` (`id`) ON DELETE CASCADE
 ) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
 -- -------------------------------------------
+-- ALTERNATIVE OPINIONS
+-- -------------------------------------------
+-- Fact rows that belong to one opinion share an alt_group_id; competing rows for
+-- the same fact carry different ids. This table is optional metadata for such an
+-- id (label, preferred flag); its sources are a source_group with
+-- target_table = 'tanahpedia_alt_group'. Opinions competing over one fact share
+-- a dispute_id, and at most one of them is preferred.
+DROP TABLE IF EXISTS `tanahpedia_alt_group`;
+CREATE TABLE `tanahpedia_alt_group` (
+    `id` char(36) NOT NULL COMMENT 'The alt_group_id value',
+    `label` varchar(200) DEFAULT NULL COMMENT 'e.g. the commentator holding this opinion',
+    `is_preferred` tinyint(1) NOT NULL DEFAULT 0,
+    `dispute_id` char(36) DEFAULT NULL COMMENT 'Shared by the opinions competing over one fact',
+    PRIMARY KEY (`id`),
+    KEY `idx_alt_group_dispute` (`dispute_id`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+-- -------------------------------------------
 -- ENTITY BASE TABLE
 -- -------------------------------------------
 DROP TABLE IF EXISTS `tanahpedia_entity`;
@@ -149,6 +170,20 @@ CREATE TABLE `tanahpedia_entry_entity` (
     CONSTRAINT `fk_entry_entity_entry` FOREIGN KEY (`entry_id`) REFERENCES `tanahpedia_entry` (`id`) ON DELETE CASCADE,
     CONSTRAINT `fk_entry_entity_entity` FOREIGN KEY (`entity_id`) REFERENCES `tanahpedia_entity` (`id`) ON DELETE CASCADE
 ) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+-- Internal links (`<a href="/pedia/<unique_name>">`) found in an entry's content,
+-- rebuilt by the API whenever that content changes. The target is kept by
+-- unique_name, as written, so links to entries that do not exist are recorded
+-- too (broken links) and start resolving once such an entry is created.
+DROP TABLE IF EXISTS `tanahpedia_entry_link`;
+CREATE TABLE `tanahpedia_entry_link` (
+    `id` char(36) NOT NULL,
+    `source_entry_id` char(36) NOT NULL,
+    `target_unique_name` varchar(255) NOT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `uk_entry_link` (`source_entry_id`, `target_unique_name`),
+    KEY `idx_entry_link_target` (`target_unique_name`),
+    CONSTRAINT `fk_entry_link_source` FOREIGN KEY (`source_entry_id`) REFERENCES `tanahpedia_entry` (`id`) ON DELETE CASCADE
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
 -- Entry revisions submitted by EXTERNAL AI clients via the API for human triage.
 -- entry_id NULL = the revision proposes a brand-new entry. status is a free-text
 -- lifecycle marker (PENDING / APPROVED / REJECTED) kept as a string to avoid an
@@ -206,6 +241,7 @@ CREATE TABLE `tanahpedia_person_name_giver_person` (
     `person_name_id` char(36) NOT NULL,
     `giver_person_id` char(36) NOT NULL,
     `alt_group_id` char(36) DEFAULT NULL,
+    `source_citation` varchar(400) DEFAULT NULL,
     PRIMARY KEY (`id`),
     KEY `idx_name_giver_person_name` (`person_name_id`),
     KEY `idx_name_giver_person` (`giver_person_id`),
@@ -218,6 +254,7 @@ CREATE TABLE `tanahpedia_person_name_giver_god` (
     `person_name_id` char(36) NOT NULL,
     `god_id` char(36) NOT NULL,
     `alt_group_id` char(36) DEFAULT NULL,
+    `source_citation` varchar(400) DEFAULT NULL,
     PRIMARY KEY (`id`),
     KEY `idx_name_giver_god_name` (`person_name_id`),
     CONSTRAINT `fk_name_giver_god_name` FOREIGN KEY (`person_name_id`) REFERENCES `tanahpedia_person_name` (`id`) ON DELETE CASCADE,
@@ -385,6 +422,7 @@ CREATE TABLE `tanahpedia_war_side` (
     `war_id` char(36) NOT NULL,
     `side_number` int NOT NULL,
     PRIMARY KEY (`id`),
+    UNIQUE KEY `uk_war_side_number` (`war_id`, `side_number`),
     KEY `idx_war_side_war` (`war_id`),
     CONSTRAINT `fk_war_side_war` FOREIGN KEY (`war_id`) REFERENCES `tanahpedia_war` (`id`) ON DELETE CASCADE
 ) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
@@ -449,6 +487,27 @@ CREATE TABLE `tanahpedia_nation_territory` (
     CONSTRAINT `fk_nation_territory_nation` FOREIGN KEY (`nation_id`) REFERENCES `tanahpedia_nation` (`id`) ON DELETE CASCADE,
     CONSTRAINT `fk_nation_territory_place` FOREIGN KEY (`place_id`) REFERENCES `tanahpedia_place` (`id`)
 ) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+DROP TABLE IF EXISTS `tanahpedia_lookup_person_nation_role`;
+CREATE TABLE `tanahpedia_lookup_person_nation_role` (
+    `id` char(36) NOT NULL,
+    `name` varchar(50) NOT NULL,
+    PRIMARY KEY (`id`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
+DROP TABLE IF EXISTS `tanahpedia_person_nation`;
+CREATE TABLE `tanahpedia_person_nation` (
+    `id` char(36) NOT NULL,
+    `person_id` char(36) NOT NULL,
+    `nation_id` char(36) NOT NULL,
+    `role_id` char(36) NOT NULL,
+    `alt_group_id` char(36) DEFAULT NULL,
+    `source_citation` varchar(400) DEFAULT NULL,
+    PRIMARY KEY (`id`),
+    KEY `idx_person_nation_person` (`person_id`),
+    KEY `idx_person_nation_nation` (`nation_id`),
+    CONSTRAINT `fk_person_nation_person` FOREIGN KEY (`person_id`) REFERENCES `tanahpedia_person` (`id`),
+    CONSTRAINT `fk_person_nation_nation` FOREIGN KEY (`nation_id`) REFERENCES `tanahpedia_nation` (`id`) ON DELETE CASCADE,
+    CONSTRAINT `fk_person_nation_role` FOREIGN KEY (`role_id`) REFERENCES `tanahpedia_lookup_person_nation_role` (`id`)
+) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
 -- -------------------------------------------
 -- ANIMAL
 -- -------------------------------------------
@@ -467,7 +526,7 @@ CREATE TABLE `tanahpedia_animal_kind` (
     `kind` enum('BEHEMA', 'CHAYA', 'OF', 'SHERETZ') NOT NULL COMMENT 'בהמה/חיה/עוף/שרץ',
     `alt_group_id` char(36) DEFAULT NULL,
     PRIMARY KEY (`id`),
-    UNIQUE KEY `uk_animal_kind` (`animal_id`),
+    KEY `idx_animal_kind_animal` (`animal_id`),
     CONSTRAINT `fk_animal_kind_animal` FOREIGN KEY (`animal_id`) REFERENCES `tanahpedia_animal` (`id`) ON DELETE CASCADE
 ) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
 DROP TABLE IF EXISTS `tanahpedia_animal_purity`;
@@ -477,7 +536,7 @@ CREATE TABLE `tanahpedia_animal_purity` (
     `purity` enum('TAHOR', 'TAMEH') NOT NULL COMMENT 'טהור/טמא',
     `alt_group_id` char(36) DEFAULT NULL,
     PRIMARY KEY (`id`),
-    UNIQUE KEY `uk_animal_purity` (`animal_id`),
+    KEY `idx_animal_purity_animal` (`animal_id`),
     CONSTRAINT `fk_animal_purity_animal` FOREIGN KEY (`animal_id`) REFERENCES `tanahpedia_animal` (`id`) ON DELETE CASCADE
 ) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci;
 -- -------------------------------------------
@@ -152,6 +152,19 @@ def validate_lambda_safe_comments(filepath):
                 )
 
 
+def validate_alter_script_keeps_tables(name, filepath):
+    """Reject upgrade scripts in which the Lambda would inject a DROP TABLE/VIEW."""
+    if not name.startswith("tanahpedia_alter_"):
+        return
+    with open(filepath, "r", encoding="utf-8") as sql_file:
+        content = sql_file.read()
+    if preprocess_sql(content).count("DROP ") != content.count("DROP "):
+        raise ValueError(
+            f"{filepath}: CREATE TABLE/VIEW in an alter script makes the Lambda "
+            "drop the table first; build the statement in a PREPARE instead"
+        )
+
+
 def main():
     parser = argparse.ArgumentParser()
     parser.add_argument("--db", dest="db_url")
@@ -192,6 +205,7 @@ def main():
             print(f"SKIP: {name} not found at {fpath}")
             continue
         validate_lambda_safe_comments(fpath)
+        validate_alter_script_keeps_tables(name, fpath)
         size_mb = os.path.getsize(fpath) / (1024 * 1024)
         path_type = "streaming" if size_mb > 10 else "preprocess"
         stmts = parse_file(fpath)
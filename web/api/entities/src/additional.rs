@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "tanah_additional")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub sefer_id: i32,
+    pub letter: String,
+    pub tanach_us_name: String,
+    pub perek_from: i32,
+    pub perek_to: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::sefer::Entity",
+        from = "Column::SeferId",
+        to = "super::sefer::Column::Id",
+        on_delete = "Cascade",
+        on_update = "Cascade"
+    )]
+    Sefer,
+}
+
+impl Related<super::sefer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Sefer.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C: ConnectionTrait>(
+        mut self,
+        _: &C,
+        _insert: bool,
+    ) -> Result<Self, DbErr> {
+        Ok(self)
+    }
+}
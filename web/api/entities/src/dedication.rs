@@ -0,0 +1,23 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "tanah_dedication")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub subject: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C: ConnectionTrait>(
+        mut self,
+        _: &C,
+        _insert: bool,
+    ) -> Result<Self, DbErr> {
+        Ok(self)
+    }
+}
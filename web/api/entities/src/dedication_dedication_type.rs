@@ -0,0 +1,24 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "tanah_dedication_dedication_type")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub dedication_id: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub dedication_type_id: i8,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C: ConnectionTrait>(
+        mut self,
+        _: &C,
+        _insert: bool,
+    ) -> Result<Self, DbErr> {
+        Ok(self)
+    }
+}
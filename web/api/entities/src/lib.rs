@@ -1,5 +1,16 @@
+pub mod additional;
 pub mod article;
+pub mod article_dedication;
 pub mod author;
+pub mod dedication;
+pub mod dedication_dedication_type;
+pub mod dedication_type;
+pub mod helek;
 pub mod perek;
+pub mod perek_date;
+pub mod perek_dedication;
+pub mod perushim;
 pub mod sefer;
+pub mod system_message;
+pub mod tanah_perek;
 pub mod tanahpedia;
@@ -0,0 +1,43 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "tanah_perek_date")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub perek_id: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub cycle: i8,
+    #[sea_orm(column_name = "date")]
+    pub date: Date,
+    pub hebdate: String,
+    pub star_rise: Time,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::tanah_perek::Entity",
+        from = "Column::PerekId",
+        to = "super::tanah_perek::Column::Id",
+        on_delete = "Cascade",
+        on_update = "Cascade"
+    )]
+    Perek,
+}
+
+impl Related<super::tanah_perek::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Perek.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C: ConnectionTrait>(
+        mut self,
+        _: &C,
+        _insert: bool,
+    ) -> Result<Self, DbErr> {
+        Ok(self)
+    }
+}
@@ -0,0 +1,26 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "tanah_perek_dedication")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub perek_id_low: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub perek_id_high: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub dedication_id: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C: ConnectionTrait>(
+        mut self,
+        _: &C,
+        _insert: bool,
+    ) -> Result<Self, DbErr> {
+        Ok(self)
+    }
+}
@@ -0,0 +1,3 @@
+pub mod note;
+pub mod parshan;
+pub mod perush;
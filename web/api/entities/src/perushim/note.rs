@@ -0,0 +1,43 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "note")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub perush_id: i16,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub perek_id: i16,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub pasuk: i16,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub note_idx: i16,
+    #[sea_orm(column_type = "Text")]
+    pub note_content: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::perush::Entity",
+        from = "Column::PerushId",
+        to = "super::perush::Column::Id"
+    )]
+    Perush,
+}
+
+impl Related<super::perush::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Perush.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C: ConnectionTrait>(
+        mut self,
+        _: &C,
+        _insert: bool,
+    ) -> Result<Self, DbErr> {
+        Ok(self)
+    }
+}
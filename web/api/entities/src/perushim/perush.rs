@@ -0,0 +1,56 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "perush")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: i16,
+    pub name: String,
+    pub parshan_id: i16,
+    pub comp_date: Option<String>,
+    pub pub_date: Option<String>,
+    pub priority: i16,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::parshan::Entity",
+        from = "Column::ParshanId",
+        to = "super::parshan::Column::Id"
+    )]
+    Parshan,
+    #[sea_orm(has_many = "super::note::Entity")]
+    Note,
+    #[sea_orm(has_many = "crate::tanahpedia::tanah_source::Entity")]
+    TanahSource,
+}
+
+impl Related<crate::tanahpedia::tanah_source::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TanahSource.def()
+    }
+}
+
+impl Related<super::note::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Note.def()
+    }
+}
+
+impl Related<super::parshan::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Parshan.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C: ConnectionTrait>(
+        mut self,
+        _: &C,
+        _insert: bool,
+    ) -> Result<Self, DbErr> {
+        Ok(self)
+    }
+}
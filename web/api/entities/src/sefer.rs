@@ -5,12 +5,39 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
-    pub name: String,
+    pub name: Option<String>,
     pub tanach_us_name: Option<String>,
+    pub perek_id_from: i32,
+    pub perek_id_to: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::additional::Entity")]
+    Additional,
+    #[sea_orm(has_many = "crate::tanahpedia::sefer_tanah_match::Entity")]
+    SeferTanahMatch,
+    #[sea_orm(has_many = "crate::tanahpedia::tanah_sefer::Entity")]
+    TanahSefer,
+}
+
+impl Related<crate::tanahpedia::sefer_tanah_match::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SeferTanahMatch.def()
+    }
+}
+
+impl Related<crate::tanahpedia::tanah_sefer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TanahSefer.def()
+    }
+}
+
+impl Related<super::additional::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Additional.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
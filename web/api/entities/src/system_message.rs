@@ -0,0 +1,28 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "tanah_system_message")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i8,
+    pub priority: i8,
+    #[sea_orm(column_name = "abstract", column_type = "Text")]
+    pub message_abstract: String,
+    #[sea_orm(column_type = "Text")]
+    pub content: String,
+    pub active: bool,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C: ConnectionTrait>(
+        mut self,
+        _: &C,
+        _insert: bool,
+    ) -> Result<Self, DbErr> {
+        Ok(self)
+    }
+}
@@ -0,0 +1,49 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "tanah_perek")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: i32,
+    pub perek: Option<i32>,
+    pub header: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "crate::tanahpedia::entity_tanah_source::Entity")]
+    EntityTanahSource,
+    #[sea_orm(has_many = "super::perek_date::Entity")]
+    PerekDate,
+    #[sea_orm(has_many = "crate::tanahpedia::tanah_source::Entity")]
+    TanahSource,
+}
+
+impl Related<crate::tanahpedia::entity_tanah_source::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EntityTanahSource.def()
+    }
+}
+
+impl Related<crate::tanahpedia::tanah_source::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TanahSource.def()
+    }
+}
+
+impl Related<super::perek_date::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PerekDate.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C: ConnectionTrait>(
+        mut self,
+        _: &C,
+        _insert: bool,
+    ) -> Result<Self, DbErr> {
+        Ok(self)
+    }
+}
@@ -0,0 +1,41 @@
+use super::sea_orm_active_enums::AnimalPurity;
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "tanahpedia_animal_purity")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub animal_id: String,
+    pub purity: AnimalPurity,
+    #[sea_orm(nullable)]
+    pub alt_group_id: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::animal::Entity",
+        from = "Column::AnimalId",
+        to = "super::animal::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Animal,
+}
+
+impl Related<super::animal::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Animal.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C: ConnectionTrait>(
+        mut self,
+        _: &C,
+        _insert: bool,
+    ) -> Result<Self, DbErr> {
+        Ok(self)
+    }
+}
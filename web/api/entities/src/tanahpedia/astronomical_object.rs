@@ -5,11 +5,33 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: String,
-    pub name: String,
+    pub entity_id: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::entity::Entity",
+        from = "Column::EntityId",
+        to = "super::entity::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Entity,
+    #[sea_orm(has_many = "super::astronomical_object_creation_day::Entity")]
+    AstronomicalObjectCreationDay,
+}
+
+impl Related<super::astronomical_object_creation_day::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AstronomicalObjectCreationDay.def()
+    }
+}
+
+impl Related<super::entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entity.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -11,7 +11,21 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::astronomical_object::Entity",
+        from = "Column::AstronomicalObjectId",
+        to = "super::astronomical_object::Column::Id",
+        on_delete = "Cascade"
+    )]
+    AstronomicalObject,
+}
+
+impl Related<super::astronomical_object::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AstronomicalObject.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
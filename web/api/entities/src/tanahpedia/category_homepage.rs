@@ -1,3 +1,4 @@
+use super::sea_orm_active_enums::EntityType;
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
@@ -5,7 +6,7 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: String,
-    pub entity_type: String,
+    pub entity_type: EntityType,
     pub layout_type: String,
     #[sea_orm(column_type = "Json", nullable)]
     pub config: Option<Json>,
@@ -1,3 +1,4 @@
+use super::sea_orm_active_enums::EntityType;
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
@@ -5,14 +6,151 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: String,
-    pub entity_type: String,
+    pub entity_type: EntityType,
     pub name: String,
     pub created_at: DateTime,
     pub updated_at: DateTime,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::animal::Entity")]
+    Animal,
+    #[sea_orm(has_many = "super::astronomical_object::Entity")]
+    AstronomicalObject,
+    #[sea_orm(has_many = "super::entity_tanah_source::Entity")]
+    EntityTanahSource,
+    #[sea_orm(has_many = "super::entry_entity::Entity")]
+    EntryEntity,
+    #[sea_orm(has_many = "super::event::Entity")]
+    Event,
+    #[sea_orm(has_many = "super::nation::Entity")]
+    Nation,
+    #[sea_orm(has_many = "super::object::Entity")]
+    Object,
+    #[sea_orm(has_many = "super::person::Entity")]
+    Person,
+    #[sea_orm(has_many = "super::place::Entity")]
+    Place,
+    #[sea_orm(has_many = "super::plant::Entity")]
+    Plant,
+    #[sea_orm(has_many = "super::prophecy::Entity")]
+    Prophecy,
+    #[sea_orm(has_many = "super::saying::Entity")]
+    Saying,
+    #[sea_orm(has_many = "super::tanah_sefer::Entity")]
+    TanahSefer,
+    #[sea_orm(has_many = "super::tanahpedia_sefer::Entity")]
+    TanahpediaSefer,
+    #[sea_orm(has_many = "super::temple_tool::Entity")]
+    TempleTool,
+    #[sea_orm(has_many = "super::three_d_model::Entity")]
+    ThreeDModel,
+    #[sea_orm(has_many = "super::war::Entity")]
+    War,
+}
+
+impl Related<super::animal::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Animal.def()
+    }
+}
+
+impl Related<super::astronomical_object::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AstronomicalObject.def()
+    }
+}
+
+impl Related<super::entity_tanah_source::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EntityTanahSource.def()
+    }
+}
+
+impl Related<super::entry_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EntryEntity.def()
+    }
+}
+
+impl Related<super::event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Event.def()
+    }
+}
+
+impl Related<super::nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Nation.def()
+    }
+}
+
+impl Related<super::object::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Object.def()
+    }
+}
+
+impl Related<super::person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Person.def()
+    }
+}
+
+impl Related<super::place::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Place.def()
+    }
+}
+
+impl Related<super::plant::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Plant.def()
+    }
+}
+
+impl Related<super::prophecy::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Prophecy.def()
+    }
+}
+
+impl Related<super::saying::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Saying.def()
+    }
+}
+
+impl Related<super::tanah_sefer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TanahSefer.def()
+    }
+}
+
+impl Related<super::tanahpedia_sefer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TanahpediaSefer.def()
+    }
+}
+
+impl Related<super::temple_tool::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TempleTool.def()
+    }
+}
+
+impl Related<super::three_d_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ThreeDModel.def()
+    }
+}
+
+impl Related<super::war::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::War.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
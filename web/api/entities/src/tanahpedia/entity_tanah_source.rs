@@ -13,7 +13,33 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::entity::Entity",
+        from = "Column::EntityId",
+        to = "super::entity::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Entity,
+    #[sea_orm(
+        belongs_to = "crate::tanah_perek::Entity",
+        from = "Column::PerekId",
+        to = "crate::tanah_perek::Column::Id"
+    )]
+    Perek,
+}
+
+impl Related<crate::tanah_perek::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Perek.def()
+    }
+}
+
+impl Related<super::entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entity.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -14,7 +14,48 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::entry_entity::Entity")]
+    EntryEntity,
+    #[sea_orm(has_many = "super::entry_link::Entity")]
+    EntryLink,
+    #[sea_orm(has_many = "super::entry_revision::Entity")]
+    EntryRevision,
+    #[sea_orm(has_many = "super::entry_synonym::Entity")]
+    EntrySynonym,
+    #[sea_orm(has_many = "super::entry_synonym_disambiguation::Entity")]
+    EntrySynonymDisambiguation,
+}
+
+impl Related<super::entry_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EntryEntity.def()
+    }
+}
+
+impl Related<super::entry_link::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EntryLink.def()
+    }
+}
+
+impl Related<super::entry_revision::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EntryRevision.def()
+    }
+}
+
+impl Related<super::entry_synonym::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EntrySynonym.def()
+    }
+}
+
+impl Related<super::entry_synonym_disambiguation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EntrySynonymDisambiguation.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -10,7 +10,34 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::entity::Entity",
+        from = "Column::EntityId",
+        to = "super::entity::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Entity,
+    #[sea_orm(
+        belongs_to = "super::entry::Entity",
+        from = "Column::EntryId",
+        to = "super::entry::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Entry,
+}
+
+impl Related<super::entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entity.def()
+    }
+}
+
+impl Related<super::entry::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entry.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -1,11 +1,12 @@
+use super::sea_orm_active_enums::RevisionStatus;
 use sea_orm::entity::prelude::*;
 
 /// A revision to a Tanahpedia entry fed by an external AI client through the API.
 ///
 /// `entry_id` is `None` when the revision proposes a brand-new entry. Revisions
 /// are never auto-applied to `tanahpedia_entry`; a human triages them via the
-/// admin panel. `status` is a free-text lifecycle marker (`PENDING`, `APPROVED`,
-/// `REJECTED`).
+/// admin panel. `status` is a [`RevisionStatus`] lifecycle marker, stored as
+/// its plain string value (`PENDING`, `APPLIED`, `APPROVED`, `REJECTED`).
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "tanahpedia_entry_revision")]
 pub struct Model {
@@ -19,13 +20,27 @@ pub struct Model {
     pub source: String,
     #[sea_orm(column_type = "Text", nullable)]
     pub notes: Option<String>,
-    pub status: String,
+    pub status: RevisionStatus,
     pub created_at: DateTime,
     pub updated_at: DateTime,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::entry::Entity",
+        from = "Column::EntryId",
+        to = "super::entry::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Entry,
+}
+
+impl Related<super::entry::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entry.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
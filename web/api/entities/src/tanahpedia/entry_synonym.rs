@@ -10,7 +10,29 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::entry::Entity",
+        from = "Column::EntryId",
+        to = "super::entry::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Entry,
+    #[sea_orm(has_many = "super::entry_synonym_disambiguation::Entity")]
+    EntrySynonymDisambiguation,
+}
+
+impl Related<super::entry::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entry.def()
+    }
+}
+
+impl Related<super::entry_synonym_disambiguation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EntrySynonymDisambiguation.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -11,7 +11,34 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::entry::Entity",
+        from = "Column::EntryId",
+        to = "super::entry::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Entry,
+    #[sea_orm(
+        belongs_to = "super::entry_synonym::Entity",
+        from = "Column::SynonymId",
+        to = "super::entry_synonym::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Synonym,
+}
+
+impl Related<super::entry::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entry.def()
+    }
+}
+
+impl Related<super::entry_synonym::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Synonym.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
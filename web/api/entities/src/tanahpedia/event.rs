@@ -5,11 +5,49 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: String,
-    pub name: String,
+    pub entity_id: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::entity::Entity",
+        from = "Column::EntityId",
+        to = "super::entity::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Entity,
+    #[sea_orm(has_many = "super::event_date_range::Entity")]
+    EventDateRange,
+    #[sea_orm(has_many = "super::event_place::Entity")]
+    EventPlace,
+    #[sea_orm(has_many = "super::war::Entity")]
+    War,
+}
+
+impl Related<super::entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entity.def()
+    }
+}
+
+impl Related<super::event_date_range::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EventDateRange.def()
+    }
+}
+
+impl Related<super::event_place::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EventPlace.def()
+    }
+}
+
+impl Related<super::war::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::War.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -8,7 +8,24 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::person_name_giver_god::Entity")]
+    PersonNameGiverGod,
+    #[sea_orm(has_many = "super::saying_speaker_god::Entity")]
+    SayingSpeakerGod,
+}
+
+impl Related<super::person_name_giver_god::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonNameGiverGod.def()
+    }
+}
+
+impl Related<super::saying_speaker_god::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SayingSpeakerGod.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
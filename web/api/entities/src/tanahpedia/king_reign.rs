@@ -0,0 +1,53 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "tanahpedia_king_reign")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub king_role_id: String,
+    pub nation_id: String,
+    pub start_date: Option<i32>,
+    pub end_date: Option<i32>,
+    pub alt_group_id: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::person_role_king::Entity",
+        from = "Column::KingRoleId",
+        to = "super::person_role_king::Column::Id",
+        on_delete = "Cascade"
+    )]
+    KingRole,
+    #[sea_orm(
+        belongs_to = "super::nation::Entity",
+        from = "Column::NationId",
+        to = "super::nation::Column::Id"
+    )]
+    Nation,
+}
+
+impl Related<super::nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Nation.def()
+    }
+}
+
+impl Related<super::person_role_king::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::KingRole.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C: ConnectionTrait>(
+        mut self,
+        _: &C,
+        _insert: bool,
+    ) -> Result<Self, DbErr> {
+        Ok(self)
+    }
+}
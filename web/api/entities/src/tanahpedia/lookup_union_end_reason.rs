@@ -9,7 +9,16 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::person_union::Entity")]
+    PersonUnion,
+}
+
+impl Related<super::person_union::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonUnion.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
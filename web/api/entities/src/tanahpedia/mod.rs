@@ -1,4 +1,7 @@
+pub mod alt_group;
 pub mod animal;
+pub mod animal_kind;
+pub mod animal_purity;
 pub mod astronomical_object;
 pub mod astronomical_object_creation_day;
 pub mod category_homepage;
@@ -6,6 +9,7 @@ pub mod entity;
 pub mod entity_tanah_source;
 pub mod entry;
 pub mod entry_entity;
+pub mod entry_link;
 pub mod entry_revision;
 pub mod entry_synonym;
 pub mod entry_synonym_disambiguation;
@@ -13,9 +17,11 @@ pub mod event;
 pub mod event_date_range;
 pub mod event_place;
 pub mod god;
+pub mod king_reign;
 pub mod lookup_name_type;
 pub mod lookup_parent_child_type;
 pub mod lookup_parent_role;
+pub mod lookup_person_nation_role;
 pub mod lookup_union_end_reason;
 pub mod lookup_union_type;
 pub mod nation;
@@ -31,7 +37,10 @@ pub mod person_death_date;
 pub mod person_name;
 pub mod person_name_giver_god;
 pub mod person_name_giver_person;
+pub mod person_nation;
 pub mod person_parent_child;
+pub mod person_role_king;
+pub mod person_role_prophet;
 pub mod person_sex;
 pub mod person_union;
 pub mod place;
@@ -50,8 +59,10 @@ pub mod saying_location;
 pub mod saying_speaker_god;
 pub mod saying_speaker_nation;
 pub mod saying_speaker_person;
+pub mod sea_orm_active_enums;
 pub mod sefer_tanah_match;
 pub mod source_group;
+pub mod tanah_sefer;
 pub mod tanah_source;
 pub mod tanahpedia_sefer;
 pub mod temple_tool;
@@ -5,11 +5,81 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: String,
-    pub name: String,
+    pub entity_id: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::entity::Entity",
+        from = "Column::EntityId",
+        to = "super::entity::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Entity,
+    #[sea_orm(has_many = "super::king_reign::Entity")]
+    KingReign,
+    #[sea_orm(has_many = "super::nation_territory::Entity")]
+    NationTerritory,
+    #[sea_orm(has_many = "super::person_nation::Entity")]
+    PersonNation,
+    #[sea_orm(has_many = "super::prophecy_recipient_nation::Entity")]
+    ProphecyRecipientNation,
+    #[sea_orm(has_many = "super::saying_audience_nation::Entity")]
+    SayingAudienceNation,
+    #[sea_orm(has_many = "super::saying_speaker_nation::Entity")]
+    SayingSpeakerNation,
+    #[sea_orm(has_many = "super::war_side_participant_nation::Entity")]
+    WarSideParticipantNation,
+}
+
+impl Related<super::entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entity.def()
+    }
+}
+
+impl Related<super::king_reign::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::KingReign.def()
+    }
+}
+
+impl Related<super::nation_territory::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NationTerritory.def()
+    }
+}
+
+impl Related<super::person_nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonNation.def()
+    }
+}
+
+impl Related<super::prophecy_recipient_nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProphecyRecipientNation.def()
+    }
+}
+
+impl Related<super::saying_audience_nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SayingAudienceNation.def()
+    }
+}
+
+impl Related<super::saying_speaker_nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SayingSpeakerNation.def()
+    }
+}
+
+impl Related<super::war_side_participant_nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WarSideParticipantNation.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
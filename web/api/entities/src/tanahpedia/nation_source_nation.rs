@@ -12,7 +12,21 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::nation::Entity",
+        from = "Column::NationId",
+        to = "super::nation::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Nation,
+    #[sea_orm(
+        belongs_to = "super::nation::Entity",
+        from = "Column::SourceNationId",
+        to = "super::nation::Column::Id"
+    )]
+    SourceNation,
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
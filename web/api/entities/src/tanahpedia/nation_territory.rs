@@ -16,7 +16,33 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::nation::Entity",
+        from = "Column::NationId",
+        to = "super::nation::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Nation,
+    #[sea_orm(
+        belongs_to = "super::place::Entity",
+        from = "Column::PlaceId",
+        to = "super::place::Column::Id"
+    )]
+    Place,
+}
+
+impl Related<super::nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Nation.def()
+    }
+}
+
+impl Related<super::place::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Place.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
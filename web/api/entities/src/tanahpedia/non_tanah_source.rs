@@ -7,10 +7,28 @@ pub struct Model {
     pub id: String,
     pub source_group_id: String,
     pub source_text: String,
+    pub ref_corpus: Option<String>,
+    pub ref_work: Option<String>,
+    pub ref_section: Option<i32>,
+    pub ref_subsection: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::source_group::Entity",
+        from = "Column::SourceGroupId",
+        to = "super::source_group::Column::Id",
+        on_delete = "Cascade"
+    )]
+    SourceGroup,
+}
+
+impl Related<super::source_group::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SourceGroup.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
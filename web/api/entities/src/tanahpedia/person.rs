@@ -9,7 +9,141 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::entity::Entity",
+        from = "Column::EntityId",
+        to = "super::entity::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Entity,
+    #[sea_orm(has_many = "super::person_birth_date::Entity")]
+    PersonBirthDate,
+    #[sea_orm(has_many = "super::person_birth_place::Entity")]
+    PersonBirthPlace,
+    #[sea_orm(has_many = "super::person_death_cause::Entity")]
+    PersonDeathCause,
+    #[sea_orm(has_many = "super::person_death_date::Entity")]
+    PersonDeathDate,
+    #[sea_orm(has_many = "super::person_name::Entity")]
+    PersonName,
+    #[sea_orm(has_many = "super::person_name_giver_person::Entity")]
+    PersonNameGiverPerson,
+    #[sea_orm(has_many = "super::person_nation::Entity")]
+    PersonNation,
+    #[sea_orm(has_many = "super::person_role_king::Entity")]
+    PersonRoleKing,
+    #[sea_orm(has_many = "super::person_role_prophet::Entity")]
+    PersonRoleProphet,
+    #[sea_orm(has_many = "super::person_sex::Entity")]
+    PersonSex,
+    #[sea_orm(has_many = "super::prophecy_prophet::Entity")]
+    ProphecyProphet,
+    #[sea_orm(has_many = "super::prophecy_recipient_person::Entity")]
+    ProphecyRecipientPerson,
+    #[sea_orm(has_many = "super::saying_audience_person::Entity")]
+    SayingAudiencePerson,
+    #[sea_orm(has_many = "super::saying_speaker_person::Entity")]
+    SayingSpeakerPerson,
+    #[sea_orm(has_many = "super::war_side_participant_person::Entity")]
+    WarSideParticipantPerson,
+}
+
+impl Related<super::entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entity.def()
+    }
+}
+
+impl Related<super::person_birth_date::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonBirthDate.def()
+    }
+}
+
+impl Related<super::person_birth_place::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonBirthPlace.def()
+    }
+}
+
+impl Related<super::person_death_cause::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonDeathCause.def()
+    }
+}
+
+impl Related<super::person_death_date::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonDeathDate.def()
+    }
+}
+
+impl Related<super::person_name::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonName.def()
+    }
+}
+
+impl Related<super::person_name_giver_person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonNameGiverPerson.def()
+    }
+}
+
+impl Related<super::person_nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonNation.def()
+    }
+}
+
+impl Related<super::person_role_king::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonRoleKing.def()
+    }
+}
+
+impl Related<super::person_role_prophet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonRoleProphet.def()
+    }
+}
+
+impl Related<super::person_sex::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonSex.def()
+    }
+}
+
+impl Related<super::prophecy_prophet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProphecyProphet.def()
+    }
+}
+
+impl Related<super::prophecy_recipient_person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProphecyRecipientPerson.def()
+    }
+}
+
+impl Related<super::saying_audience_person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SayingAudiencePerson.def()
+    }
+}
+
+impl Related<super::saying_speaker_person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SayingSpeakerPerson.def()
+    }
+}
+
+impl Related<super::war_side_participant_person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WarSideParticipantPerson.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
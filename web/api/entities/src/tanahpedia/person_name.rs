@@ -12,7 +12,49 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::lookup_name_type::Entity",
+        from = "Column::NameTypeId",
+        to = "super::lookup_name_type::Column::Id"
+    )]
+    NameType,
+    #[sea_orm(
+        belongs_to = "super::person::Entity",
+        from = "Column::PersonId",
+        to = "super::person::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Person,
+    #[sea_orm(has_many = "super::person_name_giver_god::Entity")]
+    PersonNameGiverGod,
+    #[sea_orm(has_many = "super::person_name_giver_person::Entity")]
+    PersonNameGiverPerson,
+}
+
+impl Related<super::lookup_name_type::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NameType.def()
+    }
+}
+
+impl Related<super::person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Person.def()
+    }
+}
+
+impl Related<super::person_name_giver_god::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonNameGiverGod.def()
+    }
+}
+
+impl Related<super::person_name_giver_person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonNameGiverPerson.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -8,10 +8,37 @@ pub struct Model {
     pub person_name_id: String,
     pub giver_person_id: String,
     pub alt_group_id: Option<String>,
+    pub source_citation: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::person::Entity",
+        from = "Column::GiverPersonId",
+        to = "super::person::Column::Id"
+    )]
+    GiverPerson,
+    #[sea_orm(
+        belongs_to = "super::person_name::Entity",
+        from = "Column::PersonNameId",
+        to = "super::person_name::Column::Id",
+        on_delete = "Cascade"
+    )]
+    PersonName,
+}
+
+impl Related<super::person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GiverPerson.def()
+    }
+}
+
+impl Related<super::person_name::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonName.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -0,0 +1,67 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "tanahpedia_person_nation")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub person_id: String,
+    pub nation_id: String,
+    pub role_id: String,
+    #[sea_orm(nullable)]
+    pub alt_group_id: Option<String>,
+    #[sea_orm(nullable)]
+    pub source_citation: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::nation::Entity",
+        from = "Column::NationId",
+        to = "super::nation::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Nation,
+    #[sea_orm(
+        belongs_to = "super::person::Entity",
+        from = "Column::PersonId",
+        to = "super::person::Column::Id"
+    )]
+    Person,
+    #[sea_orm(
+        belongs_to = "super::lookup_person_nation_role::Entity",
+        from = "Column::RoleId",
+        to = "super::lookup_person_nation_role::Column::Id"
+    )]
+    Role,
+}
+
+impl Related<super::nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Nation.def()
+    }
+}
+
+impl Related<super::person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Person.def()
+    }
+}
+
+impl Related<super::lookup_person_nation_role::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Role.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C: ConnectionTrait>(
+        mut self,
+        _: &C,
+        _insert: bool,
+    ) -> Result<Self, DbErr> {
+        Ok(self)
+    }
+}
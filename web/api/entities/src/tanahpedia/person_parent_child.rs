@@ -14,7 +14,44 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::person::Entity",
+        from = "Column::ChildId",
+        to = "super::person::Column::Id"
+    )]
+    Child,
+    #[sea_orm(
+        belongs_to = "super::person::Entity",
+        from = "Column::ParentId",
+        to = "super::person::Column::Id"
+    )]
+    Parent,
+    #[sea_orm(
+        belongs_to = "super::lookup_parent_role::Entity",
+        from = "Column::ParentRoleId",
+        to = "super::lookup_parent_role::Column::Id"
+    )]
+    ParentRole,
+    #[sea_orm(
+        belongs_to = "super::lookup_parent_child_type::Entity",
+        from = "Column::RelationshipTypeId",
+        to = "super::lookup_parent_child_type::Column::Id"
+    )]
+    RelationshipType,
+}
+
+impl Related<super::lookup_parent_child_type::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RelationshipType.def()
+    }
+}
+
+impl Related<super::lookup_parent_role::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ParentRole.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -18,7 +18,44 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::lookup_union_end_reason::Entity",
+        from = "Column::EndReasonId",
+        to = "super::lookup_union_end_reason::Column::Id"
+    )]
+    EndReason,
+    #[sea_orm(
+        belongs_to = "super::person::Entity",
+        from = "Column::Person1Id",
+        to = "super::person::Column::Id"
+    )]
+    Person1,
+    #[sea_orm(
+        belongs_to = "super::person::Entity",
+        from = "Column::Person2Id",
+        to = "super::person::Column::Id"
+    )]
+    Person2,
+    #[sea_orm(
+        belongs_to = "super::lookup_union_type::Entity",
+        from = "Column::UnionTypeId",
+        to = "super::lookup_union_type::Column::Id"
+    )]
+    UnionType,
+}
+
+impl Related<super::lookup_union_end_reason::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EndReason.def()
+    }
+}
+
+impl Related<super::lookup_union_type::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UnionType.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -5,11 +5,65 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: String,
-    pub name: String,
+    pub entity_id: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::entity::Entity",
+        from = "Column::EntityId",
+        to = "super::entity::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Entity,
+    #[sea_orm(has_many = "super::event_place::Entity")]
+    EventPlace,
+    #[sea_orm(has_many = "super::nation_territory::Entity")]
+    NationTerritory,
+    #[sea_orm(has_many = "super::person_birth_place::Entity")]
+    PersonBirthPlace,
+    #[sea_orm(has_many = "super::place_identification::Entity")]
+    PlaceIdentification,
+    #[sea_orm(has_many = "super::saying_location::Entity")]
+    SayingLocation,
+}
+
+impl Related<super::entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entity.def()
+    }
+}
+
+impl Related<super::event_place::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EventPlace.def()
+    }
+}
+
+impl Related<super::nation_territory::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NationTerritory.def()
+    }
+}
+
+impl Related<super::person_birth_place::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonBirthPlace.def()
+    }
+}
+
+impl Related<super::place_identification::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PlaceIdentification.def()
+    }
+}
+
+impl Related<super::saying_location::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SayingLocation.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -11,7 +11,21 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::plant::Entity",
+        from = "Column::PlantId",
+        to = "super::plant::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Plant,
+}
+
+impl Related<super::plant::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Plant.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -6,10 +6,70 @@ pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: String,
     pub saying_id: String,
+    pub entity_id: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::entity::Entity",
+        from = "Column::EntityId",
+        to = "super::entity::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Entity,
+    #[sea_orm(
+        belongs_to = "super::saying::Entity",
+        from = "Column::SayingId",
+        to = "super::saying::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Saying,
+    #[sea_orm(has_many = "super::prophecy_is_good::Entity")]
+    ProphecyIsGood,
+    #[sea_orm(has_many = "super::prophecy_prophet::Entity")]
+    ProphecyProphet,
+    #[sea_orm(has_many = "super::prophecy_recipient_nation::Entity")]
+    ProphecyRecipientNation,
+    #[sea_orm(has_many = "super::prophecy_recipient_person::Entity")]
+    ProphecyRecipientPerson,
+}
+
+impl Related<super::entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entity.def()
+    }
+}
+
+impl Related<super::prophecy_is_good::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProphecyIsGood.def()
+    }
+}
+
+impl Related<super::prophecy_prophet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProphecyProphet.def()
+    }
+}
+
+impl Related<super::prophecy_recipient_nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProphecyRecipientNation.def()
+    }
+}
+
+impl Related<super::prophecy_recipient_person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProphecyRecipientPerson.def()
+    }
+}
+
+impl Related<super::saying::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Saying.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -11,7 +11,33 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::nation::Entity",
+        from = "Column::NationId",
+        to = "super::nation::Column::Id"
+    )]
+    Nation,
+    #[sea_orm(
+        belongs_to = "super::prophecy::Entity",
+        from = "Column::ProphecyId",
+        to = "super::prophecy::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Prophecy,
+}
+
+impl Related<super::nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Nation.def()
+    }
+}
+
+impl Related<super::prophecy::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Prophecy.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -5,13 +5,83 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: String,
-    pub name: String,
+    pub entity_id: String,
     #[sea_orm(column_type = "Text", nullable)]
     pub content: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::entity::Entity",
+        from = "Column::EntityId",
+        to = "super::entity::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Entity,
+    #[sea_orm(has_many = "super::prophecy::Entity")]
+    Prophecy,
+    #[sea_orm(has_many = "super::saying_audience_nation::Entity")]
+    SayingAudienceNation,
+    #[sea_orm(has_many = "super::saying_audience_person::Entity")]
+    SayingAudiencePerson,
+    #[sea_orm(has_many = "super::saying_location::Entity")]
+    SayingLocation,
+    #[sea_orm(has_many = "super::saying_speaker_god::Entity")]
+    SayingSpeakerGod,
+    #[sea_orm(has_many = "super::saying_speaker_nation::Entity")]
+    SayingSpeakerNation,
+    #[sea_orm(has_many = "super::saying_speaker_person::Entity")]
+    SayingSpeakerPerson,
+}
+
+impl Related<super::entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entity.def()
+    }
+}
+
+impl Related<super::prophecy::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Prophecy.def()
+    }
+}
+
+impl Related<super::saying_audience_nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SayingAudienceNation.def()
+    }
+}
+
+impl Related<super::saying_audience_person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SayingAudiencePerson.def()
+    }
+}
+
+impl Related<super::saying_location::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SayingLocation.def()
+    }
+}
+
+impl Related<super::saying_speaker_god::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SayingSpeakerGod.def()
+    }
+}
+
+impl Related<super::saying_speaker_nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SayingSpeakerNation.def()
+    }
+}
+
+impl Related<super::saying_speaker_person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SayingSpeakerPerson.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -11,7 +11,33 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::nation::Entity",
+        from = "Column::NationId",
+        to = "super::nation::Column::Id"
+    )]
+    Nation,
+    #[sea_orm(
+        belongs_to = "super::saying::Entity",
+        from = "Column::SayingId",
+        to = "super::saying::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Saying,
+}
+
+impl Related<super::nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Nation.def()
+    }
+}
+
+impl Related<super::saying::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Saying.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -11,7 +11,33 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::god::Entity",
+        from = "Column::GodId",
+        to = "super::god::Column::Id"
+    )]
+    God,
+    #[sea_orm(
+        belongs_to = "super::saying::Entity",
+        from = "Column::SayingId",
+        to = "super::saying::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Saying,
+}
+
+impl Related<super::god::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::God.def()
+    }
+}
+
+impl Related<super::saying::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Saying.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
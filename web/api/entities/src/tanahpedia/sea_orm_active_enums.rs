@@ -0,0 +1,78 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "kind")]
+pub enum AnimalKind {
+    #[sea_orm(string_value = "BEHEMA")]
+    Behema,
+    #[sea_orm(string_value = "CHAYA")]
+    Chaya,
+    #[sea_orm(string_value = "OF")]
+    Of,
+    #[sea_orm(string_value = "SHERETZ")]
+    Sheretz,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "purity")]
+pub enum AnimalPurity {
+    #[sea_orm(string_value = "TAHOR")]
+    Tahor,
+    #[sea_orm(string_value = "TAMEH")]
+    Tameh,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "entity_type")]
+pub enum EntityType {
+    #[sea_orm(string_value = "PERSON")]
+    Person,
+    #[sea_orm(string_value = "PLACE")]
+    Place,
+    #[sea_orm(string_value = "EVENT")]
+    Event,
+    #[sea_orm(string_value = "WAR")]
+    War,
+    #[sea_orm(string_value = "ANIMAL")]
+    Animal,
+    #[sea_orm(string_value = "OBJECT")]
+    Object,
+    #[sea_orm(string_value = "TEMPLE_TOOL")]
+    TempleTool,
+    #[sea_orm(string_value = "PLANT")]
+    Plant,
+    #[sea_orm(string_value = "ASTRONOMICAL_OBJECT")]
+    AstronomicalObject,
+    #[sea_orm(string_value = "SAYING")]
+    Saying,
+    #[sea_orm(string_value = "SEFER")]
+    Sefer,
+    #[sea_orm(string_value = "PROPHECY")]
+    Prophecy,
+    #[sea_orm(string_value = "NATION")]
+    Nation,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
+pub enum RevisionStatus {
+    #[sea_orm(string_value = "PENDING")]
+    Pending,
+    #[sea_orm(string_value = "APPLIED")]
+    Applied,
+    #[sea_orm(string_value = "APPROVED")]
+    Approved,
+    #[sea_orm(string_value = "REJECTED")]
+    Rejected,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "sex")]
+pub enum Sex {
+    #[sea_orm(string_value = "MALE")]
+    Male,
+    #[sea_orm(string_value = "FEMALE")]
+    Female,
+    #[sea_orm(string_value = "UNKNOWN")]
+    Unknown,
+}
@@ -11,7 +11,33 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::tanahpedia_sefer::Entity",
+        from = "Column::SeferId",
+        to = "super::tanahpedia_sefer::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Sefer,
+    #[sea_orm(
+        belongs_to = "crate::sefer::Entity",
+        from = "Column::TanahSeferId",
+        to = "crate::sefer::Column::Id"
+    )]
+    TanahSefer,
+}
+
+impl Related<crate::sefer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TanahSefer.def()
+    }
+}
+
+impl Related<super::tanahpedia_sefer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Sefer.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -10,7 +10,24 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::non_tanah_source::Entity")]
+    NonTanahSource,
+    #[sea_orm(has_many = "super::tanah_source::Entity")]
+    TanahSource,
+}
+
+impl Related<super::non_tanah_source::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NonTanahSource.def()
+    }
+}
+
+impl Related<super::tanah_source::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TanahSource.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -7,12 +7,50 @@ pub struct Model {
     pub id: String,
     pub source_group_id: String,
     pub perush_id: Option<i16>,
-    pub pasuk_id: Option<i32>,
     pub perek_id: Option<i32>,
+    pub pasuk_number: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "crate::tanah_perek::Entity",
+        from = "Column::PerekId",
+        to = "crate::tanah_perek::Column::Id"
+    )]
+    Perek,
+    #[sea_orm(
+        belongs_to = "crate::perushim::perush::Entity",
+        from = "Column::PerushId",
+        to = "crate::perushim::perush::Column::Id"
+    )]
+    Perush,
+    #[sea_orm(
+        belongs_to = "super::source_group::Entity",
+        from = "Column::SourceGroupId",
+        to = "super::source_group::Column::Id",
+        on_delete = "Cascade"
+    )]
+    SourceGroup,
+}
+
+impl Related<crate::perushim::perush::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Perush.def()
+    }
+}
+
+impl Related<crate::tanah_perek::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Perek.def()
+    }
+}
+
+impl Related<super::source_group::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SourceGroup.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
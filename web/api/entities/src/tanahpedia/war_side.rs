@@ -10,7 +10,37 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::war::Entity",
+        from = "Column::WarId",
+        to = "super::war::Column::Id",
+        on_delete = "Cascade"
+    )]
+    War,
+    #[sea_orm(has_many = "super::war_side_participant_nation::Entity")]
+    WarSideParticipantNation,
+    #[sea_orm(has_many = "super::war_side_participant_person::Entity")]
+    WarSideParticipantPerson,
+}
+
+impl Related<super::war::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::War.def()
+    }
+}
+
+impl Related<super::war_side_participant_nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WarSideParticipantNation.def()
+    }
+}
+
+impl Related<super::war_side_participant_person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WarSideParticipantPerson.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
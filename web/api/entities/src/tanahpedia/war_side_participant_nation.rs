@@ -12,7 +12,33 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::nation::Entity",
+        from = "Column::NationId",
+        to = "super::nation::Column::Id"
+    )]
+    Nation,
+    #[sea_orm(
+        belongs_to = "super::war_side::Entity",
+        from = "Column::WarSideId",
+        to = "super::war_side::Column::Id",
+        on_delete = "Cascade"
+    )]
+    WarSide,
+}
+
+impl Related<super::nation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Nation.def()
+    }
+}
+
+impl Related<super::war_side::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WarSide.def()
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -1,7 +1,10 @@
+use actix_web::{HttpResponse, ResponseError, http::StatusCode};
 use async_graphql::{Error, ErrorExtensions};
 use derive_more::Display;
+use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Debug, Display)]
+#[derive(Debug, Clone, Display)]
 pub enum ServiceError {
     #[display("{_0}")]
     InternalServerError(String),
@@ -45,6 +48,32 @@ impl ErrorExtensions for ServiceError {
     }
 }
 
+/// JSON body of a failed REST request; `code` matches the GraphQL error
+/// extension.
+#[derive(Serialize, ToSchema, Debug)]
+pub struct ServiceErrorBody {
+    pub code: String,
+    pub message: String,
+}
+
+impl ResponseError for ServiceError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ServiceError::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ServiceError::NotFound(_) => StatusCode::NOT_FOUND,
+            ServiceError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ServiceError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(ServiceErrorBody {
+            code: self.code().to_string(),
+            message: self.to_string(),
+        })
+    }
+}
+
 impl ServiceError {
     pub fn internal_server_error<T: std::fmt::Display + std::fmt::Debug>(
         message: &str,
@@ -85,4 +114,13 @@ impl ServiceError {
         tracing::warn!(UNAUTHORIZED, %message);
         Self::Unauthorized(message.to_string())
     }
+
+    fn code(&self) -> &'static str {
+        match self {
+            ServiceError::InternalServerError(_) => "INTERNAL_SERVER_ERROR",
+            ServiceError::NotFound(_) => "NOT_FOUND",
+            ServiceError::BadRequest(_) => "BAD_REQUEST",
+            ServiceError::Unauthorized(_) => "UNAUTHORIZED",
+        }
+    }
 }
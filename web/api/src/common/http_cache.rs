@@ -0,0 +1,85 @@
+use actix_web::{HttpRequest, HttpResponse, http::header};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// `Cache-Control` for data only the pipelines change (sefarim, perakim).
+pub const STATIC_CACHE_CONTROL: &str = "public, max-age=3600";
+
+/// A strong ETag over a response body: its SHA-256, so every instance and
+/// every build agrees on it.
+pub fn etag(body: &[u8]) -> String {
+    format!("\"{:x}\"", Sha256::digest(body))
+}
+
+/// Whether the request's `If-None-Match` already names `etag` (or `*`).
+pub fn is_not_modified(req: &HttpRequest, etag: &str) -> bool {
+    req.headers()
+        .get_all(header::IF_NONE_MATCH)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/// `value` as JSON with its ETag, or `304 Not Modified` when the client
+/// already holds that ETag.
+pub fn json_response<T: Serialize>(
+    req: &HttpRequest,
+    value: &T,
+    cache_control: Option<&str>,
+) -> HttpResponse {
+    let body = match serde_json::to_vec(value) {
+        Ok(body) => body,
+        Err(_) => return HttpResponse::InternalServerError().finish(),
+    };
+    let etag = etag(&body);
+    let not_modified = is_not_modified(req, &etag);
+    let mut builder = if not_modified {
+        HttpResponse::NotModified()
+    } else {
+        HttpResponse::Ok()
+    };
+    builder.insert_header((header::ETAG, etag));
+    if let Some(cache_control) = cache_control {
+        builder.insert_header((header::CACHE_CONTROL, cache_control));
+    }
+    if not_modified {
+        builder.finish()
+    } else {
+        builder.content_type("application/json").body(body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{http::StatusCode, test::TestRequest};
+
+    #[test]
+    fn etag_is_quoted_and_content_addressed() {
+        assert_eq!(etag(b"[1]"), etag(b"[1]"));
+        assert_ne!(etag(b"[1]"), etag(b"[2]"));
+        assert!(etag(b"[1]").starts_with('"') && etag(b"[1]").ends_with('"'));
+    }
+
+    #[test]
+    fn json_response_answers_not_modified_for_a_matching_etag() {
+        let tag = etag(b"[1]");
+        let fresh = json_response(&TestRequest::get().to_http_request(), &[1], None);
+        assert_eq!(fresh.status(), StatusCode::OK);
+        assert_eq!(fresh.headers().get(header::ETAG).unwrap(), tag.as_str());
+
+        let cached = json_response(
+            &TestRequest::get()
+                .insert_header((header::IF_NONE_MATCH, format!("\"other\", W/{tag}")))
+                .to_http_request(),
+            &[1],
+            Some(STATIC_CACHE_CONTROL),
+        );
+        assert_eq!(cached.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(
+            cached.headers().get(header::CACHE_CONTROL).unwrap(),
+            STATIC_CACHE_CONTROL
+        );
+    }
+}
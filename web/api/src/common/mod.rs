@@ -1,2 +1,3 @@
 pub mod auth;
 pub mod error_handling;
+pub mod http_cache;
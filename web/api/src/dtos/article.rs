@@ -1,15 +1,19 @@
 use async_graphql::{ComplexObject, SimpleObject};
+use serde::Serialize;
+use utoipa::ToSchema;
 
 use entities::article::Model;
 
-#[derive(SimpleObject, Debug, Clone)]
+#[derive(SimpleObject, Serialize, ToSchema, Debug, Clone)]
 #[graphql(complex)]
+#[serde(rename_all = "camelCase")]
 pub struct Article {
     pub id: i32,
     pub perek_id: i32,
     pub author_id: i32,
     /// The article abstract (HTML content)
     #[graphql(name = "abstract")]
+    #[serde(rename = "abstract")]
     pub article_abstract: Option<String>,
     /// The full article content (HTML)
     #[graphql(name = "articleContent")]
@@ -1,10 +1,13 @@
 use async_graphql::{ComplexObject, Context, Result, SimpleObject};
+use serde::Serialize;
+use utoipa::ToSchema;
 
 use crate::{providers::Database, services::articles_service};
 use entities::author::Model;
 
-#[derive(SimpleObject, Debug, Clone)]
+#[derive(SimpleObject, Serialize, ToSchema, Debug, Clone)]
 #[graphql(complex)]
+#[serde(rename_all = "camelCase")]
 pub struct Author {
     pub id: i32,
     pub name: String,
@@ -12,6 +15,7 @@ pub struct Author {
     /// Pre-computed articles count (used by starter query to avoid N+1)
     /// When None, the count will be computed on-demand via the computed field
     #[graphql(skip)]
+    #[serde(rename = "articlesCount", skip_serializing_if = "Option::is_none")]
     pub precomputed_articles_count: Option<i64>,
 }
 
@@ -3,5 +3,20 @@ pub mod author;
 pub mod perek;
 pub mod sefer;
 pub mod starter;
+pub mod tanahpedia_animal;
+pub mod tanahpedia_auto_link;
+pub mod tanahpedia_category_homepage;
+pub mod tanahpedia_creation_day;
+pub mod tanahpedia_entry;
 pub mod tanahpedia_entry_revision;
 pub mod tanahpedia_family;
+pub mod tanahpedia_import;
+pub mod tanahpedia_model3d;
+pub mod tanahpedia_naming;
+pub mod tanahpedia_nation;
+pub mod tanahpedia_opinion;
+pub mod tanahpedia_place;
+pub mod tanahpedia_quality;
+pub mod tanahpedia_saying;
+pub mod tanahpedia_source;
+pub mod tanahpedia_war;
@@ -1,4 +1,6 @@
 use async_graphql::{ComplexObject, Context, Result, SimpleObject};
+use serde::Serialize;
+use utoipa::ToSchema;
 
 use crate::{providers::Database, services::articles_service};
 use entities::perek::Model;
@@ -98,8 +100,9 @@ fn format_hebrew_date(hebdate: &str) -> Option<String> {
     Some(format!("{} {} {}", day_heb, month_name, year_heb))
 }
 
-#[derive(SimpleObject, Debug, Clone)]
+#[derive(SimpleObject, Serialize, ToSchema, Debug, Clone)]
 #[graphql(complex)]
+#[serde(rename_all = "camelCase")]
 pub struct Perek {
     pub id: i32,
     /// The perek ID (1-929) used in the 929 project
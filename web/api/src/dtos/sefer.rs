@@ -1,9 +1,12 @@
 use async_graphql::{ComplexObject, SimpleObject};
+use serde::Serialize;
+use utoipa::ToSchema;
 
 use entities::sefer::Model;
 
-#[derive(SimpleObject, Debug, Clone)]
+#[derive(SimpleObject, Serialize, ToSchema, Debug, Clone)]
 #[graphql(complex)]
+#[serde(rename_all = "camelCase")]
 pub struct Sefer {
     pub id: i32,
     pub name: String,
@@ -16,7 +19,7 @@ impl From<Model> for Sefer {
     fn from(value: Model) -> Self {
         Self {
             id: value.id,
-            name: value.name,
+            name: value.name.unwrap_or_default(),
             tanach_us_name: value.tanach_us_name,
         }
     }
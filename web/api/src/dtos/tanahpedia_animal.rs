@@ -0,0 +1,157 @@
+use async_graphql::{
+    ComplexObject, Context, Enum, ErrorExtensions, InputObject, Result, SimpleObject,
+};
+use entities::tanahpedia::sea_orm_active_enums::{AnimalKind, AnimalPurity};
+
+use crate::{
+    dtos::{
+        tanahpedia_opinion::{
+            TanahpediaAnimalKindOpinion, TanahpediaAnimalPurityOpinion, TanahpediaOpinionGroup,
+        },
+        tanahpedia_source::{TanahpediaSources, TanahpediaTanahSourceInput},
+    },
+    providers::Database,
+    services::tanahpedia_animals_service,
+};
+
+/// בהמה/חיה/עוף/שרץ.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TanahpediaAnimalKind {
+    Behema,
+    Chaya,
+    Of,
+    Sheretz,
+}
+
+impl From<AnimalKind> for TanahpediaAnimalKind {
+    fn from(kind: AnimalKind) -> Self {
+        match kind {
+            AnimalKind::Behema => Self::Behema,
+            AnimalKind::Chaya => Self::Chaya,
+            AnimalKind::Of => Self::Of,
+            AnimalKind::Sheretz => Self::Sheretz,
+        }
+    }
+}
+
+impl From<TanahpediaAnimalKind> for AnimalKind {
+    fn from(kind: TanahpediaAnimalKind) -> Self {
+        match kind {
+            TanahpediaAnimalKind::Behema => Self::Behema,
+            TanahpediaAnimalKind::Chaya => Self::Chaya,
+            TanahpediaAnimalKind::Of => Self::Of,
+            TanahpediaAnimalKind::Sheretz => Self::Sheretz,
+        }
+    }
+}
+
+/// טהור/טמא.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TanahpediaAnimalPurity {
+    Tahor,
+    Tameh,
+}
+
+impl From<AnimalPurity> for TanahpediaAnimalPurity {
+    fn from(purity: AnimalPurity) -> Self {
+        match purity {
+            AnimalPurity::Tahor => Self::Tahor,
+            AnimalPurity::Tameh => Self::Tameh,
+        }
+    }
+}
+
+impl From<TanahpediaAnimalPurity> for AnimalPurity {
+    fn from(purity: TanahpediaAnimalPurity) -> Self {
+        match purity {
+            TanahpediaAnimalPurity::Tahor => Self::Tahor,
+            TanahpediaAnimalPurity::Tameh => Self::Tameh,
+        }
+    }
+}
+
+/// Adds (or updates) one opinion on an animal's kind. Competing opinions are
+/// separate rows told apart by `altGroupId`; the sources replace whatever was
+/// stored for this row before.
+#[derive(InputObject, Debug, Clone)]
+pub struct PutTanahpediaAnimalKindInput {
+    pub id: String,
+    pub animal_id: String,
+    pub kind: TanahpediaAnimalKind,
+    pub alt_group_id: Option<String>,
+    #[graphql(default)]
+    pub tanah_sources: Vec<TanahpediaTanahSourceInput>,
+    #[graphql(default)]
+    pub non_tanah_sources: Vec<String>,
+}
+
+/// Adds (or updates) one opinion on an animal's kashrut, e.g. a disputed
+/// species with a TAHOR and a TAMEH row in different alternative groups.
+#[derive(InputObject, Debug, Clone)]
+pub struct PutTanahpediaAnimalPurityInput {
+    pub id: String,
+    pub animal_id: String,
+    pub purity: TanahpediaAnimalPurity,
+    pub alt_group_id: Option<String>,
+    #[graphql(default)]
+    pub tanah_sources: Vec<TanahpediaTanahSourceInput>,
+    #[graphql(default)]
+    pub non_tanah_sources: Vec<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaAnimalKindWriteResult {
+    pub id: String,
+    pub animal_id: String,
+    pub kind: TanahpediaAnimalKind,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaAnimalPurityWriteResult {
+    pub id: String,
+    pub animal_id: String,
+    pub purity: TanahpediaAnimalPurity,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaAnimalKindClassification {
+    pub id: String,
+    pub kind: TanahpediaAnimalKind,
+    pub alt_group_id: Option<String>,
+    pub sources: TanahpediaSources,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaAnimalPurityClassification {
+    pub id: String,
+    pub purity: TanahpediaAnimalPurity,
+    pub alt_group_id: Option<String>,
+    pub sources: TanahpediaSources,
+}
+
+/// An animal with every recorded opinion on its kind and purity.
+#[derive(SimpleObject, Debug, Clone)]
+#[graphql(complex)]
+pub struct TanahpediaAnimal {
+    pub animal_id: String,
+    pub entity_id: String,
+    pub display_name: String,
+    pub kinds: Vec<TanahpediaAnimalKindClassification>,
+    pub purities: Vec<TanahpediaAnimalPurityClassification>,
+}
+
+#[ComplexObject]
+impl TanahpediaAnimal {
+    /// Kinds and purities grouped by opinion.
+    async fn opinions(&self, ctx: &Context<'_>) -> Result<TanahpediaAnimalOpinions> {
+        tanahpedia_animals_service::get_animal_opinions(ctx.data::<Database>()?, self)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaAnimalOpinions {
+    pub kinds: TanahpediaOpinionGroup<TanahpediaAnimalKindOpinion>,
+    pub purities: TanahpediaOpinionGroup<TanahpediaAnimalPurityOpinion>,
+}
@@ -0,0 +1,49 @@
+use async_graphql::SimpleObject;
+
+/// A mention the auto-linker wrapped in a link.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaAutoLink {
+    /// The name as it appears in the text, without prefix letters.
+    pub name: String,
+    pub target_unique_name: String,
+}
+
+/// An entry an ambiguous name may refer to.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaAutoLinkCandidate {
+    pub unique_name: String,
+    /// From `entry_synonym_disambiguation`, when the synonym has one.
+    pub disambiguation_label: Option<String>,
+}
+
+/// A name that refers to more than one entry, left unlinked for an editor.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaAmbiguousMention {
+    pub name: String,
+    pub candidates: Vec<TanahpediaAutoLinkCandidate>,
+}
+
+/// What the auto-linker found in one entry or article.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaAutoLinkProposal {
+    /// Set for entries.
+    pub entry_id: Option<String>,
+    /// Set for articles.
+    pub article_id: Option<i32>,
+    pub links: Vec<TanahpediaAutoLink>,
+    pub ambiguous: Vec<TanahpediaAmbiguousMention>,
+    /// The pending revision holding the linked content. Entries only, and only
+    /// when there is something to link.
+    pub revision_id: Option<String>,
+    /// The linked content. Articles only: article scanning is preview-only,
+    /// so this is the only copy of the proposal.
+    pub proposed_content: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaAutoLinkReport {
+    /// Entries and articles whose content was scanned.
+    pub scanned: i32,
+    /// Only documents with links or ambiguous mentions.
+    pub proposals: Vec<TanahpediaAutoLinkProposal>,
+}
@@ -0,0 +1,196 @@
+use async_graphql::{
+    ComplexObject, Context, Enum, ErrorExtensions, InputObject, Result, SimpleObject,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    dtos::tanahpedia_family::{TanahpediaEntitySummary, TanahpediaEntityType},
+    providers::Database,
+    services::tanahpedia_category_homepages_service,
+};
+
+/// How a category homepage lays out its entities
+/// (`tanahpedia_category_homepage.layout_type`).
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TanahpediaHomepageLayoutType {
+    List,
+    Map,
+    Gallery,
+    Timeline,
+}
+
+impl TanahpediaHomepageLayoutType {
+    /// The value stored in `tanahpedia_category_homepage.layout_type`.
+    pub fn stored_name(self) -> &'static str {
+        match self {
+            Self::List => "LIST",
+            Self::Map => "MAP",
+            Self::Gallery => "GALLERY",
+            Self::Timeline => "TIMELINE",
+        }
+    }
+
+    pub fn from_stored_name(name: &str) -> Option<Self> {
+        match name {
+            "LIST" => Some(Self::List),
+            "MAP" => Some(Self::Map),
+            "GALLERY" => Some(Self::Gallery),
+            "TIMELINE" => Some(Self::Timeline),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Enum, Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TanahpediaHomepageSortOrder {
+    #[default]
+    Alphabetical,
+    /// Timeline layouts only.
+    Chronological,
+    /// Featured entities first, in the configured order.
+    Manual,
+}
+
+/// Initial map view of a `MAP` layout.
+#[derive(SimpleObject, InputObject, Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+#[graphql(input_name = "TanahpediaHomepageMapSettingsInput")]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TanahpediaHomepageMapSettings {
+    pub center_latitude: f64,
+    pub center_longitude: f64,
+    /// Web-map zoom level, 1–19.
+    pub zoom: i32,
+}
+
+/// Grid geometry of a `GALLERY` layout.
+#[derive(SimpleObject, InputObject, Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+#[graphql(input_name = "TanahpediaHomepageGridSettingsInput")]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TanahpediaHomepageGridSettings {
+    /// 1–6.
+    pub columns: i32,
+    /// 1–100.
+    pub page_size: i32,
+}
+
+/// The `config` JSON of a category homepage. `map` is required for `MAP`
+/// layouts and `grid` for `GALLERY`; other layouts take neither.
+#[derive(SimpleObject, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TanahpediaCategoryHomepageConfig {
+    #[serde(default)]
+    pub featured_entity_ids: Vec<String>,
+    #[serde(default)]
+    pub sort_order: TanahpediaHomepageSortOrder,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map: Option<TanahpediaHomepageMapSettings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grid: Option<TanahpediaHomepageGridSettings>,
+}
+
+#[derive(InputObject, Debug, Clone)]
+pub struct TanahpediaCategoryHomepageConfigInput {
+    #[graphql(default)]
+    pub featured_entity_ids: Vec<String>,
+    #[graphql(default)]
+    pub sort_order: TanahpediaHomepageSortOrder,
+    pub map: Option<TanahpediaHomepageMapSettings>,
+    pub grid: Option<TanahpediaHomepageGridSettings>,
+}
+
+impl From<TanahpediaCategoryHomepageConfigInput> for TanahpediaCategoryHomepageConfig {
+    fn from(input: TanahpediaCategoryHomepageConfigInput) -> Self {
+        Self {
+            featured_entity_ids: input.featured_entity_ids,
+            sort_order: input.sort_order,
+            map: input.map,
+            grid: input.grid,
+        }
+    }
+}
+
+/// The homepage of one entity type. A null `config` means the layout's
+/// defaults.
+#[derive(SimpleObject, Debug, Clone)]
+#[graphql(complex)]
+pub struct TanahpediaCategoryHomepage {
+    pub id: String,
+    pub entity_type: TanahpediaEntityType,
+    pub layout_type: TanahpediaHomepageLayoutType,
+    pub config: Option<TanahpediaCategoryHomepageConfig>,
+    /// Intro HTML shown above the layout.
+    pub content: Option<String>,
+    pub updated_at: String,
+}
+
+#[ComplexObject]
+impl TanahpediaCategoryHomepage {
+    /// `config.featuredEntityIds` resolved to summaries, in configured order.
+    /// Ids whose entity has since been deleted are skipped.
+    async fn featured_entities(&self, ctx: &Context<'_>) -> Result<Vec<TanahpediaEntitySummary>> {
+        let ids = self
+            .config
+            .as_ref()
+            .map(|config| config.featured_entity_ids.clone())
+            .unwrap_or_default();
+        tanahpedia_category_homepages_service::get_entity_summaries(ctx.data::<Database>()?, ids)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+/// Creates or replaces the homepage of `entityType`. The config is validated
+/// against `layoutType`; an omitted config is validated as the default one,
+/// so `MAP` and `GALLERY` layouts must send their `map` or `grid` settings.
+#[derive(InputObject, Debug, Clone)]
+pub struct PutTanahpediaCategoryHomepageInput {
+    pub entity_type: TanahpediaEntityType,
+    pub layout_type: TanahpediaHomepageLayoutType,
+    pub config: Option<TanahpediaCategoryHomepageConfigInput>,
+    pub content: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_round_trips_through_stored_json() {
+        let config = TanahpediaCategoryHomepageConfig {
+            featured_entity_ids: vec!["entity-1".to_string()],
+            sort_order: TanahpediaHomepageSortOrder::Manual,
+            map: Some(TanahpediaHomepageMapSettings {
+                center_latitude: 31.5,
+                center_longitude: 35.0,
+                zoom: 7,
+            }),
+            grid: None,
+        };
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "featuredEntityIds": ["entity-1"],
+                "sortOrder": "MANUAL",
+                "map": { "centerLatitude": 31.5, "centerLongitude": 35.0, "zoom": 7 }
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<TanahpediaCategoryHomepageConfig>(json).unwrap(),
+            config
+        );
+    }
+
+    #[test]
+    fn config_rejects_unknown_keys() {
+        assert!(
+            serde_json::from_value::<TanahpediaCategoryHomepageConfig>(serde_json::json!({
+                "featuredEntityIds": [],
+                "columns": 3
+            }))
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use async_graphql::{
+    ComplexObject, Context, Enum, ErrorExtensions, InputObject, Result, SimpleObject,
+};
+
+use crate::{
+    dtos::{
+        tanahpedia_opinion::{TanahpediaCreationDayItemOpinion, TanahpediaOpinionGroup},
+        tanahpedia_source::{TanahpediaSources, TanahpediaTanahSourceInput},
+    },
+    providers::Database,
+    services::tanahpedia_creation_days_service,
+};
+
+/// What a creation-day row places on a day; each kind has its own table.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TanahpediaCreationItemType {
+    Plant,
+    AstronomicalObject,
+}
+
+impl TanahpediaCreationItemType {
+    /// The creation-day table, which is also the
+    /// `tanahpedia_source_group.target_table` of the row's sources.
+    pub fn source_table(self) -> &'static str {
+        match self {
+            Self::Plant => "tanahpedia_plant_creation_day",
+            Self::AstronomicalObject => "tanahpedia_astronomical_object_creation_day",
+        }
+    }
+
+    pub fn other(self) -> Self {
+        match self {
+            Self::Plant => Self::AstronomicalObject,
+            Self::AstronomicalObject => Self::Plant,
+        }
+    }
+}
+
+/// Adds (or updates) one opinion on the day of creation of a plant or an
+/// astronomical object. `creationDay` is 1–6. The sources replace whatever was stored for this
+/// row before.
+#[derive(InputObject, Debug, Clone)]
+pub struct PutTanahpediaCreationDayInput {
+    pub id: String,
+    pub item_type: TanahpediaCreationItemType,
+    pub item_id: String,
+    pub creation_day: i32,
+    pub alt_group_id: Option<String>,
+    #[graphql(default)]
+    pub tanah_sources: Vec<TanahpediaTanahSourceInput>,
+    #[graphql(default)]
+    pub non_tanah_sources: Vec<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaCreationDayWriteResult {
+    pub id: String,
+    pub item_type: TanahpediaCreationItemType,
+    pub item_id: String,
+    pub creation_day: i32,
+}
+
+/// One opinion placing a plant or astronomical object on a day. `id` is the
+/// creation-day row; `itemId` is the plant or astronomical object.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaCreationDayItem {
+    pub id: String,
+    pub item_type: TanahpediaCreationItemType,
+    pub item_id: String,
+    pub entity_id: String,
+    pub display_name: String,
+    pub alt_group_id: Option<String>,
+    pub sources: TanahpediaSources,
+}
+
+/// One of the six days of creation with everything any opinion places on it.
+#[derive(SimpleObject, Debug, Clone)]
+#[graphql(complex)]
+pub struct TanahpediaCreationDay {
+    pub day: i32,
+    pub plants: Vec<TanahpediaCreationDayItem>,
+    pub astronomical_objects: Vec<TanahpediaCreationDayItem>,
+}
+
+#[ComplexObject]
+impl TanahpediaCreationDay {
+    /// Plants and astronomical objects grouped by opinion.
+    async fn opinions(&self, ctx: &Context<'_>) -> Result<TanahpediaCreationDayOpinions> {
+        tanahpedia_creation_days_service::get_creation_day_opinions(ctx.data::<Database>()?, self)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaCreationDayOpinions {
+    pub plants: TanahpediaOpinionGroup<TanahpediaCreationDayItemOpinion>,
+    pub astronomical_objects: TanahpediaOpinionGroup<TanahpediaCreationDayItemOpinion>,
+}
@@ -0,0 +1,75 @@
+use async_graphql::{ComplexObject, Context, ErrorExtensions, Result, SimpleObject};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use entities::tanahpedia::entry;
+
+use crate::{providers::Database, services::tanahpedia_entries_service};
+
+/// A Tanahpedia entry, as served at `/pedia/<uniqueName>`.
+#[derive(SimpleObject, Serialize, ToSchema, Debug, Clone)]
+#[graphql(complex)]
+#[serde(rename_all = "camelCase")]
+pub struct TanahpediaEntry {
+    pub id: String,
+    pub unique_name: String,
+    pub title: String,
+    /// Entry body (HTML).
+    pub content: Option<String>,
+    pub updated_at: String,
+}
+
+impl From<entry::Model> for TanahpediaEntry {
+    fn from(value: entry::Model) -> Self {
+        Self {
+            id: value.id,
+            unique_name: value.unique_name,
+            title: value.title,
+            content: value.content,
+            updated_at: value.updated_at.to_string(),
+        }
+    }
+}
+
+#[ComplexObject]
+impl TanahpediaEntry {
+    /// Other entries whose content links to this one, by title.
+    async fn backlinks(&self, ctx: &Context<'_>) -> Result<Vec<TanahpediaEntryReference>> {
+        tanahpedia_entries_service::get_backlinks(ctx.data::<Database>()?, self.unique_name.clone())
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+/// Just enough of an entry to link to it.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaEntryReference {
+    pub id: String,
+    pub unique_name: String,
+    pub title: String,
+}
+
+impl From<entry::Model> for TanahpediaEntryReference {
+    fn from(value: entry::Model) -> Self {
+        Self {
+            id: value.id,
+            unique_name: value.unique_name,
+            title: value.title,
+        }
+    }
+}
+
+/// A link in `source`'s content to a unique name no entry has.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaBrokenEntryLink {
+    pub source: TanahpediaEntryReference,
+    pub target_unique_name: String,
+}
+
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaEntryLinksRebuildResult {
+    /// Entries whose content was re-scanned.
+    pub entries: i32,
+    /// Internal links found in them.
+    pub links: i32,
+}
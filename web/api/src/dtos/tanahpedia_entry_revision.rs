@@ -1,6 +1,37 @@
-use async_graphql::{InputObject, SimpleObject};
+use async_graphql::{Enum, InputObject, SimpleObject};
 
-use entities::tanahpedia::entry_revision::Model;
+use entities::tanahpedia::{entry_revision::Model, sea_orm_active_enums::RevisionStatus};
+
+/// Where a revision stands in human triage.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TanahpediaRevisionStatus {
+    Pending,
+    Applied,
+    Approved,
+    Rejected,
+}
+
+impl From<RevisionStatus> for TanahpediaRevisionStatus {
+    fn from(status: RevisionStatus) -> Self {
+        match status {
+            RevisionStatus::Pending => Self::Pending,
+            RevisionStatus::Applied => Self::Applied,
+            RevisionStatus::Approved => Self::Approved,
+            RevisionStatus::Rejected => Self::Rejected,
+        }
+    }
+}
+
+impl From<TanahpediaRevisionStatus> for RevisionStatus {
+    fn from(status: TanahpediaRevisionStatus) -> Self {
+        match status {
+            TanahpediaRevisionStatus::Pending => Self::Pending,
+            TanahpediaRevisionStatus::Applied => Self::Applied,
+            TanahpediaRevisionStatus::Approved => Self::Approved,
+            TanahpediaRevisionStatus::Rejected => Self::Rejected,
+        }
+    }
+}
 
 /// A Tanahpedia entry revision proposed by an external AI client.
 #[derive(SimpleObject, Debug, Clone)]
@@ -16,8 +47,7 @@ pub struct EntryRevision {
     pub source: String,
     /// AI rationale / notes for the human editor.
     pub notes: Option<String>,
-    /// Lifecycle marker: `PENDING`, `APPLIED`, `APPROVED`, or `REJECTED`.
-    pub status: String,
+    pub status: TanahpediaRevisionStatus,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -32,7 +62,7 @@ impl From<Model> for EntryRevision {
             proposed_content: value.proposed_content,
             source: value.source,
             notes: value.notes,
-            status: value.status,
+            status: value.status.into(),
             created_at: value.created_at.to_string(),
             updated_at: value.updated_at.to_string(),
         }
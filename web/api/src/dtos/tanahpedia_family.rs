@@ -1,4 +1,106 @@
-use async_graphql::{InputObject, SimpleObject};
+use async_graphql::{
+    ComplexObject, Context, Enum, ErrorExtensions, InputObject, Result, SimpleObject,
+};
+use entities::tanahpedia::sea_orm_active_enums::{EntityType, Sex};
+
+use crate::{
+    dtos::{
+        tanahpedia_model3d::TanahpediaModel3d,
+        tanahpedia_naming::TanahpediaNameGiver,
+        tanahpedia_opinion::{
+            TanahpediaDateOpinion, TanahpediaOpinionGroup, TanahpediaPersonNameOpinion,
+            TanahpediaPersonSexOpinion, TanahpediaTextOpinion,
+        },
+        tanahpedia_war::TanahpediaParticipantWar,
+    },
+    providers::{Database, Storage},
+    services::{tanahpedia_family_service, tanahpedia_models3d_service, tanahpedia_wars_service},
+};
+
+/// The domain of a `tanahpedia_entity` row.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TanahpediaEntityType {
+    Person,
+    Place,
+    Event,
+    War,
+    Animal,
+    Object,
+    TempleTool,
+    Plant,
+    AstronomicalObject,
+    Saying,
+    Sefer,
+    Prophecy,
+    Nation,
+}
+
+impl From<EntityType> for TanahpediaEntityType {
+    fn from(entity_type: EntityType) -> Self {
+        match entity_type {
+            EntityType::Person => Self::Person,
+            EntityType::Place => Self::Place,
+            EntityType::Event => Self::Event,
+            EntityType::War => Self::War,
+            EntityType::Animal => Self::Animal,
+            EntityType::Object => Self::Object,
+            EntityType::TempleTool => Self::TempleTool,
+            EntityType::Plant => Self::Plant,
+            EntityType::AstronomicalObject => Self::AstronomicalObject,
+            EntityType::Saying => Self::Saying,
+            EntityType::Sefer => Self::Sefer,
+            EntityType::Prophecy => Self::Prophecy,
+            EntityType::Nation => Self::Nation,
+        }
+    }
+}
+
+impl From<TanahpediaEntityType> for EntityType {
+    fn from(entity_type: TanahpediaEntityType) -> Self {
+        match entity_type {
+            TanahpediaEntityType::Person => Self::Person,
+            TanahpediaEntityType::Place => Self::Place,
+            TanahpediaEntityType::Event => Self::Event,
+            TanahpediaEntityType::War => Self::War,
+            TanahpediaEntityType::Animal => Self::Animal,
+            TanahpediaEntityType::Object => Self::Object,
+            TanahpediaEntityType::TempleTool => Self::TempleTool,
+            TanahpediaEntityType::Plant => Self::Plant,
+            TanahpediaEntityType::AstronomicalObject => Self::AstronomicalObject,
+            TanahpediaEntityType::Saying => Self::Saying,
+            TanahpediaEntityType::Sefer => Self::Sefer,
+            TanahpediaEntityType::Prophecy => Self::Prophecy,
+            TanahpediaEntityType::Nation => Self::Nation,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TanahpediaSex {
+    Male,
+    Female,
+    Unknown,
+}
+
+impl From<Sex> for TanahpediaSex {
+    fn from(sex: Sex) -> Self {
+        match sex {
+            Sex::Male => Self::Male,
+            Sex::Female => Self::Female,
+            Sex::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl From<TanahpediaSex> for Sex {
+    fn from(sex: TanahpediaSex) -> Self {
+        match sex {
+            TanahpediaSex::Male => Self::Male,
+            TanahpediaSex::Female => Self::Female,
+            TanahpediaSex::Unknown => Self::Unknown,
+        }
+    }
+}
 
 #[derive(InputObject, Debug, Clone)]
 pub struct PutTanahpediaEntryEntityLinkInput {
@@ -13,7 +115,7 @@ pub struct PutTanahpediaPersonNodeInput {
     pub person_id: String,
     pub display_name: String,
     pub sex_id: String,
-    pub sex: String,
+    pub sex: TanahpediaSex,
     pub sex_alt_group_id: Option<String>,
 }
 
@@ -27,7 +129,7 @@ pub struct DeleteTanahpediaPersonNodeInput {
 #[derive(InputObject, Debug, Clone)]
 pub struct DeleteTanahpediaOrphanEntityInput {
     pub entity_id: String,
-    pub entity_type: String,
+    pub entity_type: TanahpediaEntityType,
     pub display_name: String,
 }
 
@@ -85,17 +187,31 @@ pub struct TanahpediaPersonSummary {
     pub display_name: String,
 }
 
-/// A Tanahpedia entity match of any type (`PERSON`, `PLACE`, `EVENT`, `WAR`,
-/// `ANIMAL`, `OBJECT`, `TEMPLE_TOOL`, `PLANT`, `ASTRONOMICAL_OBJECT`,
-/// `SAYING`, `SEFER`, `PROPHECY`, `NATION`), used to resolve a display name to
+/// A Tanahpedia entity match of any type, used to resolve a display name to
 /// its internal `entityId` regardless of domain.
 #[derive(SimpleObject, Debug, Clone)]
+#[graphql(complex)]
 pub struct TanahpediaEntitySummary {
     pub entity_id: String,
-    pub entity_type: String,
+    pub entity_type: TanahpediaEntityType,
     pub display_name: String,
 }
 
+#[ComplexObject]
+impl TanahpediaEntitySummary {
+    /// 3D models of this entity, each with a pre-signed download URL.
+    #[graphql(name = "models3d")]
+    async fn models3d(&self, ctx: &Context<'_>) -> Result<Vec<TanahpediaModel3d>> {
+        tanahpedia_models3d_service::get_entity_models(
+            ctx.data::<Database>()?,
+            ctx.data::<Storage>()?,
+            self.entity_id.clone(),
+        )
+        .await
+        .map_err(|e| e.extend())
+    }
+}
+
 /// A union (marriage/pilegesh/etc.) link involving a given person, along with
 /// enough context about the other party to identify it in the UI.
 #[derive(SimpleObject, Debug, Clone)]
@@ -133,19 +249,21 @@ pub struct TanahpediaPersonParentChildSummary {
     pub queried_is_parent: bool,
 }
 
-/// One of a person's (possibly several, alternate-opinion) display names.
+/// One of a person's (possibly several, alternate-opinion) display names,
+/// with whoever gave it.
 #[derive(SimpleObject, Debug, Clone)]
 pub struct TanahpediaPersonName {
     pub id: String,
     pub name: String,
     pub name_type: String,
     pub alt_group_id: Option<String>,
+    pub givers: Vec<TanahpediaNameGiver>,
 }
 
 #[derive(SimpleObject, Debug, Clone)]
 pub struct TanahpediaPersonSex {
     pub id: String,
-    pub sex: String,
+    pub sex: TanahpediaSex,
     pub alt_group_id: Option<String>,
 }
 
@@ -165,20 +283,61 @@ pub struct TanahpediaEntityTanahSource {
 /// The full reviewable detail of a Tanahpedia person: every name, sex,
 /// birth/death fact (each field is a list because the schema allows multiple
 /// alternate-opinion rows per person), and the entity-level Tanah citations
-/// for the person as a whole.
+/// for the person as a whole. `opinions` returns the same facts grouped by
+/// `altGroupId`.
 #[derive(SimpleObject, Debug, Clone)]
+#[graphql(complex)]
 pub struct TanahpediaPersonDetail {
     pub entity_id: String,
     pub person_id: String,
     pub display_name: String,
     pub names: Vec<TanahpediaPersonName>,
-    pub sexes: Vec<String>,
+    pub sexes: Vec<TanahpediaSex>,
     pub sex_rows: Vec<TanahpediaPersonSex>,
     pub birth_dates: Vec<i32>,
     pub death_dates: Vec<i32>,
     pub death_causes: Vec<String>,
     pub birth_place_ids: Vec<String>,
     pub tanah_sources: Vec<TanahpediaEntityTanahSource>,
+    /// `(altGroupId, value)` rows behind the flat date/cause/place lists.
+    #[graphql(skip)]
+    pub birth_date_rows: Vec<(Option<String>, i32)>,
+    #[graphql(skip)]
+    pub death_date_rows: Vec<(Option<String>, i32)>,
+    #[graphql(skip)]
+    pub death_cause_rows: Vec<(Option<String>, String)>,
+    #[graphql(skip)]
+    pub birth_place_rows: Vec<(Option<String>, String)>,
+}
+
+/// A person's facts grouped into competing opinions.
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaPersonOpinions {
+    pub names: TanahpediaOpinionGroup<TanahpediaPersonNameOpinion>,
+    pub sexes: TanahpediaOpinionGroup<TanahpediaPersonSexOpinion>,
+    pub birth_dates: TanahpediaOpinionGroup<TanahpediaDateOpinion>,
+    pub death_dates: TanahpediaOpinionGroup<TanahpediaDateOpinion>,
+    pub death_causes: TanahpediaOpinionGroup<TanahpediaTextOpinion>,
+    pub birth_place_ids: TanahpediaOpinionGroup<TanahpediaTextOpinion>,
+}
+
+#[ComplexObject]
+impl TanahpediaPersonDetail {
+    /// Wars this person fought in, with the side for each. Resolved only when
+    /// selected, so plain detail lookups don't pay for it.
+    async fn wars(&self, ctx: &Context<'_>) -> Result<Vec<TanahpediaParticipantWar>> {
+        tanahpedia_wars_service::find_person_wars(ctx.data::<Database>()?, self.person_id.clone())
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Every fact above grouped by opinion, with each opinion's label,
+    /// preferred flag and sources.
+    async fn opinions(&self, ctx: &Context<'_>) -> Result<TanahpediaPersonOpinions> {
+        tanahpedia_family_service::get_person_opinions(ctx.data::<Database>()?, self)
+            .await
+            .map_err(|e| e.extend())
+    }
 }
 
 #[cfg(test)]
@@ -216,10 +375,24 @@ mod tests {
         assert_eq!(input.person_id, "person-1");
         assert_eq!(input.display_name, "שמשון");
         assert_eq!(input.sex_id, "sex-1");
-        assert_eq!(input.sex, "MALE");
+        assert_eq!(input.sex, TanahpediaSex::Male);
         assert_eq!(input.sex_alt_group_id.as_deref(), Some("alternate-1"));
     }
 
+    #[test]
+    fn person_node_input_rejects_unknown_sex() {
+        assert!(
+            PutTanahpediaPersonNodeInput::parse(Some(value!({
+                "entityId": "entity-1",
+                "personId": "person-1",
+                "displayName": "שמשון",
+                "sexId": "sex-1",
+                "sex": "OTHER"
+            })))
+            .is_err()
+        );
+    }
+
     #[test]
     fn delete_person_node_input_parses_and_serializes_all_graphql_fields() {
         assert!(DeleteTanahpediaPersonNodeInput::parse(None).is_err());
@@ -278,7 +451,7 @@ mod tests {
         .expect("delete orphan entity input should parse");
 
         assert_eq!(input.entity_id, "entity-1");
-        assert_eq!(input.entity_type, "PERSON");
+        assert_eq!(input.entity_type, TanahpediaEntityType::Person);
         assert_eq!(input.display_name, "שמשון");
     }
 }
@@ -0,0 +1,112 @@
+use async_graphql::{InputObject, SimpleObject};
+
+use crate::dtos::tanahpedia_family::{TanahpediaEntityType, TanahpediaSex};
+
+/// A Tanahpedia content document. Rows refer to entities by a
+/// document-local `key`. The importer never deletes anything.
+#[derive(InputObject, Debug, Clone, Default)]
+pub struct TanahpediaImportInput {
+    #[graphql(default)]
+    pub entities: Vec<TanahpediaImportEntityInput>,
+    #[graphql(default)]
+    pub parent_child: Vec<TanahpediaImportParentChildInput>,
+    #[graphql(default)]
+    pub unions: Vec<TanahpediaImportUnionInput>,
+}
+
+/// An entity, matched to an existing one by `entityId` when given, otherwise
+/// by type and name. A name shared by several entities of the type needs an
+/// `entityId`.
+#[derive(InputObject, Debug, Clone)]
+pub struct TanahpediaImportEntityInput {
+    /// Document-local name for this entity, e.g. `manoah`.
+    pub key: String,
+    /// Id of an existing entity, to pick it among namesakes or rename it.
+    /// New entities get their id from the server.
+    pub entity_id: Option<String>,
+    pub entity_type: TanahpediaEntityType,
+    pub name: String,
+    /// `PERSON` entities only.
+    pub sex: Option<TanahpediaSex>,
+    /// `PERSON` entities only.
+    #[graphql(default)]
+    pub names: Vec<TanahpediaImportPersonNameInput>,
+    /// `PLACE` entities only.
+    #[graphql(default)]
+    pub identifications: Vec<TanahpediaImportPlaceIdentificationInput>,
+    #[graphql(default)]
+    pub sources: Vec<TanahpediaImportSourceInput>,
+}
+
+#[derive(InputObject, Debug, Clone)]
+pub struct TanahpediaImportPersonNameInput {
+    pub name: String,
+    /// A `tanahpedia_lookup_name_type` name, e.g. `MAIN`.
+    pub name_type: String,
+}
+
+#[derive(InputObject, Debug, Clone)]
+pub struct TanahpediaImportPlaceIdentificationInput {
+    pub modern_name: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+/// A pasuk the entity appears in, e.g. `{ sefer: "שופטים", perek: 13, pasuk: 2 }`.
+#[derive(InputObject, Debug, Clone)]
+pub struct TanahpediaImportSourceInput {
+    /// Sefer name as the site shows it, e.g. `שמואל א`.
+    pub sefer: String,
+    /// Perek number within the sefer.
+    pub perek: i32,
+    pub pasuk: i32,
+    pub segment_start: Option<i32>,
+    pub segment_end: Option<i32>,
+}
+
+/// Matched to an existing link by parent and child.
+#[derive(InputObject, Debug, Clone)]
+pub struct TanahpediaImportParentChildInput {
+    /// Entity key of the parent.
+    pub parent: String,
+    /// Entity key of the child.
+    pub child: String,
+    pub relationship_type: String,
+    pub parent_role: String,
+    pub source_citation: Option<String>,
+}
+
+/// Matched to an existing union by its two persons, in either order.
+#[derive(InputObject, Debug, Clone)]
+pub struct TanahpediaImportUnionInput {
+    /// Entity key of the first partner.
+    pub person1: String,
+    /// Entity key of the second partner.
+    pub person2: String,
+    pub union_type: String,
+    pub union_order: Option<i32>,
+    pub start_date: Option<i32>,
+    pub end_date: Option<i32>,
+    pub end_reason: Option<String>,
+    pub source_citation: Option<String>,
+    pub person_source_citation: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaImportedEntity {
+    pub key: String,
+    pub entity_id: String,
+    /// False when the entity already existed.
+    pub created: bool,
+}
+
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaImportResult {
+    /// When true, nothing was written.
+    pub dry_run: bool,
+    pub entities: Vec<TanahpediaImportedEntity>,
+    /// Rows inserted, updated and already up to date, across all tables.
+    pub created: i32,
+    pub updated: i32,
+    pub unchanged: i32,
+}
@@ -0,0 +1,64 @@
+use async_graphql::{Enum, InputObject, SimpleObject};
+
+/// File formats accepted by `uploadTanahpediaModel3d`.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TanahpediaModel3dFormat {
+    /// glTF 2.0 JSON with embedded (data URI) buffers.
+    Gltf,
+    /// Binary glTF 2.0.
+    Glb,
+    /// Uncompressed USD zip package (AR Quick Look).
+    Usdz,
+}
+
+impl TanahpediaModel3dFormat {
+    /// The value stored in `tanahpedia_3d_model.format`.
+    pub fn stored_name(self) -> &'static str {
+        match self {
+            Self::Gltf => "glTF",
+            Self::Glb => "GLB",
+            Self::Usdz => "USDZ",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Gltf => "gltf",
+            Self::Glb => "glb",
+            Self::Usdz => "usdz",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Gltf => "model/gltf+json",
+            Self::Glb => "model/gltf-binary",
+            Self::Usdz => "model/vnd.usdz+zip",
+        }
+    }
+}
+
+/// A 3D model attached to a Tanahpedia entity, with a short-lived download
+/// URL for its asset.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaModel3d {
+    pub id: String,
+    pub entity_id: String,
+    /// As stored: `glTF`, `GLB`, `USDZ`, or a legacy value such as `OBJ`.
+    pub format: String,
+    pub label: Option<String>,
+    pub alt_group_id: Option<String>,
+    /// Pre-signed GET URL; expires after `S3_PRESIGN_TTL_SECONDS`.
+    pub url: String,
+}
+
+/// Metadata for `uploadTanahpediaModel3d`. Re-uploading with the same `id`
+/// replaces both the asset and the row.
+#[derive(InputObject, Debug, Clone)]
+pub struct UploadTanahpediaModel3dInput {
+    pub id: String,
+    pub entity_id: String,
+    pub format: TanahpediaModel3dFormat,
+    pub label: Option<String>,
+    pub alt_group_id: Option<String>,
+}
@@ -0,0 +1,50 @@
+use async_graphql::{Enum, InputObject, SimpleObject};
+
+/// Who a name giver is; decides which table `giverId` is resolved against.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TanahpediaNameGiverType {
+    Person,
+    God,
+}
+
+/// Adds (or updates) who gave a person's name.
+#[derive(InputObject, Debug, Clone)]
+pub struct PutTanahpediaNameGiverInput {
+    pub id: String,
+    pub person_name_id: String,
+    pub giver_type: TanahpediaNameGiverType,
+    pub giver_id: String,
+    pub alt_group_id: Option<String>,
+    /// Free-text citation for the naming, e.g. `"בראשית כט לב"`.
+    pub source_citation: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaNameGiverWriteResult {
+    pub id: String,
+    pub person_name_id: String,
+    pub giver_type: TanahpediaNameGiverType,
+}
+
+/// Who gave a name. `giverId` is a `personId` or the `godId` according to
+/// `giverType`.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaNameGiver {
+    pub link_id: String,
+    pub giver_type: TanahpediaNameGiverType,
+    pub giver_id: String,
+    pub display_name: String,
+    pub alt_group_id: Option<String>,
+    pub source_citation: Option<String>,
+}
+
+/// A name a giver gave, together with the person who received it.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaNaming {
+    pub person_name_id: String,
+    pub name: String,
+    pub name_type: String,
+    pub person_id: String,
+    pub person_display_name: String,
+    pub giver: TanahpediaNameGiver,
+}
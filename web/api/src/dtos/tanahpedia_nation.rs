@@ -0,0 +1,147 @@
+use async_graphql::{ComplexObject, Context, ErrorExtensions, InputObject, Result, SimpleObject};
+
+use crate::{
+    dtos::{tanahpedia_place::TanahpediaPointGeometry, tanahpedia_war::TanahpediaParticipantWar},
+    providers::Database,
+    services::tanahpedia_wars_service,
+};
+
+/// Adds (or updates) an ancestry link: `nationId` descends from
+/// `sourceNationId`, e.g. Edom from Esav's sons.
+#[derive(InputObject, Debug, Clone)]
+pub struct PutTanahpediaNationSourceInput {
+    pub id: String,
+    pub nation_id: String,
+    pub source_nation_id: String,
+    pub alt_group_id: Option<String>,
+}
+
+/// Adds (or updates) a place a nation held. Dates are `YYYYMMDD` Hebrew
+/// integers; a missing date leaves that end of the range open.
+#[derive(InputObject, Debug, Clone)]
+pub struct PutTanahpediaNationTerritoryInput {
+    pub id: String,
+    pub nation_id: String,
+    pub place_id: String,
+    pub start_date: Option<i32>,
+    pub end_date: Option<i32>,
+    pub alt_group_id: Option<String>,
+}
+
+/// Adds (or updates) a person's link to a nation. `role` is a
+/// `tanahpedia_lookup_person_nation_role` name, e.g. `FOUNDER`.
+#[derive(InputObject, Debug, Clone)]
+pub struct PutTanahpediaPersonNationInput {
+    pub id: String,
+    pub person_id: String,
+    pub nation_id: String,
+    pub role: String,
+    pub alt_group_id: Option<String>,
+    pub source_citation: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaNationLinkWriteResult {
+    pub id: String,
+    pub nation_id: String,
+}
+
+/// An ancestor or descendant nation. `depth` is 1 for a direct parent or
+/// child nation; `viaNationId` is the nation one step closer to the queried
+/// one that this link hangs from.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaNationRelative {
+    pub link_id: String,
+    pub nation_id: String,
+    pub display_name: String,
+    pub depth: i32,
+    pub via_nation_id: String,
+    pub alt_group_id: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaNationTerritory {
+    pub link_id: String,
+    pub place_id: String,
+    pub display_name: String,
+    pub start_date: Option<i32>,
+    pub end_date: Option<i32>,
+    pub alt_group_id: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaPersonNationLink {
+    pub link_id: String,
+    pub person_id: String,
+    pub person_display_name: String,
+    pub nation_id: String,
+    pub nation_display_name: String,
+    pub role: String,
+    pub alt_group_id: Option<String>,
+    pub source_citation: Option<String>,
+}
+
+/// A nation with its full ancestry and descent, the territories it held and
+/// the persons linked to it.
+#[derive(SimpleObject, Debug, Clone)]
+#[graphql(complex)]
+pub struct TanahpediaNation {
+    pub nation_id: String,
+    pub entity_id: String,
+    pub display_name: String,
+    /// Nearest first.
+    pub ancestors: Vec<TanahpediaNationRelative>,
+    /// Nearest first.
+    pub descendants: Vec<TanahpediaNationRelative>,
+    /// Limited to the requested year, when one was given.
+    pub territories: Vec<TanahpediaNationTerritory>,
+    pub persons: Vec<TanahpediaPersonNationLink>,
+}
+
+#[ComplexObject]
+impl TanahpediaNation {
+    /// Wars this nation fought in, with the side for each.
+    async fn wars(&self, ctx: &Context<'_>) -> Result<Vec<TanahpediaParticipantWar>> {
+        tanahpedia_wars_service::find_nation_wars(ctx.data::<Database>()?, self.nation_id.clone())
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+/// Properties of a territory feature: which nation held the place and when.
+/// The feature's point is the place identification named by
+/// `identificationId`.
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaTerritoryFeatureProperties {
+    pub nation_id: String,
+    pub nation_display_name: String,
+    pub territory_id: String,
+    pub place_id: String,
+    pub place_display_name: String,
+    pub identification_id: String,
+    pub modern_name: Option<String>,
+    pub start_date: Option<i32>,
+    pub end_date: Option<i32>,
+    /// The territory link's opinion.
+    pub alt_group_id: Option<String>,
+    /// The place identification's opinion.
+    pub identification_alt_group_id: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaTerritoryFeature {
+    #[graphql(name = "type")]
+    pub kind: String,
+    pub id: String,
+    pub geometry: TanahpediaPointGeometry,
+    pub properties: TanahpediaTerritoryFeatureProperties,
+}
+
+/// A GeoJSON `FeatureCollection` with one feature per territory and located
+/// identification of its place, ready for a map overlay.
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaTerritoryFeatureCollection {
+    #[graphql(name = "type")]
+    pub kind: String,
+    pub features: Vec<TanahpediaTerritoryFeature>,
+}
@@ -0,0 +1,162 @@
+use async_graphql::{InputObject, OutputType, SimpleObject};
+
+use crate::dtos::tanahpedia_animal::{
+    TanahpediaAnimalKindClassification, TanahpediaAnimalPurityClassification,
+};
+use crate::dtos::tanahpedia_creation_day::TanahpediaCreationDayItem;
+use crate::dtos::tanahpedia_family::{TanahpediaPersonName, TanahpediaPersonSex};
+use crate::dtos::tanahpedia_place::TanahpediaPlaceIdentification;
+use crate::dtos::tanahpedia_saying::{TanahpediaSayingLocation, TanahpediaSayingParty};
+use crate::dtos::tanahpedia_source::{TanahpediaSources, TanahpediaTanahSourceInput};
+use crate::dtos::tanahpedia_war::{
+    TanahpediaWarDateRange, TanahpediaWarParticipant, TanahpediaWarPlace,
+};
+
+/// Labels an opinion (an `altGroupId` value) and optionally marks it as the
+/// preferred one. The sources replace whatever was stored for it before.
+/// Opinions competing over the same fact share a `disputeId`; marking one
+/// preferred clears the flag on the others, so `isPreferred` needs it.
+#[derive(InputObject, Debug, Clone)]
+pub struct PutTanahpediaOpinionInput {
+    pub alt_group_id: String,
+    pub label: Option<String>,
+    #[graphql(default)]
+    pub is_preferred: bool,
+    pub dispute_id: Option<String>,
+    #[graphql(default)]
+    pub tanah_sources: Vec<TanahpediaTanahSourceInput>,
+    #[graphql(default)]
+    pub non_tanah_sources: Vec<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaOpinionWriteResult {
+    pub alt_group_id: String,
+    pub label: Option<String>,
+    pub is_preferred: bool,
+    pub dispute_id: Option<String>,
+}
+
+/// The stored metadata of one opinion (`tanahpedia_alt_group`).
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaOpinionMetadata {
+    pub alt_group_id: String,
+    pub label: Option<String>,
+    pub is_preferred: bool,
+    pub dispute_id: Option<String>,
+    pub sources: TanahpediaSources,
+}
+
+/// One alternative for a fact: the rows sharing an `altGroupId`, with that
+/// opinion's label, preferred flag and sources. Rows without an `altGroupId`
+/// form an opinion whose `altGroupId` is null — the undisputed facts.
+#[derive(SimpleObject, Debug, Clone)]
+#[graphql(concrete(name = "TanahpediaPersonNameOpinion", params(TanahpediaPersonName)))]
+#[graphql(concrete(name = "TanahpediaPersonSexOpinion", params(TanahpediaPersonSex)))]
+#[graphql(concrete(name = "TanahpediaDateOpinion", params(i32)))]
+#[graphql(concrete(name = "TanahpediaTextOpinion", params(String)))]
+#[graphql(concrete(name = "TanahpediaWarDateRangeOpinion", params(TanahpediaWarDateRange)))]
+#[graphql(concrete(name = "TanahpediaWarPlaceOpinion", params(TanahpediaWarPlace)))]
+#[graphql(concrete(
+    name = "TanahpediaWarParticipantOpinion",
+    params(TanahpediaWarParticipant)
+))]
+#[graphql(concrete(name = "TanahpediaSayingPartyOpinion", params(TanahpediaSayingParty)))]
+#[graphql(concrete(
+    name = "TanahpediaSayingLocationOpinion",
+    params(TanahpediaSayingLocation)
+))]
+#[graphql(concrete(
+    name = "TanahpediaAnimalKindOpinion",
+    params(TanahpediaAnimalKindClassification)
+))]
+#[graphql(concrete(
+    name = "TanahpediaAnimalPurityOpinion",
+    params(TanahpediaAnimalPurityClassification)
+))]
+#[graphql(concrete(
+    name = "TanahpediaCreationDayItemOpinion",
+    params(TanahpediaCreationDayItem)
+))]
+#[graphql(concrete(
+    name = "TanahpediaPlaceIdentificationOpinion",
+    params(TanahpediaPlaceIdentification)
+))]
+pub struct TanahpediaOpinion<T: OutputType> {
+    pub alt_group_id: Option<String>,
+    pub label: Option<String>,
+    pub is_preferred: bool,
+    pub sources: TanahpediaSources,
+    pub facts: Vec<T>,
+}
+
+pub type TanahpediaPersonNameOpinion = TanahpediaOpinion<TanahpediaPersonName>;
+pub type TanahpediaPersonSexOpinion = TanahpediaOpinion<TanahpediaPersonSex>;
+pub type TanahpediaDateOpinion = TanahpediaOpinion<i32>;
+pub type TanahpediaTextOpinion = TanahpediaOpinion<String>;
+pub type TanahpediaWarDateRangeOpinion = TanahpediaOpinion<TanahpediaWarDateRange>;
+pub type TanahpediaWarPlaceOpinion = TanahpediaOpinion<TanahpediaWarPlace>;
+pub type TanahpediaWarParticipantOpinion = TanahpediaOpinion<TanahpediaWarParticipant>;
+pub type TanahpediaSayingPartyOpinion = TanahpediaOpinion<TanahpediaSayingParty>;
+pub type TanahpediaSayingLocationOpinion = TanahpediaOpinion<TanahpediaSayingLocation>;
+pub type TanahpediaAnimalKindOpinion = TanahpediaOpinion<TanahpediaAnimalKindClassification>;
+pub type TanahpediaAnimalPurityOpinion = TanahpediaOpinion<TanahpediaAnimalPurityClassification>;
+pub type TanahpediaCreationDayItemOpinion = TanahpediaOpinion<TanahpediaCreationDayItem>;
+pub type TanahpediaPlaceIdentificationOpinion = TanahpediaOpinion<TanahpediaPlaceIdentification>;
+
+/// Every competing opinion on one fact. Undisputed rows come first, then the
+/// preferred opinion, then the rest by label. `preferredAltGroupId` names the
+/// preferred opinion, if one is marked.
+#[derive(SimpleObject, Debug, Clone)]
+#[graphql(concrete(
+    name = "TanahpediaPersonNameOpinionGroup",
+    params(TanahpediaPersonNameOpinion)
+))]
+#[graphql(concrete(
+    name = "TanahpediaPersonSexOpinionGroup",
+    params(TanahpediaPersonSexOpinion)
+))]
+#[graphql(concrete(name = "TanahpediaDateOpinionGroup", params(TanahpediaDateOpinion)))]
+#[graphql(concrete(name = "TanahpediaTextOpinionGroup", params(TanahpediaTextOpinion)))]
+#[graphql(concrete(
+    name = "TanahpediaWarDateRangeOpinionGroup",
+    params(TanahpediaWarDateRangeOpinion)
+))]
+#[graphql(concrete(
+    name = "TanahpediaWarPlaceOpinionGroup",
+    params(TanahpediaWarPlaceOpinion)
+))]
+#[graphql(concrete(
+    name = "TanahpediaWarParticipantOpinionGroup",
+    params(TanahpediaWarParticipantOpinion)
+))]
+#[graphql(concrete(
+    name = "TanahpediaSayingPartyOpinionGroup",
+    params(TanahpediaSayingPartyOpinion)
+))]
+#[graphql(concrete(
+    name = "TanahpediaSayingLocationOpinionGroup",
+    params(TanahpediaSayingLocationOpinion)
+))]
+#[graphql(concrete(
+    name = "TanahpediaAnimalKindOpinionGroup",
+    params(TanahpediaAnimalKindOpinion)
+))]
+#[graphql(concrete(
+    name = "TanahpediaAnimalPurityOpinionGroup",
+    params(TanahpediaAnimalPurityOpinion)
+))]
+#[graphql(concrete(
+    name = "TanahpediaCreationDayItemOpinionGroup",
+    params(TanahpediaCreationDayItemOpinion)
+))]
+#[graphql(concrete(
+    name = "TanahpediaPlaceIdentificationOpinionGroup",
+    params(TanahpediaPlaceIdentificationOpinion)
+))]
+pub struct TanahpediaOpinionGroup<O: OutputType> {
+    pub preferred_alt_group_id: Option<String>,
+    /// More than one opinion, i.e. the alternatives genuinely compete.
+    pub is_disputed: bool,
+    pub opinions: Vec<O>,
+}
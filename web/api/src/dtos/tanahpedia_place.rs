@@ -0,0 +1,90 @@
+use async_graphql::SimpleObject;
+
+use crate::dtos::tanahpedia_opinion::{
+    TanahpediaOpinionGroup, TanahpediaPlaceIdentificationOpinion,
+};
+
+/// One proposed modern identification of a biblical place. Competing
+/// identifications share the place and are told apart by `altGroupId`.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaPlaceIdentification {
+    pub id: String,
+    pub modern_name: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub alt_group_id: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaPlaceEventLink {
+    pub link_id: String,
+    pub event_id: String,
+    pub display_name: String,
+    pub alt_group_id: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaPlaceBirthLink {
+    pub link_id: String,
+    pub person_id: String,
+    pub display_name: String,
+    pub alt_group_id: Option<String>,
+}
+
+/// GeoJSON `Point`; `coordinates` is `[longitude, latitude]` as the spec requires.
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaPointGeometry {
+    #[graphql(name = "type")]
+    pub kind: String,
+    pub coordinates: Vec<f64>,
+}
+
+/// Properties of a place feature. The feature's point is the identification
+/// named by `identificationId`; `identifications` lists every alternative for
+/// the same place so a map can show the competing opinions together, and
+/// `identificationOpinions` groups them by opinion.
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaPlaceFeatureProperties {
+    pub place_id: String,
+    pub entity_id: String,
+    pub display_name: String,
+    pub identification_id: String,
+    pub modern_name: Option<String>,
+    pub alt_group_id: Option<String>,
+    pub identifications: Vec<TanahpediaPlaceIdentification>,
+    pub identification_opinions: TanahpediaOpinionGroup<TanahpediaPlaceIdentificationOpinion>,
+    pub events: Vec<TanahpediaPlaceEventLink>,
+    pub births: Vec<TanahpediaPlaceBirthLink>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaPlaceFeature {
+    #[graphql(name = "type")]
+    pub kind: String,
+    pub id: String,
+    pub geometry: TanahpediaPointGeometry,
+    pub properties: TanahpediaPlaceFeatureProperties,
+}
+
+/// A GeoJSON `FeatureCollection` with one feature per located identification.
+/// Selecting every field yields a document map layers can load as-is.
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaPlaceFeatureCollection {
+    #[graphql(name = "type")]
+    pub kind: String,
+    pub features: Vec<TanahpediaPlaceFeature>,
+}
+
+/// A located identification within the requested radius, nearest first.
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaPlaceNear {
+    pub place_id: String,
+    pub entity_id: String,
+    pub display_name: String,
+    pub identification_id: String,
+    pub modern_name: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub alt_group_id: Option<String>,
+    pub distance_km: f64,
+}
@@ -0,0 +1,117 @@
+use async_graphql::{Enum, SimpleObject};
+
+use crate::dtos::{
+    tanahpedia_entry::TanahpediaEntryReference, tanahpedia_family::TanahpediaEntitySummary,
+};
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaEntityFindings {
+    pub count: i32,
+    pub entities: Vec<TanahpediaEntitySummary>,
+}
+
+/// A person missing a sex, names, or both.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaIncompletePerson {
+    pub person_id: String,
+    pub entity_id: String,
+    pub display_name: String,
+    pub missing_sex: bool,
+    pub missing_names: bool,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaIncompletePersonFindings {
+    pub count: i32,
+    pub persons: Vec<TanahpediaIncompletePerson>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaEntryFindings {
+    pub count: i32,
+    pub entries: Vec<TanahpediaEntryReference>,
+}
+
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TanahpediaRelationshipKind {
+    ParentChild,
+    Union,
+}
+
+/// A family relationship with no `source_citation`.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaUncitedRelationship {
+    pub id: String,
+    pub kind: TanahpediaRelationshipKind,
+    /// The parent, or the first partner.
+    pub person1_id: String,
+    /// The child, or the second partner.
+    pub person2_id: String,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaUncitedRelationshipFindings {
+    pub count: i32,
+    pub relationships: Vec<TanahpediaUncitedRelationship>,
+}
+
+/// The lookup tables a value can come from.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TanahpediaLookup {
+    NameType,
+    ParentChildType,
+    ParentRole,
+    UnionType,
+    UnionEndReason,
+    PersonNationRole,
+}
+
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaUnusedLookupValue {
+    pub lookup: TanahpediaLookup,
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaUnusedLookupValueFindings {
+    pub count: i32,
+    pub values: Vec<TanahpediaUnusedLookupValue>,
+}
+
+/// A non-Tanah citation the Chazal parser doesn't recognize, with the row it
+/// backs (`targetTable`, `targetId`) and why it was rejected.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaUnrecognizedNonTanahSource {
+    pub id: String,
+    pub source_text: String,
+    pub target_table: String,
+    pub target_id: String,
+    pub reason: String,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaUnrecognizedNonTanahSourceFindings {
+    pub count: i32,
+    pub sources: Vec<TanahpediaUnrecognizedNonTanahSource>,
+}
+
+/// How complete Tanahpedia is. Every finding carries the ids needed to open
+/// the offending row in the admin UI.
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaQualityReport {
+    /// Entities with no `entity_tanah_source`.
+    pub entities_without_sources: TanahpediaEntityFindings,
+    /// Persons with no `person_sex` or no `person_name`.
+    pub incomplete_persons: TanahpediaIncompletePersonFindings,
+    /// Entries not linked to any entity.
+    pub entries_without_entities: TanahpediaEntryFindings,
+    /// Entities nothing refers to, as `deleteTanahpediaOrphanEntity` decides.
+    pub orphan_entities: TanahpediaEntityFindings,
+    /// Parent-child links and unions with an empty `source_citation`.
+    pub uncited_relationships: TanahpediaUncitedRelationshipFindings,
+    /// Lookup values no row uses.
+    pub unused_lookup_values: TanahpediaUnusedLookupValueFindings,
+    /// Non-Tanah sources with no structured reference.
+    pub unrecognized_non_tanah_sources: TanahpediaUnrecognizedNonTanahSourceFindings,
+}
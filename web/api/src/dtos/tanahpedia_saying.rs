@@ -0,0 +1,98 @@
+use async_graphql::{
+    ComplexObject, Context, Enum, ErrorExtensions, InputObject, Result, SimpleObject,
+};
+
+use crate::{
+    dtos::tanahpedia_opinion::{
+        TanahpediaOpinionGroup, TanahpediaSayingLocationOpinion, TanahpediaSayingPartyOpinion,
+    },
+    providers::Database,
+    services::tanahpedia_sayings_service,
+};
+
+/// Who a speaker or audience member is; decides which table `partyId` is
+/// resolved against. `GOD` is accepted for speakers only.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TanahpediaSayingPartyType {
+    Person,
+    Nation,
+    God,
+}
+
+/// Adds (or updates) a speaker/audience link on a saying.
+#[derive(InputObject, Debug, Clone)]
+pub struct PutTanahpediaSayingPartyInput {
+    pub id: String,
+    pub saying_id: String,
+    pub party_type: TanahpediaSayingPartyType,
+    pub party_id: String,
+    pub alt_group_id: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaSayingPartyWriteResult {
+    pub id: String,
+    pub saying_id: String,
+    pub party_type: TanahpediaSayingPartyType,
+}
+
+/// One side of a saying — a speaker or an audience member. `partyId` is a
+/// `personId`, `nationId` or `godId` according to `partyType`.
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaSayingParty {
+    pub link_id: String,
+    pub party_type: TanahpediaSayingPartyType,
+    pub party_id: String,
+    pub display_name: String,
+    pub alt_group_id: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaSayingLocation {
+    pub link_id: String,
+    pub place_id: String,
+    pub display_name: String,
+    pub alt_group_id: Option<String>,
+}
+
+/// A saying together with who said it, to whom, and where.
+#[derive(SimpleObject, Debug, Clone)]
+#[graphql(complex)]
+pub struct TanahpediaSaying {
+    pub saying_id: String,
+    pub entity_id: String,
+    pub display_name: String,
+    pub content: Option<String>,
+    pub speakers: Vec<TanahpediaSayingParty>,
+    pub audience: Vec<TanahpediaSayingParty>,
+    pub locations: Vec<TanahpediaSayingLocation>,
+}
+
+#[ComplexObject]
+impl TanahpediaSaying {
+    /// Speakers, audience and locations grouped by opinion.
+    async fn opinions(&self, ctx: &Context<'_>) -> Result<TanahpediaSayingOpinions> {
+        tanahpedia_sayings_service::get_saying_opinions(ctx.data::<Database>()?, self)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaSayingOpinions {
+    pub speakers: TanahpediaOpinionGroup<TanahpediaSayingPartyOpinion>,
+    pub audience: TanahpediaOpinionGroup<TanahpediaSayingPartyOpinion>,
+    pub locations: TanahpediaOpinionGroup<TanahpediaSayingLocationOpinion>,
+}
+
+/// Aggregated dialogue between a queried person and one other person:
+/// `spokeToCount` counts sayings the queried person addressed to the partner,
+/// `heardFromCount` counts sayings the partner addressed to the queried person.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaConversationPartner {
+    pub person_id: String,
+    pub display_name: String,
+    pub spoke_to_count: i32,
+    pub heard_from_count: i32,
+    pub total_count: i32,
+}
@@ -0,0 +1,130 @@
+use async_graphql::{
+    ComplexObject, Context, Enum, ErrorExtensions, InputObject, Result, SimpleObject,
+    dataloader::DataLoader,
+};
+
+use crate::services::tanahpedia_sources_service::{self, TanahSourceLoader};
+
+/// A Tanah citation backing a fact. `perushId` names the commentary (null for
+/// the pasuk itself); a null `pasukNumber` cites the whole perek.
+#[derive(InputObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaTanahSourceInput {
+    pub perush_id: Option<i16>,
+    pub perek_id: Option<i32>,
+    pub pasuk_number: Option<i32>,
+}
+
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+#[graphql(complex)]
+pub struct TanahpediaTanahSource {
+    pub id: String,
+    pub perush_id: Option<i16>,
+    pub perek_id: Option<i32>,
+    pub pasuk_number: Option<i32>,
+}
+
+#[ComplexObject]
+impl TanahpediaTanahSource {
+    /// Hebrew citation, e.g. `רש"י על בראשית ל' ד'` or `בראשית ל' ד'`.
+    async fn citation(&self, ctx: &Context<'_>) -> Result<String> {
+        tanahpedia_sources_service::get_tanah_source_citation(
+            ctx.data::<DataLoader<TanahSourceLoader>>()?,
+            self,
+        )
+        .await
+        .map_err(|e| e.extend())
+    }
+
+    /// The cited perush and its notes on the pasuk (the whole perek when
+    /// `pasukNumber` is null); null when the source cites the pasuk itself.
+    async fn commentary(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<TanahpediaTanahSourceCommentary>> {
+        tanahpedia_sources_service::get_tanah_source_commentary(
+            ctx.data::<DataLoader<TanahSourceLoader>>()?,
+            self,
+        )
+        .await
+        .map_err(|e| e.extend())
+    }
+}
+
+/// One `note` row of a perush.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaPerushNote {
+    pub pasuk: i32,
+    pub note_idx: i32,
+    pub content: String,
+}
+
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaTanahSourceCommentary {
+    pub perush_id: i16,
+    /// e.g. `רש"י`.
+    pub perush_name: String,
+    pub parshan_name: Option<String>,
+    pub notes: Vec<TanahpediaPerushNote>,
+}
+
+/// The Chazal corpora a non-Tanah citation can be recognized as.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TanahpediaChazalCorpus {
+    Bavli,
+    Yerushalmi,
+    Mishnah,
+    MidrashRabbah,
+    Rambam,
+}
+
+/// A non-Tanah citation parsed into its parts. `section` is the daf (Bavli,
+/// Yerushalmi), perek (Mishnah, Rambam) or parasha (Midrash Rabbah);
+/// `subsection` is the amud (1 = a, 2 = b), mishnah, halacha or siman, when
+/// given.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaChazalReference {
+    pub corpus: TanahpediaChazalCorpus,
+    /// English name of the tractate, midrash or Rambam hilchot, e.g.
+    /// `Sanhedrin`.
+    pub work: String,
+    pub work_hebrew: String,
+    pub section: i32,
+    pub subsection: Option<i32>,
+    /// e.g. `בבלי סנהדרין צ"ח ע"ב`.
+    pub hebrew_citation: String,
+    /// e.g. `Bavli Sanhedrin 98b`.
+    pub english_citation: String,
+}
+
+/// A free-text citation outside Tanah (Gemara, Rambam, ...). `reference` is
+/// null when the text isn't in a recognized format.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaNonTanahSource {
+    pub id: String,
+    pub source_text: String,
+    pub reference: Option<TanahpediaChazalReference>,
+}
+
+/// The outcome of parsing one citation: `reference` when recognized,
+/// otherwise `error` says what could not be read.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaChazalParseResult {
+    pub source_text: String,
+    pub reference: Option<TanahpediaChazalReference>,
+    pub error: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaNonTanahSourceNormalizeResult {
+    pub recognized: i32,
+    pub unrecognized: i32,
+    /// Rows whose stored reference changed.
+    pub updated: i32,
+}
+
+/// The source group attached to one row (`tanahpedia_source_group`).
+#[derive(SimpleObject, Debug, Clone, Default, PartialEq)]
+pub struct TanahpediaSources {
+    pub tanah: Vec<TanahpediaTanahSource>,
+    pub non_tanah: Vec<TanahpediaNonTanahSource>,
+}
@@ -0,0 +1,137 @@
+use async_graphql::{
+    ComplexObject, Context, Enum, ErrorExtensions, InputObject, Result, SimpleObject,
+};
+
+use crate::{
+    dtos::tanahpedia_opinion::{
+        TanahpediaOpinionGroup, TanahpediaWarDateRangeOpinion, TanahpediaWarParticipantOpinion,
+        TanahpediaWarPlaceOpinion,
+    },
+    providers::Database,
+    services::tanahpedia_wars_service,
+};
+
+/// Adds (or updates) a numbered side of a war. `sideNumber` starts at 1 and is
+/// unique within the war.
+#[derive(InputObject, Debug, Clone)]
+pub struct PutTanahpediaWarSideInput {
+    pub id: String,
+    pub war_id: String,
+    pub side_number: i32,
+}
+
+/// Who takes part on a war side; decides which table `participantId` is
+/// resolved against.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TanahpediaWarParticipantType {
+    Person,
+    Nation,
+}
+
+/// Adds (or updates) a participant on a war side.
+#[derive(InputObject, Debug, Clone)]
+pub struct PutTanahpediaWarParticipantInput {
+    pub id: String,
+    pub war_side_id: String,
+    pub participant_type: TanahpediaWarParticipantType,
+    pub participant_id: String,
+    pub alt_group_id: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaWarSideWriteResult {
+    pub id: String,
+    pub war_id: String,
+    pub side_number: i32,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaWarParticipantWriteResult {
+    pub id: String,
+    pub war_side_id: String,
+    pub participant_type: TanahpediaWarParticipantType,
+}
+
+/// One `tanahpedia_event_date_range` row of the war's event. Dates are
+/// `YYYYMMDD` integers, matching the person birth/death dates.
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaWarDateRange {
+    pub id: String,
+    pub start_date: Option<i32>,
+    pub end_date: Option<i32>,
+    pub alt_group_id: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaWarPlace {
+    pub link_id: String,
+    pub place_id: String,
+    pub display_name: String,
+    pub alt_group_id: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaWarParticipant {
+    pub link_id: String,
+    pub participant_type: TanahpediaWarParticipantType,
+    pub participant_id: String,
+    pub display_name: String,
+    pub alt_group_id: Option<String>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaWarSide {
+    pub side_id: String,
+    pub side_number: i32,
+    pub participants: Vec<TanahpediaWarParticipant>,
+}
+
+/// A war together with its event's date ranges and places, and every side
+/// (ordered by `sideNumber`) with its participants.
+#[derive(SimpleObject, Debug, Clone)]
+#[graphql(complex)]
+pub struct TanahpediaWar {
+    pub war_id: String,
+    pub entity_id: String,
+    pub event_id: String,
+    pub display_name: String,
+    pub date_ranges: Vec<TanahpediaWarDateRange>,
+    pub places: Vec<TanahpediaWarPlace>,
+    pub sides: Vec<TanahpediaWarSide>,
+}
+
+#[ComplexObject]
+impl TanahpediaWar {
+    /// Date ranges, places and each side's participants grouped by opinion.
+    async fn opinions(&self, ctx: &Context<'_>) -> Result<TanahpediaWarOpinions> {
+        tanahpedia_wars_service::get_war_opinions(ctx.data::<Database>()?, self)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaWarSideOpinions {
+    pub side_id: String,
+    pub side_number: i32,
+    pub participants: TanahpediaOpinionGroup<TanahpediaWarParticipantOpinion>,
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct TanahpediaWarOpinions {
+    pub date_ranges: TanahpediaOpinionGroup<TanahpediaWarDateRangeOpinion>,
+    pub places: TanahpediaOpinionGroup<TanahpediaWarPlaceOpinion>,
+    pub sides: Vec<TanahpediaWarSideOpinions>,
+}
+
+/// A war a person or nation took part in, seen from that participant: which
+/// side it fought on and the participant link that says so.
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TanahpediaParticipantWar {
+    pub war_id: String,
+    pub display_name: String,
+    pub side_id: String,
+    pub side_number: i32,
+    pub link_id: String,
+    pub alt_group_id: Option<String>,
+}
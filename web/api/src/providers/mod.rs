@@ -1,2 +1,8 @@
 pub mod database;
+pub mod persisted_queries;
+pub mod static_cache;
+pub mod storage;
 pub use database::Database;
+pub use persisted_queries::PersistedQueries;
+pub use static_cache::StaticCache;
+pub use storage::Storage;
@@ -0,0 +1,309 @@
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    env, fs,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Result, anyhow};
+use async_graphql::{
+    ErrorExtensionValues, Request, ServerError, ServerResult,
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest},
+    from_value,
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::common::auth::ApiAuth;
+
+/// Automatic persisted queries kept before the store is emptied and refilled.
+const MAX_AUTOMATIC_QUERIES: usize = 1000;
+
+#[derive(Deserialize)]
+struct PersistedQuery {
+    version: i32,
+    #[serde(rename = "sha256Hash")]
+    sha256_hash: String,
+}
+
+/// Persisted GraphQL operations, addressed by the SHA-256 hex digest of their
+/// text (the `extensions.persistedQuery` protocol used by Apollo and urql).
+///
+/// Operations registered by the website and app are loaded from the JSON
+/// manifest at `PERSISTED_QUERIES_MANIFEST` (`{ "<sha256>": "<query>" }`).
+/// Other clients register theirs on first use (automatic persisted queries),
+/// unless `PERSISTED_QUERIES_ALLOW_LIST=true`: then anonymous requests may only
+/// run registered operations, while clients presenting the revision API key
+/// keep sending ad-hoc queries.
+#[derive(Clone, Default)]
+pub struct PersistedQueries {
+    registered: Arc<HashMap<String, String>>,
+    automatic: Arc<Mutex<HashMap<String, String>>>,
+    allow_list: bool,
+}
+
+impl PersistedQueries {
+    pub fn new() -> Result<Self> {
+        let registered = match env::var("PERSISTED_QUERIES_MANIFEST") {
+            Ok(path) if !path.is_empty() => {
+                let manifest = fs::read_to_string(&path).map_err(|e| {
+                    anyhow!("Failed to read persisted queries manifest {path}: {e}")
+                })?;
+                serde_json::from_str(&manifest)
+                    .map_err(|e| anyhow!("Invalid persisted queries manifest {path}: {e}"))?
+            }
+            _ => HashMap::new(),
+        };
+        let allow_list =
+            env::var("PERSISTED_QUERIES_ALLOW_LIST").is_ok_and(|value| value == "true");
+        Self::with_registered(registered, allow_list)
+    }
+
+    /// Fails when a registered hash is not the digest of its query, which would
+    /// make the operation unreachable.
+    pub fn with_registered(registered: HashMap<String, String>, allow_list: bool) -> Result<Self> {
+        let mut by_hash = HashMap::with_capacity(registered.len());
+        for (hash, query) in registered {
+            let hash = hash.to_ascii_lowercase();
+            if sha256_hex(&query) != hash {
+                return Err(anyhow!(
+                    "Persisted query {hash} does not match the SHA-256 of its text"
+                ));
+            }
+            by_hash.insert(hash, query);
+        }
+        Ok(Self {
+            registered: Arc::new(by_hash),
+            automatic: Arc::new(Mutex::new(HashMap::new())),
+            allow_list,
+        })
+    }
+
+    fn lookup(&self, hash: &str) -> Option<String> {
+        if let Some(query) = self.registered.get(hash) {
+            return Some(query.clone());
+        }
+        if self.allow_list {
+            return None;
+        }
+        self.automatic.lock().unwrap().get(hash).cloned()
+    }
+
+    fn remember(&self, hash: String, query: String) {
+        let mut automatic = self.automatic.lock().unwrap();
+        if automatic.len() >= MAX_AUTOMATIC_QUERIES {
+            automatic.clear();
+        }
+        automatic.insert(hash, query);
+    }
+
+    fn resolve(&self, mut request: Request, authorized: bool) -> ServerResult<Request> {
+        let Some(extension) = request.extensions.remove("persistedQuery") else {
+            if self.allow_list && !authorized {
+                return Err(persisted_query_error(
+                    "PersistedQueryRequired",
+                    "PERSISTED_QUERY_REQUIRED",
+                ));
+            }
+            return Ok(request);
+        };
+
+        let persisted: PersistedQuery = from_value(extension).map_err(|_| {
+            persisted_query_error("Invalid persistedQuery extension", "BAD_REQUEST")
+        })?;
+        if persisted.version != 1 {
+            return Err(persisted_query_error(
+                "Only persistedQuery version 1 is supported",
+                "BAD_REQUEST",
+            ));
+        }
+        let hash = persisted.sha256_hash.to_ascii_lowercase();
+
+        if request.query.is_empty() {
+            request.query = self.lookup(&hash).ok_or_else(|| {
+                persisted_query_error("PersistedQueryNotFound", "PERSISTED_QUERY_NOT_FOUND")
+            })?;
+            return Ok(request);
+        }
+        if sha256_hex(&request.query) != hash {
+            return Err(persisted_query_error(
+                "provided sha does not match query",
+                "BAD_REQUEST",
+            ));
+        }
+        if !self.registered.contains_key(&hash) {
+            if self.allow_list && !authorized {
+                return Err(persisted_query_error(
+                    "PersistedQueryNotAllowed",
+                    "PERSISTED_QUERY_NOT_ALLOWED",
+                ));
+            }
+            if !self.allow_list {
+                self.remember(hash, request.query.clone());
+            }
+        }
+        Ok(request)
+    }
+}
+
+fn sha256_hex(query: &str) -> String {
+    format!("{:x}", Sha256::digest(query.as_bytes()))
+}
+
+fn persisted_query_error(message: &str, code: &str) -> ServerError {
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", code);
+    let mut error = ServerError::new(message, None);
+    error.extensions = Some(extensions);
+    error
+}
+
+impl ExtensionFactory for PersistedQueries {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(PersistedQueriesExtension {
+            queries: self.clone(),
+        })
+    }
+}
+
+struct PersistedQueriesExtension {
+    queries: PersistedQueries,
+}
+
+#[async_trait::async_trait]
+impl Extension for PersistedQueriesExtension {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        // Request data is only attached to the context after this hook runs.
+        let authorized = request
+            .data
+            .get(&TypeId::of::<ApiAuth>())
+            .and_then(|data| data.downcast_ref::<ApiAuth>())
+            .is_some_and(|auth| auth.authorize_revision_manager().is_ok());
+        let request = self.queries.resolve(request, authorized)?;
+        next.run(ctx, request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema, Value};
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            1
+        }
+    }
+
+    const QUERY: &str = "{ value }";
+
+    fn schema(queries: PersistedQueries) -> Schema<Query, EmptyMutation, EmptySubscription> {
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(queries)
+            .finish()
+    }
+
+    fn persisted(query: &str, hash: &str) -> Request {
+        let mut request = Request::new(query);
+        request.extensions.insert(
+            "persistedQuery".to_string(),
+            Value::from_json(serde_json::json!({ "version": 1, "sha256Hash": hash })).unwrap(),
+        );
+        request
+    }
+
+    fn error_code(response: &async_graphql::Response) -> Option<String> {
+        let extensions = response.errors.first()?.extensions.as_ref()?;
+        match extensions.get("code")? {
+            Value::String(code) => Some(code.clone()),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn automatic_persisted_queries_register_on_first_use() {
+        let schema = schema(PersistedQueries::default());
+        let hash = sha256_hex(QUERY);
+
+        let unknown = schema.execute(persisted("", &hash)).await;
+        assert_eq!(
+            error_code(&unknown).as_deref(),
+            Some("PERSISTED_QUERY_NOT_FOUND")
+        );
+
+        let mismatched = schema.execute(persisted("{ __typename }", &hash)).await;
+        assert_eq!(error_code(&mismatched).as_deref(), Some("BAD_REQUEST"));
+
+        let registered = schema.execute(persisted(QUERY, &hash)).await;
+        assert!(registered.errors.is_empty(), "{:?}", registered.errors);
+
+        let by_hash = schema.execute(persisted("", &hash)).await;
+        assert!(by_hash.errors.is_empty(), "{:?}", by_hash.errors);
+        assert_eq!(by_hash.data.into_json().unwrap()["value"], 1);
+    }
+
+    #[tokio::test]
+    async fn allow_list_only_runs_registered_operations_for_anonymous_clients() {
+        let hash = sha256_hex(QUERY);
+        let queries = PersistedQueries::with_registered(
+            HashMap::from([(hash.clone(), QUERY.to_string())]),
+            true,
+        )
+        .unwrap();
+        let schema = schema(queries);
+        let authorized = || {
+            ApiAuth::with_revision_api_key(
+                Some("allow-list-key".to_string()),
+                Some("allow-list-key".to_string()),
+            )
+        };
+
+        let registered = schema.execute(persisted("", &hash)).await;
+        assert!(registered.errors.is_empty(), "{:?}", registered.errors);
+
+        let ad_hoc = schema.execute(Request::new("{ __typename }")).await;
+        assert_eq!(
+            error_code(&ad_hoc).as_deref(),
+            Some("PERSISTED_QUERY_REQUIRED")
+        );
+
+        let other = "{ value __typename }";
+        let unregistered = schema.execute(persisted(other, &sha256_hex(other))).await;
+        assert_eq!(
+            error_code(&unregistered).as_deref(),
+            Some("PERSISTED_QUERY_NOT_ALLOWED")
+        );
+        let not_remembered = schema.execute(persisted("", &sha256_hex(other))).await;
+        assert_eq!(
+            error_code(&not_remembered).as_deref(),
+            Some("PERSISTED_QUERY_NOT_FOUND")
+        );
+
+        let revision_client = schema
+            .execute(Request::new("{ __typename }").data(authorized()))
+            .await;
+        assert!(
+            revision_client.errors.is_empty(),
+            "{:?}",
+            revision_client.errors
+        );
+    }
+
+    #[test]
+    fn manifest_hashes_must_match_their_queries() {
+        let result = PersistedQueries::with_registered(
+            HashMap::from([("0".repeat(64), QUERY.to_string())]),
+            true,
+        );
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,262 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    env,
+    future::Future,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use async_graphql::{
+    CacheControl, Response, ServerError, ServerResult, Variables,
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery},
+    parser::types::{ExecutableDocument, OperationType, Selection, SelectionSet},
+    registry::Registry,
+};
+
+const DEFAULT_TTL_SECS: u64 = 3600;
+
+struct CacheEntry {
+    value: Arc<dyn Any + Send + Sync>,
+    stored_at: Instant,
+}
+
+/// In-process cache for resolvers over data only the pipelines change
+/// (sefarim, perakim and their headers). Entries expire after
+/// `STATIC_CACHE_TTL_SECS` (default one hour) since perek dates follow the
+/// current 929 cycle, and the whole cache is dropped after any successful
+/// mutation (see [`StaticCacheInvalidation`]).
+#[derive(Clone)]
+pub struct StaticCache {
+    entries: Arc<Mutex<HashMap<String, CacheEntry>>>,
+    ttl: Duration,
+}
+
+impl Default for StaticCache {
+    fn default() -> Self {
+        Self::with_ttl(Duration::from_secs(DEFAULT_TTL_SECS))
+    }
+}
+
+impl StaticCache {
+    pub fn new() -> Self {
+        let ttl = env::var("STATIC_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_TTL_SECS);
+        Self::with_ttl(Duration::from_secs(ttl))
+    }
+
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            ttl,
+        }
+    }
+
+    fn get<T: Clone + 'static>(&self, key: &str) -> Option<T> {
+        let entries = self.entries.lock().unwrap();
+        let entry = entries.get(key)?;
+        if entry.stored_at.elapsed() >= self.ttl {
+            return None;
+        }
+        entry.value.downcast_ref::<T>().cloned()
+    }
+
+    /// The cached value for `key`, or the result of `load`, which is cached
+    /// when it succeeds. Errors are never cached.
+    pub async fn get_or_load<T, E, F, Fut>(&self, key: String, load: F) -> Result<T, E>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if let Some(value) = self.get::<T>(&key) {
+            return Ok(value);
+        }
+        let value = load().await?;
+        self.entries.lock().unwrap().insert(
+            key,
+            CacheEntry {
+                value: Arc::new(value.clone()),
+                stored_at: Instant::now(),
+            },
+        );
+        Ok(value)
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+/// Request data marking a GraphQL request that came over `GET`; mutations are
+/// rejected for it, so a link or an image tag can never write.
+pub struct ReadOnlyRequest;
+
+/// Schema extension that drops the [`StaticCache`] after every successful
+/// mutation, and marks mutation responses `no-cache, private`. No mutation
+/// writes the Tanah tables today; clearing on all of them keeps a future one
+/// from serving stale data without having to remember the cache.
+///
+/// Queries are `no-cache, private` too unless every root field carries a
+/// `cache_control` hint: async-graphql reads an unhinted field as "no
+/// opinion", so `{ sefarim { id } tanahpediaEntry(...) { ... } }` would
+/// otherwise be served `max-age=3600`.
+pub struct StaticCacheInvalidation {
+    cache: StaticCache,
+}
+
+impl StaticCacheInvalidation {
+    pub fn new(cache: StaticCache) -> Self {
+        Self { cache }
+    }
+}
+
+impl ExtensionFactory for StaticCacheInvalidation {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(StaticCacheInvalidationExtension {
+            cache: self.cache.clone(),
+            is_mutation: AtomicBool::new(false),
+            is_cacheable: AtomicBool::new(false),
+        })
+    }
+}
+
+struct StaticCacheInvalidationExtension {
+    cache: StaticCache,
+    is_mutation: AtomicBool,
+    is_cacheable: AtomicBool,
+}
+
+/// Whether every root field `selection_set` selects, through fragments too,
+/// has a `cache_control` hint on `type_name`.
+fn root_fields_are_hinted(
+    registry: &Registry,
+    document: &ExecutableDocument,
+    type_name: &str,
+    selection_set: &SelectionSet,
+) -> bool {
+    selection_set
+        .items
+        .iter()
+        .all(|selection| match &selection.node {
+            Selection::Field(field) => {
+                let name = field.node.name.node.as_str();
+                name == "__typename"
+                    || registry
+                        .concrete_type_by_name(type_name)
+                        .and_then(|ty| ty.field_by_name(name))
+                        .is_some_and(|field| field.cache_control.max_age > 0)
+            }
+            Selection::FragmentSpread(spread) => document
+                .fragments
+                .get(&spread.node.fragment_name.node)
+                .is_some_and(|fragment| {
+                    root_fields_are_hinted(
+                        registry,
+                        document,
+                        type_name,
+                        &fragment.node.selection_set.node,
+                    )
+                }),
+            Selection::InlineFragment(fragment) => root_fields_are_hinted(
+                registry,
+                document,
+                type_name,
+                &fragment.node.selection_set.node,
+            ),
+        })
+}
+
+#[async_trait::async_trait]
+impl Extension for StaticCacheInvalidationExtension {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        let is_mutation = document
+            .operations
+            .iter()
+            .any(|(_, operation)| operation.node.ty == OperationType::Mutation);
+        if is_mutation && ctx.data_opt::<ReadOnlyRequest>().is_some() {
+            return Err(ServerError::new("Mutations are not allowed over GET", None));
+        }
+        let registry = &ctx.schema_env.registry;
+        let is_cacheable = document.operations.iter().all(|(_, operation)| {
+            operation.node.ty == OperationType::Query
+                && root_fields_are_hinted(
+                    registry,
+                    &document,
+                    &registry.query_type,
+                    &operation.node.selection_set.node,
+                )
+        });
+        self.is_mutation.store(is_mutation, Ordering::SeqCst);
+        self.is_cacheable.store(is_cacheable, Ordering::SeqCst);
+        Ok(document)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let mut response = next.run(ctx, operation_name).await;
+        if self.is_mutation.load(Ordering::SeqCst) && response.is_ok() {
+            self.cache.clear();
+        }
+        if !self.is_cacheable.load(Ordering::SeqCst) {
+            response.cache_control = CacheControl {
+                public: false,
+                max_age: -1,
+            };
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn get_or_load_caches_successes_only() {
+        let cache = StaticCache::default();
+
+        let failed: Result<i32, &str> = cache
+            .get_or_load("k".to_string(), || async { Err("db") })
+            .await;
+        assert_eq!(failed, Err("db"));
+
+        let loaded: Result<i32, &str> =
+            cache.get_or_load("k".to_string(), || async { Ok(1) }).await;
+        let cached: Result<i32, &str> =
+            cache.get_or_load("k".to_string(), || async { Ok(2) }).await;
+        assert_eq!((loaded, cached), (Ok(1), Ok(1)));
+
+        cache.clear();
+        let reloaded: Result<i32, &str> =
+            cache.get_or_load("k".to_string(), || async { Ok(3) }).await;
+        assert_eq!(reloaded, Ok(3));
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_reloaded() {
+        let cache = StaticCache::with_ttl(Duration::ZERO);
+
+        let _: Result<i32, ()> = cache.get_or_load("k".to_string(), || async { Ok(1) }).await;
+        let reloaded: Result<i32, ()> =
+            cache.get_or_load("k".to_string(), || async { Ok(2) }).await;
+
+        assert_eq!(reloaded, Ok(2));
+    }
+}
@@ -0,0 +1,115 @@
+use std::env;
+use std::time::Duration;
+
+use anyhow::Result;
+use aws_sdk_s3::{
+    Client,
+    config::{BehaviorVersion, Builder, Credentials, Region},
+    presigning::PresigningConfig,
+    primitives::ByteStream,
+};
+
+const DEFAULT_PRESIGN_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// S3-compatible object storage for Tanahpedia assets. Talks to AWS in prod
+/// and to the local S3 emulator in dev/test, as selected by `S3_ENDPOINT`.
+#[derive(Debug, Clone)]
+pub struct Storage {
+    client: Client,
+    bucket: String,
+    presign_ttl: Duration,
+}
+
+impl Storage {
+    pub async fn new() -> Self {
+        let region = env::var("S3_REGION").unwrap_or_else(|_| "il-central-1".to_string());
+        let bucket = env::var("S3_BUCKET").unwrap_or_else(|_| "bible-on-site-assets".to_string());
+        let presign_ttl = env::var("S3_PRESIGN_TTL_SECONDS")
+            .ok()
+            .and_then(|seconds| seconds.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_PRESIGN_TTL);
+
+        // Explicit keys win (local emulator); otherwise fall back to the default
+        // chain so the ECS task role is picked up in prod.
+        let mut loader =
+            aws_config::defaults(BehaviorVersion::latest()).region(Region::new(region));
+        if let (Ok(access_key_id), Ok(secret_access_key)) = (
+            env::var("S3_ACCESS_KEY_ID"),
+            env::var("S3_SECRET_ACCESS_KEY"),
+        ) {
+            loader = loader.credentials_provider(Credentials::new(
+                access_key_id,
+                secret_access_key,
+                None,
+                None,
+                "bible-on-site-api",
+            ));
+        }
+        let mut config = Builder::from(&loader.load().await);
+        if let Ok(endpoint) = env::var("S3_ENDPOINT") {
+            config = config.endpoint_url(endpoint);
+        }
+        config =
+            config.force_path_style(env::var("S3_FORCE_PATH_STYLE").is_ok_and(|v| v == "true"));
+
+        Self {
+            client: Client::from_conf(config.build()),
+            bucket,
+            presign_ttl,
+        }
+    }
+
+    /// Storage pointed at the local S3 emulator with static credentials.
+    /// Presigning is offline, so tests can use it without the emulator running.
+    #[cfg(test)]
+    pub fn for_testing() -> Self {
+        let config = Builder::new()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .credentials_provider(Credentials::new("test", "test_1234", None, None, "test"))
+            .endpoint_url("http://localhost:4566")
+            .force_path_style(true)
+            .build();
+
+        Self {
+            client: Client::from_conf(config),
+            bucket: "bible-on-site-assets-test".to_string(),
+            presign_ttl: DEFAULT_PRESIGN_TTL,
+        }
+    }
+
+    /// A time-limited GET URL for `key`, valid for `S3_PRESIGN_TTL_SECONDS`.
+    pub async fn presigned_get_url(&self, key: &str) -> Result<String> {
+        let request = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .presigned(PresigningConfig::expires_in(self.presign_ttl)?)
+            .await?;
+        Ok(request.uri().to_string())
+    }
+
+    pub async fn put_object(&self, key: &str, body: Vec<u8>, content_type: &str) -> Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(body))
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_object(&self, key: &str) -> Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await?;
+        Ok(())
+    }
+}
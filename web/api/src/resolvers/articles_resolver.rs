@@ -32,6 +32,7 @@ impl ArticlesQuery {
     }
 
     /// Get all articles by a specific author
+    #[graphql(complexity = "5 * child_complexity")]
     async fn articles_by_author_id(
         &self,
         ctx: &Context<'_>,
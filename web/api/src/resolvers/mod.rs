@@ -3,5 +3,20 @@ pub mod authors_resolver;
 pub mod perakim_resolver;
 pub mod sefarim_resolver;
 pub mod starter_resolver;
+pub mod tanahpedia_animals_resolver;
+pub mod tanahpedia_auto_linker_resolver;
+pub mod tanahpedia_category_homepages_resolver;
+pub mod tanahpedia_chazal_resolver;
+pub mod tanahpedia_creation_days_resolver;
+pub mod tanahpedia_entries_resolver;
 pub mod tanahpedia_family_resolver;
+pub mod tanahpedia_import_resolver;
+pub mod tanahpedia_models3d_resolver;
+pub mod tanahpedia_namings_resolver;
+pub mod tanahpedia_nations_resolver;
+pub mod tanahpedia_opinions_resolver;
+pub mod tanahpedia_places_resolver;
+pub mod tanahpedia_quality_resolver;
 pub mod tanahpedia_revisions_resolver;
+pub mod tanahpedia_sayings_resolver;
+pub mod tanahpedia_wars_resolver;
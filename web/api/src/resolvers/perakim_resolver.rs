@@ -1,7 +1,7 @@
 use async_graphql::{Context, ErrorExtensions, Object, Result};
 
 use crate::dtos::perek::Perek;
-use crate::providers::Database;
+use crate::providers::{Database, StaticCache};
 use crate::services::perakim_service;
 
 #[derive(Default)]
@@ -10,16 +10,21 @@ pub struct PerakimQuery;
 #[Object]
 impl PerakimQuery {
     /// Get a perek by its perek ID (1-929)
+    #[graphql(cache_control(max_age = 3600))]
     async fn perek_by_perek_id(&self, ctx: &Context<'_>, perek_id: i32) -> Result<Perek> {
-        Ok(
-            perakim_service::find_one_by_perek_id(ctx.data::<Database>()?, perek_id)
-                .await
-                .map_err(|e| e.extend())?
-                .into(),
-        )
+        let db = ctx.data::<Database>()?;
+        ctx.data::<StaticCache>()?
+            .get_or_load(format!("perekByPerekId:{perek_id}"), || async {
+                Ok(perakim_service::find_one_by_perek_id(db, perek_id)
+                    .await
+                    .map_err(|e| e.extend())?
+                    .into())
+            })
+            .await
     }
 
     /// Get all perakim (chapters) in the 929 project
+    #[graphql(cache_control(max_age = 3600), complexity = "100 + child_complexity")]
     async fn perakim(&self, ctx: &Context<'_>) -> Result<Vec<Perek>> {
         Ok(perakim_service::find_all(ctx.data::<Database>()?)
             .await
@@ -30,14 +35,18 @@ impl PerakimQuery {
     }
 
     /// Get all perakim for a specific sefer
+    #[graphql(cache_control(max_age = 3600), complexity = "5 * child_complexity")]
     async fn perakim_by_sefer_id(&self, ctx: &Context<'_>, sefer_id: i32) -> Result<Vec<Perek>> {
-        Ok(
-            perakim_service::find_by_sefer_id(ctx.data::<Database>()?, sefer_id)
-                .await
-                .map_err(|e| e.extend())?
-                .into_iter()
-                .map(Into::into)
-                .collect(),
-        )
+        let db = ctx.data::<Database>()?;
+        ctx.data::<StaticCache>()?
+            .get_or_load(format!("perakimBySeferId:{sefer_id}"), || async {
+                Ok(perakim_service::find_by_sefer_id(db, sefer_id)
+                    .await
+                    .map_err(|e| e.extend())?
+                    .into_iter()
+                    .map(Into::into)
+                    .collect())
+            })
+            .await
     }
 }
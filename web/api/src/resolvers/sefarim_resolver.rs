@@ -1,7 +1,7 @@
 use async_graphql::{Context, ErrorExtensions, Object, Result};
 
 use crate::dtos::sefer::Sefer;
-use crate::providers::Database;
+use crate::providers::{Database, StaticCache};
 use crate::services::sefarim_service;
 
 #[derive(Default)]
@@ -10,6 +10,7 @@ pub struct SefarimQuery;
 #[Object]
 impl SefarimQuery {
     /// Get a sefer by its ID
+    #[graphql(cache_control(max_age = 3600))]
     async fn sefer_by_id(&self, ctx: &Context<'_>, id: i32) -> Result<Sefer> {
         Ok(sefarim_service::find_one_by_id(ctx.data::<Database>()?, id)
             .await
@@ -18,12 +19,18 @@ impl SefarimQuery {
     }
 
     /// Get all sefarim (books of the Tanah)
+    #[graphql(cache_control(max_age = 3600), complexity = "5 * child_complexity")]
     async fn sefarim(&self, ctx: &Context<'_>) -> Result<Vec<Sefer>> {
-        Ok(sefarim_service::find_all(ctx.data::<Database>()?)
+        let db = ctx.data::<Database>()?;
+        ctx.data::<StaticCache>()?
+            .get_or_load("sefarim".to_string(), || async {
+                Ok(sefarim_service::find_all(db)
+                    .await
+                    .map_err(|e| e.extend())?
+                    .into_iter()
+                    .map(Into::into)
+                    .collect())
+            })
             .await
-            .map_err(|e| e.extend())?
-            .into_iter()
-            .map(Into::into)
-            .collect())
     }
 }
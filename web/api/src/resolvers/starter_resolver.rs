@@ -12,6 +12,7 @@ pub struct StarterQuery;
 #[Object]
 impl StarterQuery {
     /// Get starter data including all authors, all articles, and article counts per perek
+    #[graphql(complexity = "100 + child_complexity")]
     async fn starter(&self, ctx: &Context<'_>) -> Result<Starter> {
         let db = ctx.data::<Database>()?;
 
@@ -0,0 +1,98 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::common::auth::ApiAuth;
+use crate::dtos::tanahpedia_animal::{
+    PutTanahpediaAnimalKindInput, PutTanahpediaAnimalPurityInput, TanahpediaAnimal,
+    TanahpediaAnimalKind, TanahpediaAnimalKindWriteResult, TanahpediaAnimalPurity,
+    TanahpediaAnimalPurityWriteResult,
+};
+use crate::providers::Database;
+use crate::services::tanahpedia_animals_service;
+
+#[derive(Default)]
+pub struct TanahpediaAnimalsQuery;
+
+#[Object]
+impl TanahpediaAnimalsQuery {
+    /// Animals ordered by name with every kind and purity opinion and its
+    /// sources. `kind`/`purity` keep animals that any opinion classifies so.
+    #[graphql(complexity = "5 * child_complexity")]
+    async fn tanahpedia_animals(
+        &self,
+        ctx: &Context<'_>,
+        kind: Option<TanahpediaAnimalKind>,
+        purity: Option<TanahpediaAnimalPurity>,
+    ) -> Result<Vec<TanahpediaAnimal>> {
+        tanahpedia_animals_service::find_animals(ctx.data::<Database>()?, kind, purity)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+#[derive(Default)]
+pub struct TanahpediaAnimalsMutation;
+
+#[Object]
+impl TanahpediaAnimalsMutation {
+    /// Add or replace an opinion on an animal's kind and its sources
+    /// (authorized clients only).
+    async fn put_tanahpedia_animal_kind(
+        &self,
+        ctx: &Context<'_>,
+        input: PutTanahpediaAnimalKindInput,
+    ) -> Result<TanahpediaAnimalKindWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_animals_service::put_animal_kind(ctx.data::<Database>()?, input)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Remove an animal kind opinion and its sources (authorized clients only).
+    async fn delete_tanahpedia_animal_kind(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<TanahpediaAnimalKindWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_animals_service::delete_animal_kind(ctx.data::<Database>()?, id)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Add or replace an opinion on an animal's kashrut and its sources
+    /// (authorized clients only).
+    async fn put_tanahpedia_animal_purity(
+        &self,
+        ctx: &Context<'_>,
+        input: PutTanahpediaAnimalPurityInput,
+    ) -> Result<TanahpediaAnimalPurityWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_animals_service::put_animal_purity(ctx.data::<Database>()?, input)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Remove an animal purity opinion and its sources (authorized clients only).
+    async fn delete_tanahpedia_animal_purity(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<TanahpediaAnimalPurityWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_animals_service::delete_animal_purity(ctx.data::<Database>()?, id)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
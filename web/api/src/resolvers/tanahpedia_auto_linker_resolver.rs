@@ -0,0 +1,37 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::common::auth::ApiAuth;
+use crate::dtos::tanahpedia_auto_link::TanahpediaAutoLinkReport;
+use crate::providers::Database;
+use crate::services::tanahpedia_auto_linker_service;
+
+#[derive(Default)]
+pub struct TanahpediaAutoLinkerMutation;
+
+#[Object]
+impl TanahpediaAutoLinkerMutation {
+    /// Propose links for the names mentioned in one entry, one article, or
+    /// everything when both are omitted (authorized clients only). Entry
+    /// proposals become pending `auto-linker` revisions. Article scanning is
+    /// preview-only: nothing is stored, and a proposal not applied by the
+    /// caller is gone.
+    #[graphql(complexity = "100 + child_complexity")]
+    async fn propose_tanahpedia_auto_links(
+        &self,
+        ctx: &Context<'_>,
+        entry_id: Option<String>,
+        article_id: Option<i32>,
+    ) -> Result<TanahpediaAutoLinkReport> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_auto_linker_service::propose_auto_links(
+            ctx.data::<Database>()?,
+            entry_id,
+            article_id,
+        )
+        .await
+        .map_err(|e| e.extend())
+    }
+}
@@ -0,0 +1,50 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::common::auth::ApiAuth;
+use crate::dtos::tanahpedia_category_homepage::{
+    PutTanahpediaCategoryHomepageInput, TanahpediaCategoryHomepage,
+};
+use crate::dtos::tanahpedia_family::TanahpediaEntityType;
+use crate::providers::Database;
+use crate::services::tanahpedia_category_homepages_service;
+
+#[derive(Default)]
+pub struct TanahpediaCategoryHomepagesQuery;
+
+#[Object]
+impl TanahpediaCategoryHomepagesQuery {
+    /// The homepage of an entity type, or null when none is configured.
+    async fn tanahpedia_category_homepage(
+        &self,
+        ctx: &Context<'_>,
+        entity_type: TanahpediaEntityType,
+    ) -> Result<Option<TanahpediaCategoryHomepage>> {
+        tanahpedia_category_homepages_service::get_category_homepage(
+            ctx.data::<Database>()?,
+            entity_type,
+        )
+        .await
+        .map_err(|e| e.extend())
+    }
+}
+
+#[derive(Default)]
+pub struct TanahpediaCategoryHomepagesMutation;
+
+#[Object]
+impl TanahpediaCategoryHomepagesMutation {
+    /// Create or replace an entity type's homepage (authorized clients only).
+    async fn put_tanahpedia_category_homepage(
+        &self,
+        ctx: &Context<'_>,
+        input: PutTanahpediaCategoryHomepageInput,
+    ) -> Result<TanahpediaCategoryHomepage> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_category_homepages_service::put_category_homepage(ctx.data::<Database>()?, input)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
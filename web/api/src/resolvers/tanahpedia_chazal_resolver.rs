@@ -0,0 +1,44 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::common::auth::ApiAuth;
+use crate::dtos::tanahpedia_source::{
+    TanahpediaChazalParseResult, TanahpediaNonTanahSourceNormalizeResult,
+};
+use crate::providers::Database;
+use crate::services::tanahpedia_chazal_service;
+
+#[derive(Default)]
+pub struct TanahpediaChazalQuery;
+
+#[Object]
+impl TanahpediaChazalQuery {
+    /// Parses a non-Tanah citation the way it would be stored, without
+    /// storing it. `error` explains a citation that isn't recognized.
+    async fn parse_tanahpedia_chazal_reference(
+        &self,
+        source_text: String,
+    ) -> TanahpediaChazalParseResult {
+        tanahpedia_chazal_service::parse_non_tanah_source(source_text)
+    }
+}
+
+#[derive(Default)]
+pub struct TanahpediaChazalMutation;
+
+#[Object]
+impl TanahpediaChazalMutation {
+    /// Re-parse every stored non-Tanah source and update its structured
+    /// reference (authorized clients only).
+    async fn normalize_tanahpedia_non_tanah_sources(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<TanahpediaNonTanahSourceNormalizeResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_chazal_service::normalize_non_tanah_sources(ctx.data::<Database>()?)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
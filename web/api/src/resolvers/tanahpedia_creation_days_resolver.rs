@@ -0,0 +1,64 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::common::auth::ApiAuth;
+use crate::dtos::tanahpedia_creation_day::{
+    PutTanahpediaCreationDayInput, TanahpediaCreationDay, TanahpediaCreationDayWriteResult,
+};
+use crate::providers::Database;
+use crate::services::tanahpedia_creation_days_service;
+
+#[derive(Default)]
+pub struct TanahpediaCreationDaysQuery;
+
+#[Object]
+impl TanahpediaCreationDaysQuery {
+    /// The six days of creation, each with the plants and astronomical
+    /// objects any opinion places on it and their sources.
+    #[graphql(complexity = "5 * child_complexity")]
+    async fn tanahpedia_creation_days(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<TanahpediaCreationDay>> {
+        tanahpedia_creation_days_service::find_creation_days(ctx.data::<Database>()?)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+#[derive(Default)]
+pub struct TanahpediaCreationDaysMutation;
+
+#[Object]
+impl TanahpediaCreationDaysMutation {
+    /// Add or replace an opinion on an item's day of creation and its sources
+    /// (authorized clients only).
+    async fn put_tanahpedia_creation_day(
+        &self,
+        ctx: &Context<'_>,
+        input: PutTanahpediaCreationDayInput,
+    ) -> Result<TanahpediaCreationDayWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_creation_days_service::put_creation_day(ctx.data::<Database>()?, input)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Remove a creation-day opinion and its sources (authorized clients
+    /// only).
+    async fn delete_tanahpedia_creation_day(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<TanahpediaCreationDayWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_creation_days_service::delete_creation_day(ctx.data::<Database>()?, id)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
@@ -0,0 +1,61 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::common::auth::ApiAuth;
+use crate::dtos::tanahpedia_entry::{
+    TanahpediaBrokenEntryLink, TanahpediaEntry, TanahpediaEntryLinksRebuildResult,
+};
+use crate::providers::Database;
+use crate::services::tanahpedia_entries_service;
+
+#[derive(Default)]
+pub struct TanahpediaEntriesQuery;
+
+#[Object]
+impl TanahpediaEntriesQuery {
+    /// The entry at `/pedia/<uniqueName>`, or null when there is none.
+    async fn tanahpedia_entry(
+        &self,
+        ctx: &Context<'_>,
+        unique_name: String,
+    ) -> Result<Option<TanahpediaEntry>> {
+        tanahpedia_entries_service::get_entry(ctx.data::<Database>()?, unique_name)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Links in entry content to unique names no entry has, by target.
+    #[graphql(complexity = "100 + child_complexity")]
+    async fn tanahpedia_broken_entry_links(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<TanahpediaBrokenEntryLink>> {
+        tanahpedia_entries_service::find_broken_links(ctx.data::<Database>()?)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+#[derive(Default)]
+pub struct TanahpediaEntriesMutation;
+
+#[Object]
+impl TanahpediaEntriesMutation {
+    /// Re-extract the internal links of one entry, or of every entry when
+    /// `entryId` is omitted (authorized clients only). Links are otherwise kept
+    /// up to date by `applyEntryRevision`; this is for content written around
+    /// the API.
+    #[graphql(complexity = "100 + child_complexity")]
+    async fn rebuild_tanahpedia_entry_links(
+        &self,
+        ctx: &Context<'_>,
+        entry_id: Option<String>,
+    ) -> Result<TanahpediaEntryLinksRebuildResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_entries_service::rebuild_links(ctx.data::<Database>()?, entry_id)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
@@ -5,9 +5,9 @@ use crate::dtos::tanahpedia_family::{
     DeleteTanahpediaOrphanEntityInput, DeleteTanahpediaPersonNodeInput,
     PutTanahpediaEntryEntityLinkInput, PutTanahpediaParentChildInput, PutTanahpediaPersonNodeInput,
     PutTanahpediaPersonUnionInput, TanahpediaEntitySummary, TanahpediaEntityTanahSource,
-    TanahpediaEntryEntityLinkWriteResult, TanahpediaFamilyLinkWriteResult, TanahpediaPersonDetail,
-    TanahpediaPersonNodeWriteResult, TanahpediaPersonParentChildSummary, TanahpediaPersonSummary,
-    TanahpediaPersonUnionSummary,
+    TanahpediaEntityType, TanahpediaEntryEntityLinkWriteResult, TanahpediaFamilyLinkWriteResult,
+    TanahpediaPersonDetail, TanahpediaPersonNodeWriteResult, TanahpediaPersonParentChildSummary,
+    TanahpediaPersonSummary, TanahpediaPersonUnionSummary,
 };
 use crate::providers::Database;
 use crate::services::tanahpedia_family_service;
@@ -145,6 +145,7 @@ impl TanahpediaFamilyQuery {
     /// Requires an `Authorization: Bearer <TANAHPEDIA_REVISION_API_KEY>` header.
     /// Returns every match, since Torah names are frequently shared by more than
     /// one entity — callers must disambiguate using the returned `entityId`.
+    #[graphql(complexity = "5 * child_complexity")]
     async fn tanahpedia_find_persons(
         &self,
         ctx: &Context<'_>,
@@ -159,17 +160,16 @@ impl TanahpediaFamilyQuery {
             .map_err(|e| e.extend())
     }
 
-    /// Finds Tanahpedia entities of any type by exact display name
-    /// (`PERSON`, `PLACE`, `EVENT`, `WAR`, `ANIMAL`, `OBJECT`, `TEMPLE_TOOL`,
-    /// `PLANT`, `ASTRONOMICAL_OBJECT`, `SAYING`, `SEFER`, `PROPHECY`, `NATION`).
-    /// Pass `entityType` to narrow the search to a single type.
+    /// Finds Tanahpedia entities of any type by exact display name. Pass
+    /// `entityType` to narrow the search to a single type.
     ///
     /// Requires an `Authorization: Bearer <TANAHPEDIA_REVISION_API_KEY>` header.
+    #[graphql(complexity = "5 * child_complexity")]
     async fn tanahpedia_find_entities(
         &self,
         ctx: &Context<'_>,
         name: String,
-        entity_type: Option<String>,
+        entity_type: Option<TanahpediaEntityType>,
     ) -> Result<Vec<TanahpediaEntitySummary>> {
         ctx.data::<ApiAuth>()?
             .authorize_revision_manager()
@@ -180,11 +180,23 @@ impl TanahpediaFamilyQuery {
             .map_err(|e| e.extend())
     }
 
+    /// A single entity of any type, e.g. to reach its `models3d`.
+    async fn tanahpedia_entity(
+        &self,
+        ctx: &Context<'_>,
+        entity_id: String,
+    ) -> Result<Option<TanahpediaEntitySummary>> {
+        tanahpedia_family_service::get_entity(ctx.data::<Database>()?, entity_id)
+            .await
+            .map_err(|e| e.extend())
+    }
+
     /// Lists the direct Tanah citations (perek + pasuk) attached to an entity —
     /// the "source for the entity itself", as opposed to a specific
     /// relationship's `sourceCitation` free-text field.
     ///
     /// Requires an `Authorization: Bearer <TANAHPEDIA_REVISION_API_KEY>` header.
+    #[graphql(complexity = "5 * child_complexity")]
     async fn tanahpedia_entity_tanah_sources(
         &self,
         ctx: &Context<'_>,
@@ -204,6 +216,7 @@ impl TanahpediaFamilyQuery {
     /// `sourceCitation` needed to review or correct that link.
     ///
     /// Requires an `Authorization: Bearer <TANAHPEDIA_REVISION_API_KEY>` header.
+    #[graphql(complexity = "5 * child_complexity")]
     async fn tanahpedia_person_unions(
         &self,
         ctx: &Context<'_>,
@@ -223,6 +236,7 @@ impl TanahpediaFamilyQuery {
     /// and the `sourceCitation` needed to review or correct that link.
     ///
     /// Requires an `Authorization: Bearer <TANAHPEDIA_REVISION_API_KEY>` header.
+    #[graphql(complexity = "5 * child_complexity")]
     async fn tanahpedia_person_parent_child(
         &self,
         ctx: &Context<'_>,
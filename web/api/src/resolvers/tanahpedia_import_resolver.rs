@@ -0,0 +1,31 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::common::auth::ApiAuth;
+use crate::dtos::tanahpedia_import::{TanahpediaImportInput, TanahpediaImportResult};
+use crate::providers::Database;
+use crate::services::tanahpedia_import_service;
+
+#[derive(Default)]
+pub struct TanahpediaImportMutation;
+
+#[Object]
+impl TanahpediaImportMutation {
+    /// Import entities, names, places, sources and family edges described by
+    /// key (authorized clients only). Re-importing the same document changes
+    /// nothing; `dryRun` reports what would change without writing.
+    #[graphql(complexity = "100 + child_complexity")]
+    async fn import_tanahpedia_content(
+        &self,
+        ctx: &Context<'_>,
+        input: TanahpediaImportInput,
+        #[graphql(default)] dry_run: bool,
+    ) -> Result<TanahpediaImportResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_import_service::import_content(ctx.data::<Database>()?, input, dry_run)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
@@ -0,0 +1,55 @@
+use std::io::Read;
+
+use async_graphql::{Context, ErrorExtensions, Object, Result, Upload};
+
+use crate::common::auth::ApiAuth;
+use crate::common::error_handling::ServiceError;
+use crate::dtos::tanahpedia_model3d::{TanahpediaModel3d, UploadTanahpediaModel3dInput};
+use crate::providers::{Database, Storage};
+use crate::services::tanahpedia_models3d_service::{self, MAX_MODEL_BYTES};
+
+#[derive(Default)]
+pub struct TanahpediaModels3dMutation;
+
+#[Object]
+impl TanahpediaModels3dMutation {
+    /// Upload a glTF, GLB or USDZ model for an entity (authorized clients
+    /// only). Send as a GraphQL multipart request with the asset in `file`.
+    #[graphql(name = "uploadTanahpediaModel3d")]
+    async fn upload_tanahpedia_model3d(
+        &self,
+        ctx: &Context<'_>,
+        input: UploadTanahpediaModel3dInput,
+        file: Upload,
+    ) -> Result<TanahpediaModel3d> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        // Read one byte past the cap so oversized files fail validation
+        // instead of being silently truncated. The upload is a temp file, so
+        // it is read on the blocking pool rather than on the async runtime.
+        let upload = file.value(ctx)?;
+        let bytes = tokio::task::spawn_blocking(move || {
+            let mut bytes = Vec::new();
+            upload
+                .into_read()
+                .take(MAX_MODEL_BYTES as u64 + 1)
+                .read_to_end(&mut bytes)
+                .map(|_| bytes)
+        })
+        .await
+        .ok()
+        .and_then(Result::ok)
+        .ok_or_else(|| ServiceError::bad_request("file could not be read").extend())?;
+
+        tanahpedia_models3d_service::upload_model(
+            ctx.data::<Database>()?,
+            ctx.data::<Storage>()?,
+            input,
+            bytes,
+        )
+        .await
+        .map_err(|e| e.extend())
+    }
+}
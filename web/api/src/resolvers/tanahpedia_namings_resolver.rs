@@ -0,0 +1,63 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::common::auth::ApiAuth;
+use crate::dtos::tanahpedia_naming::{
+    PutTanahpediaNameGiverInput, TanahpediaNameGiverWriteResult, TanahpediaNaming,
+};
+use crate::providers::Database;
+use crate::services::tanahpedia_namings_service;
+
+#[derive(Default)]
+pub struct TanahpediaNamingsQuery;
+
+#[Object]
+impl TanahpediaNamingsQuery {
+    /// Names given by `giverId` (a `personId` or the `godId`), each with the
+    /// person who received it.
+    #[graphql(complexity = "5 * child_complexity")]
+    async fn tanahpedia_namings(
+        &self,
+        ctx: &Context<'_>,
+        giver_id: String,
+    ) -> Result<Vec<TanahpediaNaming>> {
+        tanahpedia_namings_service::find_namings(ctx.data::<Database>()?, giver_id)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+#[derive(Default)]
+pub struct TanahpediaNamingsMutation;
+
+#[Object]
+impl TanahpediaNamingsMutation {
+    /// Add or replace who gave a person's name (authorized clients only).
+    async fn put_tanahpedia_name_giver(
+        &self,
+        ctx: &Context<'_>,
+        input: PutTanahpediaNameGiverInput,
+    ) -> Result<TanahpediaNameGiverWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_namings_service::put_name_giver(ctx.data::<Database>()?, input)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Remove a name giver link by id (authorized clients only).
+    async fn delete_tanahpedia_name_giver(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<TanahpediaNameGiverWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_namings_service::delete_name_giver(ctx.data::<Database>()?, id)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
@@ -0,0 +1,153 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::common::auth::ApiAuth;
+use crate::dtos::tanahpedia_nation::{
+    PutTanahpediaNationSourceInput, PutTanahpediaNationTerritoryInput,
+    PutTanahpediaPersonNationInput, TanahpediaNation, TanahpediaNationLinkWriteResult,
+    TanahpediaPersonNationLink, TanahpediaTerritoryFeatureCollection,
+};
+use crate::providers::Database;
+use crate::services::tanahpedia_nations_service;
+
+#[derive(Default)]
+pub struct TanahpediaNationsQuery;
+
+#[Object]
+impl TanahpediaNationsQuery {
+    /// A nation with its ancestry, descendants, territories and linked
+    /// persons. When `year` (Hebrew) is given, only territories held that
+    /// year are returned.
+    #[graphql(complexity = "100 + child_complexity")]
+    async fn tanahpedia_nation(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        year: Option<i32>,
+    ) -> Result<TanahpediaNation> {
+        tanahpedia_nations_service::get_nation(ctx.data::<Database>()?, id, year)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// The nations a person is linked to, e.g. as founder.
+    #[graphql(complexity = "5 * child_complexity")]
+    async fn tanahpedia_person_nations(
+        &self,
+        ctx: &Context<'_>,
+        person_id: String,
+    ) -> Result<Vec<TanahpediaPersonNationLink>> {
+        tanahpedia_nations_service::find_person_nations(ctx.data::<Database>()?, person_id)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Nation territories as a GeoJSON feature collection for map overlays,
+    /// optionally limited to a Hebrew `year` and to `nationIds`.
+    #[graphql(complexity = "100 + child_complexity")]
+    async fn tanahpedia_nation_territories_geo_json(
+        &self,
+        ctx: &Context<'_>,
+        year: Option<i32>,
+        nation_ids: Option<Vec<String>>,
+    ) -> Result<TanahpediaTerritoryFeatureCollection> {
+        tanahpedia_nations_service::territories_geojson(ctx.data::<Database>()?, year, nation_ids)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+#[derive(Default)]
+pub struct TanahpediaNationsMutation;
+
+#[Object]
+impl TanahpediaNationsMutation {
+    /// Add or update a nation ancestry link (authorized clients only).
+    async fn put_tanahpedia_nation_source(
+        &self,
+        ctx: &Context<'_>,
+        input: PutTanahpediaNationSourceInput,
+    ) -> Result<TanahpediaNationLinkWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_nations_service::put_nation_source(ctx.data::<Database>()?, input)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Remove a nation ancestry link by id (authorized clients only).
+    async fn delete_tanahpedia_nation_source(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<TanahpediaNationLinkWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_nations_service::delete_nation_source(ctx.data::<Database>()?, id)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Add or update a nation territory (authorized clients only).
+    async fn put_tanahpedia_nation_territory(
+        &self,
+        ctx: &Context<'_>,
+        input: PutTanahpediaNationTerritoryInput,
+    ) -> Result<TanahpediaNationLinkWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_nations_service::put_nation_territory(ctx.data::<Database>()?, input)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Remove a nation territory by id (authorized clients only).
+    async fn delete_tanahpedia_nation_territory(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<TanahpediaNationLinkWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_nations_service::delete_nation_territory(ctx.data::<Database>()?, id)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Add or update a person-nation link (authorized clients only).
+    async fn put_tanahpedia_person_nation(
+        &self,
+        ctx: &Context<'_>,
+        input: PutTanahpediaPersonNationInput,
+    ) -> Result<TanahpediaNationLinkWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_nations_service::put_person_nation(ctx.data::<Database>()?, input)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Remove a person-nation link by id (authorized clients only).
+    async fn delete_tanahpedia_person_nation(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<TanahpediaNationLinkWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_nations_service::delete_person_nation(ctx.data::<Database>()?, id)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
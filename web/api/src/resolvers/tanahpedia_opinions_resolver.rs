@@ -0,0 +1,65 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::common::auth::ApiAuth;
+use crate::dtos::tanahpedia_opinion::{
+    PutTanahpediaOpinionInput, TanahpediaOpinionMetadata, TanahpediaOpinionWriteResult,
+};
+use crate::providers::Database;
+use crate::services::tanahpedia_opinions_service;
+
+#[derive(Default)]
+pub struct TanahpediaOpinionsQuery;
+
+#[Object]
+impl TanahpediaOpinionsQuery {
+    /// Label, preferred flag and sources of each given `altGroupId` that has
+    /// any, in request order.
+    #[graphql(complexity = "5 * child_complexity")]
+    async fn tanahpedia_opinions(
+        &self,
+        ctx: &Context<'_>,
+        alt_group_ids: Vec<String>,
+    ) -> Result<Vec<TanahpediaOpinionMetadata>> {
+        tanahpedia_opinions_service::get_opinions(ctx.data::<Database>()?, alt_group_ids)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+#[derive(Default)]
+pub struct TanahpediaOpinionsMutation;
+
+#[Object]
+impl TanahpediaOpinionsMutation {
+    /// Label an opinion, mark it as the preferred one of its dispute and
+    /// replace its sources (authorized clients only).
+    async fn put_tanahpedia_opinion(
+        &self,
+        ctx: &Context<'_>,
+        input: PutTanahpediaOpinionInput,
+    ) -> Result<TanahpediaOpinionWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_opinions_service::put_opinion(ctx.data::<Database>()?, input)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Remove an opinion's metadata and sources; its fact rows stay
+    /// (authorized clients only).
+    async fn delete_tanahpedia_opinion(
+        &self,
+        ctx: &Context<'_>,
+        alt_group_id: String,
+    ) -> Result<TanahpediaOpinionWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_opinions_service::delete_opinion(ctx.data::<Database>()?, alt_group_id)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
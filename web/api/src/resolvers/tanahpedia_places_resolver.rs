@@ -0,0 +1,40 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::dtos::tanahpedia_place::{TanahpediaPlaceFeatureCollection, TanahpediaPlaceNear};
+use crate::providers::Database;
+use crate::services::tanahpedia_places_service;
+
+#[derive(Default)]
+pub struct TanahpediaPlacesQuery;
+
+#[Object]
+impl TanahpediaPlacesQuery {
+    /// GeoJSON `FeatureCollection` of located place identifications for the
+    /// map layer. `bbox` is `[minLongitude, minLatitude, maxLongitude,
+    /// maxLatitude]`; `entityIds` limits the result to those places' entities.
+    #[graphql(complexity = "100 + child_complexity")]
+    async fn tanahpedia_places_geo_json(
+        &self,
+        ctx: &Context<'_>,
+        bbox: Option<Vec<f64>>,
+        entity_ids: Option<Vec<String>>,
+    ) -> Result<TanahpediaPlaceFeatureCollection> {
+        tanahpedia_places_service::places_geojson(ctx.data::<Database>()?, bbox, entity_ids)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Place identifications within `radiusKm` of (`lat`, `lon`), nearest first.
+    #[graphql(complexity = "5 * child_complexity")]
+    async fn tanahpedia_places_near(
+        &self,
+        ctx: &Context<'_>,
+        lat: f64,
+        lon: f64,
+        radius_km: f64,
+    ) -> Result<Vec<TanahpediaPlaceNear>> {
+        tanahpedia_places_service::places_near(ctx.data::<Database>()?, lat, lon, radius_km)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
@@ -0,0 +1,22 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::dtos::tanahpedia_quality::TanahpediaQualityReport;
+use crate::providers::Database;
+use crate::services::tanahpedia_quality_service;
+
+#[derive(Default)]
+pub struct TanahpediaQualityQuery;
+
+#[Object]
+impl TanahpediaQualityQuery {
+    /// What is missing or unused in Tanahpedia, with the ids of the rows to fix.
+    #[graphql(complexity = "100 + child_complexity")]
+    async fn tanahpedia_quality_report(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<TanahpediaQualityReport> {
+        tanahpedia_quality_service::get_quality_report(ctx.data::<Database>()?)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
@@ -1,7 +1,9 @@
 use async_graphql::{Context, ErrorExtensions, Object, Result};
 
 use crate::common::auth::ApiAuth;
-use crate::dtos::tanahpedia_entry_revision::{EntryRevision, SubmitEntryRevisionInput};
+use crate::dtos::tanahpedia_entry_revision::{
+    EntryRevision, SubmitEntryRevisionInput, TanahpediaRevisionStatus,
+};
 use crate::providers::Database;
 use crate::services::tanahpedia_revisions_service;
 
@@ -11,12 +13,12 @@ pub struct TanahpediaRevisionsQuery;
 #[Object]
 impl TanahpediaRevisionsQuery {
     /// List Tanahpedia entry revisions (newest first) for human triage,
-    /// optionally filtered by `status` (PENDING / APPROVED / REJECTED) and/or the
-    /// targeted `entryId`.
+    /// optionally filtered by `status` and/or the targeted `entryId`.
+    #[graphql(complexity = "5 * child_complexity")]
     async fn tanahpedia_entry_revisions(
         &self,
         ctx: &Context<'_>,
-        status: Option<String>,
+        status: Option<TanahpediaRevisionStatus>,
         entry_id: Option<String>,
     ) -> Result<Vec<EntryRevision>> {
         Ok(
@@ -0,0 +1,130 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::common::auth::ApiAuth;
+use crate::dtos::tanahpedia_saying::{
+    PutTanahpediaSayingPartyInput, TanahpediaConversationPartner, TanahpediaSaying,
+    TanahpediaSayingPartyWriteResult,
+};
+use crate::providers::Database;
+use crate::services::tanahpedia_sayings_service;
+
+#[derive(Default)]
+pub struct TanahpediaSayingsQuery;
+
+#[Object]
+impl TanahpediaSayingsQuery {
+    /// Sayings spoken by `speakerId` (a `personId`, `nationId` or `godId`),
+    /// each with its full speaker/audience/location lists.
+    #[graphql(complexity = "5 * child_complexity")]
+    async fn tanahpedia_sayings_by_speaker(
+        &self,
+        ctx: &Context<'_>,
+        speaker_id: String,
+    ) -> Result<Vec<TanahpediaSaying>> {
+        tanahpedia_sayings_service::find_sayings_by_speaker(ctx.data::<Database>()?, speaker_id)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Sayings addressed to `audienceId` (a `personId` or `nationId`).
+    #[graphql(complexity = "5 * child_complexity")]
+    async fn tanahpedia_sayings_by_audience(
+        &self,
+        ctx: &Context<'_>,
+        audience_id: String,
+    ) -> Result<Vec<TanahpediaSaying>> {
+        tanahpedia_sayings_service::find_sayings_by_audience(ctx.data::<Database>()?, audience_id)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Sayings located at `placeId`.
+    #[graphql(complexity = "5 * child_complexity")]
+    async fn tanahpedia_sayings_at_place(
+        &self,
+        ctx: &Context<'_>,
+        place_id: String,
+    ) -> Result<Vec<TanahpediaSaying>> {
+        tanahpedia_sayings_service::find_sayings_at_place(ctx.data::<Database>()?, place_id)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Persons `personId` has spoken to or heard from, most frequent first.
+    #[graphql(complexity = "5 * child_complexity")]
+    async fn tanahpedia_conversation_partners(
+        &self,
+        ctx: &Context<'_>,
+        person_id: String,
+    ) -> Result<Vec<TanahpediaConversationPartner>> {
+        tanahpedia_sayings_service::get_conversation_partners(ctx.data::<Database>()?, person_id)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+#[derive(Default)]
+pub struct TanahpediaSayingsMutation;
+
+#[Object]
+impl TanahpediaSayingsMutation {
+    /// Add or replace a speaker link on a saying (authorized clients only).
+    async fn put_tanahpedia_saying_speaker(
+        &self,
+        ctx: &Context<'_>,
+        input: PutTanahpediaSayingPartyInput,
+    ) -> Result<TanahpediaSayingPartyWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_sayings_service::put_saying_speaker(ctx.data::<Database>()?, input)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Remove a speaker link by id (authorized clients only).
+    async fn delete_tanahpedia_saying_speaker(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<TanahpediaSayingPartyWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_sayings_service::delete_saying_speaker(ctx.data::<Database>()?, id)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Add or replace an audience link on a saying (authorized clients only).
+    async fn put_tanahpedia_saying_audience(
+        &self,
+        ctx: &Context<'_>,
+        input: PutTanahpediaSayingPartyInput,
+    ) -> Result<TanahpediaSayingPartyWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_sayings_service::put_saying_audience(ctx.data::<Database>()?, input)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Remove an audience link by id (authorized clients only).
+    async fn delete_tanahpedia_saying_audience(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<TanahpediaSayingPartyWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_sayings_service::delete_saying_audience(ctx.data::<Database>()?, id)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
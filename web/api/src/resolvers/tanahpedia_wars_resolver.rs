@@ -0,0 +1,115 @@
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+
+use crate::common::auth::ApiAuth;
+use crate::dtos::tanahpedia_war::{
+    PutTanahpediaWarParticipantInput, PutTanahpediaWarSideInput, TanahpediaParticipantWar,
+    TanahpediaWar, TanahpediaWarParticipantWriteResult, TanahpediaWarSideWriteResult,
+};
+use crate::providers::Database;
+use crate::services::tanahpedia_wars_service;
+
+#[derive(Default)]
+pub struct TanahpediaWarsQuery;
+
+#[Object]
+impl TanahpediaWarsQuery {
+    /// A war with its event date ranges and places, and each side (ordered by
+    /// `sideNumber`) with its person and nation participants.
+    async fn tanahpedia_war(&self, ctx: &Context<'_>, id: String) -> Result<TanahpediaWar> {
+        tanahpedia_wars_service::get_war(ctx.data::<Database>()?, id)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Wars `personId` fought in. Also available as `wars` on
+    /// `tanahpediaPersonDetails`.
+    #[graphql(complexity = "5 * child_complexity")]
+    async fn tanahpedia_person_wars(
+        &self,
+        ctx: &Context<'_>,
+        person_id: String,
+    ) -> Result<Vec<TanahpediaParticipantWar>> {
+        tanahpedia_wars_service::find_person_wars(ctx.data::<Database>()?, person_id)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Wars `nationId` fought in.
+    #[graphql(complexity = "5 * child_complexity")]
+    async fn tanahpedia_nation_wars(
+        &self,
+        ctx: &Context<'_>,
+        nation_id: String,
+    ) -> Result<Vec<TanahpediaParticipantWar>> {
+        tanahpedia_wars_service::find_nation_wars(ctx.data::<Database>()?, nation_id)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
+
+#[derive(Default)]
+pub struct TanahpediaWarsMutation;
+
+#[Object]
+impl TanahpediaWarsMutation {
+    /// Add or replace a numbered war side (authorized clients only).
+    async fn put_tanahpedia_war_side(
+        &self,
+        ctx: &Context<'_>,
+        input: PutTanahpediaWarSideInput,
+    ) -> Result<TanahpediaWarSideWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_wars_service::put_war_side(ctx.data::<Database>()?, input)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Remove a war side and its participants (authorized clients only).
+    async fn delete_tanahpedia_war_side(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<TanahpediaWarSideWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_wars_service::delete_war_side(ctx.data::<Database>()?, id)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Add or replace a person/nation participant on a war side (authorized
+    /// clients only).
+    async fn put_tanahpedia_war_participant(
+        &self,
+        ctx: &Context<'_>,
+        input: PutTanahpediaWarParticipantInput,
+    ) -> Result<TanahpediaWarParticipantWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_wars_service::put_war_participant(ctx.data::<Database>()?, input)
+            .await
+            .map_err(|e| e.extend())
+    }
+
+    /// Remove a war participant link by id (authorized clients only).
+    async fn delete_tanahpedia_war_participant(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<TanahpediaWarParticipantWriteResult> {
+        ctx.data::<ApiAuth>()?
+            .authorize_revision_manager()
+            .map_err(|e| e.extend())?;
+
+        tanahpedia_wars_service::delete_war_participant(ctx.data::<Database>()?, id)
+            .await
+            .map_err(|e| e.extend())
+    }
+}
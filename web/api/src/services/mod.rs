@@ -2,5 +2,22 @@ pub mod articles_service;
 pub mod authors_service;
 pub mod perakim_service;
 pub mod sefarim_service;
+pub mod tanahpedia_animals_service;
+pub mod tanahpedia_auto_linker_service;
+pub mod tanahpedia_category_homepages_service;
+pub mod tanahpedia_chazal_service;
+pub mod tanahpedia_creation_days_service;
+pub mod tanahpedia_entries_service;
 pub mod tanahpedia_family_service;
+pub mod tanahpedia_graph_service;
+pub mod tanahpedia_import_service;
+pub mod tanahpedia_models3d_service;
+pub mod tanahpedia_namings_service;
+pub mod tanahpedia_nations_service;
+pub mod tanahpedia_opinions_service;
+pub mod tanahpedia_places_service;
+pub mod tanahpedia_quality_service;
 pub mod tanahpedia_revisions_service;
+pub mod tanahpedia_sayings_service;
+pub mod tanahpedia_sources_service;
+pub mod tanahpedia_wars_service;
@@ -0,0 +1,574 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::tanahpedia_animal::{
+        PutTanahpediaAnimalKindInput, PutTanahpediaAnimalPurityInput, TanahpediaAnimal,
+        TanahpediaAnimalKind, TanahpediaAnimalKindClassification, TanahpediaAnimalKindWriteResult,
+        TanahpediaAnimalOpinions, TanahpediaAnimalPurity, TanahpediaAnimalPurityClassification,
+        TanahpediaAnimalPurityWriteResult,
+    },
+    providers::Database,
+    services::{
+        tanahpedia_family_service::{db_error, entity_display_names, optional, required},
+        tanahpedia_opinions_service::{OpinionCatalog, alt_group_ids},
+        tanahpedia_sources_service::{SourcesInput, delete_sources, load_sources, replace_sources},
+    },
+};
+use entities::tanahpedia::{
+    animal, animal_kind, animal_purity,
+    sea_orm_active_enums::{AnimalKind, AnimalPurity},
+};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter, QuerySelect,
+    TransactionTrait,
+};
+
+/// `tanahpedia_source_group.target_table` values for classification sources.
+const KIND_SOURCE_TABLE: &str = "tanahpedia_animal_kind";
+const PURITY_SOURCE_TABLE: &str = "tanahpedia_animal_purity";
+
+/// Animal ids with at least one opinion matching the filter; `None` when the
+/// filter is absent.
+async fn animal_ids_with_kind(
+    conn: &DatabaseConnection,
+    kind: Option<TanahpediaAnimalKind>,
+) -> Result<Option<HashSet<String>>, ServiceError> {
+    let Some(kind) = kind else {
+        return Ok(None);
+    };
+    let ids = animal_kind::Entity::find()
+        .select_only()
+        .column(animal_kind::Column::AnimalId)
+        .filter(animal_kind::Column::Kind.eq(AnimalKind::from(kind)))
+        .into_tuple::<String>()
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    Ok(Some(ids.into_iter().collect()))
+}
+
+async fn animal_ids_with_purity(
+    conn: &DatabaseConnection,
+    purity: Option<TanahpediaAnimalPurity>,
+) -> Result<Option<HashSet<String>>, ServiceError> {
+    let Some(purity) = purity else {
+        return Ok(None);
+    };
+    let ids = animal_purity::Entity::find()
+        .select_only()
+        .column(animal_purity::Column::AnimalId)
+        .filter(animal_purity::Column::Purity.eq(AnimalPurity::from(purity)))
+        .into_tuple::<String>()
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    Ok(Some(ids.into_iter().collect()))
+}
+
+/// Lists animals, optionally only those with an opinion of the given kind
+/// and/or purity. A disputed animal matches every classification any opinion
+/// gives it, and is returned with all of its opinions and their sources.
+pub async fn find_animals(
+    db: &Database,
+    kind: Option<TanahpediaAnimalKind>,
+    purity: Option<TanahpediaAnimalPurity>,
+) -> Result<Vec<TanahpediaAnimal>, ServiceError> {
+    let conn = db.get_connection();
+    let filter = match (
+        animal_ids_with_kind(conn, kind).await?,
+        animal_ids_with_purity(conn, purity).await?,
+    ) {
+        (Some(kinds), Some(purities)) => Some(&kinds & &purities),
+        (kinds, purities) => kinds.or(purities),
+    };
+    if filter.as_ref().is_some_and(HashSet::is_empty) {
+        return Ok(Vec::new());
+    }
+
+    let mut query = animal::Entity::find();
+    if let Some(ids) = filter {
+        query = query.filter(animal::Column::Id.is_in(ids));
+    }
+    let animals = query.all(conn).await.map_err(db_error)?;
+    if animals.is_empty() {
+        return Ok(Vec::new());
+    }
+    let animal_ids: HashSet<String> = animals.iter().map(|row| row.id.clone()).collect();
+
+    let kinds = animal_kind::Entity::find()
+        .filter(animal_kind::Column::AnimalId.is_in(animal_ids.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let purities = animal_purity::Entity::find()
+        .filter(animal_purity::Column::AnimalId.is_in(animal_ids))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let mut kind_sources = load_sources(
+        conn,
+        KIND_SOURCE_TABLE,
+        kinds.iter().map(|row| row.id.clone()).collect(),
+    )
+    .await?;
+    let mut purity_sources = load_sources(
+        conn,
+        PURITY_SOURCE_TABLE,
+        purities.iter().map(|row| row.id.clone()).collect(),
+    )
+    .await?;
+    let names = entity_display_names(
+        conn,
+        animals.iter().map(|row| row.entity_id.clone()).collect(),
+    )
+    .await?;
+
+    let mut kinds_by_animal: HashMap<String, Vec<TanahpediaAnimalKindClassification>> =
+        HashMap::new();
+    for row in kinds {
+        kinds_by_animal.entry(row.animal_id).or_default().push(
+            TanahpediaAnimalKindClassification {
+                sources: kind_sources.remove(&row.id).unwrap_or_default(),
+                id: row.id,
+                kind: row.kind.into(),
+                alt_group_id: row.alt_group_id,
+            },
+        );
+    }
+    let mut purities_by_animal: HashMap<String, Vec<TanahpediaAnimalPurityClassification>> =
+        HashMap::new();
+    for row in purities {
+        purities_by_animal.entry(row.animal_id).or_default().push(
+            TanahpediaAnimalPurityClassification {
+                sources: purity_sources.remove(&row.id).unwrap_or_default(),
+                id: row.id,
+                purity: row.purity.into(),
+                alt_group_id: row.alt_group_id,
+            },
+        );
+    }
+
+    let mut result: Vec<TanahpediaAnimal> = animals
+        .into_iter()
+        .map(|row| {
+            let mut kinds = kinds_by_animal.remove(&row.id).unwrap_or_default();
+            kinds.sort_by(|a, b| a.alt_group_id.cmp(&b.alt_group_id).then(a.id.cmp(&b.id)));
+            let mut purities = purities_by_animal.remove(&row.id).unwrap_or_default();
+            purities.sort_by(|a, b| a.alt_group_id.cmp(&b.alt_group_id).then(a.id.cmp(&b.id)));
+            TanahpediaAnimal {
+                display_name: names.get(&row.entity_id).cloned().unwrap_or_default(),
+                animal_id: row.id,
+                entity_id: row.entity_id,
+                kinds,
+                purities,
+            }
+        })
+        .collect();
+    result.sort_by(|a, b| {
+        a.display_name
+            .cmp(&b.display_name)
+            .then_with(|| a.animal_id.cmp(&b.animal_id))
+    });
+    Ok(result)
+}
+
+/// Groups an already-loaded animal by opinion; only the opinion metadata is
+/// queried.
+pub async fn get_animal_opinions(
+    db: &Database,
+    animal: &TanahpediaAnimal,
+) -> Result<TanahpediaAnimalOpinions, ServiceError> {
+    let catalog = OpinionCatalog::load(
+        db.get_connection(),
+        alt_group_ids(
+            animal
+                .kinds
+                .iter()
+                .map(|row| &row.alt_group_id)
+                .chain(animal.purities.iter().map(|row| &row.alt_group_id)),
+        ),
+    )
+    .await?;
+
+    Ok(TanahpediaAnimalOpinions {
+        kinds: catalog.group(
+            animal
+                .kinds
+                .iter()
+                .map(|row| (row.alt_group_id.clone(), row.clone()))
+                .collect(),
+        ),
+        purities: catalog.group(
+            animal
+                .purities
+                .iter()
+                .map(|row| (row.alt_group_id.clone(), row.clone()))
+                .collect(),
+        ),
+    })
+}
+
+async fn require_animal(conn: &DatabaseConnection, animal_id: &str) -> Result<(), ServiceError> {
+    if animal::Entity::find_by_id(animal_id.to_string())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+        .is_none()
+    {
+        return Err(ServiceError::bad_request(&format!(
+            "animalId {animal_id} does not reference an existing animal"
+        )));
+    }
+    Ok(())
+}
+
+pub async fn put_animal_kind(
+    db: &Database,
+    input: PutTanahpediaAnimalKindInput,
+) -> Result<TanahpediaAnimalKindWriteResult, ServiceError> {
+    let conn = db.get_connection();
+    let id = required(input.id, "id", 36)?;
+    let animal_id = required(input.animal_id, "animalId", 36)?;
+    let alt_group_id = optional(input.alt_group_id, "altGroupId", 36)?;
+    let sources = SourcesInput::parse(input.tanah_sources, input.non_tanah_sources)?;
+    require_animal(conn, &animal_id).await?;
+
+    let transaction = conn.begin().await.map_err(db_error)?;
+    animal_kind::Entity::insert(
+        animal_kind::Model {
+            id: id.clone(),
+            animal_id: animal_id.clone(),
+            kind: input.kind.into(),
+            alt_group_id,
+        }
+        .into_active_model(),
+    )
+    .on_conflict(
+        OnConflict::column(animal_kind::Column::Id)
+            .update_columns([
+                animal_kind::Column::AnimalId,
+                animal_kind::Column::Kind,
+                animal_kind::Column::AltGroupId,
+            ])
+            .to_owned(),
+    )
+    .exec(&transaction)
+    .await
+    .map_err(db_error)?;
+    replace_sources(&transaction, KIND_SOURCE_TABLE, &id, sources).await?;
+    transaction.commit().await.map_err(db_error)?;
+
+    Ok(TanahpediaAnimalKindWriteResult {
+        id,
+        animal_id,
+        kind: input.kind,
+    })
+}
+
+pub async fn delete_animal_kind(
+    db: &Database,
+    id: String,
+) -> Result<TanahpediaAnimalKindWriteResult, ServiceError> {
+    let id = required(id, "id", 36)?;
+    let conn = db.get_connection();
+    let Some(row) = animal_kind::Entity::find_by_id(id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+    else {
+        return Err(ServiceError::not_found(
+            "animal kind not found",
+            None::<&str>,
+        ));
+    };
+
+    let transaction = conn.begin().await.map_err(db_error)?;
+    delete_sources(&transaction, KIND_SOURCE_TABLE, &id).await?;
+    animal_kind::Entity::delete_by_id(id.clone())
+        .exec(&transaction)
+        .await
+        .map_err(db_error)?;
+    transaction.commit().await.map_err(db_error)?;
+
+    Ok(TanahpediaAnimalKindWriteResult {
+        id,
+        animal_id: row.animal_id,
+        kind: row.kind.into(),
+    })
+}
+
+pub async fn put_animal_purity(
+    db: &Database,
+    input: PutTanahpediaAnimalPurityInput,
+) -> Result<TanahpediaAnimalPurityWriteResult, ServiceError> {
+    let conn = db.get_connection();
+    let id = required(input.id, "id", 36)?;
+    let animal_id = required(input.animal_id, "animalId", 36)?;
+    let alt_group_id = optional(input.alt_group_id, "altGroupId", 36)?;
+    let sources = SourcesInput::parse(input.tanah_sources, input.non_tanah_sources)?;
+    require_animal(conn, &animal_id).await?;
+
+    let transaction = conn.begin().await.map_err(db_error)?;
+    animal_purity::Entity::insert(
+        animal_purity::Model {
+            id: id.clone(),
+            animal_id: animal_id.clone(),
+            purity: input.purity.into(),
+            alt_group_id,
+        }
+        .into_active_model(),
+    )
+    .on_conflict(
+        OnConflict::column(animal_purity::Column::Id)
+            .update_columns([
+                animal_purity::Column::AnimalId,
+                animal_purity::Column::Purity,
+                animal_purity::Column::AltGroupId,
+            ])
+            .to_owned(),
+    )
+    .exec(&transaction)
+    .await
+    .map_err(db_error)?;
+    replace_sources(&transaction, PURITY_SOURCE_TABLE, &id, sources).await?;
+    transaction.commit().await.map_err(db_error)?;
+
+    Ok(TanahpediaAnimalPurityWriteResult {
+        id,
+        animal_id,
+        purity: input.purity,
+    })
+}
+
+pub async fn delete_animal_purity(
+    db: &Database,
+    id: String,
+) -> Result<TanahpediaAnimalPurityWriteResult, ServiceError> {
+    let id = required(id, "id", 36)?;
+    let conn = db.get_connection();
+    let Some(row) = animal_purity::Entity::find_by_id(id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+    else {
+        return Err(ServiceError::not_found(
+            "animal purity not found",
+            None::<&str>,
+        ));
+    };
+
+    let transaction = conn.begin().await.map_err(db_error)?;
+    delete_sources(&transaction, PURITY_SOURCE_TABLE, &id).await?;
+    animal_purity::Entity::delete_by_id(id.clone())
+        .exec(&transaction)
+        .await
+        .map_err(db_error)?;
+    transaction.commit().await.map_err(db_error)?;
+
+    Ok(TanahpediaAnimalPurityWriteResult {
+        id,
+        animal_id: row.animal_id,
+        purity: row.purity.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::tanahpedia_source::TanahpediaTanahSourceInput;
+    use entities::tanahpedia::{entity, sea_orm_active_enums::EntityType};
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    fn animal_model(id: &str, entity_id: &str) -> animal::Model {
+        animal::Model {
+            id: id.to_string(),
+            entity_id: entity_id.to_string(),
+        }
+    }
+
+    fn entity_model(id: &str, name: &str) -> entity::Model {
+        entity::Model {
+            id: id.to_string(),
+            entity_type: EntityType::Animal,
+            name: name.to_string(),
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    fn purity_model(id: &str, purity: AnimalPurity, alt_group_id: &str) -> animal_purity::Model {
+        animal_purity::Model {
+            id: id.to_string(),
+            animal_id: "animal-1".to_string(),
+            purity,
+            alt_group_id: Some(alt_group_id.to_string()),
+        }
+    }
+
+    fn purity_input() -> PutTanahpediaAnimalPurityInput {
+        PutTanahpediaAnimalPurityInput {
+            id: "purity-1".to_string(),
+            animal_id: "animal-1".to_string(),
+            purity: TanahpediaAnimalPurity::Tahor,
+            alt_group_id: Some("alt-1".to_string()),
+            tanah_sources: vec![TanahpediaTanahSourceInput {
+                perush_id: None,
+                perek_id: Some(90),
+                pasuk_number: Some(4),
+            }],
+            non_tanah_sources: vec!["חולין נט ע\"א".to_string()],
+        }
+    }
+
+    #[test]
+    fn graphql_enums_round_trip_entity_enums() {
+        for kind in [
+            AnimalKind::Behema,
+            AnimalKind::Chaya,
+            AnimalKind::Of,
+            AnimalKind::Sheretz,
+        ] {
+            assert_eq!(AnimalKind::from(TanahpediaAnimalKind::from(kind)), kind);
+        }
+        for purity in [AnimalPurity::Tahor, AnimalPurity::Tameh] {
+            assert_eq!(
+                AnimalPurity::from(TanahpediaAnimalPurity::from(purity)),
+                purity
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn find_animals_returns_nothing_when_filters_do_not_intersect() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![std::collections::BTreeMap::from([(
+                    "animal_id",
+                    sea_orm::Value::from("animal-1"),
+                )])]])
+                .append_query_results([vec![std::collections::BTreeMap::from([(
+                    "animal_id",
+                    sea_orm::Value::from("animal-2"),
+                )])]])
+                .into_connection(),
+        );
+
+        let animals = find_animals(
+            &db,
+            Some(TanahpediaAnimalKind::Behema),
+            Some(TanahpediaAnimalPurity::Tahor),
+        )
+        .await
+        .expect("filtered animals should load");
+
+        assert!(animals.is_empty());
+    }
+
+    #[tokio::test]
+    async fn find_animals_keeps_every_purity_opinion_of_a_disputed_animal() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![animal_model("animal-1", "entity-1")]])
+                .append_query_results::<animal_kind::Model, Vec<_>, _>([vec![]])
+                .append_query_results([vec![
+                    purity_model("purity-2", AnimalPurity::Tameh, "alt-2"),
+                    purity_model("purity-1", AnimalPurity::Tahor, "alt-1"),
+                ]])
+                .append_query_results::<entities::tanahpedia::source_group::Model, Vec<_>, _>([
+                    vec![],
+                ])
+                .append_query_results([vec![entity_model("entity-1", "שפן")]])
+                .into_connection(),
+        );
+
+        let animals = find_animals(&db, None, None)
+            .await
+            .expect("animals should load");
+
+        assert_eq!(animals.len(), 1);
+        assert_eq!(animals[0].display_name, "שפן");
+        assert_eq!(
+            animals[0]
+                .purities
+                .iter()
+                .map(|opinion| (opinion.id.as_str(), opinion.purity))
+                .collect::<Vec<_>>(),
+            vec![
+                ("purity-1", TanahpediaAnimalPurity::Tahor),
+                ("purity-2", TanahpediaAnimalPurity::Tameh),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn put_animal_purity_rejects_sources_without_perek_before_querying() {
+        let db =
+            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
+        let mut input = purity_input();
+        input.tanah_sources[0].perek_id = None;
+
+        let err = put_animal_purity(&db, input).await.unwrap_err();
+
+        assert!(matches!(err, ServiceError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn put_animal_purity_rejects_unknown_animal() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<animal::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let err = put_animal_purity(&db, purity_input()).await.unwrap_err();
+
+        assert!(matches!(err, ServiceError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn put_animal_purity_stores_opinion_with_its_sources() {
+        let one_row = MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        };
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![animal_model("animal-1", "entity-1")]])
+                .append_exec_results([
+                    one_row.clone(),
+                    MockExecResult {
+                        last_insert_id: 0,
+                        rows_affected: 0,
+                    },
+                    one_row.clone(),
+                    one_row.clone(),
+                    one_row,
+                ])
+                .into_connection(),
+        );
+
+        let result = put_animal_purity(&db, purity_input())
+            .await
+            .expect("purity opinion should be stored");
+
+        assert_eq!(result.purity, TanahpediaAnimalPurity::Tahor);
+        assert_eq!(result.animal_id, "animal-1");
+    }
+
+    #[tokio::test]
+    async fn delete_animal_kind_reports_missing_row() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<animal_kind::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let err = delete_animal_kind(&db, "missing".to_string())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
@@ -0,0 +1,714 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::tanahpedia_auto_link::{
+        TanahpediaAmbiguousMention, TanahpediaAutoLink, TanahpediaAutoLinkCandidate,
+        TanahpediaAutoLinkProposal, TanahpediaAutoLinkReport,
+    },
+    providers::Database,
+    services::{
+        tanahpedia_entries_service::{entry_path, extract_link_targets},
+        tanahpedia_family_service::{db_error, optional},
+    },
+};
+use entities::article;
+use entities::tanahpedia::{
+    entity, entry, entry_entity, entry_revision, entry_synonym, entry_synonym_disambiguation,
+    person, person_name, sea_orm_active_enums::RevisionStatus,
+};
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter};
+
+/// `entry_revision.source` of the revisions this service proposes.
+pub(crate) const AUTO_LINKER_SOURCE: &str = "auto-linker";
+
+/// Shorter names are too likely to be ordinary words.
+const MIN_NAME_CHARS: usize = 2;
+
+/// One-letter prefixes (ו, ה, ב…) a mention may carry, as in "וליעקב".
+const PREFIX_LETTERS: &str = "ובהכלמש";
+const MAX_PREFIX_CHARS: usize = 2;
+
+/// The rows names and their entries are collected from.
+#[derive(Debug, Default)]
+struct NameSources {
+    entries: Vec<entry::Model>,
+    entry_entities: Vec<entry_entity::Model>,
+    entities: Vec<entity::Model>,
+    persons: Vec<person::Model>,
+    person_names: Vec<person_name::Model>,
+    synonyms: Vec<entry_synonym::Model>,
+    disambiguations: Vec<entry_synonym_disambiguation::Model>,
+}
+
+async fn load_name_sources<C: ConnectionTrait>(conn: &C) -> Result<NameSources, ServiceError> {
+    Ok(NameSources {
+        entries: entry::Entity::find().all(conn).await.map_err(db_error)?,
+        entry_entities: entry_entity::Entity::find()
+            .all(conn)
+            .await
+            .map_err(db_error)?,
+        entities: entity::Entity::find().all(conn).await.map_err(db_error)?,
+        persons: person::Entity::find().all(conn).await.map_err(db_error)?,
+        person_names: person_name::Entity::find()
+            .all(conn)
+            .await
+            .map_err(db_error)?,
+        synonyms: entry_synonym::Entity::find()
+            .all(conn)
+            .await
+            .map_err(db_error)?,
+        disambiguations: entry_synonym_disambiguation::Entity::find()
+            .all(conn)
+            .await
+            .map_err(db_error)?,
+    })
+}
+
+/// Every linkable name with the entries it may refer to.
+#[derive(Debug, Default)]
+struct NameIndex {
+    /// Names by first character, longest first.
+    by_first_char: HashMap<char, Vec<(String, Vec<TanahpediaAutoLinkCandidate>)>>,
+}
+
+impl NameIndex {
+    /// Entity and person names reach entries through `entry_entity`; synonyms
+    /// name their entry directly, and their disambiguation rows add the other
+    /// entries they may mean.
+    fn new(sources: &NameSources) -> Self {
+        let unique_names: HashMap<&str, &str> = sources
+            .entries
+            .iter()
+            .map(|entry| (entry.id.as_str(), entry.unique_name.as_str()))
+            .collect();
+        let mut entries_of_entity: HashMap<&str, Vec<&str>> = HashMap::new();
+        for link in &sources.entry_entities {
+            entries_of_entity
+                .entry(link.entity_id.as_str())
+                .or_default()
+                .push(link.entry_id.as_str());
+        }
+        let entity_of_person: HashMap<&str, &str> = sources
+            .persons
+            .iter()
+            .map(|person| (person.id.as_str(), person.entity_id.as_str()))
+            .collect();
+        let synonym_names: HashMap<&str, &str> = sources
+            .synonyms
+            .iter()
+            .map(|synonym| (synonym.id.as_str(), synonym.name.as_str()))
+            .collect();
+
+        let mut names: BTreeMap<String, BTreeMap<String, Option<String>>> = BTreeMap::new();
+        let mut add = |name: &str, entry_id: &str, label: Option<&str>| {
+            let name = name.trim();
+            if name.chars().count() < MIN_NAME_CHARS {
+                return;
+            }
+            let Some(unique_name) = unique_names.get(entry_id) else {
+                return;
+            };
+            let slot = names
+                .entry(name.to_string())
+                .or_default()
+                .entry(unique_name.to_string())
+                .or_default();
+            if let Some(label) = label {
+                *slot = Some(label.to_string());
+            }
+        };
+
+        for entity in &sources.entities {
+            for entry_id in entries_of_entity
+                .get(entity.id.as_str())
+                .into_iter()
+                .flatten()
+            {
+                add(&entity.name, entry_id, None);
+            }
+        }
+        for person_name in &sources.person_names {
+            let Some(entity_id) = entity_of_person.get(person_name.person_id.as_str()) else {
+                continue;
+            };
+            for entry_id in entries_of_entity.get(entity_id).into_iter().flatten() {
+                add(&person_name.name, entry_id, None);
+            }
+        }
+        for synonym in &sources.synonyms {
+            add(&synonym.name, &synonym.entry_id, None);
+        }
+        for disambiguation in &sources.disambiguations {
+            let Some(name) = synonym_names.get(disambiguation.synonym_id.as_str()) else {
+                continue;
+            };
+            add(
+                name,
+                &disambiguation.entry_id,
+                Some(&disambiguation.disambiguation_label),
+            );
+        }
+
+        let mut by_first_char: HashMap<char, Vec<_>> = HashMap::new();
+        for (name, candidates) in names {
+            let Some(first) = name.chars().next() else {
+                continue;
+            };
+            let candidates = candidates
+                .into_iter()
+                .map(
+                    |(unique_name, disambiguation_label)| TanahpediaAutoLinkCandidate {
+                        unique_name,
+                        disambiguation_label,
+                    },
+                )
+                .collect();
+            by_first_char
+                .entry(first)
+                .or_default()
+                .push((name, candidates));
+        }
+        for names in by_first_char.values_mut() {
+            names.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+        }
+        Self { by_first_char }
+    }
+
+    /// The longest name starting `text`, possibly after prefix letters, that
+    /// ends at a word boundary, with the byte length of the prefix.
+    fn match_at(&self, text: &str) -> Option<(usize, &str, &[TanahpediaAutoLinkCandidate])> {
+        let mut prefix_len = 0;
+        for _ in 0..=MAX_PREFIX_CHARS {
+            let rest = &text[prefix_len..];
+            let first = rest.chars().next()?;
+            for (name, candidates) in self.by_first_char.get(&first).into_iter().flatten() {
+                if rest.starts_with(name.as_str())
+                    && !rest[name.len()..].chars().next().is_some_and(is_word_char)
+                {
+                    return Some((prefix_len, name, candidates));
+                }
+            }
+            if !PREFIX_LETTERS.contains(first) {
+                return None;
+            }
+            prefix_len += first.len_utf8();
+        }
+        None
+    }
+}
+
+/// Letters, digits and Hebrew points; maqaf, paseq and sof pasuq split words.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric()
+        || (('\u{0591}'..='\u{05C7}').contains(&c)
+            && !matches!(c, '\u{05BE}' | '\u{05C0}' | '\u{05C3}' | '\u{05C6}'))
+}
+
+/// Whether lowercased `tag` opens (`<a`) or closes (`</a`) an anchor.
+fn is_tag(tag: &str, start: &str) -> bool {
+    tag.strip_prefix(start)
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_whitespace() || c == '>'))
+}
+
+/// A name that may mean several entries is left for an editor to link.
+fn is_ambiguous(candidates: &[TanahpediaAutoLinkCandidate]) -> bool {
+    candidates.len() > 1
+        || candidates
+            .iter()
+            .any(|candidate| candidate.disambiguation_label.is_some())
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct AutoLinked {
+    content: String,
+    links: Vec<TanahpediaAutoLink>,
+    ambiguous: Vec<TanahpediaAmbiguousMention>,
+}
+
+/// Links the first mention of each entry named in the text of `content`.
+/// Text inside tags and existing links is left alone, as are entries the
+/// content already links to and `own_unique_name` itself.
+fn auto_link(content: &str, index: &NameIndex, own_unique_name: Option<&str>) -> AutoLinked {
+    let mut linked: HashSet<String> = extract_link_targets(content).into_iter().collect();
+    linked.extend(own_unique_name.map(str::to_string));
+    let mut flagged = HashSet::new();
+    let mut result = AutoLinked {
+        content: String::with_capacity(content.len()),
+        ..Default::default()
+    };
+
+    let mut in_anchor = false;
+    let mut at = 0;
+    while at < content.len() {
+        let rest = &content[at..];
+        if rest.starts_with('<') {
+            let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+            let tag = rest[..end].to_ascii_lowercase();
+            if is_tag(&tag, "<a") {
+                in_anchor = true;
+            } else if is_tag(&tag, "</a") {
+                in_anchor = false;
+            }
+            result.content.push_str(&rest[..end]);
+            at += end;
+            continue;
+        }
+
+        let word_start = !content[..at].chars().next_back().is_some_and(is_word_char);
+        if word_start
+            && !in_anchor
+            && let Some((prefix_len, name, candidates)) = index.match_at(rest)
+        {
+            let mention = &rest[..prefix_len + name.len()];
+            if is_ambiguous(candidates) {
+                if flagged.insert(name) {
+                    result.ambiguous.push(TanahpediaAmbiguousMention {
+                        name: name.to_string(),
+                        candidates: candidates.to_vec(),
+                    });
+                }
+                result.content.push_str(mention);
+            } else if linked.insert(candidates[0].unique_name.clone()) {
+                let target = &candidates[0].unique_name;
+                result.content.push_str(&rest[..prefix_len]);
+                result
+                    .content
+                    .push_str(&format!(r#"<a href="{}">{name}</a>"#, entry_path(target)));
+                result.links.push(TanahpediaAutoLink {
+                    name: name.to_string(),
+                    target_unique_name: target.clone(),
+                });
+            } else {
+                result.content.push_str(mention);
+            }
+            at += mention.len();
+            continue;
+        }
+
+        let len = rest.chars().next().map_or(1, char::len_utf8);
+        result.content.push_str(&rest[..len]);
+        at += len;
+    }
+    result
+}
+
+/// Revision notes telling the editor what was linked and what was not.
+fn revision_notes(linked: &AutoLinked) -> String {
+    let links = linked
+        .links
+        .iter()
+        .map(|link| format!("{} → {}", link.name, link.target_unique_name))
+        .collect::<Vec<_>>()
+        .join("; ");
+    let mut notes = format!("Linked: {links}.");
+    if !linked.ambiguous.is_empty() {
+        let ambiguous = linked
+            .ambiguous
+            .iter()
+            .map(|mention| {
+                let candidates = mention
+                    .candidates
+                    .iter()
+                    .map(|candidate| match &candidate.disambiguation_label {
+                        Some(label) => format!("{} ({label})", candidate.unique_name),
+                        None => candidate.unique_name.clone(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                format!("{} [{candidates}]", mention.name)
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+        notes.push_str(&format!("\nAmbiguous, left unlinked: {ambiguous}."));
+    }
+    notes
+}
+
+/// Scans entry and article content for names from `person_name`,
+/// `tanahpedia_entity` and `entry_synonym` and proposes links to their entries.
+/// Scans one entry or one article when asked to, and everything otherwise.
+///
+/// Entry proposals are stored as pending `auto-linker` revisions; entries that
+/// already have one are skipped until it is triaged. Article scanning is
+/// preview-only: articles are not Tanahpedia entries, so there is no revision
+/// row to hold their proposals and nothing is stored for them.
+pub async fn propose_auto_links(
+    db: &Database,
+    entry_id: Option<String>,
+    article_id: Option<i32>,
+) -> Result<TanahpediaAutoLinkReport, ServiceError> {
+    tracing::info_span!("tanahpedia_auto_linker_service::propose_auto_links");
+
+    let entry_id = optional(entry_id, "entryId", 36)?;
+    let scan_all = entry_id.is_none() && article_id.is_none();
+    let conn = db.get_connection();
+
+    let sources = load_name_sources(conn).await?;
+    let index = NameIndex::new(&sources);
+
+    let mut entries = Vec::new();
+    if scan_all || entry_id.is_some() {
+        entries = sources
+            .entries
+            .iter()
+            .filter(|entry| entry_id.as_ref().is_none_or(|id| &entry.id == id))
+            .collect::<Vec<_>>();
+        if entry_id.is_some() && entries.is_empty() {
+            return Err(ServiceError::not_found("entry not found", None::<&str>));
+        }
+
+        let pending: HashSet<String> = entry_revision::Entity::find()
+            .filter(entry_revision::Column::Source.eq(AUTO_LINKER_SOURCE))
+            .filter(entry_revision::Column::Status.eq(RevisionStatus::Pending))
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .filter_map(|revision| revision.entry_id)
+            .collect();
+        entries.retain(|entry| !pending.contains(&entry.id));
+    }
+
+    let mut articles = Vec::new();
+    if scan_all || article_id.is_some() {
+        let mut query = article::Entity::find();
+        if let Some(article_id) = article_id {
+            query = query.filter(article::Column::Id.eq(article_id));
+        }
+        articles = query.all(conn).await.map_err(db_error)?;
+        if article_id.is_some() && articles.is_empty() {
+            return Err(ServiceError::not_found("article not found", None::<&str>));
+        }
+    }
+
+    let now = chrono::Utc::now().naive_utc();
+    let mut proposals = Vec::new();
+    for entry in &entries {
+        let Some(content) = entry.content.as_deref() else {
+            continue;
+        };
+        let linked = auto_link(content, &index, Some(&entry.unique_name));
+        if linked.links.is_empty() && linked.ambiguous.is_empty() {
+            continue;
+        }
+
+        let mut revision_id = None;
+        if !linked.links.is_empty() {
+            let revision = entry_revision::Model {
+                id: uuid::Uuid::new_v4().to_string(),
+                entry_id: Some(entry.id.clone()),
+                proposed_unique_name: None,
+                proposed_title: None,
+                proposed_content: Some(linked.content.clone()),
+                source: AUTO_LINKER_SOURCE.to_string(),
+                notes: Some(revision_notes(&linked)),
+                status: RevisionStatus::Pending,
+                created_at: now,
+                updated_at: now,
+            };
+            entry_revision::Entity::insert(revision.clone().into_active_model())
+                .exec(conn)
+                .await
+                .map_err(db_error)?;
+            revision_id = Some(revision.id);
+        }
+
+        proposals.push(TanahpediaAutoLinkProposal {
+            entry_id: Some(entry.id.clone()),
+            article_id: None,
+            links: linked.links,
+            ambiguous: linked.ambiguous,
+            revision_id,
+            proposed_content: None,
+        });
+    }
+
+    for article in &articles {
+        let Some(content) = article.content.as_deref() else {
+            continue;
+        };
+        let linked = auto_link(content, &index, None);
+        if linked.links.is_empty() && linked.ambiguous.is_empty() {
+            continue;
+        }
+        let proposed_content = (!linked.links.is_empty()).then_some(linked.content);
+        proposals.push(TanahpediaAutoLinkProposal {
+            entry_id: None,
+            article_id: Some(article.id),
+            links: linked.links,
+            ambiguous: linked.ambiguous,
+            revision_id: None,
+            proposed_content,
+        });
+    }
+
+    tracing::info!(
+        entries = entries.len(),
+        articles = articles.len(),
+        proposals = proposals.len(),
+        "Proposed auto-links"
+    );
+    Ok(TanahpediaAutoLinkReport {
+        scanned: (entries.len() + articles.len()) as i32,
+        proposals,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::tanahpedia::sea_orm_active_enums::EntityType;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    fn entry_model(id: &str, unique_name: &str, content: Option<&str>) -> entry::Model {
+        entry::Model {
+            id: id.to_string(),
+            unique_name: unique_name.to_string(),
+            title: unique_name.to_string(),
+            content: content.map(str::to_string),
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    fn entry_entity_model(entry_id: &str, entity_id: &str) -> entry_entity::Model {
+        entry_entity::Model {
+            id: format!("{entry_id}-{entity_id}"),
+            entry_id: entry_id.to_string(),
+            entity_id: entity_id.to_string(),
+        }
+    }
+
+    fn entity_model(id: &str, name: &str) -> entity::Model {
+        entity::Model {
+            id: id.to_string(),
+            entity_type: EntityType::Person,
+            name: name.to_string(),
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    fn synonym_model(id: &str, name: &str, entry_id: &str) -> entry_synonym::Model {
+        entry_synonym::Model {
+            id: id.to_string(),
+            name: name.to_string(),
+            entry_id: entry_id.to_string(),
+        }
+    }
+
+    fn disambiguation_model(
+        synonym_id: &str,
+        entry_id: &str,
+        label: &str,
+    ) -> entry_synonym_disambiguation::Model {
+        entry_synonym_disambiguation::Model {
+            id: format!("{synonym_id}-{entry_id}"),
+            synonym_id: synonym_id.to_string(),
+            entry_id: entry_id.to_string(),
+            disambiguation_label: label.to_string(),
+        }
+    }
+
+    /// יעקב (with the person name ישראל), the city שכם, שכם בן חמור, and the
+    /// synonym "שכם" disambiguated between them.
+    fn sources() -> NameSources {
+        NameSources {
+            entries: vec![
+                entry_model("e-yaakov", "יעקב", None),
+                entry_model("e-shechem", "שכם (עיר)", None),
+                entry_model("e-shechem-ben-hamor", "שכם בן חמור", None),
+            ],
+            entry_entities: vec![
+                entry_entity_model("e-yaakov", "ent-yaakov"),
+                entry_entity_model("e-shechem-ben-hamor", "ent-shechem"),
+            ],
+            entities: vec![
+                entity_model("ent-yaakov", "יעקב"),
+                entity_model("ent-shechem", "שכם בן חמור"),
+                entity_model("ent-orphan", "עשו"),
+            ],
+            persons: vec![person::Model {
+                id: "p-yaakov".to_string(),
+                entity_id: "ent-yaakov".to_string(),
+            }],
+            person_names: vec![person_name::Model {
+                id: "pn-1".to_string(),
+                person_id: "p-yaakov".to_string(),
+                name: "ישראל".to_string(),
+                name_type_id: "nt".to_string(),
+                alt_group_id: None,
+            }],
+            synonyms: vec![
+                synonym_model("s-shechem", "שכם", "e-shechem"),
+                synonym_model("s-short", "י", "e-yaakov"),
+            ],
+            disambiguations: vec![disambiguation_model(
+                "s-shechem",
+                "e-shechem-ben-hamor",
+                "בן חמור",
+            )],
+        }
+    }
+
+    #[test]
+    fn name_index_collects_names_from_every_source() {
+        let index = NameIndex::new(&sources());
+        let names: BTreeMap<&str, Vec<(&str, Option<&str>)>> = index
+            .by_first_char
+            .values()
+            .flatten()
+            .map(|(name, candidates)| {
+                (
+                    name.as_str(),
+                    candidates
+                        .iter()
+                        .map(|c| (c.unique_name.as_str(), c.disambiguation_label.as_deref()))
+                        .collect(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            names,
+            BTreeMap::from([
+                ("יעקב", vec![("יעקב", None)]),
+                ("ישראל", vec![("יעקב", None)]),
+                (
+                    "שכם",
+                    vec![("שכם (עיר)", None), ("שכם בן חמור", Some("בן חמור"))]
+                ),
+                ("שכם בן חמור", vec![("שכם בן חמור", None)]),
+            ])
+        );
+    }
+
+    #[test]
+    fn auto_link_links_first_mentions_with_prefixes() {
+        let index = NameIndex::new(&sources());
+        let content = r#"<p title="יעקב">וכשיעקב ויעקב הלך אל <b>שכם בן חמור</b>, ושכם בן חמור ענה.</p><p>ישראל <a href="/pedia/x">יעקב</a></p>"#;
+
+        let linked = auto_link(content, &index, None);
+
+        assert_eq!(
+            linked.content,
+            concat!(
+                r#"<p title="יעקב">וכשיעקב ו<a href="/pedia/יעקב">יעקב</a> הלך אל "#,
+                r#"<b><a href="/pedia/שכם%20בן%20חמור">שכם בן חמור</a></b>, ושכם בן חמור ענה.</p>"#,
+                r#"<p>ישראל <a href="/pedia/x">יעקב</a></p>"#,
+            )
+        );
+        assert_eq!(
+            linked.links,
+            vec![
+                TanahpediaAutoLink {
+                    name: "יעקב".to_string(),
+                    target_unique_name: "יעקב".to_string(),
+                },
+                TanahpediaAutoLink {
+                    name: "שכם בן חמור".to_string(),
+                    target_unique_name: "שכם בן חמור".to_string(),
+                },
+            ]
+        );
+        assert!(linked.ambiguous.is_empty());
+    }
+
+    #[test]
+    fn auto_link_flags_ambiguous_names_and_skips_linked_targets() {
+        let index = NameIndex::new(&sources());
+        let content = r#"<p>יעקב בא לשכם. <a href="/pedia/יעקב">ישראל</a> ישב בשכם.</p>"#;
+
+        let linked = auto_link(content, &index, Some("שכם (עיר)"));
+
+        assert_eq!(linked.content, content);
+        assert!(linked.links.is_empty());
+        assert_eq!(
+            linked.ambiguous,
+            vec![TanahpediaAmbiguousMention {
+                name: "שכם".to_string(),
+                candidates: vec![
+                    TanahpediaAutoLinkCandidate {
+                        unique_name: "שכם (עיר)".to_string(),
+                        disambiguation_label: None,
+                    },
+                    TanahpediaAutoLinkCandidate {
+                        unique_name: "שכם בן חמור".to_string(),
+                        disambiguation_label: Some("בן חמור".to_string()),
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn propose_auto_links_stores_a_pending_revision_for_an_entry() {
+        let sources = sources();
+        let mut entries = sources.entries.clone();
+        entries.push(entry_model(
+            "e-dina",
+            "דינה",
+            Some("<p>דינה בת יעקב יצאה לשכם.</p>"),
+        ));
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([entries])
+                .append_query_results([sources.entry_entities])
+                .append_query_results([sources.entities])
+                .append_query_results([sources.persons])
+                .append_query_results([sources.person_names])
+                .append_query_results([sources.synonyms])
+                .append_query_results([sources.disambiguations])
+                .append_query_results([Vec::<entry_revision::Model>::new()])
+                .append_exec_results([MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                }])
+                .into_connection(),
+        );
+
+        let report = propose_auto_links(&db, Some("e-dina".to_string()), None)
+            .await
+            .unwrap();
+
+        assert_eq!(report.scanned, 1);
+        let [proposal] = report.proposals.as_slice() else {
+            panic!("expected one proposal, got {:?}", report.proposals);
+        };
+        assert_eq!(proposal.entry_id.as_deref(), Some("e-dina"));
+        assert!(proposal.revision_id.is_some());
+        assert_eq!(proposal.links.len(), 1);
+        assert_eq!(proposal.ambiguous.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn propose_auto_links_rejects_unknown_article() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([Vec::<entry::Model>::new()])
+                .append_query_results([Vec::<entry_entity::Model>::new()])
+                .append_query_results([Vec::<entity::Model>::new()])
+                .append_query_results([Vec::<person::Model>::new()])
+                .append_query_results([Vec::<person_name::Model>::new()])
+                .append_query_results([Vec::<entry_synonym::Model>::new()])
+                .append_query_results([Vec::<entry_synonym_disambiguation::Model>::new()])
+                .append_query_results([Vec::<article::Model>::new()])
+                .into_connection(),
+        );
+
+        let err = propose_auto_links(&db, None, Some(7)).await.unwrap_err();
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+
+    #[test]
+    fn revision_notes_list_links_and_ambiguous_names() {
+        let index = NameIndex::new(&sources());
+        let linked = auto_link("<p>יעקב בשכם</p>", &index, None);
+
+        assert_eq!(
+            revision_notes(&linked),
+            "Linked: יעקב → יעקב.\nAmbiguous, left unlinked: שכם [שכם (עיר) | שכם בן חמור (בן חמור)]."
+        );
+    }
+}
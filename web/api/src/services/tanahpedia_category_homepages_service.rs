@@ -0,0 +1,466 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::error_handling::{INTERNAL_SERVER_ERROR, ServiceError},
+    dtos::{
+        tanahpedia_category_homepage::{
+            PutTanahpediaCategoryHomepageInput, TanahpediaCategoryHomepage,
+            TanahpediaCategoryHomepageConfig, TanahpediaHomepageLayoutType,
+            TanahpediaHomepageSortOrder,
+        },
+        tanahpedia_family::{TanahpediaEntitySummary, TanahpediaEntityType},
+    },
+    providers::Database,
+    services::tanahpedia_family_service::{db_error, optional, required},
+};
+use entities::tanahpedia::{category_homepage, entity, sea_orm_active_enums::EntityType};
+use sea_orm::{ActiveValue::Set, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter};
+
+const MAX_FEATURED_ENTITIES: usize = 50;
+const MAX_CONTENT_LEN: usize = 65_535;
+
+fn invalid_stored_row(message: &str) -> ServiceError {
+    ServiceError::internal_server_error(INTERNAL_SERVER_ERROR, Some(message))
+}
+
+fn to_dto(row: category_homepage::Model) -> Result<TanahpediaCategoryHomepage, ServiceError> {
+    let layout_type = TanahpediaHomepageLayoutType::from_stored_name(&row.layout_type)
+        .ok_or_else(|| invalid_stored_row("unknown category homepage layout_type"))?;
+    let config = row
+        .config
+        .map(serde_json::from_value::<TanahpediaCategoryHomepageConfig>)
+        .transpose()
+        .map_err(|_| invalid_stored_row("invalid category homepage config"))?;
+
+    Ok(TanahpediaCategoryHomepage {
+        id: row.id,
+        entity_type: row.entity_type.into(),
+        layout_type,
+        config,
+        content: row.content,
+        updated_at: row.updated_at.to_string(),
+    })
+}
+
+fn check_range<T: PartialOrd + std::fmt::Display>(
+    value: T,
+    field: &str,
+    min: T,
+    max: T,
+) -> Result<(), ServiceError> {
+    if value < min || value > max {
+        return Err(ServiceError::bad_request(&format!(
+            "{field} must be between {min} and {max}"
+        )));
+    }
+    Ok(())
+}
+
+/// Checks a config against the schema of its layout type and normalizes the
+/// featured ids. Entity existence is checked separately.
+pub(crate) fn validate_config(
+    layout_type: TanahpediaHomepageLayoutType,
+    config: TanahpediaCategoryHomepageConfig,
+) -> Result<TanahpediaCategoryHomepageConfig, ServiceError> {
+    let featured_entity_ids = config
+        .featured_entity_ids
+        .into_iter()
+        .map(|id| required(id, "featuredEntityIds", 36))
+        .collect::<Result<Vec<_>, _>>()?;
+    if featured_entity_ids.len() > MAX_FEATURED_ENTITIES {
+        return Err(ServiceError::bad_request(&format!(
+            "featuredEntityIds must have at most {MAX_FEATURED_ENTITIES} ids"
+        )));
+    }
+    if featured_entity_ids.iter().collect::<HashSet<_>>().len() != featured_entity_ids.len() {
+        return Err(ServiceError::bad_request(
+            "featuredEntityIds must not repeat an id",
+        ));
+    }
+    if config.sort_order == TanahpediaHomepageSortOrder::Manual && featured_entity_ids.is_empty() {
+        return Err(ServiceError::bad_request(
+            "MANUAL sortOrder requires featuredEntityIds",
+        ));
+    }
+    if config.sort_order == TanahpediaHomepageSortOrder::Chronological
+        && layout_type != TanahpediaHomepageLayoutType::Timeline
+    {
+        return Err(ServiceError::bad_request(
+            "CHRONOLOGICAL sortOrder is only valid for TIMELINE layouts",
+        ));
+    }
+
+    match (layout_type, &config.map) {
+        (TanahpediaHomepageLayoutType::Map, None) => {
+            return Err(ServiceError::bad_request(
+                "MAP layouts require map settings",
+            ));
+        }
+        (TanahpediaHomepageLayoutType::Map, Some(map)) => {
+            check_range(map.center_latitude, "map.centerLatitude", -90.0, 90.0)?;
+            check_range(map.center_longitude, "map.centerLongitude", -180.0, 180.0)?;
+            check_range(map.zoom, "map.zoom", 1, 19)?;
+        }
+        (_, Some(_)) => {
+            return Err(ServiceError::bad_request(
+                "map settings are only valid for MAP layouts",
+            ));
+        }
+        (_, None) => {}
+    }
+    match (layout_type, &config.grid) {
+        (TanahpediaHomepageLayoutType::Gallery, None) => {
+            return Err(ServiceError::bad_request(
+                "GALLERY layouts require grid settings",
+            ));
+        }
+        (TanahpediaHomepageLayoutType::Gallery, Some(grid)) => {
+            check_range(grid.columns, "grid.columns", 1, 6)?;
+            check_range(grid.page_size, "grid.pageSize", 1, 100)?;
+        }
+        (_, Some(_)) => {
+            return Err(ServiceError::bad_request(
+                "grid settings are only valid for GALLERY layouts",
+            ));
+        }
+        (_, None) => {}
+    }
+
+    Ok(TanahpediaCategoryHomepageConfig {
+        featured_entity_ids,
+        ..config
+    })
+}
+
+/// Summaries of the given entities in the given order, skipping unknown ids.
+pub async fn get_entity_summaries(
+    db: &Database,
+    entity_ids: Vec<String>,
+) -> Result<Vec<TanahpediaEntitySummary>, ServiceError> {
+    if entity_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut rows: HashMap<String, entity::Model> = entity::Entity::find()
+        .filter(entity::Column::Id.is_in(entity_ids.clone()))
+        .all(db.get_connection())
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| (row.id.clone(), row))
+        .collect();
+
+    Ok(entity_ids
+        .iter()
+        .filter_map(|id| rows.remove(id))
+        .map(|e| TanahpediaEntitySummary {
+            entity_id: e.id,
+            entity_type: e.entity_type.into(),
+            display_name: e.name,
+        })
+        .collect())
+}
+
+pub async fn get_category_homepage(
+    db: &Database,
+    entity_type: TanahpediaEntityType,
+) -> Result<Option<TanahpediaCategoryHomepage>, ServiceError> {
+    category_homepage::Entity::find()
+        .filter(category_homepage::Column::EntityType.eq(EntityType::from(entity_type)))
+        .one(db.get_connection())
+        .await
+        .map_err(db_error)?
+        .map(to_dto)
+        .transpose()
+}
+
+/// Creates or replaces the homepage of an entity type. Featured entities must
+/// exist and be of that type.
+pub async fn put_category_homepage(
+    db: &Database,
+    input: PutTanahpediaCategoryHomepageInput,
+) -> Result<TanahpediaCategoryHomepage, ServiceError> {
+    let entity_type = EntityType::from(input.entity_type);
+    let content = optional(input.content, "content", MAX_CONTENT_LEN)?;
+    let config = validate_config(
+        input.layout_type,
+        input.config.map(Into::into).unwrap_or_default(),
+    )?;
+
+    let conn = db.get_connection();
+    let featured_entity_ids = config.featured_entity_ids.clone();
+    if !featured_entity_ids.is_empty() {
+        let found = entity::Entity::find()
+            .filter(entity::Column::Id.is_in(featured_entity_ids.clone()))
+            .filter(entity::Column::EntityType.eq(entity_type))
+            .all(conn)
+            .await
+            .map_err(db_error)?;
+        if found.len() != featured_entity_ids.len() {
+            return Err(ServiceError::bad_request(
+                "featuredEntityIds must reference existing entities of entityType",
+            ));
+        }
+    }
+
+    let config = serde_json::to_value(config)
+        .map_err(|e| ServiceError::internal_server_error(INTERNAL_SERVER_ERROR, Some(e)))?;
+    let now = chrono::Utc::now().naive_utc();
+    let existing = category_homepage::Entity::find()
+        .filter(category_homepage::Column::EntityType.eq(entity_type))
+        .one(conn)
+        .await
+        .map_err(db_error)?;
+
+    let row = match existing {
+        Some(existing) => {
+            let mut active = existing.into_active_model();
+            active.layout_type = Set(input.layout_type.stored_name().to_string());
+            active.config = Set(Some(config));
+            active.content = Set(content);
+            active.updated_at = Set(now);
+            category_homepage::Entity::update(active)
+                .exec(conn)
+                .await
+                .map_err(db_error)?
+        }
+        None => {
+            let model = category_homepage::Model {
+                id: uuid::Uuid::new_v4().to_string(),
+                entity_type,
+                layout_type: input.layout_type.stored_name().to_string(),
+                config: Some(config),
+                content,
+                updated_at: now,
+            };
+            category_homepage::Entity::insert(model.clone().into_active_model())
+                .exec(conn)
+                .await
+                .map_err(db_error)?;
+            model
+        }
+    };
+
+    to_dto(row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::tanahpedia_category_homepage::{
+        TanahpediaCategoryHomepageConfigInput, TanahpediaHomepageGridSettings,
+        TanahpediaHomepageMapSettings,
+    };
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    fn map_settings() -> TanahpediaHomepageMapSettings {
+        TanahpediaHomepageMapSettings {
+            center_latitude: 31.5,
+            center_longitude: 35.0,
+            zoom: 7,
+        }
+    }
+
+    fn homepage_row(
+        layout_type: &str,
+        config: Option<serde_json::Value>,
+    ) -> category_homepage::Model {
+        category_homepage::Model {
+            id: "homepage-1".to_string(),
+            entity_type: EntityType::Place,
+            layout_type: layout_type.to_string(),
+            config,
+            content: Some("<p>מקומות</p>".to_string()),
+            updated_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    fn entity_row(id: &str, name: &str) -> entity::Model {
+        let now = chrono::Utc::now().naive_utc();
+        entity::Model {
+            id: id.to_string(),
+            entity_type: EntityType::Place,
+            name: name.to_string(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn validate_config_enforces_layout_specific_settings() {
+        let map_config = TanahpediaCategoryHomepageConfig {
+            map: Some(map_settings()),
+            ..Default::default()
+        };
+        assert!(validate_config(TanahpediaHomepageLayoutType::Map, map_config.clone()).is_ok());
+        assert!(validate_config(TanahpediaHomepageLayoutType::List, map_config).is_err());
+        assert!(
+            validate_config(
+                TanahpediaHomepageLayoutType::Map,
+                TanahpediaCategoryHomepageConfig::default()
+            )
+            .is_err()
+        );
+        assert!(
+            validate_config(
+                TanahpediaHomepageLayoutType::Gallery,
+                TanahpediaCategoryHomepageConfig {
+                    grid: Some(TanahpediaHomepageGridSettings {
+                        columns: 7,
+                        page_size: 20,
+                    }),
+                    ..Default::default()
+                }
+            )
+            .is_err()
+        );
+        assert!(
+            validate_config(
+                TanahpediaHomepageLayoutType::List,
+                TanahpediaCategoryHomepageConfig {
+                    sort_order: TanahpediaHomepageSortOrder::Chronological,
+                    ..Default::default()
+                }
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn validate_config_trims_and_rejects_repeated_featured_ids() {
+        let config = validate_config(
+            TanahpediaHomepageLayoutType::List,
+            TanahpediaCategoryHomepageConfig {
+                featured_entity_ids: vec![" entity-1 ".to_string()],
+                sort_order: TanahpediaHomepageSortOrder::Manual,
+                ..Default::default()
+            },
+        )
+        .expect("config should validate");
+        assert_eq!(config.featured_entity_ids, vec!["entity-1".to_string()]);
+
+        assert!(
+            validate_config(
+                TanahpediaHomepageLayoutType::List,
+                TanahpediaCategoryHomepageConfig {
+                    featured_entity_ids: vec!["entity-1".to_string(), "entity-1".to_string()],
+                    ..Default::default()
+                }
+            )
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn get_category_homepage_parses_stored_config() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![homepage_row(
+                    "MAP",
+                    Some(serde_json::json!({
+                        "featuredEntityIds": ["entity-1"],
+                        "map": { "centerLatitude": 31.5, "centerLongitude": 35.0, "zoom": 7 }
+                    })),
+                )]])
+                .into_connection(),
+        );
+
+        let homepage = get_category_homepage(&db, TanahpediaEntityType::Place)
+            .await
+            .expect("homepage should load")
+            .expect("homepage should exist");
+
+        assert_eq!(homepage.layout_type, TanahpediaHomepageLayoutType::Map);
+        let config = homepage.config.expect("config should parse");
+        assert_eq!(config.map, Some(map_settings()));
+        assert_eq!(config.sort_order, TanahpediaHomepageSortOrder::Alphabetical);
+    }
+
+    #[tokio::test]
+    async fn get_category_homepage_keeps_null_config() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![homepage_row("MAP", None)]])
+                .into_connection(),
+        );
+
+        let homepage = get_category_homepage(&db, TanahpediaEntityType::Place)
+            .await
+            .expect("homepage should load")
+            .expect("homepage should exist");
+
+        assert!(homepage.config.is_none());
+    }
+
+    #[tokio::test]
+    async fn get_entity_summaries_keeps_requested_order() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![
+                    entity_row("entity-1", "ירושלים"),
+                    entity_row("entity-2", "חברון"),
+                ]])
+                .into_connection(),
+        );
+
+        let summaries = get_entity_summaries(
+            &db,
+            vec![
+                "entity-2".to_string(),
+                "missing".to_string(),
+                "entity-1".to_string(),
+            ],
+        )
+        .await
+        .expect("summaries should load");
+
+        let names: Vec<_> = summaries.iter().map(|s| s.display_name.as_str()).collect();
+        assert_eq!(names, vec!["חברון", "ירושלים"]);
+    }
+
+    #[tokio::test]
+    async fn put_category_homepage_rejects_featured_entities_of_another_type() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<entity::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let result = put_category_homepage(
+            &db,
+            PutTanahpediaCategoryHomepageInput {
+                entity_type: TanahpediaEntityType::Place,
+                layout_type: TanahpediaHomepageLayoutType::List,
+                config: Some(TanahpediaCategoryHomepageConfigInput {
+                    featured_entity_ids: vec!["person-1".to_string()],
+                    sort_order: TanahpediaHomepageSortOrder::Manual,
+                    map: None,
+                    grid: None,
+                }),
+                content: None,
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn put_category_homepage_validates_a_missing_config_as_the_default() {
+        let db =
+            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
+
+        let result = put_category_homepage(
+            &db,
+            PutTanahpediaCategoryHomepageInput {
+                entity_type: TanahpediaEntityType::Place,
+                layout_type: TanahpediaHomepageLayoutType::Map,
+                config: None,
+                content: None,
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+    }
+}
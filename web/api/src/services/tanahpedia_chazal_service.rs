@@ -0,0 +1,1151 @@
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::{
+        perek::number_to_hebrew,
+        tanahpedia_source::{
+            TanahpediaChazalCorpus, TanahpediaChazalParseResult, TanahpediaChazalReference,
+            TanahpediaNonTanahSourceNormalizeResult,
+        },
+    },
+    providers::Database,
+    services::tanahpedia_family_service::db_error,
+};
+use entities::tanahpedia::non_tanah_source;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
+
+/// A tractate, midrash or Rambam hilchot: its canonical names plus the other
+/// spellings and abbreviations editors use for it.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ChazalWork {
+    hebrew: &'static str,
+    english: &'static str,
+    aliases: &'static [&'static str],
+}
+
+struct Tractate {
+    work: ChazalWork,
+    bavli: bool,
+    yerushalmi: bool,
+}
+
+const fn tractate(
+    hebrew: &'static str,
+    english: &'static str,
+    aliases: &'static [&'static str],
+    bavli: bool,
+    yerushalmi: bool,
+) -> Tractate {
+    Tractate {
+        work: ChazalWork {
+            hebrew,
+            english,
+            aliases,
+        },
+        bavli,
+        yerushalmi,
+    }
+}
+
+const fn work(
+    hebrew: &'static str,
+    english: &'static str,
+    aliases: &'static [&'static str],
+) -> ChazalWork {
+    ChazalWork {
+        hebrew,
+        english,
+        aliases,
+    }
+}
+
+/// Every Mishnah tractate, with whether the Bavli and the Yerushalmi have
+/// Gemara on it. Shekalim counts for the Bavli because the printed Shas
+/// carries the Yerushalmi's Shekalim with its own dapim.
+const TRACTATES: &[Tractate] = &[
+    tractate("ברכות", "Berakhot", &["berachot", "brachot"], true, true),
+    tractate("פאה", "Peah", &[], false, true),
+    tractate("דמאי", "Demai", &[], false, true),
+    tractate("כלאים", "Kilayim", &["kilaim"], false, true),
+    tractate("שביעית", "Sheviit", &["shviit"], false, true),
+    tractate("תרומות", "Terumot", &[], false, true),
+    tractate("מעשרות", "Maasrot", &["maaserot"], false, true),
+    tractate("מעשר שני", "Maaser Sheni", &[], false, true),
+    tractate("חלה", "Challah", &["halla", "chala"], false, true),
+    tractate("ערלה", "Orlah", &[], false, true),
+    tractate("ביכורים", "Bikkurim", &["בכורים", "bikurim"], false, true),
+    tractate("שבת", "Shabbat", &["shabbos"], true, true),
+    tractate("עירובין", "Eruvin", &["ערובין"], true, true),
+    tractate("פסחים", "Pesachim", &["psachim"], true, true),
+    tractate("שקלים", "Shekalim", &[], true, true),
+    tractate("יומא", "Yoma", &[], true, true),
+    tractate("סוכה", "Sukkah", &["sukka", "succah"], true, true),
+    tractate("ביצה", "Beitzah", &["beitza", "beitsah"], true, true),
+    tractate(
+        "ראש השנה",
+        "Rosh Hashanah",
+        &["ר\"ה", "rosh hashana"],
+        true,
+        true,
+    ),
+    tractate("תענית", "Taanit", &["taanis"], true, true),
+    tractate("מגילה", "Megillah", &["מגלה", "megila"], true, true),
+    tractate("מועד קטן", "Moed Katan", &["מו\"ק"], true, true),
+    tractate("חגיגה", "Chagigah", &["hagigah"], true, true),
+    tractate("יבמות", "Yevamot", &["yevamos"], true, true),
+    tractate("כתובות", "Ketubot", &["ketubos", "kesubos"], true, true),
+    tractate("נדרים", "Nedarim", &[], true, true),
+    tractate("נזיר", "Nazir", &[], true, true),
+    tractate("סוטה", "Sotah", &["sota"], true, true),
+    tractate("גיטין", "Gittin", &["גטין"], true, true),
+    tractate("קידושין", "Kiddushin", &["קדושין", "kidushin"], true, true),
+    tractate(
+        "בבא קמא",
+        "Bava Kamma",
+        &["ב\"ק", "bava kama", "baba kamma"],
+        true,
+        true,
+    ),
+    tractate(
+        "בבא מציעא",
+        "Bava Metzia",
+        &["ב\"מ", "baba metzia"],
+        true,
+        true,
+    ),
+    tractate(
+        "בבא בתרא",
+        "Bava Batra",
+        &["ב\"ב", "baba batra"],
+        true,
+        true,
+    ),
+    tractate("סנהדרין", "Sanhedrin", &[], true, true),
+    tractate("מכות", "Makkot", &["makot", "makkos"], true, true),
+    tractate("שבועות", "Shevuot", &["shevuos"], true, true),
+    tractate("עדיות", "Eduyot", &["עדויות"], false, false),
+    tractate(
+        "עבודה זרה",
+        "Avodah Zarah",
+        &["ע\"ז", "avoda zara"],
+        true,
+        true,
+    ),
+    tractate(
+        "אבות",
+        "Avot",
+        &["פרקי אבות", "pirkei avot", "avos"],
+        false,
+        false,
+    ),
+    tractate("הוריות", "Horayot", &["horayos"], true, true),
+    tractate("זבחים", "Zevachim", &[], true, false),
+    tractate("מנחות", "Menachot", &["menachos"], true, false),
+    tractate(
+        "חולין",
+        "Chullin",
+        &["חלין", "hullin", "chulin"],
+        true,
+        false,
+    ),
+    tractate("בכורות", "Bekhorot", &["bechorot"], true, false),
+    tractate("ערכין", "Arakhin", &["arachin"], true, false),
+    tractate("תמורה", "Temurah", &[], true, false),
+    tractate("כריתות", "Keritot", &["kerisos"], true, false),
+    tractate("מעילה", "Meilah", &[], true, false),
+    tractate("תמיד", "Tamid", &[], true, false),
+    tractate("מידות", "Middot", &["מדות", "midot"], false, false),
+    tractate("קינים", "Kinnim", &["קנים"], false, false),
+    tractate("כלים", "Kelim", &[], false, false),
+    tractate("אהלות", "Oholot", &["אוהלות", "ohalot"], false, false),
+    tractate("נגעים", "Negaim", &[], false, false),
+    tractate("פרה", "Parah", &[], false, false),
+    tractate("טהרות", "Tahorot", &["טהרות"], false, false),
+    tractate("מקואות", "Mikvaot", &["מקוואות"], false, false),
+    tractate("נדה", "Niddah", &["נידה", "nidah"], true, true),
+    tractate("מכשירין", "Makhshirin", &["machshirin"], false, false),
+    tractate("זבים", "Zavim", &[], false, false),
+    tractate("טבול יום", "Tevul Yom", &[], false, false),
+    tractate("ידים", "Yadayim", &["ידיים"], false, false),
+    tractate("עוקצין", "Oktzin", &["עוקצים", "uktzin"], false, false),
+];
+
+const MIDRASH_RABBAH: &[ChazalWork] = &[
+    work(
+        "בראשית רבה",
+        "Bereshit Rabbah",
+        &["ב\"ר", "בר\"ר", "bereishit rabbah", "genesis rabbah"],
+    ),
+    work(
+        "שמות רבה",
+        "Shemot Rabbah",
+        &["שמו\"ר", "ש\"ר", "exodus rabbah"],
+    ),
+    work(
+        "ויקרא רבה",
+        "Vayikra Rabbah",
+        &["ויק\"ר", "leviticus rabbah"],
+    ),
+    work(
+        "במדבר רבה",
+        "Bamidbar Rabbah",
+        &["במד\"ר", "במ\"ר", "numbers rabbah"],
+    ),
+    work(
+        "דברים רבה",
+        "Devarim Rabbah",
+        &["דב\"ר", "deuteronomy rabbah"],
+    ),
+    work(
+        "שיר השירים רבה",
+        "Shir HaShirim Rabbah",
+        &["שה\"ש רבה", "שהש\"ר", "song of songs rabbah"],
+    ),
+    work("רות רבה", "Ruth Rabbah", &["רו\"ר"]),
+    work(
+        "איכה רבה",
+        "Eichah Rabbah",
+        &["איכ\"ר", "eicha rabbah", "lamentations rabbah"],
+    ),
+    work(
+        "קהלת רבה",
+        "Kohelet Rabbah",
+        &["קה\"ר", "ecclesiastes rabbah"],
+    ),
+    work("אסתר רבה", "Esther Rabbah", &["אסת\"ר"]),
+];
+
+/// The 83 sets of hilchot of the Mishneh Torah. English names follow the
+/// common translation; a hilchot may also be cited by its first word alone
+/// when no other hilchot starts with it (`הלכות מלכים`).
+const RAMBAM_HILCHOT: &[ChazalWork] = &[
+    work("יסודי התורה", "Foundations of the Torah", &[]),
+    work("דעות", "Human Dispositions", &["deot"]),
+    work("תלמוד תורה", "Torah Study", &[]),
+    work(
+        "עבודה זרה",
+        "Foreign Worship",
+        &["עבודת כוכבים", "עבודה זרה וחוקות הגויים"],
+    ),
+    work("תשובה", "Repentance", &["teshuvah"]),
+    work("קריאת שמע", "Reading the Shema", &["ק\"ש"]),
+    work("תפילה וברכת כהנים", "Prayer and the Priestly Blessing", &[]),
+    work(
+        "תפילין ומזוזה וספר תורה",
+        "Tefillin, Mezuzah and the Torah Scroll",
+        &[],
+    ),
+    work("ציצית", "Fringes", &["tzitzit"]),
+    work("ברכות", "Blessings", &[]),
+    work("מילה", "Circumcision", &[]),
+    work("שבת", "Sabbath", &[]),
+    work("עירובין", "Eruvin", &[]),
+    work("שביתת עשור", "Rest on the Tenth of Tishrei", &[]),
+    work("שביתת יום טוב", "Rest on a Holiday", &[]),
+    work("חמץ ומצה", "Leavened and Unleavened Bread", &[]),
+    work("שופר וסוכה ולולב", "Shofar, Sukkah and Lulav", &[]),
+    work("שקלים", "Sheqel Dues", &[]),
+    work("קידוש החודש", "Sanctification of the New Month", &[]),
+    work("תעניות", "Fasts", &[]),
+    work("מגילה וחנוכה", "Scroll of Esther and Hanukkah", &[]),
+    work("אישות", "Marriage", &[]),
+    work("גירושין", "Divorce", &[]),
+    work("יבום וחליצה", "Levirate Marriage and Release", &[]),
+    work("נערה בתולה", "Virgin Maiden", &[]),
+    work("סוטה", "Woman Suspected of Infidelity", &[]),
+    work("איסורי ביאה", "Forbidden Intercourse", &[]),
+    work("מאכלות אסורות", "Forbidden Foods", &[]),
+    work("שחיטה", "Ritual Slaughter", &[]),
+    work("שבועות", "Oaths", &[]),
+    work("נדרים", "Vows", &[]),
+    work("נזירות", "Nazariteship", &[]),
+    work("ערכים וחרמים", "Appraisals and Devoted Property", &[]),
+    work("כלאים", "Diverse Species", &[]),
+    work("מתנות עניים", "Gifts to the Poor", &[]),
+    work("תרומות", "Heave Offerings", &[]),
+    work("מעשר", "Tithes", &["מעשרות"]),
+    work(
+        "מעשר שני ונטע רבעי",
+        "Second Tithes and Fourth Year's Fruit",
+        &[],
+    ),
+    work("ביכורים", "First Fruits", &["בכורים"]),
+    work(
+        "שמיטה ויובל",
+        "Sabbatical Year and the Jubilee",
+        &["שמיטה ויובל"],
+    ),
+    work("בית הבחירה", "The Chosen Temple", &[]),
+    work("כלי המקדש", "Vessels of the Sanctuary", &[]),
+    work("ביאת המקדש", "Admittance into the Sanctuary", &[]),
+    work(
+        "איסורי המזבח",
+        "Things Forbidden on the Altar",
+        &["איסורי מזבח"],
+    ),
+    work("מעשה הקרבנות", "Sacrificial Procedure", &[]),
+    work(
+        "תמידין ומוספין",
+        "Daily Offerings and Additional Offerings",
+        &[],
+    ),
+    work("פסולי המוקדשין", "Sacrifices Rendered Unfit", &[]),
+    work(
+        "עבודת יום הכפורים",
+        "Service on the Day of Atonement",
+        &["עבודת יוה\"כ"],
+    ),
+    work("מעילה", "Trespass", &[]),
+    work("קרבן פסח", "Paschal Offering", &[]),
+    work("חגיגה", "Festival Offering", &[]),
+    work("בכורות", "Firstlings", &[]),
+    work("שגגות", "Offerings for Unintentional Transgressions", &[]),
+    work(
+        "מחוסרי כפרה",
+        "Offerings for Those with Incomplete Atonement",
+        &[],
+    ),
+    work("תמורה", "Substitution", &[]),
+    work("טומאת מת", "Defilement by a Corpse", &[]),
+    work("פרה אדומה", "Red Heifer", &[]),
+    work("טומאת צרעת", "Defilement by Leprosy", &[]),
+    work("מטמאי משכב ומושב", "Those Who Defile Bed or Seat", &[]),
+    work("שאר אבות הטומאות", "Other Sources of Defilement", &[]),
+    work("טומאת אוכלין", "Defilement of Foods", &[]),
+    work("כלים", "Vessels", &[]),
+    work("מקואות", "Immersion Pools", &["מקוואות"]),
+    work("נזקי ממון", "Damages to Property", &[]),
+    work("גניבה", "Theft", &[]),
+    work("גזילה ואבידה", "Robbery and Lost Property", &[]),
+    work("חובל ומזיק", "One Who Injures a Person or Property", &[]),
+    work(
+        "רוצח ושמירת נפש",
+        "Murderer and the Preservation of Life",
+        &[],
+    ),
+    work("מכירה", "Sales", &[]),
+    work(
+        "זכייה ומתנה",
+        "Ownerless Property and Gifts",
+        &["זכיה ומתנה"],
+    ),
+    work("שכנים", "Neighbors", &[]),
+    work("שלוחין ושותפין", "Agents and Partners", &[]),
+    work("עבדים", "Slaves", &[]),
+    work("שכירות", "Hiring", &[]),
+    work("שאלה ופקדון", "Borrowing and Deposit", &[]),
+    work("מלוה ולוה", "Creditor and Debtor", &[]),
+    work("טוען ונטען", "Plaintiff and Defendant", &[]),
+    work("נחלות", "Inheritances", &[]),
+    work(
+        "סנהדרין",
+        "The Sanhedrin",
+        &["סנהדרין והעונשין המסורין להם"],
+    ),
+    work("עדות", "Testimony", &[]),
+    work("ממרים", "Rebels", &[]),
+    work("אבל", "Mourning", &["אבלות"]),
+    work("מלכים ומלחמות", "Kings and Wars", &[]),
+];
+
+/// A non-Tanah citation recognized by [`parse_reference`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ChazalReference {
+    pub(crate) corpus: TanahpediaChazalCorpus,
+    pub(crate) work: &'static ChazalWork,
+    pub(crate) section: i32,
+    pub(crate) subsection: Option<i32>,
+}
+
+pub(crate) fn corpus_as_str(corpus: TanahpediaChazalCorpus) -> &'static str {
+    match corpus {
+        TanahpediaChazalCorpus::Bavli => "BAVLI",
+        TanahpediaChazalCorpus::Yerushalmi => "YERUSHALMI",
+        TanahpediaChazalCorpus::Mishnah => "MISHNAH",
+        TanahpediaChazalCorpus::MidrashRabbah => "MIDRASH_RABBAH",
+        TanahpediaChazalCorpus::Rambam => "RAMBAM",
+    }
+}
+
+fn corpus_from_str(value: &str) -> Option<TanahpediaChazalCorpus> {
+    match value {
+        "BAVLI" => Some(TanahpediaChazalCorpus::Bavli),
+        "YERUSHALMI" => Some(TanahpediaChazalCorpus::Yerushalmi),
+        "MISHNAH" => Some(TanahpediaChazalCorpus::Mishnah),
+        "MIDRASH_RABBAH" => Some(TanahpediaChazalCorpus::MidrashRabbah),
+        "RAMBAM" => Some(TanahpediaChazalCorpus::Rambam),
+        _ => None,
+    }
+}
+
+fn corpus_works(corpus: TanahpediaChazalCorpus) -> Box<dyn Iterator<Item = &'static ChazalWork>> {
+    match corpus {
+        TanahpediaChazalCorpus::Bavli
+        | TanahpediaChazalCorpus::Yerushalmi
+        | TanahpediaChazalCorpus::Mishnah => Box::new(TRACTATES.iter().map(|t| &t.work)),
+        TanahpediaChazalCorpus::MidrashRabbah => Box::new(MIDRASH_RABBAH.iter()),
+        TanahpediaChazalCorpus::Rambam => Box::new(RAMBAM_HILCHOT.iter()),
+    }
+}
+
+impl ChazalReference {
+    /// Rebuilds a reference from the `ref_*` columns of a
+    /// `tanahpedia_non_tanah_source` row; `None` when the row wasn't
+    /// recognized or names a work this build doesn't know.
+    pub(crate) fn from_columns(
+        corpus: Option<&str>,
+        work: Option<&str>,
+        section: Option<i32>,
+        subsection: Option<i32>,
+    ) -> Option<Self> {
+        let corpus = corpus_from_str(corpus?)?;
+        let work = work?;
+        Some(Self {
+            corpus,
+            work: corpus_works(corpus).find(|candidate| candidate.english == work)?,
+            section: section?,
+            subsection,
+        })
+    }
+
+    fn hebrew_locator(&self) -> String {
+        match (self.corpus, self.subsection) {
+            (TanahpediaChazalCorpus::Bavli | TanahpediaChazalCorpus::Yerushalmi, Some(amud)) => {
+                format!(
+                    "{} {}",
+                    number_to_hebrew(self.section),
+                    if amud == 1 { "ע\"א" } else { "ע\"ב" }
+                )
+            }
+            (_, Some(subsection)) => format!(
+                "{}, {}",
+                number_to_hebrew(self.section),
+                number_to_hebrew(subsection)
+            ),
+            (_, None) => number_to_hebrew(self.section),
+        }
+    }
+
+    fn english_locator(&self) -> String {
+        match (self.corpus, self.subsection) {
+            (TanahpediaChazalCorpus::Bavli | TanahpediaChazalCorpus::Yerushalmi, Some(amud)) => {
+                format!("{}{}", self.section, if amud == 1 { "a" } else { "b" })
+            }
+            (_, Some(subsection)) => format!("{}:{}", self.section, subsection),
+            (_, None) => self.section.to_string(),
+        }
+    }
+
+    pub(crate) fn hebrew_citation(&self) -> String {
+        let prefix = match self.corpus {
+            TanahpediaChazalCorpus::Bavli => "בבלי ",
+            TanahpediaChazalCorpus::Yerushalmi => "ירושלמי ",
+            TanahpediaChazalCorpus::Mishnah => "משנה ",
+            TanahpediaChazalCorpus::MidrashRabbah => "",
+            TanahpediaChazalCorpus::Rambam => "רמב\"ם הלכות ",
+        };
+        format!("{prefix}{} {}", self.work.hebrew, self.hebrew_locator())
+    }
+
+    pub(crate) fn english_citation(&self) -> String {
+        let prefix = match self.corpus {
+            TanahpediaChazalCorpus::Bavli => "Bavli ",
+            TanahpediaChazalCorpus::Yerushalmi => "Yerushalmi ",
+            TanahpediaChazalCorpus::Mishnah => "Mishnah ",
+            TanahpediaChazalCorpus::MidrashRabbah => "",
+            TanahpediaChazalCorpus::Rambam => "Mishneh Torah, ",
+        };
+        format!("{prefix}{} {}", self.work.english, self.english_locator())
+    }
+}
+
+impl From<&ChazalReference> for TanahpediaChazalReference {
+    fn from(reference: &ChazalReference) -> Self {
+        Self {
+            corpus: reference.corpus,
+            work: reference.work.english.to_string(),
+            work_hebrew: reference.work.hebrew.to_string(),
+            section: reference.section,
+            subsection: reference.subsection,
+            hebrew_citation: reference.hebrew_citation(),
+            english_citation: reference.english_citation(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Number(i32),
+    /// An explicit amud: `ע"ב`, `98b`.
+    Amud(i32),
+    /// A `.` or `:` right after a word, which after a daf means amud a or b.
+    Mark(i32),
+}
+
+/// Strips niqqud and unifies the quote marks and separators editors type, so
+/// `ע״ב`, `ע"ב` and `ע”ב` all read the same.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| match c {
+            '\u{05BE}' | ',' | ';' | '(' | ')' | '[' | ']' | '-' | '–' | '—' => Some(' '),
+            '\u{0591}'..='\u{05C7}' => None,
+            '\u{05F4}' | '“' | '”' | '„' => Some('"'),
+            '\u{05F3}' | '‘' | '’' | '`' => Some('\''),
+            c => Some(c),
+        })
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    for chunk in normalize(text).split_whitespace() {
+        let (body, mark) = match chunk.strip_suffix('.') {
+            Some(body) => (body, Some(1)),
+            None => match chunk.strip_suffix(':') {
+                Some(body) => (body, Some(2)),
+                None => (chunk, None),
+            },
+        };
+        for part in body.split(':').filter(|part| !part.is_empty()) {
+            if let Ok(number) = part.parse::<i32>() {
+                tokens.push(Token::Number(number));
+            } else if let Some((digits, amud)) = part
+                .strip_suffix('a')
+                .map(|digits| (digits, 1))
+                .or_else(|| part.strip_suffix('b').map(|digits| (digits, 2)))
+                && let Ok(number) = digits.parse::<i32>()
+            {
+                tokens.push(Token::Number(number));
+                tokens.push(Token::Amud(amud));
+            } else {
+                match part {
+                    "ע\"א" => tokens.push(Token::Amud(1)),
+                    "ע\"ב" => tokens.push(Token::Amud(2)),
+                    _ => tokens.push(Token::Word(part.to_string())),
+                }
+            }
+        }
+        if let Some(mark) = mark
+            && !body.is_empty()
+        {
+            tokens.push(Token::Mark(mark));
+        }
+    }
+    tokens
+}
+
+fn letter_value(c: char) -> Option<i32> {
+    Some(match c {
+        'א' => 1,
+        'ב' => 2,
+        'ג' => 3,
+        'ד' => 4,
+        'ה' => 5,
+        'ו' => 6,
+        'ז' => 7,
+        'ח' => 8,
+        'ט' => 9,
+        'י' => 10,
+        'כ' | 'ך' => 20,
+        'ל' => 30,
+        'מ' | 'ם' => 40,
+        'נ' | 'ן' => 50,
+        'ס' => 60,
+        'ע' => 70,
+        'פ' | 'ף' => 80,
+        'צ' | 'ץ' => 90,
+        'ק' => 100,
+        'ר' => 200,
+        'ש' => 300,
+        'ת' => 400,
+        _ => return None,
+    })
+}
+
+/// Reads a Hebrew numeral (`צח`, `צ"ח`, `ג'`, `ט"ו`). Letters must not rise
+/// in value, which keeps ordinary words like `דף` from reading as numbers.
+fn hebrew_numeral(word: &str) -> Option<i32> {
+    let letters = word.trim_end_matches('\'').replace('"', "");
+    if letters.is_empty() || letters.chars().count() > 5 {
+        return None;
+    }
+    let mut total = 0;
+    let mut previous = i32::MAX;
+    for c in letters.chars() {
+        let value = letter_value(c)?;
+        if value > previous {
+            return None;
+        }
+        previous = value;
+        total += value;
+    }
+    Some(total)
+}
+
+/// Words that only say what kind of number follows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Marker {
+    Daf,
+    Amud,
+    Chapter,
+    Subsection,
+}
+
+fn marker(word: &str) -> Option<Marker> {
+    match word {
+        "דף" | "ד'" | "daf" => Some(Marker::Daf),
+        "עמוד" | "עמ'" | "amud" => Some(Marker::Amud),
+        "פרק" | "פ'" | "פרשה" | "פר'" | "perek" | "chapter" | "ch" | "parashah" | "parasha" => {
+            Some(Marker::Chapter)
+        }
+        "משנה" | "מ'" | "הלכה" | "הל'" | "ה'" | "סימן" | "סי'" | "ס'" | "אות" | "mishnah"
+        | "halakhah" | "halacha" | "siman" => Some(Marker::Subsection),
+        _ => None,
+    }
+}
+
+/// What the words before the work say about the corpus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Hint {
+    Bavli,
+    Yerushalmi,
+    Mishnah,
+    Rambam,
+}
+
+fn words_at(tokens: &[Token], pos: usize, count: usize) -> Option<Vec<&str>> {
+    tokens
+        .get(pos..pos + count)?
+        .iter()
+        .map(|token| match token {
+            Token::Word(word) => Some(word.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The longest name of any of `works` at `pos`, with the number of tokens it
+/// spans. Rambam hilchot also match by a first word no other hilchot shares.
+fn match_work<'a>(
+    tokens: &[Token],
+    pos: usize,
+    works: impl Iterator<Item = &'a ChazalWork> + Clone,
+    allow_first_word: bool,
+) -> Option<(&'a ChazalWork, usize)> {
+    let mut best: Option<(&ChazalWork, usize)> = None;
+    for candidate in works.clone() {
+        let names = [candidate.hebrew, candidate.english]
+            .into_iter()
+            .chain(candidate.aliases.iter().copied());
+        for name in names {
+            let name = normalize(name);
+            let words: Vec<&str> = name.split_whitespace().collect();
+            if words_at(tokens, pos, words.len()).is_some_and(|found| found == words)
+                && best.is_none_or(|(_, len)| words.len() > len)
+            {
+                best = Some((candidate, words.len()));
+            }
+        }
+    }
+    if best.is_none() && allow_first_word {
+        let first = words_at(tokens, pos, 1)?[0];
+        let mut matches = works.filter(|candidate| {
+            candidate.hebrew.split_whitespace().next() == Some(first)
+                || normalize(candidate.english).split_whitespace().next() == Some(first)
+        });
+        if let (Some(only), None) = (matches.next(), matches.next()) {
+            best = Some((only, 1));
+        }
+    }
+    best
+}
+
+/// The numbers after the work, with whatever the surrounding words and marks
+/// said about them.
+#[derive(Debug, Default)]
+struct Locator {
+    numbers: Vec<i32>,
+    amud: Option<i32>,
+    /// A `.`/`:` right after the first number.
+    mark: Option<i32>,
+    daf_marker: bool,
+    chapter_marker: bool,
+}
+
+fn read_locator(tokens: &[Token]) -> Result<Locator, String> {
+    let mut locator = Locator::default();
+    let mut expect_amud = false;
+    let mut previous_was_first_number = false;
+    for token in tokens {
+        let number = match token {
+            Token::Number(number) => Some(*number),
+            Token::Word(word) => match marker(word) {
+                Some(Marker::Daf) => {
+                    locator.daf_marker = true;
+                    None
+                }
+                Some(Marker::Amud) => {
+                    expect_amud = true;
+                    None
+                }
+                Some(Marker::Chapter | Marker::Subsection) => {
+                    locator.chapter_marker = true;
+                    None
+                }
+                None => Some(hebrew_numeral(word).ok_or_else(|| format!("unexpected \"{word}\""))?),
+            },
+            Token::Amud(amud) => {
+                if locator.amud.replace(*amud).is_some() {
+                    return Err("amud given twice".to_string());
+                }
+                None
+            }
+            Token::Mark(mark) => {
+                if previous_was_first_number {
+                    locator.mark = Some(*mark);
+                }
+                None
+            }
+        };
+        previous_was_first_number = false;
+        if let Some(number) = number {
+            if expect_amud {
+                if !(1..=2).contains(&number) || locator.amud.replace(number).is_some() {
+                    return Err("amud must be א or ב".to_string());
+                }
+                expect_amud = false;
+            } else {
+                locator.numbers.push(number);
+                previous_was_first_number = locator.numbers.len() == 1;
+            }
+        }
+    }
+    if expect_amud {
+        return Err("missing amud".to_string());
+    }
+    Ok(locator)
+}
+
+/// Section and optional subsection for corpora cited as `perek, mishnah` and
+/// the like; a stray `.`/`:` is just punctuation there.
+fn chapter_and_subsection(
+    locator: &Locator,
+    corpus: TanahpediaChazalCorpus,
+) -> Result<(i32, Option<i32>), String> {
+    if locator.amud.is_some() || locator.daf_marker {
+        return Err(format!(
+            "{} is not cited by daf and amud",
+            corpus_as_str(corpus)
+        ));
+    }
+    match locator.numbers[..] {
+        [section] => Ok((section, None)),
+        [section, subsection] => Ok((section, Some(subsection))),
+        [] => Err("missing perek or parasha".to_string()),
+        _ => Err("too many numbers".to_string()),
+    }
+}
+
+fn daf_and_amud(locator: &Locator) -> Result<(i32, Option<i32>), String> {
+    match locator.numbers[..] {
+        [daf] => Ok((daf, locator.amud.or(locator.mark))),
+        [] => Err("missing daf".to_string()),
+        _ => Err("expected a daf and amud, not perek and mishnah".to_string()),
+    }
+}
+
+/// Parses a free-text non-Tanah citation into a structured reference, or
+/// explains what it could not read. Recognizes the Bavli and Yerushalmi by
+/// daf and amud, the Mishnah by perek and mishnah, Midrash Rabbah by parasha
+/// and siman, and the Rambam by hilchot, perek and halacha, in Hebrew or
+/// English (`סנהדרין צח ע"ב`, `Sanhedrin 98b`, `בראשית רבה פרשה מד`).
+pub(crate) fn parse_reference(text: &str) -> Result<ChazalReference, String> {
+    let tokens = tokenize(text);
+    let mut pos = 0;
+    let mut hint = None;
+    while let Some(Token::Word(word)) = tokens.get(pos) {
+        let next = match tokens.get(pos + 1) {
+            Some(Token::Word(next)) => next.as_str(),
+            _ => "",
+        };
+        match (word.as_str(), next) {
+            ("משנה", "תורה") | ("mishneh", "torah") => {
+                hint = Some(Hint::Rambam);
+                pos += 1;
+            }
+            ("רמב\"ם" | "rambam" | "maimonides", _) => hint = Some(Hint::Rambam),
+            ("הלכות" | "הל'" | "hilchot" | "hilkhot", _) => {
+                if hint.is_some_and(|hint| hint != Hint::Rambam) {
+                    return Err("hilchot are only cited from the Rambam".to_string());
+                }
+                hint = Some(Hint::Rambam);
+            }
+            ("בבלי" | "bavli" | "babylonian", _) => hint = Some(Hint::Bavli),
+            ("ירושלמי" | "ירוש'" | "yerushalmi" | "jerusalem", _) => {
+                hint = Some(Hint::Yerushalmi)
+            }
+            ("משנה" | "mishnah" | "mishna", _) => hint = Some(Hint::Mishnah),
+            (
+                "תלמוד" | "גמרא" | "גמ'" | "talmud" | "מסכת" | "מס'" | "masechet" | "tractate"
+                | "מדרש" | "midrash" | "laws" | "of",
+                _,
+            ) => {}
+            _ => break,
+        }
+        pos += 1;
+    }
+    if pos == tokens.len() {
+        return Err("missing the tractate, midrash or hilchot".to_string());
+    }
+    let work_name = match &tokens[pos] {
+        Token::Word(word) => word.clone(),
+        _ => String::new(),
+    };
+
+    if hint == Some(Hint::Rambam) {
+        let (work, len) = match_work(&tokens, pos, RAMBAM_HILCHOT.iter(), true)
+            .ok_or_else(|| format!("unknown Rambam hilchot \"{work_name}\""))?;
+        let locator = read_locator(&tokens[pos + len..])?;
+        let (section, subsection) =
+            chapter_and_subsection(&locator, TanahpediaChazalCorpus::Rambam)?;
+        return validate(ChazalReference {
+            corpus: TanahpediaChazalCorpus::Rambam,
+            work,
+            section,
+            subsection,
+        });
+    }
+
+    if hint.is_none()
+        && let Some((work, len)) = match_work(&tokens, pos, MIDRASH_RABBAH.iter(), false)
+    {
+        let locator = read_locator(&tokens[pos + len..])?;
+        let (section, subsection) =
+            chapter_and_subsection(&locator, TanahpediaChazalCorpus::MidrashRabbah)?;
+        return validate(ChazalReference {
+            corpus: TanahpediaChazalCorpus::MidrashRabbah,
+            work,
+            section,
+            subsection,
+        });
+    }
+
+    let Some((tractate, len)) = TRACTATES
+        .iter()
+        .filter_map(|tractate| {
+            match_work(&tokens, pos, std::iter::once(&tractate.work), false)
+                .map(|(_, len)| (tractate, len))
+        })
+        .max_by_key(|(_, len)| *len)
+    else {
+        return Err(format!("unknown tractate or midrash \"{work_name}\""));
+    };
+    let locator = read_locator(&tokens[pos + len..])?;
+    let corpus = match hint {
+        Some(Hint::Bavli) => TanahpediaChazalCorpus::Bavli,
+        Some(Hint::Yerushalmi) => TanahpediaChazalCorpus::Yerushalmi,
+        Some(Hint::Mishnah) => TanahpediaChazalCorpus::Mishnah,
+        Some(Hint::Rambam) => unreachable!("handled above"),
+        None if locator.amud.is_some() || locator.daf_marker || locator.mark == Some(2) => {
+            TanahpediaChazalCorpus::Bavli
+        }
+        None if locator.numbers.len() <= 1 && tractate.bavli && !locator.chapter_marker => {
+            TanahpediaChazalCorpus::Bavli
+        }
+        None => TanahpediaChazalCorpus::Mishnah,
+    };
+    let (section, subsection) = match corpus {
+        TanahpediaChazalCorpus::Bavli => {
+            if !tractate.bavli {
+                return Err(format!(
+                    "{} has no Bavli; cite its perek and mishnah",
+                    tractate.work.english
+                ));
+            }
+            daf_and_amud(&locator)?
+        }
+        TanahpediaChazalCorpus::Yerushalmi => {
+            if !tractate.yerushalmi {
+                return Err(format!("{} has no Yerushalmi", tractate.work.english));
+            }
+            daf_and_amud(&locator)?
+        }
+        corpus => chapter_and_subsection(&locator, corpus)?,
+    };
+    validate(ChazalReference {
+        corpus,
+        work: &tractate.work,
+        section,
+        subsection,
+    })
+}
+
+fn validate(reference: ChazalReference) -> Result<ChazalReference, String> {
+    let talmud = matches!(
+        reference.corpus,
+        TanahpediaChazalCorpus::Bavli | TanahpediaChazalCorpus::Yerushalmi
+    );
+    if talmud && reference.section < 2 {
+        return Err("dapim start at 2".to_string());
+    }
+    if !(1..=999).contains(&reference.section) {
+        return Err(format!("{} is out of range", reference.section));
+    }
+    if let Some(subsection) = reference.subsection
+        && (subsection < 1 || (talmud && subsection > 2) || subsection > 999)
+    {
+        return Err(format!("{subsection} is out of range"));
+    }
+    Ok(reference)
+}
+
+/// Previews how `text` would be stored, for the editor's citation field.
+pub fn parse_non_tanah_source(text: String) -> TanahpediaChazalParseResult {
+    let (reference, error) = match parse_reference(&text) {
+        Ok(reference) => (Some((&reference).into()), None),
+        Err(error) => (None, Some(error)),
+    };
+    TanahpediaChazalParseResult {
+        source_text: text,
+        reference,
+        error,
+    }
+}
+
+/// The `ref_*` column values for `text`: all null when it isn't recognized.
+pub(crate) fn reference_columns(
+    text: &str,
+) -> (Option<String>, Option<String>, Option<i32>, Option<i32>) {
+    match parse_reference(text) {
+        Ok(reference) => (
+            Some(corpus_as_str(reference.corpus).to_string()),
+            Some(reference.work.english.to_string()),
+            Some(reference.section),
+            reference.subsection,
+        ),
+        Err(_) => (None, None, None, None),
+    }
+}
+
+/// Re-parses every stored non-Tanah source and updates its reference
+/// columns, e.g. after the parser learns a new format or for rows written
+/// before references were stored.
+pub async fn normalize_non_tanah_sources(
+    db: &Database,
+) -> Result<TanahpediaNonTanahSourceNormalizeResult, ServiceError> {
+    tracing::info_span!("tanahpedia_chazal_service::normalize_non_tanah_sources");
+    let conn = db.get_connection();
+    let rows = non_tanah_source::Entity::find()
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+
+    let mut result = TanahpediaNonTanahSourceNormalizeResult {
+        recognized: 0,
+        unrecognized: 0,
+        updated: 0,
+    };
+    let transaction = conn.begin().await.map_err(db_error)?;
+    for row in rows {
+        let columns = reference_columns(&row.source_text);
+        if columns.0.is_some() {
+            result.recognized += 1;
+        } else {
+            result.unrecognized += 1;
+        }
+        if columns
+            == (
+                row.ref_corpus,
+                row.ref_work,
+                row.ref_section,
+                row.ref_subsection,
+            )
+        {
+            continue;
+        }
+        let (corpus, work, section, subsection) = columns;
+        non_tanah_source::Entity::update_many()
+            .col_expr(non_tanah_source::Column::RefCorpus, Expr::value(corpus))
+            .col_expr(non_tanah_source::Column::RefWork, Expr::value(work))
+            .col_expr(non_tanah_source::Column::RefSection, Expr::value(section))
+            .col_expr(
+                non_tanah_source::Column::RefSubsection,
+                Expr::value(subsection),
+            )
+            .filter(non_tanah_source::Column::Id.eq(row.id))
+            .exec(&transaction)
+            .await
+            .map_err(db_error)?;
+        result.updated += 1;
+    }
+    transaction.commit().await.map_err(db_error)?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    fn citations(text: &str) -> (String, String) {
+        let reference = parse_reference(text).unwrap_or_else(|e| panic!("{text}: {e}"));
+        (reference.hebrew_citation(), reference.english_citation())
+    }
+
+    #[test]
+    fn hebrew_numeral_reads_gematria_and_rejects_words() {
+        assert_eq!(hebrew_numeral("צח"), Some(98));
+        assert_eq!(hebrew_numeral("צ\"ח"), Some(98));
+        assert_eq!(hebrew_numeral("ג'"), Some(3));
+        assert_eq!(hebrew_numeral("ט\"ו"), Some(15));
+        assert_eq!(hebrew_numeral("קכג"), Some(123));
+        assert_eq!(hebrew_numeral("דף"), None);
+        assert_eq!(hebrew_numeral("פרק"), None);
+    }
+
+    #[test]
+    fn parses_bavli_with_daf_and_amud() {
+        assert_eq!(
+            citations("סנהדרין צח ע\"ב"),
+            (
+                "בבלי סנהדרין צ\"ח ע\"ב".to_string(),
+                "Bavli Sanhedrin 98b".to_string()
+            )
+        );
+        assert_eq!(citations("בבא מציעא נט:").1, "Bavli Bava Metzia 59b");
+        assert_eq!(citations("מסכת ב״ק דף ב׳ עמוד א").1, "Bavli Bava Kamma 2a");
+        assert_eq!(citations("Sanhedrin 98b").0, "בבלי סנהדרין צ\"ח ע\"ב");
+        assert_eq!(citations("חולין נט").1, "Bavli Chullin 59");
+    }
+
+    #[test]
+    fn parses_yerushalmi_only_when_named() {
+        assert_eq!(
+            citations("ירושלמי ברכות ב ע\"א"),
+            (
+                "ירושלמי ברכות ב' ע\"א".to_string(),
+                "Yerushalmi Berakhot 2a".to_string()
+            )
+        );
+        assert!(parse_reference("ירושלמי חולין ב ע\"א").is_err());
+    }
+
+    #[test]
+    fn parses_mishnah_by_perek_and_mishnah() {
+        assert_eq!(
+            citations("אבות ג, א"),
+            (
+                "משנה אבות ג', א'".to_string(),
+                "Mishnah Avot 3:1".to_string()
+            )
+        );
+        assert_eq!(citations("משנה ברכות א:א").1, "Mishnah Berakhot 1:1");
+        assert_eq!(
+            citations("סנהדרין פרק י משנה א").1,
+            "Mishnah Sanhedrin 10:1"
+        );
+        assert!(parse_reference("אבות ג ע\"א").is_err());
+    }
+
+    #[test]
+    fn parses_midrash_rabbah_by_parasha() {
+        assert_eq!(
+            citations("בראשית רבה פרשה מד"),
+            (
+                "בראשית רבה מ\"ד".to_string(),
+                "Bereshit Rabbah 44".to_string()
+            )
+        );
+        assert_eq!(citations("ב\"ר מד, ג").1, "Bereshit Rabbah 44:3");
+        assert_eq!(citations("מדרש שמות רבה א.").1, "Shemot Rabbah 1");
+    }
+
+    #[test]
+    fn parses_rambam_hilchot_by_full_or_first_word() {
+        assert_eq!(
+            citations("רמב\"ם הלכות מלכים ומלחמות פרק יא הלכה ד"),
+            (
+                "רמב\"ם הלכות מלכים ומלחמות י\"א, ד'".to_string(),
+                "Mishneh Torah, Kings and Wars 11:4".to_string()
+            )
+        );
+        assert_eq!(
+            citations("רמב״ם הל׳ מלכים יא:ד").1,
+            "Mishneh Torah, Kings and Wars 11:4"
+        );
+        assert_eq!(
+            citations("משנה תורה, הלכות תשובה ג").1,
+            "Mishneh Torah, Repentance 3"
+        );
+        assert!(parse_reference("רמב\"ם הלכות טומאת ג").is_err());
+    }
+
+    #[test]
+    fn reports_why_a_citation_is_unrecognized() {
+        assert_eq!(
+            parse_reference("זוהר חלק א").unwrap_err(),
+            "unknown tractate or midrash \"זוהר\""
+        );
+        assert_eq!(
+            parse_reference("אבות ג:").unwrap_err(),
+            "Avot has no Bavli; cite its perek and mishnah"
+        );
+        assert_eq!(parse_reference("סנהדרין").unwrap_err(), "missing daf");
+        assert!(parse_reference("").is_err());
+    }
+
+    #[test]
+    fn from_columns_round_trips_reference_columns() {
+        let (corpus, work, section, subsection) = reference_columns("ב\"מ נט ע\"ב");
+        let reference =
+            ChazalReference::from_columns(corpus.as_deref(), work.as_deref(), section, subsection)
+                .expect("stored columns should rebuild the reference");
+
+        assert_eq!(reference, parse_reference("בבא מציעא נט:").unwrap());
+        assert_eq!(
+            ChazalReference::from_columns(Some("BAVLI"), Some("Unknown"), Some(2), None),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn normalize_non_tanah_sources_updates_only_changed_rows() {
+        let row = |id: &str, text: &str, corpus: Option<&str>| non_tanah_source::Model {
+            id: id.to_string(),
+            source_group_id: "group-1".to_string(),
+            source_text: text.to_string(),
+            ref_corpus: corpus.map(str::to_string),
+            ref_work: corpus.map(|_| "Sanhedrin".to_string()),
+            ref_section: corpus.map(|_| 98),
+            ref_subsection: corpus.map(|_| 2),
+        };
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![
+                    row("s-1", "סנהדרין צח ע\"ב", Some("BAVLI")),
+                    row("s-2", "אבות ג, א", None),
+                    row("s-3", "זוהר חלק א", None),
+                ]])
+                .append_exec_results([MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                }])
+                .into_connection(),
+        );
+
+        let result = normalize_non_tanah_sources(&db)
+            .await
+            .expect("sources should be normalized");
+
+        assert_eq!(
+            result,
+            TanahpediaNonTanahSourceNormalizeResult {
+                recognized: 2,
+                unrecognized: 1,
+                updated: 1,
+            }
+        );
+    }
+}
@@ -0,0 +1,570 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::tanahpedia_creation_day::{
+        PutTanahpediaCreationDayInput, TanahpediaCreationDay, TanahpediaCreationDayItem,
+        TanahpediaCreationDayOpinions, TanahpediaCreationDayWriteResult,
+        TanahpediaCreationItemType,
+    },
+    providers::Database,
+    services::{
+        tanahpedia_family_service::{db_error, entity_display_names, optional, required},
+        tanahpedia_opinions_service::{OpinionCatalog, alt_group_ids},
+        tanahpedia_sources_service::{SourcesInput, delete_sources, load_sources, replace_sources},
+    },
+};
+use entities::tanahpedia::{
+    astronomical_object, astronomical_object_creation_day, plant, plant_creation_day,
+};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    TransactionTrait,
+};
+
+/// The days of creation, Bereshit 1; the seventh is the Shabbat.
+const CREATION_DAYS: std::ops::RangeInclusive<i32> = 1..=6;
+
+/// A creation-day row of either table, before names and sources are attached.
+struct CreationDayRow {
+    id: String,
+    item_id: String,
+    creation_day: i32,
+    alt_group_id: Option<String>,
+}
+
+impl From<plant_creation_day::Model> for CreationDayRow {
+    fn from(row: plant_creation_day::Model) -> Self {
+        Self {
+            id: row.id,
+            item_id: row.plant_id,
+            creation_day: row.creation_day.into(),
+            alt_group_id: row.alt_group_id,
+        }
+    }
+}
+
+impl From<astronomical_object_creation_day::Model> for CreationDayRow {
+    fn from(row: astronomical_object_creation_day::Model) -> Self {
+        Self {
+            id: row.id,
+            item_id: row.astronomical_object_id,
+            creation_day: row.creation_day.into(),
+            alt_group_id: row.alt_group_id,
+        }
+    }
+}
+
+/// Attaches display names and sources to the rows of one table.
+async fn creation_day_items(
+    conn: &DatabaseConnection,
+    item_type: TanahpediaCreationItemType,
+    rows: Vec<CreationDayRow>,
+) -> Result<Vec<(i32, TanahpediaCreationDayItem)>, ServiceError> {
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let item_ids: HashSet<String> = rows.iter().map(|row| row.item_id.clone()).collect();
+    let entity_ids: Vec<(String, String)> = match item_type {
+        TanahpediaCreationItemType::Plant => plant::Entity::find()
+            .filter(plant::Column::Id.is_in(item_ids))
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|row| (row.id, row.entity_id))
+            .collect(),
+        TanahpediaCreationItemType::AstronomicalObject => astronomical_object::Entity::find()
+            .filter(astronomical_object::Column::Id.is_in(item_ids))
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|row| (row.id, row.entity_id))
+            .collect(),
+    };
+    let mut sources = load_sources(
+        conn,
+        item_type.source_table(),
+        rows.iter().map(|row| row.id.clone()).collect(),
+    )
+    .await?;
+    let names = entity_display_names(
+        conn,
+        entity_ids
+            .iter()
+            .map(|(_, entity_id)| entity_id.clone())
+            .collect(),
+    )
+    .await?;
+    let entity_ids: HashMap<String, String> = entity_ids.into_iter().collect();
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let entity_id = entity_ids.get(&row.item_id).cloned().unwrap_or_default();
+            (
+                row.creation_day,
+                TanahpediaCreationDayItem {
+                    sources: sources.remove(&row.id).unwrap_or_default(),
+                    display_name: names.get(&entity_id).cloned().unwrap_or_default(),
+                    item_type,
+                    id: row.id,
+                    item_id: row.item_id,
+                    entity_id,
+                    alt_group_id: row.alt_group_id,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Items of one day ordered by name; competing opinions on the same item
+/// stay together.
+fn day_items(
+    items: &[(i32, TanahpediaCreationDayItem)],
+    day: i32,
+) -> Vec<TanahpediaCreationDayItem> {
+    let mut result: Vec<TanahpediaCreationDayItem> = items
+        .iter()
+        .filter(|(creation_day, _)| *creation_day == day)
+        .map(|(_, item)| item.clone())
+        .collect();
+    result.sort_by(|a, b| {
+        a.display_name
+            .cmp(&b.display_name)
+            .then_with(|| a.item_id.cmp(&b.item_id))
+            .then_with(|| a.alt_group_id.cmp(&b.alt_group_id))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    result
+}
+
+/// Days 1–6, each with every plant and astronomical object that any opinion
+/// places on it and that opinion's sources. An item disputed between two days
+/// appears on both, under different `altGroupId`s.
+pub async fn find_creation_days(db: &Database) -> Result<Vec<TanahpediaCreationDay>, ServiceError> {
+    let conn = db.get_connection();
+
+    let plant_rows = plant_creation_day::Entity::find()
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(CreationDayRow::from)
+        .collect();
+    let astronomical_rows = astronomical_object_creation_day::Entity::find()
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(CreationDayRow::from)
+        .collect();
+    let plants = creation_day_items(conn, TanahpediaCreationItemType::Plant, plant_rows).await?;
+    let astronomical_objects = creation_day_items(
+        conn,
+        TanahpediaCreationItemType::AstronomicalObject,
+        astronomical_rows,
+    )
+    .await?;
+
+    Ok(CREATION_DAYS
+        .map(|day| TanahpediaCreationDay {
+            day,
+            plants: day_items(&plants, day),
+            astronomical_objects: day_items(&astronomical_objects, day),
+        })
+        .collect())
+}
+
+/// Groups an already-loaded day by opinion; only the opinion metadata is
+/// queried.
+pub async fn get_creation_day_opinions(
+    db: &Database,
+    day: &TanahpediaCreationDay,
+) -> Result<TanahpediaCreationDayOpinions, ServiceError> {
+    let catalog = OpinionCatalog::load(
+        db.get_connection(),
+        alt_group_ids(
+            day.plants
+                .iter()
+                .map(|row| &row.alt_group_id)
+                .chain(day.astronomical_objects.iter().map(|row| &row.alt_group_id)),
+        ),
+    )
+    .await?;
+
+    Ok(TanahpediaCreationDayOpinions {
+        plants: catalog.group(
+            day.plants
+                .iter()
+                .map(|row| (row.alt_group_id.clone(), row.clone()))
+                .collect(),
+        ),
+        astronomical_objects: catalog.group(
+            day.astronomical_objects
+                .iter()
+                .map(|row| (row.alt_group_id.clone(), row.clone()))
+                .collect(),
+        ),
+    })
+}
+
+async fn require_item(
+    conn: &DatabaseConnection,
+    item_type: TanahpediaCreationItemType,
+    item_id: &str,
+) -> Result<(), ServiceError> {
+    let exists = match item_type {
+        TanahpediaCreationItemType::Plant => plant::Entity::find_by_id(item_id.to_string())
+            .one(conn)
+            .await
+            .map_err(db_error)?
+            .is_some(),
+        TanahpediaCreationItemType::AstronomicalObject => {
+            astronomical_object::Entity::find_by_id(item_id.to_string())
+                .one(conn)
+                .await
+                .map_err(db_error)?
+                .is_some()
+        }
+    };
+    if !exists {
+        return Err(ServiceError::bad_request(&format!(
+            "itemId {item_id} does not reference an existing {}",
+            match item_type {
+                TanahpediaCreationItemType::Plant => "plant",
+                TanahpediaCreationItemType::AstronomicalObject => "astronomical object",
+            }
+        )));
+    }
+    Ok(())
+}
+
+/// Deletes creation-day row `id` of `item_type`'s table and its sources, if
+/// present.
+async fn delete_creation_day_row<C: ConnectionTrait>(
+    conn: &C,
+    item_type: TanahpediaCreationItemType,
+    id: &str,
+) -> Result<(), ServiceError> {
+    delete_sources(conn, item_type.source_table(), id).await?;
+    match item_type {
+        TanahpediaCreationItemType::Plant => {
+            plant_creation_day::Entity::delete_by_id(id.to_string())
+                .exec(conn)
+                .await
+                .map_err(db_error)?
+        }
+        TanahpediaCreationItemType::AstronomicalObject => {
+            astronomical_object_creation_day::Entity::delete_by_id(id.to_string())
+                .exec(conn)
+                .await
+                .map_err(db_error)?
+        }
+    };
+    Ok(())
+}
+
+/// Stores an opinion on an item's day of creation. Replaying the same `id`
+/// with the other `itemType` moves the row to the other table.
+pub async fn put_creation_day(
+    db: &Database,
+    input: PutTanahpediaCreationDayInput,
+) -> Result<TanahpediaCreationDayWriteResult, ServiceError> {
+    let conn = db.get_connection();
+    let id = required(input.id, "id", 36)?;
+    let item_type = input.item_type;
+    let item_id = required(input.item_id, "itemId", 36)?;
+    if !CREATION_DAYS.contains(&input.creation_day) {
+        return Err(ServiceError::bad_request(
+            "creationDay must be between 1 and 6",
+        ));
+    }
+    let creation_day = input.creation_day as i8;
+    let alt_group_id = optional(input.alt_group_id, "altGroupId", 36)?;
+    let sources = SourcesInput::parse(input.tanah_sources, input.non_tanah_sources)?;
+    require_item(conn, item_type, &item_id).await?;
+
+    let transaction = conn.begin().await.map_err(db_error)?;
+    delete_creation_day_row(&transaction, item_type.other(), &id).await?;
+    match item_type {
+        TanahpediaCreationItemType::Plant => plant_creation_day::Entity::insert(
+            plant_creation_day::Model {
+                id: id.clone(),
+                plant_id: item_id.clone(),
+                creation_day,
+                alt_group_id,
+            }
+            .into_active_model(),
+        )
+        .on_conflict(
+            OnConflict::column(plant_creation_day::Column::Id)
+                .update_columns([
+                    plant_creation_day::Column::PlantId,
+                    plant_creation_day::Column::CreationDay,
+                    plant_creation_day::Column::AltGroupId,
+                ])
+                .to_owned(),
+        )
+        .exec(&transaction)
+        .await
+        .map(|_| ())
+        .map_err(db_error)?,
+        TanahpediaCreationItemType::AstronomicalObject => {
+            astronomical_object_creation_day::Entity::insert(
+                astronomical_object_creation_day::Model {
+                    id: id.clone(),
+                    astronomical_object_id: item_id.clone(),
+                    creation_day,
+                    alt_group_id,
+                }
+                .into_active_model(),
+            )
+            .on_conflict(
+                OnConflict::column(astronomical_object_creation_day::Column::Id)
+                    .update_columns([
+                        astronomical_object_creation_day::Column::AstronomicalObjectId,
+                        astronomical_object_creation_day::Column::CreationDay,
+                        astronomical_object_creation_day::Column::AltGroupId,
+                    ])
+                    .to_owned(),
+            )
+            .exec(&transaction)
+            .await
+            .map(|_| ())
+            .map_err(db_error)?
+        }
+    };
+    replace_sources(&transaction, item_type.source_table(), &id, sources).await?;
+    transaction.commit().await.map_err(db_error)?;
+
+    Ok(TanahpediaCreationDayWriteResult {
+        id,
+        item_type,
+        item_id,
+        creation_day: input.creation_day,
+    })
+}
+
+pub async fn delete_creation_day(
+    db: &Database,
+    id: String,
+) -> Result<TanahpediaCreationDayWriteResult, ServiceError> {
+    let id = required(id, "id", 36)?;
+    let conn = db.get_connection();
+
+    let existing = if let Some(row) = plant_creation_day::Entity::find_by_id(id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+    {
+        Some((TanahpediaCreationItemType::Plant, CreationDayRow::from(row)))
+    } else {
+        astronomical_object_creation_day::Entity::find_by_id(id.clone())
+            .one(conn)
+            .await
+            .map_err(db_error)?
+            .map(|row| {
+                (
+                    TanahpediaCreationItemType::AstronomicalObject,
+                    CreationDayRow::from(row),
+                )
+            })
+    };
+    let Some((item_type, row)) = existing else {
+        return Err(ServiceError::not_found(
+            "creation day not found",
+            None::<&str>,
+        ));
+    };
+
+    let transaction = conn.begin().await.map_err(db_error)?;
+    delete_creation_day_row(&transaction, item_type, &id).await?;
+    transaction.commit().await.map_err(db_error)?;
+
+    Ok(TanahpediaCreationDayWriteResult {
+        id,
+        item_type,
+        item_id: row.item_id,
+        creation_day: row.creation_day,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::tanahpedia::{entity, sea_orm_active_enums::EntityType, source_group};
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    fn plant_day(
+        id: &str,
+        plant_id: &str,
+        day: i8,
+        alt_group_id: Option<&str>,
+    ) -> plant_creation_day::Model {
+        plant_creation_day::Model {
+            id: id.to_string(),
+            plant_id: plant_id.to_string(),
+            creation_day: day,
+            alt_group_id: alt_group_id.map(str::to_string),
+        }
+    }
+
+    fn entity_model(id: &str, name: &str) -> entity::Model {
+        entity::Model {
+            id: id.to_string(),
+            entity_type: EntityType::Plant,
+            name: name.to_string(),
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    fn creation_day_input() -> PutTanahpediaCreationDayInput {
+        PutTanahpediaCreationDayInput {
+            id: "day-1".to_string(),
+            item_type: TanahpediaCreationItemType::Plant,
+            item_id: "plant-1".to_string(),
+            creation_day: 3,
+            alt_group_id: None,
+            tanah_sources: Vec::new(),
+            non_tanah_sources: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn find_creation_days_returns_six_days_with_disputed_items_on_each() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![
+                    plant_day("day-2", "plant-1", 3, Some("alt-b")),
+                    plant_day("day-1", "plant-1", 3, Some("alt-a")),
+                    plant_day("day-3", "plant-2", 3, None),
+                    plant_day("day-4", "plant-1", 6, Some("alt-c")),
+                ]])
+                .append_query_results::<astronomical_object_creation_day::Model, Vec<_>, _>([
+                    vec![],
+                ])
+                .append_query_results([vec![
+                    plant::Model {
+                        id: "plant-1".to_string(),
+                        entity_id: "entity-1".to_string(),
+                    },
+                    plant::Model {
+                        id: "plant-2".to_string(),
+                        entity_id: "entity-2".to_string(),
+                    },
+                ]])
+                .append_query_results::<source_group::Model, Vec<_>, _>([vec![]])
+                .append_query_results([vec![
+                    entity_model("entity-1", "עץ הדעת"),
+                    entity_model("entity-2", "דשא"),
+                ]])
+                .into_connection(),
+        );
+
+        let days = find_creation_days(&db)
+            .await
+            .expect("creation days should load");
+
+        assert_eq!(
+            days.iter().map(|day| day.day).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+        assert_eq!(
+            days[2]
+                .plants
+                .iter()
+                .map(|item| (item.display_name.as_str(), item.id.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("דשא", "day-3"), ("עץ הדעת", "day-1"), ("עץ הדעת", "day-2")]
+        );
+        assert_eq!(days[5].plants.len(), 1);
+        assert_eq!(
+            days[5].plants[0].item_type,
+            TanahpediaCreationItemType::Plant
+        );
+        assert!(days[0].plants.is_empty());
+        assert!(days.iter().all(|day| day.astronomical_objects.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn put_creation_day_rejects_day_outside_the_week_before_querying() {
+        let db =
+            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
+        let mut input = creation_day_input();
+        input.creation_day = 7;
+
+        let err = put_creation_day(&db, input).await.unwrap_err();
+
+        assert!(matches!(err, ServiceError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn put_creation_day_rejects_unknown_item() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<plant::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let err = put_creation_day(&db, creation_day_input())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ServiceError::BadRequest(message) if message.contains("plant")));
+    }
+
+    #[tokio::test]
+    async fn put_creation_day_stores_plant_row() {
+        let none = MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 0,
+        };
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![plant::Model {
+                    id: "plant-1".to_string(),
+                    entity_id: "entity-1".to_string(),
+                }]])
+                .append_exec_results([
+                    none.clone(),
+                    none.clone(),
+                    MockExecResult {
+                        last_insert_id: 0,
+                        rows_affected: 1,
+                    },
+                    none,
+                ])
+                .into_connection(),
+        );
+
+        let result = put_creation_day(&db, creation_day_input())
+            .await
+            .expect("creation day should be stored");
+
+        assert_eq!(result.item_type, TanahpediaCreationItemType::Plant);
+        assert_eq!(result.creation_day, 3);
+    }
+
+    #[tokio::test]
+    async fn delete_creation_day_reports_missing_row() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<plant_creation_day::Model, Vec<_>, _>([vec![]])
+                .append_query_results::<astronomical_object_creation_day::Model, Vec<_>, _>([
+                    vec![],
+                ])
+                .into_connection(),
+        );
+
+        let err = delete_creation_day(&db, "missing".to_string())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+}
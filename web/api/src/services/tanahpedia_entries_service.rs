@@ -0,0 +1,576 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::tanahpedia_entry::{
+        TanahpediaBrokenEntryLink, TanahpediaEntry, TanahpediaEntryLinksRebuildResult,
+        TanahpediaEntryReference,
+    },
+    providers::Database,
+    services::{
+        tanahpedia_family_service::{db_error, optional, required},
+        tanahpedia_graph_service::{SITE_BASE, iri_segment},
+    },
+};
+use entities::tanahpedia::{entry, entry_link};
+use sea_orm::sea_query::{Expr, OnConflict, Query};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+    TransactionTrait,
+};
+
+/// Length of `tanahpedia_entry.unique_name`.
+const UNIQUE_NAME_MAX_LEN: usize = 255;
+
+/// Entry pages live under `/pedia/<unique_name>`.
+const ENTRY_PATH: &str = "/pedia/";
+
+/// Origins an absolute entry link may use; relative `/pedia/` links need none.
+const SITE_ORIGINS: &[&str] = &[SITE_BASE, "https://תנך.co.il"];
+
+/// Byte ranges of the `href` values of the `<a>` tags in `content`.
+fn anchor_hrefs(content: &str) -> Vec<Range<usize>> {
+    // ASCII lowercasing keeps byte offsets, so ranges index `content` too.
+    let lower = content.to_ascii_lowercase();
+    let mut hrefs = Vec::new();
+    let mut at = 0;
+    while let Some(found) = lower[at..].find("<a") {
+        let tag_start = at + found;
+        let tag_end = lower[tag_start..]
+            .find('>')
+            .map_or(lower.len(), |end| tag_start + end);
+        at = tag_end;
+        // Skips `<abbr>`, `<article>` and the like.
+        if !lower[tag_start + 2..].starts_with(|c: char| c.is_ascii_whitespace()) {
+            continue;
+        }
+        if let Some(href) = attribute_value(&lower[tag_start..tag_end], "href") {
+            hrefs.push(tag_start + href.start..tag_start + href.end);
+        }
+    }
+    hrefs
+}
+
+/// Byte range of the value of attribute `name` in a lowercased `tag`.
+fn attribute_value(tag: &str, name: &str) -> Option<Range<usize>> {
+    let mut at = 0;
+    while let Some(found) = tag[at..].find(name) {
+        let name_start = at + found;
+        at = name_start + name.len();
+        if !tag[..name_start].ends_with(|c: char| c.is_ascii_whitespace()) {
+            continue;
+        }
+        let Some(value) = tag[at..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let value_start = tag.len() - value.len();
+        return match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let len = value[1..].find(quote).unwrap_or(value.len() - 1);
+                Some(value_start + 1..value_start + 1 + len)
+            }
+            Some(_) => {
+                let len = value
+                    .find(|c: char| c.is_ascii_whitespace())
+                    .unwrap_or(value.len());
+                Some(value_start..value_start + len)
+            }
+            None => None,
+        };
+    }
+    None
+}
+
+/// Splits an internal entry href into what precedes `/pedia/`, the still
+/// encoded unique name, and the `?`/`#` suffix.
+fn split_entry_href(href: &str) -> Option<(&str, &str, &str)> {
+    let path_start = if href.starts_with(ENTRY_PATH) {
+        0
+    } else {
+        SITE_ORIGINS
+            .iter()
+            .find(|origin| {
+                href.strip_prefix(**origin)
+                    .is_some_and(|rest| rest.starts_with(ENTRY_PATH))
+            })?
+            .len()
+    };
+    let name_start = path_start + ENTRY_PATH.len();
+    let name_end = href[name_start..]
+        .find(['?', '#'])
+        .map_or(href.len(), |end| name_start + end);
+    Some((
+        &href[..path_start],
+        &href[name_start..name_end],
+        &href[name_end..],
+    ))
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut at = 0;
+    while at < bytes.len() {
+        if bytes[at] == b'%' {
+            let hex = value.get(at + 1..at + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            at += 3;
+        } else {
+            decoded.push(bytes[at]);
+            at += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// The unique name an href links to, when it is an internal entry link.
+fn link_target(href: &str) -> Option<String> {
+    let (_, name, _) = split_entry_href(href)?;
+    let name = percent_decode(&name.replace("&amp;", "&"))?;
+    let name = name.trim().trim_end_matches('/').trim();
+    (!name.is_empty() && name.chars().count() <= UNIQUE_NAME_MAX_LEN).then(|| name.to_string())
+}
+
+/// `name` folded the way `utf8mb4_unicode_ci` compares it for Hebrew and ASCII
+/// names: case and Hebrew points (niqqud, cantillation) are ignored, while
+/// maqaf, paseq and sof pasuq still count.
+fn collation_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| {
+            !('\u{0591}'..='\u{05C7}').contains(c)
+                || matches!(c, '\u{05BE}' | '\u{05C0}' | '\u{05C3}' | '\u{05C6}')
+        })
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Distinct unique names linked from `content`, in order of first appearance.
+/// Names `uk_entry_link` treats as equal are kept once, as first written.
+pub(crate) fn extract_link_targets(content: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    anchor_hrefs(content)
+        .into_iter()
+        .filter_map(|href| link_target(&content[href]))
+        .filter(|target| seen.insert(collation_key(target)))
+        .collect()
+}
+
+/// Site-relative path of entry `unique_name`, escaped for an HTML attribute.
+pub(crate) fn entry_path(unique_name: &str) -> String {
+    let segment = iri_segment(unique_name)
+        .replace('&', "&amp;")
+        .replace('\'', "%27");
+    format!("{ENTRY_PATH}{segment}")
+}
+
+/// `content` with every link to `from` pointed at `to`, matching `from` the way
+/// the database does. Each link keeps its origin and its `?`/`#` suffix.
+pub(crate) fn rewrite_link_targets(content: &str, from: &str, to: &str) -> String {
+    let from = collation_key(from);
+    let mut rewritten = String::with_capacity(content.len());
+    let mut copied = 0;
+    for href in anchor_hrefs(content) {
+        let value = &content[href.clone()];
+        let Some((origin, _, suffix)) = split_entry_href(value) else {
+            continue;
+        };
+        if link_target(value).is_none_or(|target| collation_key(&target) != from) {
+            continue;
+        }
+        rewritten.push_str(&content[copied..href.start]);
+        rewritten.push_str(origin);
+        rewritten.push_str(&entry_path(to));
+        rewritten.push_str(suffix);
+        copied = href.end;
+    }
+    rewritten.push_str(&content[copied..]);
+    rewritten
+}
+
+/// Replaces the stored links of `entry_id` with those found in `content` and
+/// returns how many were stored. A target the unique key still sees as a
+/// duplicate (a collation difference [`collation_key`] does not fold) is
+/// skipped rather than failing the write.
+pub(crate) async fn sync_entry_links<C: ConnectionTrait>(
+    conn: &C,
+    entry_id: &str,
+    content: Option<&str>,
+) -> Result<usize, ServiceError> {
+    entry_link::Entity::delete_many()
+        .filter(entry_link::Column::SourceEntryId.eq(entry_id))
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+
+    let targets = content.map(extract_link_targets).unwrap_or_default();
+    if targets.is_empty() {
+        return Ok(0);
+    }
+    let count = targets.len();
+    entry_link::Entity::insert_many(targets.into_iter().map(|target_unique_name| {
+        entry_link::Model {
+            id: uuid::Uuid::new_v4().to_string(),
+            source_entry_id: entry_id.to_string(),
+            target_unique_name,
+        }
+        .into_active_model()
+    }))
+    .on_conflict(
+        OnConflict::columns([
+            entry_link::Column::SourceEntryId,
+            entry_link::Column::TargetUniqueName,
+        ])
+        .do_nothing_on([entry_link::Column::SourceEntryId])
+        .to_owned(),
+    )
+    .exec_without_returning(conn)
+    .await
+    .map_err(db_error)?;
+    Ok(count)
+}
+
+/// Follows a rename from `old_unique_name` to `new_unique_name`: every entry
+/// linking to the old name gets its links rewritten and re-synced. Returns how
+/// many entries' content changed.
+pub(crate) async fn rewrite_links_to<C: ConnectionTrait>(
+    conn: &C,
+    old_unique_name: &str,
+    new_unique_name: &str,
+    now: chrono::NaiveDateTime,
+) -> Result<usize, ServiceError> {
+    let source_ids = entry_link::Entity::find()
+        .filter(entry_link::Column::TargetUniqueName.eq(old_unique_name))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|link| link.source_entry_id)
+        .collect::<Vec<_>>();
+    if source_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let sources = entry::Entity::find()
+        .filter(entry::Column::Id.is_in(source_ids))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let mut rewritten = 0;
+    for source in sources {
+        let content = source
+            .content
+            .as_deref()
+            .map(|content| rewrite_link_targets(content, old_unique_name, new_unique_name));
+        if content != source.content {
+            entry::Entity::update_many()
+                .col_expr(entry::Column::Content, Expr::value(content.clone()))
+                .col_expr(entry::Column::UpdatedAt, Expr::value(now))
+                .filter(entry::Column::Id.eq(source.id.clone()))
+                .exec(conn)
+                .await
+                .map_err(db_error)?;
+            rewritten += 1;
+        }
+        sync_entry_links(conn, &source.id, content.as_deref()).await?;
+    }
+    Ok(rewritten)
+}
+
+pub async fn get_entry(
+    db: &Database,
+    unique_name: String,
+) -> Result<Option<TanahpediaEntry>, ServiceError> {
+    let unique_name = required(unique_name, "uniqueName", UNIQUE_NAME_MAX_LEN)?;
+    Ok(entry::Entity::find()
+        .filter(entry::Column::UniqueName.eq(unique_name))
+        .one(db.get_connection())
+        .await
+        .map_err(db_error)?
+        .map(Into::into))
+}
+
+/// Entries other than `unique_name` itself whose content links to it.
+pub async fn get_backlinks(
+    db: &Database,
+    unique_name: String,
+) -> Result<Vec<TanahpediaEntryReference>, ServiceError> {
+    let rows = entry::Entity::find()
+        .inner_join(entry_link::Entity)
+        .filter(entry_link::Column::TargetUniqueName.eq(unique_name.clone()))
+        .filter(entry::Column::UniqueName.ne(unique_name))
+        .order_by_asc(entry::Column::Title)
+        .all(db.get_connection())
+        .await
+        .map_err(db_error)?;
+    Ok(rows.into_iter().map(Into::into).collect())
+}
+
+/// Stored links to unique names no entry has, by target.
+pub async fn find_broken_links(
+    db: &Database,
+) -> Result<Vec<TanahpediaBrokenEntryLink>, ServiceError> {
+    let conn = db.get_connection();
+    let links = entry_link::Entity::find()
+        .filter(
+            entry_link::Column::TargetUniqueName.not_in_subquery(
+                Query::select()
+                    .column(entry::Column::UniqueName)
+                    .from(entry::Entity)
+                    .to_owned(),
+            ),
+        )
+        .order_by_asc(entry_link::Column::TargetUniqueName)
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    if links.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let sources = entry::Entity::find()
+        .filter(entry::Column::Id.is_in(links.iter().map(|link| link.source_entry_id.clone())))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|source| (source.id.clone(), TanahpediaEntryReference::from(source)))
+        .collect::<HashMap<_, _>>();
+
+    Ok(links
+        .into_iter()
+        .filter_map(|link| {
+            Some(TanahpediaBrokenEntryLink {
+                source: sources.get(&link.source_entry_id)?.clone(),
+                target_unique_name: link.target_unique_name,
+            })
+        })
+        .collect())
+}
+
+/// Re-scans the content of one entry, or of every entry when `entry_id` is
+/// omitted, and replaces their stored links.
+pub async fn rebuild_links(
+    db: &Database,
+    entry_id: Option<String>,
+) -> Result<TanahpediaEntryLinksRebuildResult, ServiceError> {
+    let entry_id = optional(entry_id, "entryId", 36)?;
+    let conn = db.get_connection();
+
+    let mut query = entry::Entity::find();
+    if let Some(entry_id) = entry_id.clone() {
+        query = query.filter(entry::Column::Id.eq(entry_id));
+    }
+    let entries = query.all(conn).await.map_err(db_error)?;
+    if entry_id.is_some() && entries.is_empty() {
+        return Err(ServiceError::not_found("entry not found", None::<&str>));
+    }
+
+    let transaction = conn.begin().await.map_err(db_error)?;
+    let mut links = 0;
+    for entry in &entries {
+        links += sync_entry_links(&transaction, &entry.id, entry.content.as_deref()).await?;
+    }
+    transaction.commit().await.map_err(db_error)?;
+
+    Ok(TanahpediaEntryLinksRebuildResult {
+        entries: entries.len() as i32,
+        links: links as i32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    fn exec_result() -> MockExecResult {
+        MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }
+    }
+
+    fn entry_model(id: &str, unique_name: &str, content: Option<&str>) -> entry::Model {
+        entry::Model {
+            id: id.to_string(),
+            unique_name: unique_name.to_string(),
+            title: unique_name.to_string(),
+            content: content.map(str::to_string),
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    fn link_model(source_entry_id: &str, target_unique_name: &str) -> entry_link::Model {
+        entry_link::Model {
+            id: format!("{source_entry_id}->{target_unique_name}"),
+            source_entry_id: source_entry_id.to_string(),
+            target_unique_name: target_unique_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn extract_link_targets_finds_internal_links_once() {
+        let content = r#"<p><a href="/pedia/%D7%9E%D7%A0%D7%95%D7%97">מנוח</a> אבי
+            <A class="x" HREF='https://xn--febl3a.co.il/pedia/שמשון#נזיר'>שמשון</A>,
+            <a href="https://תנך.co.il/pedia/דן?tab=map">דן</a>
+            <a href="/pedia/שמשון">שוב</a>
+            <a href="https://he.wikipedia.org/wiki/שמשון">ויקיפדיה</a>
+            <abbr href="/pedia/לא">לא</abbr> <a name="anchor">עוגן</a>
+            <a href=/pedia/צרעה>צרעה</a></p>"#;
+
+        assert_eq!(
+            extract_link_targets(content),
+            vec!["מנוח", "שמשון", "דן", "צרעה"]
+        );
+    }
+
+    #[test]
+    fn extract_link_targets_folds_case_and_niqqud_like_the_unique_key() {
+        let content = r#"<a href="/pedia/Shimshon">א</a> <a href="/pedia/shimshon">ב</a>
+            <a href="/pedia/שִׁמְשׁוֹן">ג</a> <a href="/pedia/שמשון">ד</a>
+            <a href="/pedia/בית־לחם">ה</a> <a href="/pedia/בית לחם">ו</a>"#;
+
+        assert_eq!(
+            extract_link_targets(content),
+            vec!["Shimshon", "שִׁמְשׁוֹן", "בית־לחם", "בית לחם"]
+        );
+    }
+
+    #[test]
+    fn extract_link_targets_skips_malformed_links() {
+        assert!(
+            extract_link_targets(r#"<a href="/pedia/">x</a><a href="/pedia/%E0%A4">y</a>"#)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn rewrite_link_targets_only_rewrites_links_to_the_old_name() {
+        let content = r#"<p><a href="https://xn--febl3a.co.il/pedia/%D7%A9%D7%9E%D7%A9%D7%95%D7%9F#נזיר" class="x">שמשון</a>
+            <a href="/pedia/דן">דן</a> <a href='/pedia/שמשון'>שוב</a> /pedia/שמשון</p>"#;
+
+        assert_eq!(
+            rewrite_link_targets(content, "שמשון", "שמשון הגיבור"),
+            r#"<p><a href="https://xn--febl3a.co.il/pedia/שמשון%20הגיבור#נזיר" class="x">שמשון</a>
+            <a href="/pedia/דן">דן</a> <a href='/pedia/שמשון%20הגיבור'>שוב</a> /pedia/שמשון</p>"#
+        );
+        assert_eq!(rewrite_link_targets(content, "מנוח", "x"), content);
+        assert_eq!(
+            rewrite_link_targets(r#"<a href="/pedia/שִׁמְשׁוֹן">שמשון</a>"#, "שמשון", "דן"),
+            r#"<a href="/pedia/דן">שמשון</a>"#
+        );
+    }
+
+    #[tokio::test]
+    async fn get_backlinks_maps_linking_entries() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![entry_model("entry-manoah", "מנוח", None)]])
+                .into_connection(),
+        );
+
+        let backlinks = get_backlinks(&db, "שמשון".to_string())
+            .await
+            .expect("backlinks should load");
+
+        assert_eq!(
+            backlinks,
+            vec![TanahpediaEntryReference {
+                id: "entry-manoah".to_string(),
+                unique_name: "מנוח".to_string(),
+                title: "מנוח".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn find_broken_links_pairs_links_with_their_source() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![link_model("entry-shimshon", "צרעה")]])
+                .append_query_results([vec![entry_model("entry-shimshon", "שמשון", None)]])
+                .into_connection(),
+        );
+
+        let broken = find_broken_links(&db).await.expect("report should load");
+
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].source.unique_name, "שמשון");
+        assert_eq!(broken[0].target_unique_name, "צרעה");
+    }
+
+    #[tokio::test]
+    async fn rebuild_links_rescans_every_entry() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![
+                    entry_model(
+                        "entry-shimshon",
+                        "שמשון",
+                        Some(r#"<a href="/pedia/מנוח">מנוח</a> <a href="/pedia/דן">דן</a>"#),
+                    ),
+                    entry_model("entry-manoah", "מנוח", None),
+                ]])
+                .append_exec_results([exec_result(), exec_result(), exec_result()])
+                .into_connection(),
+        );
+
+        let result = rebuild_links(&db, None)
+            .await
+            .expect("rebuild should succeed");
+
+        assert_eq!(
+            result,
+            TanahpediaEntryLinksRebuildResult {
+                entries: 2,
+                links: 2
+            }
+        );
+        let executed_sql = format!("{:?}", db.get_connection().clone().into_transaction_log());
+        assert!(
+            executed_sql.contains("ON DUPLICATE KEY UPDATE `source_entry_id` = `source_entry_id`")
+        );
+    }
+
+    #[tokio::test]
+    async fn rebuild_links_requires_existing_entry() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<entry::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let result = rebuild_links(&db, Some("missing".to_string())).await;
+
+        assert!(matches!(result, Err(ServiceError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn rewrite_links_to_rewrites_linking_entries() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![link_model("entry-manoah", "שמשון")]])
+                .append_query_results([vec![entry_model(
+                    "entry-manoah",
+                    "מנוח",
+                    Some(r#"<a href="/pedia/שמשון">בנו</a>"#),
+                )]])
+                .append_exec_results([exec_result(), exec_result(), exec_result()])
+                .into_connection(),
+        );
+
+        let rewritten = rewrite_links_to(
+            db.get_connection(),
+            "שמשון",
+            "שמשון בן מנוח",
+            chrono::Utc::now().naive_utc(),
+        )
+        .await
+        .expect("rewrite should succeed");
+
+        assert_eq!(rewritten, 1);
+    }
+}
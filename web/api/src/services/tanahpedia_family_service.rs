@@ -7,24 +7,30 @@ use crate::{
         DeleteTanahpediaOrphanEntityInput, DeleteTanahpediaPersonNodeInput,
         PutTanahpediaEntryEntityLinkInput, PutTanahpediaParentChildInput,
         PutTanahpediaPersonNodeInput, PutTanahpediaPersonUnionInput, TanahpediaEntitySummary,
-        TanahpediaEntityTanahSource, TanahpediaEntryEntityLinkWriteResult,
+        TanahpediaEntityTanahSource, TanahpediaEntityType, TanahpediaEntryEntityLinkWriteResult,
         TanahpediaFamilyLinkWriteResult, TanahpediaPersonDetail, TanahpediaPersonName,
-        TanahpediaPersonNodeWriteResult, TanahpediaPersonParentChildSummary, TanahpediaPersonSex,
-        TanahpediaPersonSummary, TanahpediaPersonUnionSummary,
+        TanahpediaPersonNodeWriteResult, TanahpediaPersonOpinions,
+        TanahpediaPersonParentChildSummary, TanahpediaPersonSex, TanahpediaPersonSummary,
+        TanahpediaPersonUnionSummary,
     },
     providers::Database,
+    services::{
+        tanahpedia_namings_service::name_givers,
+        tanahpedia_opinions_service::{OpinionCatalog, alt_group_ids},
+    },
 };
 use entities::perek;
 use entities::tanahpedia::{
     entity, entity_tanah_source, entry, entry_entity, lookup_name_type, lookup_parent_child_type,
-    lookup_parent_role, lookup_union_end_reason, lookup_union_type, person, person_birth_date,
-    person_birth_place, person_death_cause, person_death_date, person_name, person_parent_child,
-    person_sex, person_union,
+    lookup_parent_role, lookup_union_end_reason, lookup_union_type, nation, person,
+    person_birth_date, person_birth_place, person_death_cause, person_death_date, person_name,
+    person_parent_child, person_sex, person_union, place,
+    sea_orm_active_enums::{EntityType, Sex},
 };
 use sea_orm::sea_query::OnConflict;
 use sea_orm::{
-    ColumnTrait, Condition, DatabaseBackend, EntityTrait, FromQueryResult, IntoActiveModel,
-    QueryFilter, QuerySelect, Statement, TransactionTrait, Value,
+    ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, EntityTrait, FromQueryResult,
+    IntoActiveModel, QueryFilter, QuerySelect, Statement, TransactionTrait, Value,
 };
 
 #[derive(FromQueryResult)]
@@ -47,6 +53,7 @@ const PERSON_DEPENDENCY_SQL: &str = r#"SELECT (
     EXISTS(SELECT 1 FROM tanahpedia_person_role_prophet WHERE person_id = ?) +
     EXISTS(SELECT 1 FROM tanahpedia_person_role_king WHERE person_id = ?) +
     EXISTS(SELECT 1 FROM tanahpedia_person_name_giver_person WHERE giver_person_id = ?) +
+    EXISTS(SELECT 1 FROM tanahpedia_person_nation WHERE person_id = ?) +
     EXISTS(SELECT 1 FROM tanahpedia_war_side_participant_person WHERE person_id = ?) +
     EXISTS(SELECT 1 FROM tanahpedia_saying_speaker_person WHERE person_id = ?) +
     EXISTS(SELECT 1 FROM tanahpedia_saying_audience_person WHERE person_id = ?) +
@@ -55,9 +62,9 @@ const PERSON_DEPENDENCY_SQL: &str = r#"SELECT (
     EXISTS(SELECT 1 FROM tanahpedia_person_parent_child WHERE parent_id = ? OR child_id = ?) +
     EXISTS(SELECT 1 FROM tanahpedia_person_union WHERE person1_id = ? OR person2_id = ?)
 ) AS dependency_count"#;
-const PERSON_DEPENDENCY_PERSON_BIND_COUNT: usize = 17;
+const PERSON_DEPENDENCY_PERSON_BIND_COUNT: usize = 18;
 
-const ENTITY_DEPENDENCY_SQL: &str = r#"SELECT (
+pub(crate) const ENTITY_DEPENDENCY_SQL: &str = r#"SELECT (
     EXISTS(SELECT 1 FROM tanahpedia_entity_tanah_source WHERE entity_id = ?) +
     EXISTS(SELECT 1 FROM tanahpedia_entry_entity WHERE entity_id = ?) +
     EXISTS(SELECT 1 FROM tanahpedia_person WHERE entity_id = ?) +
@@ -91,11 +98,11 @@ fn entity_dependency_values(entity_id: &str) -> Vec<Value> {
     std::iter::repeat_n(entity_id.to_string().into(), ENTITY_DEPENDENCY_BIND_COUNT).collect()
 }
 
-fn db_error(db_err: sea_orm::DbErr) -> ServiceError {
+pub(crate) fn db_error(db_err: sea_orm::DbErr) -> ServiceError {
     ServiceError::internal_server_error(INTERNAL_SERVER_ERROR, Some(db_err))
 }
 
-fn required(value: String, field: &str, max_len: usize) -> Result<String, ServiceError> {
+pub(crate) fn required(value: String, field: &str, max_len: usize) -> Result<String, ServiceError> {
     let value = value.trim().to_string();
     if value.is_empty() {
         return Err(ServiceError::bad_request(&format!("{field} is required")));
@@ -108,7 +115,7 @@ fn required(value: String, field: &str, max_len: usize) -> Result<String, Servic
     Ok(value)
 }
 
-fn optional(
+pub(crate) fn optional(
     value: Option<String>,
     field: &str,
     max_len: usize,
@@ -118,17 +125,6 @@ fn optional(
         .transpose()
 }
 
-fn normalized_sex(value: String) -> Result<String, ServiceError> {
-    let sex = required(value, "sex", 7)?.to_uppercase();
-    if matches!(sex.as_str(), "MALE" | "FEMALE" | "UNKNOWN") {
-        Ok(sex)
-    } else {
-        Err(ServiceError::bad_request(
-            "sex must be MALE, FEMALE, or UNKNOWN",
-        ))
-    }
-}
-
 pub async fn put_entry_entity_link(
     db: &Database,
     input: PutTanahpediaEntryEntityLinkInput,
@@ -261,8 +257,8 @@ pub async fn delete_entry_entity_link(
     })
 }
 
-async fn require_person(
-    conn: &sea_orm::DatabaseConnection,
+pub(crate) async fn require_person<C: ConnectionTrait>(
+    conn: &C,
     person_id: &str,
 ) -> Result<(), ServiceError> {
     if person::Entity::find_by_id(person_id.to_string())
@@ -278,8 +274,8 @@ async fn require_person(
     Ok(())
 }
 
-async fn parent_child_type_id(
-    conn: &sea_orm::DatabaseConnection,
+pub(crate) async fn parent_child_type_id<C: ConnectionTrait>(
+    conn: &C,
     name: String,
 ) -> Result<String, ServiceError> {
     let name = required(name, "relationshipType", 50)?.to_uppercase();
@@ -292,8 +288,8 @@ async fn parent_child_type_id(
         .ok_or_else(|| ServiceError::bad_request(&format!("unknown relationshipType {name}")))
 }
 
-async fn parent_role_id(
-    conn: &sea_orm::DatabaseConnection,
+pub(crate) async fn parent_role_id<C: ConnectionTrait>(
+    conn: &C,
     name: String,
 ) -> Result<String, ServiceError> {
     let name = required(name, "parentRole", 50)?.to_uppercase();
@@ -306,8 +302,22 @@ async fn parent_role_id(
         .ok_or_else(|| ServiceError::bad_request(&format!("unknown parentRole {name}")))
 }
 
-async fn union_type_id(
-    conn: &sea_orm::DatabaseConnection,
+pub(crate) async fn name_type_id<C: ConnectionTrait>(
+    conn: &C,
+    name: String,
+) -> Result<String, ServiceError> {
+    let name = required(name, "nameType", 50)?.to_uppercase();
+    lookup_name_type::Entity::find()
+        .filter(lookup_name_type::Column::Name.eq(name.clone()))
+        .one(conn)
+        .await
+        .map_err(db_error)?
+        .map(|row| row.id)
+        .ok_or_else(|| ServiceError::bad_request(&format!("unknown nameType {name}")))
+}
+
+pub(crate) async fn union_type_id<C: ConnectionTrait>(
+    conn: &C,
     name: String,
 ) -> Result<String, ServiceError> {
     let name = required(name, "unionType", 50)?.to_uppercase();
@@ -320,8 +330,8 @@ async fn union_type_id(
         .ok_or_else(|| ServiceError::bad_request(&format!("unknown unionType {name}")))
 }
 
-async fn union_end_reason_id(
-    conn: &sea_orm::DatabaseConnection,
+pub(crate) async fn union_end_reason_id<C: ConnectionTrait>(
+    conn: &C,
     name: Option<String>,
 ) -> Result<Option<String>, ServiceError> {
     let Some(name) = optional(name, "endReason", 50)?.map(|name| name.to_uppercase()) else {
@@ -344,7 +354,7 @@ pub async fn put_person_node(
     let person_id = required(input.person_id, "personId", 36)?;
     let display_name = required(input.display_name, "displayName", 255)?;
     let sex_id = required(input.sex_id, "sexId", 36)?;
-    let sex = normalized_sex(input.sex)?;
+    let sex = Sex::from(input.sex);
     let sex_alt_group_id = optional(input.sex_alt_group_id, "sexAltGroupId", 36)?;
 
     let transaction = db.get_connection().begin().await.map_err(db_error)?;
@@ -354,7 +364,7 @@ pub async fn put_person_node(
         .map_err(db_error)?;
     if existing_entity
         .as_ref()
-        .is_some_and(|existing| existing.entity_type != "PERSON")
+        .is_some_and(|existing| existing.entity_type != EntityType::Person)
     {
         return Err(ServiceError::bad_request(
             "entityId references a non-PERSON entity",
@@ -399,7 +409,7 @@ pub async fn put_person_node(
         entity::Entity::insert(
             entity::Model {
                 id: entity_id.clone(),
-                entity_type: "PERSON".to_string(),
+                entity_type: EntityType::Person,
                 name: display_name,
                 created_at: now,
                 updated_at: now,
@@ -538,7 +548,7 @@ pub async fn delete_orphan_entity(
     input: DeleteTanahpediaOrphanEntityInput,
 ) -> Result<TanahpediaEntitySummary, ServiceError> {
     let entity_id = required(input.entity_id, "entityId", 36)?;
-    let entity_type = required(input.entity_type, "entityType", 50)?.to_uppercase();
+    let entity_type = EntityType::from(input.entity_type);
     let display_name = required(input.display_name, "displayName", 255)?;
     let transaction = db.get_connection().begin().await.map_err(db_error)?;
 
@@ -575,7 +585,7 @@ pub async fn delete_orphan_entity(
 
     Ok(TanahpediaEntitySummary {
         entity_id,
-        entity_type,
+        entity_type: entity_type.into(),
         display_name,
     })
 }
@@ -727,20 +737,17 @@ pub async fn delete_person_union(
 }
 
 /// Formats a perek + pasuk pair as a human-readable Hebrew citation, e.g.
-/// `"בראשית ל ד"`. Falls back to an empty string when the perek can't be
-/// resolved (a dangling `perek_id` should never happen in practice, but this
-/// keeps the read path total rather than failing the whole query).
-fn format_citation(perek_row: &perek::Model, pasuk_number: i32) -> String {
+/// `"בראשית ל ד"`, or just the perek when `pasuk_number` is `None`. Falls back
+/// to an empty string when the perek can't be resolved (a dangling `perek_id`
+/// should never happen in practice, but this keeps the read path total rather
+/// than failing the whole query).
+pub(crate) fn format_citation(perek_row: &perek::Model, pasuk_number: Option<i32>) -> String {
     let sefer_name = perek_row.sefer_name.clone().unwrap_or_default();
     let perek_num = perek_row.perek.unwrap_or_default();
-    format!(
-        "{} {} {}",
-        sefer_name,
-        number_to_hebrew(perek_num),
-        number_to_hebrew(pasuk_number)
-    )
-    .trim()
-    .to_string()
+    let pasuk = pasuk_number.map(number_to_hebrew).unwrap_or_default();
+    format!("{} {} {}", sefer_name, number_to_hebrew(perek_num), pasuk)
+        .trim()
+        .to_string()
 }
 
 /// Resolves a display name to the matching Tanahpedia `PERSON` entities.
@@ -758,7 +765,7 @@ pub async fn find_persons_by_name(
     let conn = db.get_connection();
 
     let entities = entity::Entity::find()
-        .filter(entity::Column::EntityType.eq("PERSON"))
+        .filter(entity::Column::EntityType.eq(EntityType::Person))
         .filter(entity::Column::Name.eq(name))
         .all(conn)
         .await
@@ -783,26 +790,93 @@ pub async fn find_persons_by_name(
     Ok(summaries)
 }
 
-/// Looks up the display name of the `PERSON` entity linked to `person_id`, or
-/// `None` if the person row (or its linked entity) no longer exists.
-async fn display_name_for_person(
+/// Batch form of the entity name lookup: maps each `entity_id` to its
+/// `tanahpedia_entity.name`. Ids without a row are simply absent.
+pub(crate) async fn entity_display_names(
     conn: &sea_orm::DatabaseConnection,
-    person_id: &str,
-) -> Result<Option<String>, ServiceError> {
-    let Some(person) = person::Entity::find_by_id(person_id.to_string())
-        .one(conn)
+    entity_ids: HashSet<String>,
+) -> Result<HashMap<String, String>, ServiceError> {
+    if entity_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(entity::Entity::find()
+        .filter(entity::Column::Id.is_in(entity_ids))
+        .all(conn)
         .await
         .map_err(db_error)?
-    else {
-        return Ok(None);
-    };
+        .into_iter()
+        .map(|row| (row.id, row.name))
+        .collect())
+}
 
-    let entity = entity::Entity::find_by_id(person.entity_id)
-        .one(conn)
+/// Maps each `personId` to the name of its backing entity.
+pub(crate) async fn person_display_names(
+    conn: &sea_orm::DatabaseConnection,
+    person_ids: HashSet<String>,
+) -> Result<HashMap<String, String>, ServiceError> {
+    if person_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(person::Entity::find()
+        .filter(person::Column::Id.is_in(person_ids))
+        .find_also_related(entity::Entity)
+        .all(conn)
         .await
-        .map_err(db_error)?;
+        .map_err(db_error)?
+        .into_iter()
+        .filter_map(|(row, entity)| entity.map(|e| (row.id, e.name)))
+        .collect())
+}
+
+/// Maps each `nationId` to the name of its backing entity.
+pub(crate) async fn nation_display_names(
+    conn: &sea_orm::DatabaseConnection,
+    nation_ids: HashSet<String>,
+) -> Result<HashMap<String, String>, ServiceError> {
+    if nation_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(nation::Entity::find()
+        .filter(nation::Column::Id.is_in(nation_ids))
+        .find_also_related(entity::Entity)
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .filter_map(|(row, entity)| entity.map(|e| (row.id, e.name)))
+        .collect())
+}
+
+/// Maps each `placeId` to the name of its backing entity.
+pub(crate) async fn place_display_names(
+    conn: &sea_orm::DatabaseConnection,
+    place_ids: HashSet<String>,
+) -> Result<HashMap<String, String>, ServiceError> {
+    if place_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(place::Entity::find()
+        .filter(place::Column::Id.is_in(place_ids))
+        .find_also_related(entity::Entity)
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .filter_map(|(row, entity)| entity.map(|e| (row.id, e.name)))
+        .collect())
+}
 
-    Ok(entity.map(|e| e.name))
+/// The side of a two-person link that isn't `person_id`.
+fn other_party(person_id: &str, first_id: &str, second_id: &str) -> String {
+    if first_id == person_id {
+        second_id.to_string()
+    } else {
+        first_id.to_string()
+    }
 }
 
 /// Lists every union (marriage/pilegesh/betrothal/etc.) link involving
@@ -825,26 +899,14 @@ pub async fn get_person_unions(
                 .add(person_union::Column::Person1Id.eq(person_id.clone()))
                 .add(person_union::Column::Person2Id.eq(person_id.clone())),
         )
+        .find_also_related(lookup_union_type::Entity)
         .all(conn)
         .await
         .map_err(db_error)?;
 
-    let union_type_ids = unions
-        .iter()
-        .map(|union| union.union_type_id.clone())
-        .collect::<HashSet<_>>();
-    let union_types = lookup_union_type::Entity::find()
-        .filter(lookup_union_type::Column::Id.is_in(union_type_ids))
-        .all(conn)
-        .await
-        .map_err(db_error)?
-        .into_iter()
-        .map(|row| (row.id, row.name))
-        .collect::<HashMap<_, _>>();
-
     let end_reason_ids = unions
         .iter()
-        .filter_map(|union| union.end_reason_id.clone())
+        .filter_map(|(union, _)| union.end_reason_id.clone())
         .collect::<HashSet<_>>();
     let end_reasons = if end_reason_ids.is_empty() {
         HashMap::new()
@@ -859,25 +921,27 @@ pub async fn get_person_unions(
             .collect()
     };
 
+    let other_names = person_display_names(
+        conn,
+        unions
+            .iter()
+            .map(|(u, _)| other_party(&person_id, &u.person1_id, &u.person2_id))
+            .collect(),
+    )
+    .await?;
+
     let mut summaries = Vec::with_capacity(unions.len());
-    for u in unions {
-        let union_type = union_types
-            .get(&u.union_type_id)
-            .cloned()
-            .unwrap_or_default();
+    for (u, union_type) in unions {
+        let union_type = union_type.map(|t| t.name).unwrap_or_default();
         let end_reason = u
             .end_reason_id
             .as_ref()
             .and_then(|id| end_reasons.get(id).cloned());
 
-        let other_person_id = if u.person1_id == person_id {
-            u.person2_id.clone()
-        } else {
-            u.person1_id.clone()
-        };
-
-        let other_display_name = display_name_for_person(conn, &other_person_id)
-            .await?
+        let other_person_id = other_party(&person_id, &u.person1_id, &u.person2_id);
+        let other_display_name = other_names
+            .get(&other_person_id)
+            .cloned()
             .unwrap_or_default();
 
         summaries.push(TanahpediaPersonUnionSummary {
@@ -900,14 +964,12 @@ pub async fn get_person_unions(
     Ok(summaries)
 }
 
-/// Resolves a display name to matching Tanahpedia entities of any type
-/// (`PERSON`, `PLACE`, `EVENT`, `WAR`, `ANIMAL`, `OBJECT`, `TEMPLE_TOOL`,
-/// `PLANT`, `ASTRONOMICAL_OBJECT`, `SAYING`, `SEFER`, `PROPHECY`, `NATION`).
-/// Pass `entity_type` to narrow the search to a single type.
+/// Resolves a display name to matching Tanahpedia entities of any type. Pass
+/// `entity_type` to narrow the search to a single type.
 pub async fn find_entities(
     db: &Database,
     name: String,
-    entity_type: Option<String>,
+    entity_type: Option<TanahpediaEntityType>,
 ) -> Result<Vec<TanahpediaEntitySummary>, ServiceError> {
     let name = name.trim().to_string();
     if name.is_empty() {
@@ -917,11 +979,8 @@ pub async fn find_entities(
     let conn = db.get_connection();
 
     let mut query = entity::Entity::find().filter(entity::Column::Name.eq(name));
-    if let Some(entity_type) = entity_type
-        .map(|t| t.trim().to_uppercase())
-        .filter(|t| !t.is_empty())
-    {
-        query = query.filter(entity::Column::EntityType.eq(entity_type));
+    if let Some(entity_type) = entity_type {
+        query = query.filter(entity::Column::EntityType.eq(EntityType::from(entity_type)));
     }
 
     let rows = query.all(conn).await.map_err(db_error)?;
@@ -930,12 +989,30 @@ pub async fn find_entities(
         .into_iter()
         .map(|e| TanahpediaEntitySummary {
             entity_id: e.id,
-            entity_type: e.entity_type,
+            entity_type: e.entity_type.into(),
             display_name: e.name,
         })
         .collect())
 }
 
+/// Looks up a single entity of any type by id.
+pub async fn get_entity(
+    db: &Database,
+    entity_id: String,
+) -> Result<Option<TanahpediaEntitySummary>, ServiceError> {
+    let entity_id = required(entity_id, "entityId", 36)?;
+    let row = entity::Entity::find_by_id(entity_id)
+        .one(db.get_connection())
+        .await
+        .map_err(db_error)?;
+
+    Ok(row.map(|e| TanahpediaEntitySummary {
+        entity_id: e.id,
+        entity_type: e.entity_type.into(),
+        display_name: e.name,
+    }))
+}
+
 /// Lists the direct Tanah citations (perek + pasuk) attached to an entity —
 /// the "source for the entity itself", as opposed to a specific
 /// relationship's `sourceCitation` free-text field.
@@ -964,7 +1041,7 @@ pub async fn get_entity_tanah_sources(
             .map_err(db_error)?;
         let citation = perek_row
             .as_ref()
-            .map(|p| format_citation(p, row.pasuk_number))
+            .map(|p| format_citation(p, Some(row.pasuk_number)))
             .unwrap_or_default();
 
         sources.push(TanahpediaEntityTanahSource {
@@ -999,35 +1076,46 @@ pub async fn get_person_parent_child(
                 .add(person_parent_child::Column::ParentId.eq(person_id.clone()))
                 .add(person_parent_child::Column::ChildId.eq(person_id.clone())),
         )
+        .find_also_related(lookup_parent_child_type::Entity)
         .all(conn)
         .await
         .map_err(db_error)?;
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let parent_roles = lookup_parent_role::Entity::find()
+        .filter(
+            lookup_parent_role::Column::Id
+                .is_in(rows.iter().map(|(row, _)| row.parent_role_id.clone())),
+        )
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|role| (role.id, role.name))
+        .collect::<HashMap<_, _>>();
+    let other_names = person_display_names(
+        conn,
+        rows.iter()
+            .map(|(row, _)| other_party(&person_id, &row.parent_id, &row.child_id))
+            .collect(),
+    )
+    .await?;
 
     let mut summaries = Vec::with_capacity(rows.len());
-    for row in rows {
-        let relationship_type =
-            lookup_parent_child_type::Entity::find_by_id(row.relationship_type_id.clone())
-                .one(conn)
-                .await
-                .map_err(db_error)?
-                .map(|t| t.name)
-                .unwrap_or_default();
-
-        let parent_role = lookup_parent_role::Entity::find_by_id(row.parent_role_id.clone())
-            .one(conn)
-            .await
-            .map_err(db_error)?
-            .map(|t| t.name)
+    for (row, relationship_type) in rows {
+        let relationship_type = relationship_type.map(|t| t.name).unwrap_or_default();
+        let parent_role = parent_roles
+            .get(&row.parent_role_id)
+            .cloned()
             .unwrap_or_default();
 
         let queried_is_parent = row.parent_id == person_id;
-        let other_person_id = if queried_is_parent {
-            row.child_id.clone()
-        } else {
-            row.parent_id.clone()
-        };
-        let other_display_name = display_name_for_person(conn, &other_person_id)
-            .await?
+        let other_person_id = other_party(&person_id, &row.parent_id, &row.child_id);
+        let other_display_name = other_names
+            .get(&other_person_id)
+            .cloned()
             .unwrap_or_default();
 
         summaries.push(TanahpediaPersonParentChildSummary {
@@ -1080,6 +1168,8 @@ pub async fn get_person_details(
         .all(conn)
         .await
         .map_err(db_error)?;
+    let mut givers =
+        name_givers(conn, name_rows.iter().map(|row| row.id.clone()).collect()).await?;
     let mut names = Vec::with_capacity(name_rows.len());
     for row in name_rows {
         let name_type = lookup_name_type::Entity::find_by_id(row.name_type_id.clone())
@@ -1089,6 +1179,7 @@ pub async fn get_person_details(
             .map(|t| t.name)
             .unwrap_or_default();
         names.push(TanahpediaPersonName {
+            givers: givers.remove(&row.id).unwrap_or_default(),
             id: row.id,
             name: row.name,
             name_type,
@@ -1101,50 +1192,50 @@ pub async fn get_person_details(
         .all(conn)
         .await
         .map_err(db_error)?;
-    let sexes = sex_models.iter().map(|row| row.sex.clone()).collect();
+    let sexes = sex_models.iter().map(|row| row.sex.into()).collect();
     let sex_rows = sex_models
         .into_iter()
         .map(|row| TanahpediaPersonSex {
             id: row.id,
-            sex: row.sex,
+            sex: row.sex.into(),
             alt_group_id: row.alt_group_id,
         })
         .collect();
 
-    let birth_dates = person_birth_date::Entity::find()
+    let birth_date_rows: Vec<(Option<String>, i32)> = person_birth_date::Entity::find()
         .filter(person_birth_date::Column::PersonId.eq(person_id.clone()))
         .all(conn)
         .await
         .map_err(db_error)?
         .into_iter()
-        .map(|row| row.birth_date)
+        .map(|row| (row.alt_group_id, row.birth_date))
         .collect();
 
-    let death_dates = person_death_date::Entity::find()
+    let death_date_rows: Vec<(Option<String>, i32)> = person_death_date::Entity::find()
         .filter(person_death_date::Column::PersonId.eq(person_id.clone()))
         .all(conn)
         .await
         .map_err(db_error)?
         .into_iter()
-        .map(|row| row.death_date)
+        .map(|row| (row.alt_group_id, row.death_date))
         .collect();
 
-    let death_causes = person_death_cause::Entity::find()
+    let death_cause_rows: Vec<(Option<String>, String)> = person_death_cause::Entity::find()
         .filter(person_death_cause::Column::PersonId.eq(person_id.clone()))
         .all(conn)
         .await
         .map_err(db_error)?
         .into_iter()
-        .map(|row| row.death_cause)
+        .map(|row| (row.alt_group_id, row.death_cause))
         .collect();
 
-    let birth_place_ids = person_birth_place::Entity::find()
+    let birth_place_rows: Vec<(Option<String>, String)> = person_birth_place::Entity::find()
         .filter(person_birth_place::Column::PersonId.eq(person_id.clone()))
         .all(conn)
         .await
         .map_err(db_error)?
         .into_iter()
-        .map(|row| row.place_id)
+        .map(|row| (row.alt_group_id, row.place_id))
         .collect();
 
     let tanah_sources = get_entity_tanah_sources(db, entity_row.id.clone()).await?;
@@ -1156,11 +1247,62 @@ pub async fn get_person_details(
         names,
         sexes,
         sex_rows,
-        birth_dates,
-        death_dates,
-        death_causes,
-        birth_place_ids,
+        birth_dates: birth_date_rows.iter().map(|(_, date)| *date).collect(),
+        death_dates: death_date_rows.iter().map(|(_, date)| *date).collect(),
+        death_causes: death_cause_rows
+            .iter()
+            .map(|(_, cause)| cause.clone())
+            .collect(),
+        birth_place_ids: birth_place_rows.iter().map(|(_, id)| id.clone()).collect(),
         tanah_sources,
+        birth_date_rows,
+        death_date_rows,
+        death_cause_rows,
+        birth_place_rows,
+    })
+}
+
+/// Groups an already-loaded person detail by opinion; only the opinion
+/// metadata is queried.
+pub async fn get_person_opinions(
+    db: &Database,
+    detail: &TanahpediaPersonDetail,
+) -> Result<TanahpediaPersonOpinions, ServiceError> {
+    let catalog = OpinionCatalog::load(
+        db.get_connection(),
+        alt_group_ids(
+            detail
+                .names
+                .iter()
+                .map(|row| &row.alt_group_id)
+                .chain(detail.sex_rows.iter().map(|row| &row.alt_group_id))
+                .chain(detail.birth_date_rows.iter().map(|(id, _)| id))
+                .chain(detail.death_date_rows.iter().map(|(id, _)| id))
+                .chain(detail.death_cause_rows.iter().map(|(id, _)| id))
+                .chain(detail.birth_place_rows.iter().map(|(id, _)| id)),
+        ),
+    )
+    .await?;
+
+    Ok(TanahpediaPersonOpinions {
+        names: catalog.group(
+            detail
+                .names
+                .iter()
+                .map(|row| (row.alt_group_id.clone(), row.clone()))
+                .collect(),
+        ),
+        sexes: catalog.group(
+            detail
+                .sex_rows
+                .iter()
+                .map(|row| (row.alt_group_id.clone(), row.clone()))
+                .collect(),
+        ),
+        birth_dates: catalog.group(detail.birth_date_rows.clone()),
+        death_dates: catalog.group(detail.death_date_rows.clone()),
+        death_causes: catalog.group(detail.death_cause_rows.clone()),
+        birth_place_ids: catalog.group(detail.birth_place_rows.clone()),
     })
 }
 
@@ -1169,12 +1311,14 @@ mod tests {
     use std::collections::{BTreeMap, HashSet};
 
     use super::*;
+    use crate::dtos::tanahpedia_family::TanahpediaSex;
+    use entities::tanahpedia::{person_name_giver_god, person_name_giver_person};
     use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, QueryTrait, Value};
 
     fn entity_model(id: &str, name: &str) -> entity::Model {
         entity::Model {
             id: id.to_string(),
-            entity_type: "PERSON".to_string(),
+            entity_type: EntityType::Person,
             name: name.to_string(),
             created_at: chrono::Utc::now().naive_utc(),
             updated_at: chrono::Utc::now().naive_utc(),
@@ -1211,7 +1355,7 @@ mod tests {
         person_sex::Model {
             id: id.to_string(),
             person_id: person_id.to_string(),
-            sex: "MALE".to_string(),
+            sex: Sex::Male,
             alt_group_id: None,
         }
     }
@@ -1237,7 +1381,7 @@ mod tests {
             }
         }
 
-        assert_eq!(reference_tables.len(), 16);
+        assert_eq!(reference_tables.len(), 17);
         assert!(reference_tables.remove("tanahpedia_person_sex"));
         for table in reference_tables {
             assert!(PERSON_DEPENDENCY_SQL.contains(table), "missing {table}");
@@ -1340,7 +1484,7 @@ mod tests {
             person_id: "person-1".to_string(),
             display_name: "שמשון".to_string(),
             sex_id: "sex-1".to_string(),
-            sex: "male".to_string(),
+            sex: TanahpediaSex::Male,
             sex_alt_group_id: None,
         }
     }
@@ -1638,18 +1782,6 @@ mod tests {
         assert!(matches!(error, ServiceError::NotFound(_)));
     }
 
-    #[tokio::test]
-    async fn put_person_node_rejects_unknown_sex_before_querying() {
-        let db =
-            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
-        let mut input = person_node_input();
-        input.sex = "other".to_string();
-
-        let err = put_person_node(&db, input).await.unwrap_err();
-
-        assert!(matches!(err, ServiceError::BadRequest(_)));
-    }
-
     #[tokio::test]
     async fn delete_orphan_person_node_preserves_entity() {
         let exec_result = MockExecResult {
@@ -1812,7 +1944,7 @@ mod tests {
             &db,
             DeleteTanahpediaOrphanEntityInput {
                 entity_id: "entity-1".to_string(),
-                entity_type: "person".to_string(),
+                entity_type: TanahpediaEntityType::Person,
                 display_name: "שמשון".to_string(),
             },
         )
@@ -1820,7 +1952,7 @@ mod tests {
         .expect("should delete exact orphan entity");
 
         assert_eq!(deleted.entity_id, "entity-1");
-        assert_eq!(deleted.entity_type, "PERSON");
+        assert_eq!(deleted.entity_type, TanahpediaEntityType::Person);
         assert_eq!(deleted.display_name, "שמשון");
         let transaction_log = db.get_connection().clone().into_transaction_log();
         assert!(format!("{:?}", transaction_log[0]).contains("DELETE FROM `tanahpedia_entity`"));
@@ -1844,7 +1976,7 @@ mod tests {
             &db,
             DeleteTanahpediaOrphanEntityInput {
                 entity_id: "entity-1".to_string(),
-                entity_type: "PERSON".to_string(),
+                entity_type: TanahpediaEntityType::Person,
                 display_name: "שמשון".to_string(),
             },
         )
@@ -1869,7 +2001,7 @@ mod tests {
             &db,
             DeleteTanahpediaOrphanEntityInput {
                 entity_id: "entity-1".to_string(),
-                entity_type: "PLACE".to_string(),
+                entity_type: TanahpediaEntityType::Place,
                 display_name: "שמשון".to_string(),
             },
         )
@@ -1879,13 +2011,6 @@ mod tests {
         assert!(matches!(error, ServiceError::BadRequest(_)));
     }
 
-    #[test]
-    fn normalized_sex_accepts_case_insensitive_values() {
-        assert_eq!(normalized_sex("male".to_string()).unwrap(), "MALE");
-        assert_eq!(normalized_sex("FEMALE".to_string()).unwrap(), "FEMALE");
-        assert_eq!(normalized_sex(" unknown ".to_string()).unwrap(), "UNKNOWN");
-    }
-
     #[tokio::test]
     async fn put_person_node_upserts_the_complete_node() {
         let mock_db = MockDatabase::new(DatabaseBackend::MySql)
@@ -1921,7 +2046,7 @@ mod tests {
                 person_sex::Model {
                     id: "sex-1".to_string(),
                     person_id: "person-1".to_string(),
-                    sex: "MALE".to_string(),
+                    sex: Sex::Male,
                     alt_group_id: None,
                 },
             ]])
@@ -1989,7 +2114,7 @@ mod tests {
     #[tokio::test]
     async fn put_person_node_rejects_non_person_entity_collision() {
         let mut existing = entity_model("entity-1", "מקום");
-        existing.entity_type = "PLACE".to_string();
+        existing.entity_type = EntityType::Place;
         let db = Database::from_connection(
             MockDatabase::new(DatabaseBackend::MySql)
                 .append_query_results::<entity::Model, Vec<entity::Model>, _>([vec![existing]])
@@ -2058,7 +2183,7 @@ mod tests {
                     person_sex::Model {
                         id: "sex-1".to_string(),
                         person_id: "person-other".to_string(),
-                        sex: "MALE".to_string(),
+                        sex: Sex::Male,
                         alt_group_id: None,
                     },
                 ]])
@@ -2254,7 +2379,7 @@ mod tests {
     #[tokio::test]
     async fn get_person_unions_resolves_other_party_display_name() {
         let mock_db = MockDatabase::new(DatabaseBackend::MySql)
-            .append_query_results::<person_union::Model, Vec<person_union::Model>, _>([vec![
+            .append_query_results([vec![(
                 union_model(
                     "union-1",
                     "yaakov",
@@ -2263,23 +2388,17 @@ mod tests {
                     Some("בראשית כט"),
                     Some("בראשית כט טז"),
                 ),
-            ]])
-            .append_query_results::<lookup_union_type::Model, Vec<lookup_union_type::Model>, _>([
-                vec![union_type_model("ut-marriage", "MARRIAGE")],
-            ])
+                union_type_model("ut-marriage", "MARRIAGE"),
+            )]])
             .append_query_results::<lookup_union_end_reason::Model, Vec<lookup_union_end_reason::Model>, _>([
                 vec![lookup_union_end_reason::Model {
                     id: "uer-death".to_string(),
                     name: "DEATH".to_string(),
                 }],
             ])
-            .append_query_results::<person::Model, Vec<person::Model>, _>([vec![person_model(
-                "leah",
-                "entity-leah",
-            )]])
-            .append_query_results::<entity::Model, Vec<entity::Model>, _>([vec![entity_model(
-                "entity-leah",
-                "לאה",
+            .append_query_results([vec![(
+                person_model("leah", "entity-leah"),
+                entity_model("entity-leah", "לאה"),
             )]])
             .into_connection();
         let db = Database::from_connection(mock_db);
@@ -2393,7 +2512,7 @@ mod tests {
         let mock_db = MockDatabase::new(DatabaseBackend::MySql)
             .append_query_results::<entity::Model, Vec<entity::Model>, _>([vec![entity::Model {
                 id: "entity-mizrayim".to_string(),
-                entity_type: "PLACE".to_string(),
+                entity_type: EntityType::Place,
                 name: "מצרים".to_string(),
                 created_at: chrono::Utc::now().naive_utc(),
                 updated_at: chrono::Utc::now().naive_utc(),
@@ -2401,13 +2520,13 @@ mod tests {
             .into_connection();
         let db = Database::from_connection(mock_db);
 
-        let results = find_entities(&db, "מצרים".to_string(), Some("place".to_string()))
+        let results = find_entities(&db, "מצרים".to_string(), Some(TanahpediaEntityType::Place))
             .await
             .expect("should query");
 
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].entity_id, "entity-mizrayim");
-        assert_eq!(results[0].entity_type, "PLACE");
+        assert_eq!(results[0].entity_type, TanahpediaEntityType::Place);
         assert_eq!(results[0].display_name, "מצרים");
     }
 
@@ -2465,33 +2584,22 @@ mod tests {
     #[tokio::test]
     async fn get_person_parent_child_marks_queried_side_correctly() {
         let mock_db = MockDatabase::new(DatabaseBackend::MySql)
-            .append_query_results::<person_parent_child::Model, Vec<person_parent_child::Model>, _>(
-                [vec![parent_child_model(
-                    "pc-1",
-                    "yaakov",
-                    "yosef",
-                    Some("בראשית ל כד"),
-                )]],
-            )
-            .append_query_results::<lookup_parent_child_type::Model, Vec<lookup_parent_child_type::Model>, _>(
-                [vec![lookup_parent_child_type::Model {
+            .append_query_results([vec![(
+                parent_child_model("pc-1", "yaakov", "yosef", Some("בראשית ל כד")),
+                lookup_parent_child_type::Model {
                     id: "pct-biological".to_string(),
                     name: "BIOLOGICAL".to_string(),
-                }]],
-            )
+                },
+            )]])
             .append_query_results::<lookup_parent_role::Model, Vec<lookup_parent_role::Model>, _>([
                 vec![lookup_parent_role::Model {
                     id: "pr-father".to_string(),
                     name: "FATHER".to_string(),
                 }],
             ])
-            .append_query_results::<person::Model, Vec<person::Model>, _>([vec![person_model(
-                "yosef",
-                "entity-yosef",
-            )]])
-            .append_query_results::<entity::Model, Vec<entity::Model>, _>([vec![entity_model(
-                "entity-yosef",
-                "יוסף",
+            .append_query_results([vec![(
+                person_model("yosef", "entity-yosef"),
+                entity_model("entity-yosef", "יוסף"),
             )]])
             .into_connection();
         let db = Database::from_connection(mock_db);
@@ -2545,6 +2653,8 @@ mod tests {
             .append_query_results::<person_name::Model, Vec<person_name::Model>, _>([vec![
                 name_model("name-1", "bilhah", "בלהה"),
             ]])
+            .append_query_results::<person_name_giver_person::Model, Vec<_>, _>([vec![]])
+            .append_query_results::<person_name_giver_god::Model, Vec<_>, _>([vec![]])
             .append_query_results::<lookup_name_type::Model, Vec<lookup_name_type::Model>, _>([
                 vec![lookup_name_type::Model {
                     id: "nt-birth".to_string(),
@@ -2555,7 +2665,7 @@ mod tests {
                 person_sex::Model {
                     id: "sex-1".to_string(),
                     person_id: "bilhah".to_string(),
-                    sex: "FEMALE".to_string(),
+                    sex: Sex::Female,
                     alt_group_id: None,
                 },
             ]])
@@ -2596,11 +2706,71 @@ mod tests {
         assert_eq!(detail.display_name, "בלהה");
         assert_eq!(detail.names.len(), 1);
         assert_eq!(detail.names[0].name_type, "BIRTH");
-        assert_eq!(detail.sexes, vec!["FEMALE".to_string()]);
+        assert!(detail.names[0].givers.is_empty());
+        assert_eq!(detail.sexes, vec![TanahpediaSex::Female]);
         assert_eq!(detail.sex_rows.len(), 1);
         assert_eq!(detail.sex_rows[0].id, "sex-1");
         assert!(detail.birth_dates.is_empty());
         assert_eq!(detail.tanah_sources.len(), 1);
         assert_eq!(detail.tanah_sources[0].citation, "בראשית ל' ד'");
     }
+
+    #[tokio::test]
+    async fn get_person_opinions_groups_alternatives_with_their_labels() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![entities::tanahpedia::alt_group::Model {
+                    id: "alt-birth-2".to_string(),
+                    label: Some("רש\"י".to_string()),
+                    is_preferred: true,
+                    dispute_id: Some("dispute-birth".to_string()),
+                }]])
+                .append_query_results::<entities::tanahpedia::source_group::Model, Vec<_>, _>([
+                    vec![],
+                ])
+                .into_connection(),
+        );
+        let detail = TanahpediaPersonDetail {
+            entity_id: "entity-1".to_string(),
+            person_id: "person-1".to_string(),
+            display_name: "שמשון".to_string(),
+            names: vec![],
+            sexes: vec![TanahpediaSex::Male],
+            sex_rows: vec![TanahpediaPersonSex {
+                id: "sex-1".to_string(),
+                sex: TanahpediaSex::Male,
+                alt_group_id: None,
+            }],
+            birth_dates: vec![10, 20],
+            death_dates: vec![],
+            death_causes: vec![],
+            birth_place_ids: vec![],
+            tanah_sources: vec![],
+            birth_date_rows: vec![
+                (Some("alt-birth-1".to_string()), 10),
+                (Some("alt-birth-2".to_string()), 20),
+            ],
+            death_date_rows: vec![],
+            death_cause_rows: vec![],
+            birth_place_rows: vec![],
+        };
+
+        let opinions = get_person_opinions(&db, &detail)
+            .await
+            .expect("person opinions should load");
+
+        assert!(!opinions.sexes.is_disputed);
+        assert!(opinions.birth_dates.is_disputed);
+        assert_eq!(
+            opinions.birth_dates.preferred_alt_group_id.as_deref(),
+            Some("alt-birth-2")
+        );
+        assert_eq!(opinions.birth_dates.opinions[0].facts, vec![20]);
+        assert_eq!(
+            opinions.birth_dates.opinions[0].label.as_deref(),
+            Some("רש\"י")
+        );
+        assert_eq!(opinions.birth_dates.opinions[1].facts, vec![10]);
+        assert!(opinions.names.opinions.is_empty());
+    }
 }
@@ -0,0 +1,659 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::{Map, Value, json};
+
+use crate::{
+    common::error_handling::ServiceError, providers::Database,
+    services::tanahpedia_family_service::db_error,
+};
+use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+
+/// Public site root; entry pages live under `/pedia/<unique_name>`.
+pub const SITE_BASE: &str = "https://xn--febl3a.co.il";
+const SCHEMA_NS: &str = "https://schema.org/";
+const VOCAB_PATH: &str = "/tanahpedia/vocab#";
+
+const ENTITIES_SQL: &str =
+    "SELECT id, CAST(entity_type AS CHAR) AS entity_type, name FROM tanahpedia_entity ORDER BY id";
+
+const ENTRY_LINKS_SQL: &str = "SELECT ee.entity_id, e.unique_name \
+     FROM tanahpedia_entry_entity ee \
+     INNER JOIN tanahpedia_entry e ON e.id = ee.entry_id \
+     ORDER BY e.unique_name";
+
+const OPINIONS_SQL: &str = "SELECT id, label, is_preferred FROM tanahpedia_alt_group ORDER BY id";
+
+/// Entity-to-entity facts: `(subject, object, alt_group_id)` entity ids.
+const EDGES: &[(&str, &str)] = &[
+    (
+        "schema:parent",
+        "SELECT c.entity_id AS subject, p.entity_id AS object, pc.alt_group_id \
+         FROM tanahpedia_person_parent_child pc \
+         INNER JOIN tanahpedia_person c ON c.id = pc.child_id \
+         INNER JOIN tanahpedia_person p ON p.id = pc.parent_id",
+    ),
+    (
+        "schema:children",
+        "SELECT p.entity_id AS subject, c.entity_id AS object, pc.alt_group_id \
+         FROM tanahpedia_person_parent_child pc \
+         INNER JOIN tanahpedia_person c ON c.id = pc.child_id \
+         INNER JOIN tanahpedia_person p ON p.id = pc.parent_id",
+    ),
+    (
+        "schema:spouse",
+        "SELECT p1.entity_id AS subject, p2.entity_id AS object, u.alt_group_id \
+         FROM tanahpedia_person_union u \
+         INNER JOIN tanahpedia_person p1 ON p1.id = u.person1_id \
+         INNER JOIN tanahpedia_person p2 ON p2.id = u.person2_id \
+         UNION ALL \
+         SELECT p2.entity_id, p1.entity_id, u.alt_group_id \
+         FROM tanahpedia_person_union u \
+         INNER JOIN tanahpedia_person p1 ON p1.id = u.person1_id \
+         INNER JOIN tanahpedia_person p2 ON p2.id = u.person2_id",
+    ),
+    (
+        "schema:birthPlace",
+        "SELECT p.entity_id AS subject, pl.entity_id AS object, bp.alt_group_id \
+         FROM tanahpedia_person_birth_place bp \
+         INNER JOIN tanahpedia_person p ON p.id = bp.person_id \
+         INNER JOIN tanahpedia_place pl ON pl.id = bp.place_id",
+    ),
+    (
+        "schema:location",
+        "SELECT ev.entity_id AS subject, pl.entity_id AS object, ep.alt_group_id \
+         FROM tanahpedia_event_place ep \
+         INNER JOIN tanahpedia_event ev ON ev.id = ep.event_id \
+         INNER JOIN tanahpedia_place pl ON pl.id = ep.place_id",
+    ),
+    (
+        "tp:speaker",
+        "SELECT s.entity_id AS subject, p.entity_id AS object, sp.alt_group_id \
+         FROM tanahpedia_saying_speaker_person sp \
+         INNER JOIN tanahpedia_saying s ON s.id = sp.saying_id \
+         INNER JOIN tanahpedia_person p ON p.id = sp.person_id \
+         UNION ALL \
+         SELECT s.entity_id, n.entity_id, sn.alt_group_id \
+         FROM tanahpedia_saying_speaker_nation sn \
+         INNER JOIN tanahpedia_saying s ON s.id = sn.saying_id \
+         INNER JOIN tanahpedia_nation n ON n.id = sn.nation_id",
+    ),
+    (
+        "tp:audience",
+        "SELECT s.entity_id AS subject, p.entity_id AS object, ap.alt_group_id \
+         FROM tanahpedia_saying_audience_person ap \
+         INNER JOIN tanahpedia_saying s ON s.id = ap.saying_id \
+         INNER JOIN tanahpedia_person p ON p.id = ap.person_id \
+         UNION ALL \
+         SELECT s.entity_id, n.entity_id, an.alt_group_id \
+         FROM tanahpedia_saying_audience_nation an \
+         INNER JOIN tanahpedia_saying s ON s.id = an.saying_id \
+         INNER JOIN tanahpedia_nation n ON n.id = an.nation_id",
+    ),
+    (
+        "tp:saying",
+        "SELECT pr.entity_id AS subject, s.entity_id AS object, NULL AS alt_group_id \
+         FROM tanahpedia_prophecy pr \
+         INNER JOIN tanahpedia_saying s ON s.id = pr.saying_id",
+    ),
+    (
+        "tp:prophet",
+        "SELECT pr.entity_id AS subject, p.entity_id AS object, pp.alt_group_id \
+         FROM tanahpedia_prophecy_prophet pp \
+         INNER JOIN tanahpedia_prophecy pr ON pr.id = pp.prophecy_id \
+         INNER JOIN tanahpedia_person p ON p.id = pp.person_id",
+    ),
+    (
+        "tp:recipient",
+        "SELECT pr.entity_id AS subject, p.entity_id AS object, rp.alt_group_id \
+         FROM tanahpedia_prophecy_recipient_person rp \
+         INNER JOIN tanahpedia_prophecy pr ON pr.id = rp.prophecy_id \
+         INNER JOIN tanahpedia_person p ON p.id = rp.person_id \
+         UNION ALL \
+         SELECT pr.entity_id, n.entity_id, rn.alt_group_id \
+         FROM tanahpedia_prophecy_recipient_nation rn \
+         INNER JOIN tanahpedia_prophecy pr ON pr.id = rn.prophecy_id \
+         INNER JOIN tanahpedia_nation n ON n.id = rn.nation_id",
+    ),
+];
+
+/// Literal facts: `(subject entity id, value, alt_group_id)`.
+const LITERALS: &[(&str, &str)] = &[
+    (
+        "schema:alternateName",
+        "SELECT p.entity_id AS subject, n.name AS value, n.alt_group_id \
+         FROM tanahpedia_person_name n \
+         INNER JOIN tanahpedia_person p ON p.id = n.person_id",
+    ),
+    (
+        "schema:gender",
+        "SELECT p.entity_id AS subject, CAST(s.sex AS CHAR) AS value, s.alt_group_id \
+         FROM tanahpedia_person_sex s \
+         INNER JOIN tanahpedia_person p ON p.id = s.person_id",
+    ),
+    (
+        "tp:birthDate",
+        "SELECT p.entity_id AS subject, CAST(d.birth_date AS CHAR) AS value, d.alt_group_id \
+         FROM tanahpedia_person_birth_date d \
+         INNER JOIN tanahpedia_person p ON p.id = d.person_id",
+    ),
+    (
+        "tp:deathDate",
+        "SELECT p.entity_id AS subject, CAST(d.death_date AS CHAR) AS value, d.alt_group_id \
+         FROM tanahpedia_person_death_date d \
+         INNER JOIN tanahpedia_person p ON p.id = d.person_id",
+    ),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphFormat {
+    JsonLd,
+    Turtle,
+}
+
+impl GraphFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::JsonLd => "application/ld+json",
+            Self::Turtle => "text/turtle; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Object {
+    /// Another entity's IRI.
+    Node(String),
+    /// An entry page on the site.
+    Url(String),
+    Term(&'static str),
+    Text(String),
+    /// Biblical `YYYYMMDD` date (Hebrew year from creation).
+    Date(i64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Fact {
+    predicate: &'static str,
+    object: Object,
+    alt_group_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Node {
+    types: Vec<&'static str>,
+    facts: Vec<Fact>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Opinion {
+    label: Option<String>,
+    is_preferred: bool,
+}
+
+/// The Tanahpedia entities and their relations, keyed by IRI.
+#[derive(Debug, Default)]
+pub struct KnowledgeGraph {
+    nodes: BTreeMap<String, Node>,
+    opinions: BTreeMap<String, Opinion>,
+}
+
+#[derive(FromQueryResult)]
+struct EntityRow {
+    id: String,
+    entity_type: String,
+    name: String,
+}
+
+#[derive(FromQueryResult)]
+struct EntryLinkRow {
+    entity_id: String,
+    unique_name: String,
+}
+
+#[derive(FromQueryResult)]
+struct OpinionRow {
+    id: String,
+    label: Option<String>,
+    is_preferred: bool,
+}
+
+#[derive(FromQueryResult)]
+struct EdgeRow {
+    subject: String,
+    object: String,
+    alt_group_id: Option<String>,
+}
+
+#[derive(FromQueryResult)]
+struct LiteralRow {
+    subject: String,
+    value: String,
+    alt_group_id: Option<String>,
+}
+
+/// Percent-encodes the characters Turtle forbids inside `<...>` plus the IRI
+/// delimiters, leaving Hebrew as-is.
+pub(crate) fn iri_segment(value: &str) -> String {
+    let mut encoded = String::new();
+    for ch in value.chars() {
+        if ch.is_control() || " <>\"{}|^`\\#?/%".contains(ch) {
+            let mut buf = [0u8; 4];
+            for byte in ch.encode_utf8(&mut buf).bytes() {
+                encoded.push_str(&format!("%{byte:02X}"));
+            }
+        } else {
+            encoded.push(ch);
+        }
+    }
+    encoded
+}
+
+fn entry_url(unique_name: &str) -> String {
+    format!("{SITE_BASE}/pedia/{}", iri_segment(unique_name))
+}
+
+/// An entity's IRI. It is built from the id, never from an entry name, so it
+/// survives entries being renamed, merged or split.
+fn entity_iri(entity_id: &str) -> String {
+    format!("{SITE_BASE}/tanahpedia/id/{}", iri_segment(entity_id))
+}
+
+fn opinion_iri(alt_group_id: &str) -> String {
+    format!(
+        "{SITE_BASE}/tanahpedia/opinion/{}",
+        iri_segment(alt_group_id)
+    )
+}
+
+fn entity_types(entity_type: &str) -> Vec<&'static str> {
+    match entity_type {
+        "PERSON" => vec!["schema:Person"],
+        "PLACE" => vec!["schema:Place"],
+        "EVENT" => vec!["schema:Event"],
+        "WAR" => vec!["schema:Event", "tp:War"],
+        "NATION" => vec!["tp:Nation"],
+        "SAYING" => vec!["tp:Saying"],
+        "PROPHECY" => vec!["tp:Prophecy"],
+        "SEFER" => vec!["schema:Book", "tp:Sefer"],
+        "ANIMAL" => vec!["tp:Animal"],
+        "PLANT" => vec!["tp:Plant"],
+        "OBJECT" => vec!["tp:Object"],
+        "TEMPLE_TOOL" => vec!["tp:TempleTool"],
+        "ASTRONOMICAL_OBJECT" => vec!["tp:AstronomicalObject"],
+        _ => vec!["tp:Entity"],
+    }
+}
+
+fn gender_term(sex: &str) -> Option<&'static str> {
+    match sex {
+        "MALE" => Some("schema:Male"),
+        "FEMALE" => Some("schema:Female"),
+        _ => None,
+    }
+}
+
+impl KnowledgeGraph {
+    /// Builds the node of every entity. Each entry describing the entity is
+    /// linked with `schema:url`.
+    fn new(entities: Vec<EntityRow>, entry_links: Vec<EntryLinkRow>) -> Self {
+        let mut links: HashMap<String, Vec<EntryLinkRow>> = HashMap::new();
+        for link in entry_links {
+            links.entry(link.entity_id.clone()).or_default().push(link);
+        }
+
+        let mut graph = Self::default();
+        for entity in entities {
+            let mut node = Node {
+                types: entity_types(&entity.entity_type),
+                facts: vec![Fact {
+                    predicate: "schema:name",
+                    object: Object::Text(entity.name),
+                    alt_group_id: None,
+                }],
+            };
+            for link in links.remove(&entity.id).unwrap_or_default() {
+                node.facts.push(Fact {
+                    predicate: "schema:url",
+                    object: Object::Url(entry_url(&link.unique_name)),
+                    alt_group_id: None,
+                });
+            }
+            graph.nodes.insert(entity_iri(&entity.id), node);
+        }
+        graph
+    }
+
+    fn contains(&self, entity_id: &str) -> bool {
+        self.nodes.contains_key(&entity_iri(entity_id))
+    }
+
+    fn add_fact(
+        &mut self,
+        subject: &str,
+        predicate: &'static str,
+        object: Object,
+        alt_group_id: Option<String>,
+    ) {
+        if let Some(node) = self.nodes.get_mut(&entity_iri(subject)) {
+            node.facts.push(Fact {
+                predicate,
+                object,
+                alt_group_id,
+            });
+        }
+    }
+
+    fn add_opinion(&mut self, id: String, label: Option<String>, is_preferred: bool) {
+        self.opinions.insert(
+            id,
+            Opinion {
+                label,
+                is_preferred,
+            },
+        );
+    }
+
+    /// schema.org JSON-LD. schema.org terms are bare; Tanahpedia vocabulary
+    /// terms keep their `tp:` prefix, so a nation or a saying is emitted with
+    /// its `tp:` type. Opinions are Turtle-only.
+    pub fn to_json_ld(&self) -> Value {
+        let mut graph = Vec::new();
+        for (iri, node) in &self.nodes {
+            let types: Vec<&str> = node.types.iter().map(|t| json_ld_term(t)).collect();
+
+            let mut object = Map::new();
+            object.insert("@id".to_string(), json!(iri));
+            object.insert(
+                "@type".to_string(),
+                if types.len() == 1 {
+                    json!(types[0])
+                } else {
+                    json!(types)
+                },
+            );
+            let mut properties: BTreeMap<&str, Vec<Value>> = BTreeMap::new();
+            for fact in &node.facts {
+                let property = json_ld_term(fact.predicate);
+                let value = match &fact.object {
+                    Object::Node(target) => json!({ "@id": target }),
+                    Object::Url(url) => json!(url),
+                    Object::Term(term) => json!({ "@id": term.replace("schema:", SCHEMA_NS) }),
+                    Object::Text(text) => json!(text),
+                    Object::Date(date) => json!(date),
+                };
+                properties.entry(property).or_default().push(value);
+            }
+            for (property, mut values) in properties {
+                let value = if values.len() == 1 {
+                    values.remove(0)
+                } else {
+                    Value::Array(values)
+                };
+                object.insert(property.to_string(), value);
+            }
+            graph.push(Value::Object(object));
+        }
+
+        json!({
+            "@context": {
+                "@vocab": SCHEMA_NS,
+                "@language": "he",
+                "tp": format!("{SITE_BASE}{VOCAB_PATH}"),
+                "url": { "@type": "@id" },
+            },
+            "@graph": graph,
+        })
+    }
+
+    /// RDF Turtle with schema.org terms plus the Tanahpedia vocabulary.
+    /// Facts that belong to an opinion are also reified as `rdf:Statement`s
+    /// pointing at their `tp:Opinion`.
+    pub fn to_turtle(&self) -> String {
+        let mut turtle = format!(
+            "@prefix schema: <{SCHEMA_NS}> .\n\
+             @prefix tp: <{SITE_BASE}{VOCAB_PATH}> .\n\
+             @prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .\n\
+             @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .\n\n"
+        );
+        let mut reified = Vec::new();
+
+        for (iri, node) in &self.nodes {
+            let mut lines = vec![format!("a {}", node.types.join(", "))];
+            for fact in &node.facts {
+                let object = turtle_object(&fact.object);
+                lines.push(format!("{} {}", fact.predicate, object));
+                if let Some(alt_group_id) = &fact.alt_group_id {
+                    reified.push(format!(
+                        "[] a rdf:Statement ;\n    rdf:subject <{iri}> ;\n    rdf:predicate {} ;\n    rdf:object {object} ;\n    tp:opinion <{}> .\n",
+                        fact.predicate,
+                        opinion_iri(alt_group_id)
+                    ));
+                }
+            }
+            turtle.push_str(&format!("<{iri}> {} .\n\n", lines.join(" ;\n    ")));
+        }
+
+        for (id, opinion) in &self.opinions {
+            let mut lines = vec!["a tp:Opinion".to_string()];
+            if let Some(label) = &opinion.label {
+                lines.push(format!("rdfs:label {}", turtle_text(label)));
+            }
+            lines.push(format!("tp:preferred {}", opinion.is_preferred));
+            turtle.push_str(&format!(
+                "<{}> {} .\n\n",
+                opinion_iri(id),
+                lines.join(" ;\n    ")
+            ));
+        }
+        for statement in reified {
+            turtle.push_str(&statement);
+            turtle.push('\n');
+        }
+        turtle
+    }
+}
+
+/// A term as JSON-LD writes it: bare under the schema.org `@vocab`, or as a
+/// `tp:` compact IRI.
+fn json_ld_term(term: &'static str) -> &'static str {
+    term.strip_prefix("schema:").unwrap_or(term)
+}
+
+fn turtle_object(object: &Object) -> String {
+    match object {
+        Object::Node(iri) | Object::Url(iri) => format!("<{iri}>"),
+        Object::Term(term) => term.to_string(),
+        Object::Text(text) => turtle_text(text),
+        Object::Date(date) => date.to_string(),
+    }
+}
+
+fn turtle_text(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("\"{escaped}\"@he")
+}
+
+async fn fetch<T: FromQueryResult>(db: &Database, sql: &str) -> Result<Vec<T>, ServiceError> {
+    T::find_by_statement(Statement::from_string(DatabaseBackend::MySql, sql))
+        .all(db.get_connection())
+        .await
+        .map_err(db_error)
+}
+
+/// Loads every entity, relation and opinion into a [`KnowledgeGraph`].
+pub async fn load_graph(db: &Database) -> Result<KnowledgeGraph, ServiceError> {
+    let entities = fetch::<EntityRow>(db, ENTITIES_SQL).await?;
+    let entry_links = fetch::<EntryLinkRow>(db, ENTRY_LINKS_SQL).await?;
+    let mut graph = KnowledgeGraph::new(entities, entry_links);
+
+    for &(predicate, sql) in EDGES {
+        for row in fetch::<EdgeRow>(db, sql).await? {
+            if !graph.contains(&row.object) {
+                continue;
+            }
+            graph.add_fact(
+                &row.subject,
+                predicate,
+                Object::Node(entity_iri(&row.object)),
+                row.alt_group_id,
+            );
+        }
+    }
+    for &(predicate, sql) in LITERALS {
+        for row in fetch::<LiteralRow>(db, sql).await? {
+            let object = match predicate {
+                "schema:gender" => match gender_term(&row.value) {
+                    Some(term) => Object::Term(term),
+                    None => continue,
+                },
+                "tp:birthDate" | "tp:deathDate" => match row.value.parse() {
+                    Ok(date) => Object::Date(date),
+                    Err(_) => continue,
+                },
+                _ => Object::Text(row.value),
+            };
+            graph.add_fact(&row.subject, predicate, object, row.alt_group_id);
+        }
+    }
+    for row in fetch::<OpinionRow>(db, OPINIONS_SQL).await? {
+        graph.add_opinion(row.id, row.label, row.is_preferred);
+    }
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(id: &str, entity_type: &str, name: &str) -> EntityRow {
+        EntityRow {
+            id: id.to_string(),
+            entity_type: entity_type.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    fn link(entity_id: &str, unique_name: &str) -> EntryLinkRow {
+        EntryLinkRow {
+            entity_id: entity_id.to_string(),
+            unique_name: unique_name.to_string(),
+        }
+    }
+
+    fn family_graph() -> KnowledgeGraph {
+        let mut graph = KnowledgeGraph::new(
+            vec![
+                entity("e-manoah", "PERSON", "מנוח"),
+                entity("e-shimshon", "PERSON", "שמשון"),
+                entity("e-tzora", "PLACE", "צרעה"),
+                entity("e-saying", "SAYING", "חידת שמשון"),
+            ],
+            vec![
+                link("e-shimshon", "שמשון"),
+                link("e-tzora", "צרעה"),
+                link("e-saying", "צרעה"),
+            ],
+        );
+        graph.add_fact(
+            "e-shimshon",
+            "schema:parent",
+            Object::Node(entity_iri("e-manoah")),
+            None,
+        );
+        graph.add_fact(
+            "e-shimshon",
+            "schema:birthPlace",
+            Object::Node(entity_iri("e-tzora")),
+            Some("alt-1".to_string()),
+        );
+        graph.add_fact(
+            "e-shimshon",
+            "schema:gender",
+            Object::Term("schema:Male"),
+            None,
+        );
+        graph.add_fact(
+            "e-saying",
+            "tp:speaker",
+            Object::Node(entity_iri("e-shimshon")),
+            None,
+        );
+        graph.add_opinion("alt-1".to_string(), Some("רש\"י".to_string()), true);
+        graph
+    }
+
+    #[test]
+    fn iris_are_entity_ids_and_entries_are_urls() {
+        let graph = family_graph();
+
+        assert_eq!(
+            entity_iri("e-shimshon"),
+            "https://xn--febl3a.co.il/tanahpedia/id/e-shimshon"
+        );
+        let tzora = &graph.nodes[&entity_iri("e-tzora")];
+        assert!(tzora.facts.contains(&Fact {
+            predicate: "schema:url",
+            object: Object::Url("https://xn--febl3a.co.il/pedia/צרעה".to_string()),
+            alt_group_id: None,
+        }));
+        assert_eq!(iri_segment("a b/c#d"), "a%20b%2Fc%23d");
+    }
+
+    #[test]
+    fn json_ld_uses_schema_org_terms_and_the_tp_vocabulary() {
+        let graph = family_graph();
+        let json_ld = graph.to_json_ld();
+
+        let nodes = json_ld["@graph"].as_array().unwrap();
+        assert_eq!(nodes.len(), 4);
+        let node = |entity_id: &str| {
+            nodes
+                .iter()
+                .find(|node| node["@id"] == json!(entity_iri(entity_id)))
+                .unwrap()
+        };
+        let shimshon = node("e-shimshon");
+        assert_eq!(shimshon["@type"], "Person");
+        assert_eq!(shimshon["name"], "שמשון");
+        assert_eq!(shimshon["parent"], json!({ "@id": entity_iri("e-manoah") }));
+        assert_eq!(
+            shimshon["birthPlace"],
+            json!({ "@id": entity_iri("e-tzora") })
+        );
+        assert_eq!(
+            shimshon["gender"],
+            json!({ "@id": "https://schema.org/Male" })
+        );
+        assert_eq!(shimshon["url"], "https://xn--febl3a.co.il/pedia/שמשון");
+        let saying = node("e-saying");
+        assert_eq!(saying["@type"], "tp:Saying");
+        assert_eq!(
+            saying["tp:speaker"],
+            json!({ "@id": entity_iri("e-shimshon") })
+        );
+        assert_eq!(
+            json_ld["@context"]["tp"],
+            "https://xn--febl3a.co.il/tanahpedia/vocab#"
+        );
+    }
+
+    #[test]
+    fn turtle_reifies_opinion_facts_and_escapes_literals() {
+        let graph = family_graph();
+        let turtle = graph.to_turtle();
+
+        assert!(turtle.contains("@prefix tp: <https://xn--febl3a.co.il/tanahpedia/vocab#> ."));
+        assert!(turtle.contains(&format!("tp:speaker <{}>", entity_iri("e-shimshon"))));
+        assert!(turtle.contains("rdf:predicate schema:birthPlace ;"));
+        assert!(
+            turtle.contains("tp:opinion <https://xn--febl3a.co.il/tanahpedia/opinion/alt-1> .")
+        );
+        assert!(turtle.contains("rdfs:label \"רש\\\"י\"@he ;\n    tp:preferred true ."));
+    }
+}
@@ -0,0 +1,1109 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::{
+        tanahpedia_family::TanahpediaEntityType,
+        tanahpedia_import::{
+            TanahpediaImportEntityInput, TanahpediaImportInput, TanahpediaImportParentChildInput,
+            TanahpediaImportResult, TanahpediaImportSourceInput, TanahpediaImportUnionInput,
+            TanahpediaImportedEntity,
+        },
+    },
+    providers::Database,
+    services::{
+        tanahpedia_family_service::{
+            db_error, name_type_id, optional, parent_child_type_id, parent_role_id, required,
+            union_end_reason_id, union_type_id,
+        },
+        tanahpedia_places_service::{validate_latitude, validate_longitude},
+    },
+};
+use entities::perek;
+use entities::tanahpedia::{
+    entity, entity_tanah_source, person, person_name, person_parent_child, person_sex,
+    person_union, place, place_identification,
+    sea_orm_active_enums::{EntityType, Sex},
+};
+use sea_orm::prelude::Decimal;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter,
+    TransactionTrait, Value,
+};
+
+/// `place_identification` keeps coordinates to 8 decimal places.
+const COORDINATE_SCALE: u32 = 8;
+
+/// Rows an import inserted, updated or found already up to date.
+#[derive(Debug, Default, PartialEq)]
+struct Tally {
+    created: i32,
+    updated: i32,
+    unchanged: i32,
+}
+
+impl Tally {
+    /// Records a row that was `existing` and is now `wanted`, and tells the
+    /// caller whether it needs writing.
+    fn record<M: PartialEq>(&mut self, existing: Option<&M>, wanted: &M) -> bool {
+        match existing {
+            None => self.created += 1,
+            Some(existing) if existing == wanted => {
+                self.unchanged += 1;
+                return false;
+            }
+            Some(_) => self.updated += 1,
+        }
+        true
+    }
+}
+
+/// `column = value`, or `column IS NULL` when there is no value.
+fn eq_or_null<V: Into<Value>>(column: impl ColumnTrait, value: Option<V>) -> Condition {
+    match value {
+        Some(value) => Condition::all().add(column.eq(value)),
+        None => Condition::all().add(column.is_null()),
+    }
+}
+
+fn new_id(existing: Option<&str>) -> String {
+    existing.map_or_else(|| uuid::Uuid::new_v4().to_string(), str::to_string)
+}
+
+fn coordinate(value: Option<f64>) -> Option<Decimal> {
+    value
+        .and_then(|value| Decimal::try_from(value).ok())
+        .map(|value| value.round_dp(COORDINATE_SCALE))
+}
+
+/// Trims and checks `input` with the rules `tanahpedia_family_service` applies
+/// to the same fields, and checks that every key and entity id is defined
+/// once, that no two entities share a type and name, and that every key
+/// refers to an entity of the right type.
+fn normalize(input: TanahpediaImportInput) -> Result<TanahpediaImportInput, ServiceError> {
+    let mut types = HashMap::new();
+    let mut entity_ids = HashMap::new();
+    let mut names = HashMap::new();
+    let mut entities = Vec::with_capacity(input.entities.len());
+    for (i, entity) in input.entities.into_iter().enumerate() {
+        let field = format!("entities[{i}]");
+        let key = required(entity.key, &format!("{field}.key"), 100)?;
+        if types.insert(key.clone(), entity.entity_type).is_some() {
+            return Err(ServiceError::bad_request(&format!(
+                "{field}.key {key} is defined more than once"
+            )));
+        }
+        let entity_id = optional(entity.entity_id, &format!("{field}.entityId"), 36)?;
+        if let Some(entity_id) = &entity_id {
+            if uuid::Uuid::parse_str(entity_id).is_err() {
+                return Err(ServiceError::bad_request(&format!(
+                    "{field}.entityId must be a UUID"
+                )));
+            }
+            if let Some(other) = entity_ids.insert(entity_id.clone(), key.clone()) {
+                return Err(ServiceError::bad_request(&format!(
+                    "{field}.entityId {entity_id} is also used by {other}"
+                )));
+            }
+        }
+        let name = required(entity.name, &format!("{field}.name"), 255)?;
+        if let Some(other) = names.insert((entity.entity_type, name.clone()), key.clone()) {
+            return Err(ServiceError::bad_request(&format!(
+                "{field}: {other} has the same type and name"
+            )));
+        }
+        let is_person = entity.entity_type == TanahpediaEntityType::Person;
+        let is_place = entity.entity_type == TanahpediaEntityType::Place;
+        if !is_person && (entity.sex.is_some() || !entity.names.is_empty()) {
+            return Err(ServiceError::bad_request(&format!(
+                "{field}: sex and names are for PERSON entities only"
+            )));
+        }
+        if !is_place && !entity.identifications.is_empty() {
+            return Err(ServiceError::bad_request(&format!(
+                "{field}: identifications are for PLACE entities only"
+            )));
+        }
+
+        let mut names = Vec::with_capacity(entity.names.len());
+        for (j, mut name) in entity.names.into_iter().enumerate() {
+            name.name = required(name.name, &format!("{field}.names[{j}].name"), 255)?;
+            name.name_type = required(name.name_type, &format!("{field}.names[{j}].nameType"), 50)?;
+            names.push(name);
+        }
+        let mut identifications = Vec::with_capacity(entity.identifications.len());
+        for (j, mut identification) in entity.identifications.into_iter().enumerate() {
+            let field = format!("{field}.identifications[{j}]");
+            identification.modern_name = optional(
+                identification.modern_name,
+                &format!("{field}.modernName"),
+                255,
+            )?;
+            if identification.latitude.is_some() != identification.longitude.is_some() {
+                return Err(ServiceError::bad_request(&format!(
+                    "{field} needs both latitude and longitude, or neither"
+                )));
+            }
+            if let (Some(latitude), Some(longitude)) =
+                (identification.latitude, identification.longitude)
+            {
+                validate_latitude(latitude, &field)?;
+                validate_longitude(longitude, &field)?;
+            }
+            identifications.push(identification);
+        }
+        let mut sources = Vec::with_capacity(entity.sources.len());
+        for (j, mut source) in entity.sources.into_iter().enumerate() {
+            let field = format!("{field}.sources[{j}]");
+            source.sefer = required(source.sefer, &format!("{field}.sefer"), 100)?;
+            if source.perek < 1 || source.pasuk < 1 {
+                return Err(ServiceError::bad_request(&format!(
+                    "{field}: perek and pasuk must be positive"
+                )));
+            }
+            sources.push(source);
+        }
+
+        entities.push(TanahpediaImportEntityInput {
+            key,
+            entity_id,
+            entity_type: entity.entity_type,
+            name,
+            sex: entity.sex,
+            names,
+            identifications,
+            sources,
+        });
+    }
+
+    let person_key = |key: String, field: String| -> Result<String, ServiceError> {
+        let key = required(key, &field, 100)?;
+        match types.get(&key) {
+            Some(TanahpediaEntityType::Person) => Ok(key),
+            Some(_) => Err(ServiceError::bad_request(&format!(
+                "{field} {key} is not a PERSON entity"
+            ))),
+            None => Err(ServiceError::bad_request(&format!(
+                "{field} {key} is not defined under entities"
+            ))),
+        }
+    };
+
+    let mut parent_child = Vec::with_capacity(input.parent_child.len());
+    for (i, link) in input.parent_child.into_iter().enumerate() {
+        let field = format!("parentChild[{i}]");
+        let link = TanahpediaImportParentChildInput {
+            parent: person_key(link.parent, format!("{field}.parent"))?,
+            child: person_key(link.child, format!("{field}.child"))?,
+            relationship_type: required(
+                link.relationship_type,
+                &format!("{field}.relationshipType"),
+                50,
+            )?,
+            parent_role: required(link.parent_role, &format!("{field}.parentRole"), 50)?,
+            source_citation: optional(
+                link.source_citation,
+                &format!("{field}.sourceCitation"),
+                400,
+            )?,
+        };
+        if link.parent == link.child {
+            return Err(ServiceError::bad_request(&format!(
+                "{field}: a person cannot be their own parent"
+            )));
+        }
+        parent_child.push(link);
+    }
+
+    let mut unions = Vec::with_capacity(input.unions.len());
+    for (i, union) in input.unions.into_iter().enumerate() {
+        let field = format!("unions[{i}]");
+        let union = TanahpediaImportUnionInput {
+            person1: person_key(union.person1, format!("{field}.person1"))?,
+            person2: person_key(union.person2, format!("{field}.person2"))?,
+            union_type: required(union.union_type, &format!("{field}.unionType"), 50)?,
+            end_reason: optional(union.end_reason, &format!("{field}.endReason"), 50)?,
+            source_citation: optional(
+                union.source_citation,
+                &format!("{field}.sourceCitation"),
+                400,
+            )?,
+            person_source_citation: optional(
+                union.person_source_citation,
+                &format!("{field}.personSourceCitation"),
+                400,
+            )?,
+            ..union
+        };
+        if union.person1 == union.person2 {
+            return Err(ServiceError::bad_request(&format!(
+                "{field}: a person cannot be united with themselves"
+            )));
+        }
+        unions.push(union);
+    }
+
+    Ok(TanahpediaImportInput {
+        entities,
+        parent_child,
+        unions,
+    })
+}
+
+/// The existing entity `input` names: the one with its `entityId`, or else
+/// the only entity of its type and name. `None` when there is none yet.
+async fn find_entity<C: ConnectionTrait>(
+    conn: &C,
+    input: &TanahpediaImportEntityInput,
+) -> Result<Option<entity::Model>, ServiceError> {
+    let entity_type = EntityType::from(input.entity_type);
+    let Some(entity_id) = &input.entity_id else {
+        let mut namesakes = entity::Entity::find()
+            .filter(entity::Column::EntityType.eq(entity_type))
+            .filter(entity::Column::Name.eq(input.name.clone()))
+            .all(conn)
+            .await
+            .map_err(db_error)?;
+        if namesakes.len() > 1 {
+            return Err(ServiceError::bad_request(&format!(
+                "entity {}: {} entities are named {}; give the entityId of the one to import into",
+                input.key,
+                namesakes.len(),
+                input.name
+            )));
+        }
+        return Ok(namesakes.pop());
+    };
+
+    let existing = entity::Entity::find_by_id(entity_id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| {
+            ServiceError::bad_request(&format!(
+                "entity {}: entityId {entity_id} does not exist; omit it to create the entity",
+                input.key
+            ))
+        })?;
+    if existing.entity_type != entity_type {
+        return Err(ServiceError::bad_request(&format!(
+            "entity {}: {entity_id} is an entity of another type",
+            input.key
+        )));
+    }
+    Ok(Some(existing))
+}
+
+/// The entity `input` names, renamed when the document names it differently,
+/// or a new one under a fresh id.
+async fn import_entity<C: ConnectionTrait>(
+    conn: &C,
+    input: &TanahpediaImportEntityInput,
+    tally: &mut Tally,
+) -> Result<(String, bool), ServiceError> {
+    let now = chrono::Utc::now().naive_utc();
+    if let Some(existing) = find_entity(conn, input).await? {
+        if existing.name == input.name {
+            tally.unchanged += 1;
+        } else {
+            tally.updated += 1;
+            entity::Entity::update_many()
+                .col_expr(entity::Column::Name, Expr::value(input.name.clone()))
+                .col_expr(entity::Column::UpdatedAt, Expr::value(now))
+                .filter(entity::Column::Id.eq(existing.id.clone()))
+                .exec(conn)
+                .await
+                .map_err(db_error)?;
+        }
+        return Ok((existing.id, false));
+    }
+
+    let model = entity::Model {
+        id: uuid::Uuid::new_v4().to_string(),
+        entity_type: EntityType::from(input.entity_type),
+        name: input.name.clone(),
+        created_at: now,
+        updated_at: now,
+    };
+    tally.created += 1;
+    entity::Entity::insert(model.clone().into_active_model())
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+    Ok((model.id, true))
+}
+
+async fn import_person<C: ConnectionTrait>(
+    conn: &C,
+    entity_id: &str,
+    input: &TanahpediaImportEntityInput,
+    tally: &mut Tally,
+) -> Result<String, ServiceError> {
+    let existing = person::Entity::find()
+        .filter(person::Column::EntityId.eq(entity_id))
+        .one(conn)
+        .await
+        .map_err(db_error)?;
+    let person_id = match existing {
+        Some(existing) => {
+            tally.unchanged += 1;
+            existing.id
+        }
+        None => {
+            let model = person::Model {
+                id: uuid::Uuid::new_v4().to_string(),
+                entity_id: entity_id.to_string(),
+            };
+            tally.created += 1;
+            person::Entity::insert(model.clone().into_active_model())
+                .exec(conn)
+                .await
+                .map_err(db_error)?;
+            model.id
+        }
+    };
+
+    if let Some(sex) = input.sex {
+        let existing = person_sex::Entity::find()
+            .filter(person_sex::Column::PersonId.eq(person_id.clone()))
+            .filter(person_sex::Column::AltGroupId.is_null())
+            .one(conn)
+            .await
+            .map_err(db_error)?;
+        let model = person_sex::Model {
+            id: new_id(existing.as_ref().map(|existing| existing.id.as_str())),
+            person_id: person_id.clone(),
+            sex: Sex::from(sex),
+            alt_group_id: None,
+        };
+        if tally.record(existing.as_ref(), &model) {
+            person_sex::Entity::insert(model.into_active_model())
+                .on_conflict(
+                    OnConflict::column(person_sex::Column::Id)
+                        .update_column(person_sex::Column::Sex)
+                        .to_owned(),
+                )
+                .exec(conn)
+                .await
+                .map_err(db_error)?;
+        }
+    }
+
+    for name in &input.names {
+        let existing = person_name::Entity::find()
+            .filter(person_name::Column::PersonId.eq(person_id.clone()))
+            .filter(person_name::Column::Name.eq(name.name.clone()))
+            .one(conn)
+            .await
+            .map_err(db_error)?;
+        let model = person_name::Model {
+            id: new_id(existing.as_ref().map(|existing| existing.id.as_str())),
+            person_id: person_id.clone(),
+            name: name.name.clone(),
+            name_type_id: name_type_id(conn, name.name_type.clone()).await?,
+            alt_group_id: existing
+                .as_ref()
+                .and_then(|existing| existing.alt_group_id.clone()),
+        };
+        if tally.record(existing.as_ref(), &model) {
+            person_name::Entity::insert(model.into_active_model())
+                .on_conflict(
+                    OnConflict::column(person_name::Column::Id)
+                        .update_column(person_name::Column::NameTypeId)
+                        .to_owned(),
+                )
+                .exec(conn)
+                .await
+                .map_err(db_error)?;
+        }
+    }
+
+    Ok(person_id)
+}
+
+async fn import_place<C: ConnectionTrait>(
+    conn: &C,
+    entity_id: &str,
+    input: &TanahpediaImportEntityInput,
+    tally: &mut Tally,
+) -> Result<(), ServiceError> {
+    let existing = place::Entity::find()
+        .filter(place::Column::EntityId.eq(entity_id))
+        .one(conn)
+        .await
+        .map_err(db_error)?;
+    let place_id = match existing {
+        Some(existing) => {
+            tally.unchanged += 1;
+            existing.id
+        }
+        None => {
+            let model = place::Model {
+                id: uuid::Uuid::new_v4().to_string(),
+                entity_id: entity_id.to_string(),
+            };
+            tally.created += 1;
+            place::Entity::insert(model.clone().into_active_model())
+                .exec(conn)
+                .await
+                .map_err(db_error)?;
+            model.id
+        }
+    };
+
+    for identification in &input.identifications {
+        let existing = place_identification::Entity::find()
+            .filter(place_identification::Column::PlaceId.eq(place_id.clone()))
+            .filter(eq_or_null(
+                place_identification::Column::ModernName,
+                identification.modern_name.clone(),
+            ))
+            .filter(place_identification::Column::AltGroupId.is_null())
+            .one(conn)
+            .await
+            .map_err(db_error)?;
+        let model = place_identification::Model {
+            id: new_id(existing.as_ref().map(|existing| existing.id.as_str())),
+            place_id: place_id.clone(),
+            modern_name: identification.modern_name.clone(),
+            latitude: coordinate(identification.latitude),
+            longitude: coordinate(identification.longitude),
+            alt_group_id: None,
+        };
+        if tally.record(existing.as_ref(), &model) {
+            place_identification::Entity::insert(model.into_active_model())
+                .on_conflict(
+                    OnConflict::column(place_identification::Column::Id)
+                        .update_columns([
+                            place_identification::Column::Latitude,
+                            place_identification::Column::Longitude,
+                        ])
+                        .to_owned(),
+                )
+                .exec(conn)
+                .await
+                .map_err(db_error)?;
+        }
+    }
+    Ok(())
+}
+
+/// The `tanah_perek_view` id of `perek` in `sefer` ("בראשית", "שמואל א").
+async fn perek_id<C: ConnectionTrait>(
+    conn: &C,
+    source: &TanahpediaImportSourceInput,
+) -> Result<i32, ServiceError> {
+    perek::Entity::find()
+        .filter(perek::Column::PerekInContext.eq(source.perek))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .find(|row| {
+            let sefer = row.sefer_name.as_deref().unwrap_or_default();
+            match row.additional_letter.as_deref() {
+                Some(letter) => format!("{sefer} {letter}") == source.sefer,
+                None => sefer == source.sefer,
+            }
+        })
+        .map(|row| row.id)
+        .ok_or_else(|| {
+            ServiceError::bad_request(&format!(
+                "unknown perek {} in sefer {}",
+                source.perek, source.sefer
+            ))
+        })
+}
+
+async fn import_sources<C: ConnectionTrait>(
+    conn: &C,
+    entity_id: &str,
+    input: &TanahpediaImportEntityInput,
+    tally: &mut Tally,
+) -> Result<(), ServiceError> {
+    for source in &input.sources {
+        let perek_id = perek_id(conn, source).await?;
+        let existing = entity_tanah_source::Entity::find()
+            .filter(entity_tanah_source::Column::EntityId.eq(entity_id))
+            .filter(entity_tanah_source::Column::PerekId.eq(perek_id))
+            .filter(entity_tanah_source::Column::PasukNumber.eq(source.pasuk))
+            .filter(eq_or_null(
+                entity_tanah_source::Column::SegmentStart,
+                source.segment_start,
+            ))
+            .filter(eq_or_null(
+                entity_tanah_source::Column::SegmentEnd,
+                source.segment_end,
+            ))
+            .one(conn)
+            .await
+            .map_err(db_error)?;
+        if existing.is_some() {
+            tally.unchanged += 1;
+            continue;
+        }
+        tally.created += 1;
+        entity_tanah_source::Entity::insert(
+            entity_tanah_source::Model {
+                id: uuid::Uuid::new_v4().to_string(),
+                entity_id: entity_id.to_string(),
+                perek_id,
+                pasuk_number: source.pasuk,
+                segment_start: source.segment_start,
+                segment_end: source.segment_end,
+            }
+            .into_active_model(),
+        )
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+    }
+    Ok(())
+}
+
+async fn import_parent_child<C: ConnectionTrait>(
+    conn: &C,
+    parent_id: &str,
+    child_id: &str,
+    input: &TanahpediaImportParentChildInput,
+    tally: &mut Tally,
+) -> Result<(), ServiceError> {
+    let existing = person_parent_child::Entity::find()
+        .filter(person_parent_child::Column::ParentId.eq(parent_id))
+        .filter(person_parent_child::Column::ChildId.eq(child_id))
+        .filter(person_parent_child::Column::AltGroupId.is_null())
+        .one(conn)
+        .await
+        .map_err(db_error)?;
+    let model = person_parent_child::Model {
+        id: new_id(existing.as_ref().map(|existing| existing.id.as_str())),
+        parent_id: parent_id.to_string(),
+        child_id: child_id.to_string(),
+        relationship_type_id: parent_child_type_id(conn, input.relationship_type.clone()).await?,
+        parent_role_id: parent_role_id(conn, input.parent_role.clone()).await?,
+        alt_group_id: None,
+        source_citation: input.source_citation.clone(),
+    };
+    if tally.record(existing.as_ref(), &model) {
+        person_parent_child::Entity::insert(model.into_active_model())
+            .on_conflict(
+                OnConflict::column(person_parent_child::Column::Id)
+                    .update_columns([
+                        person_parent_child::Column::RelationshipTypeId,
+                        person_parent_child::Column::ParentRoleId,
+                        person_parent_child::Column::SourceCitation,
+                    ])
+                    .to_owned(),
+            )
+            .exec(conn)
+            .await
+            .map_err(db_error)?;
+    }
+    Ok(())
+}
+
+async fn import_union<C: ConnectionTrait>(
+    conn: &C,
+    person1_id: &str,
+    person2_id: &str,
+    input: &TanahpediaImportUnionInput,
+    tally: &mut Tally,
+) -> Result<(), ServiceError> {
+    let existing = person_union::Entity::find()
+        .filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(person_union::Column::Person1Id.eq(person1_id))
+                        .add(person_union::Column::Person2Id.eq(person2_id)),
+                )
+                .add(
+                    Condition::all()
+                        .add(person_union::Column::Person1Id.eq(person2_id))
+                        .add(person_union::Column::Person2Id.eq(person1_id)),
+                ),
+        )
+        .filter(person_union::Column::AltGroupId.is_null())
+        .one(conn)
+        .await
+        .map_err(db_error)?;
+    // An existing union keeps its partner order.
+    let (person1_id, person2_id) = existing
+        .as_ref()
+        .map_or((person1_id, person2_id), |existing| {
+            (existing.person1_id.as_str(), existing.person2_id.as_str())
+        });
+    let model = person_union::Model {
+        id: new_id(existing.as_ref().map(|existing| existing.id.as_str())),
+        person1_id: person1_id.to_string(),
+        person2_id: person2_id.to_string(),
+        union_type_id: union_type_id(conn, input.union_type.clone()).await?,
+        union_order: input.union_order,
+        start_date: input.start_date,
+        end_date: input.end_date,
+        end_reason_id: union_end_reason_id(conn, input.end_reason.clone()).await?,
+        alt_group_id: None,
+        source_citation: input.source_citation.clone(),
+        person_source_citation: input.person_source_citation.clone(),
+    };
+    if tally.record(existing.as_ref(), &model) {
+        person_union::Entity::insert(model.into_active_model())
+            .on_conflict(
+                OnConflict::column(person_union::Column::Id)
+                    .update_columns([
+                        person_union::Column::UnionTypeId,
+                        person_union::Column::UnionOrder,
+                        person_union::Column::StartDate,
+                        person_union::Column::EndDate,
+                        person_union::Column::EndReasonId,
+                        person_union::Column::SourceCitation,
+                        person_union::Column::PersonSourceCitation,
+                    ])
+                    .to_owned(),
+            )
+            .exec(conn)
+            .await
+            .map_err(db_error)?;
+    }
+    Ok(())
+}
+
+/// Imports a content document in one transaction. Entities are matched by
+/// their id or their type and name, and every other row by its natural key,
+/// so importing the same document again changes nothing. With `dry_run` the transaction is rolled back and
+/// the result tells what would have been written.
+pub async fn import_content(
+    db: &Database,
+    input: TanahpediaImportInput,
+    dry_run: bool,
+) -> Result<TanahpediaImportResult, ServiceError> {
+    tracing::info_span!("tanahpedia_import_service::import_content");
+
+    let document = normalize(input)?;
+    let transaction = db.get_connection().begin().await.map_err(db_error)?;
+    let mut tally = Tally::default();
+    let mut entities = Vec::with_capacity(document.entities.len());
+    let mut person_ids = HashMap::new();
+    let mut keys_by_entity = HashMap::new();
+
+    for input in &document.entities {
+        let (entity_id, created) = import_entity(&transaction, input, &mut tally).await?;
+        if let Some(other) = keys_by_entity.insert(entity_id.clone(), input.key.as_str()) {
+            return Err(ServiceError::bad_request(&format!(
+                "entity {}: {other} already imports into entity {entity_id}",
+                input.key
+            )));
+        }
+        match input.entity_type {
+            TanahpediaEntityType::Person => {
+                let person_id = import_person(&transaction, &entity_id, input, &mut tally).await?;
+                person_ids.insert(input.key.as_str(), person_id);
+            }
+            TanahpediaEntityType::Place => {
+                import_place(&transaction, &entity_id, input, &mut tally).await?;
+            }
+            _ => {}
+        }
+        import_sources(&transaction, &entity_id, input, &mut tally).await?;
+        entities.push(TanahpediaImportedEntity {
+            key: input.key.clone(),
+            entity_id,
+            created,
+        });
+    }
+
+    // `normalize` guarantees every edge key names a PERSON entity.
+    let mut seen_edges = HashSet::new();
+    for link in &document.parent_child {
+        let (parent_id, child_id) = (
+            &person_ids[link.parent.as_str()],
+            &person_ids[link.child.as_str()],
+        );
+        if !seen_edges.insert((parent_id.clone(), child_id.clone())) {
+            return Err(ServiceError::bad_request(&format!(
+                "parentChild lists {} → {} more than once",
+                link.parent, link.child
+            )));
+        }
+        import_parent_child(&transaction, parent_id, child_id, link, &mut tally).await?;
+    }
+    for union in &document.unions {
+        let (person1_id, person2_id) = (
+            &person_ids[union.person1.as_str()],
+            &person_ids[union.person2.as_str()],
+        );
+        import_union(&transaction, person1_id, person2_id, union, &mut tally).await?;
+    }
+
+    if dry_run {
+        transaction.rollback().await.map_err(db_error)?;
+    } else {
+        transaction.commit().await.map_err(db_error)?;
+    }
+
+    tracing::info!(
+        dry_run,
+        created = tally.created,
+        updated = tally.updated,
+        unchanged = tally.unchanged,
+        "Imported Tanahpedia content"
+    );
+    Ok(TanahpediaImportResult {
+        dry_run,
+        entities,
+        created: tally.created,
+        updated: tally.updated,
+        unchanged: tally.unchanged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::{
+        tanahpedia_family::TanahpediaSex, tanahpedia_import::TanahpediaImportPersonNameInput,
+    };
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    fn exec_result() -> MockExecResult {
+        MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }
+    }
+
+    /// A fixed UUID per key, for entities that already exist.
+    fn entity_id(key: &str) -> String {
+        let seed = key
+            .trim()
+            .bytes()
+            .fold(0u128, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u128));
+        uuid::Uuid::from_u128(seed).to_string()
+    }
+
+    fn entity_input(
+        key: &str,
+        entity_type: TanahpediaEntityType,
+        name: &str,
+    ) -> TanahpediaImportEntityInput {
+        TanahpediaImportEntityInput {
+            key: key.to_string(),
+            entity_id: None,
+            entity_type,
+            name: name.to_string(),
+            sex: None,
+            names: Vec::new(),
+            identifications: Vec::new(),
+            sources: Vec::new(),
+        }
+    }
+
+    fn parent_child_input(parent: &str, child: &str) -> TanahpediaImportParentChildInput {
+        TanahpediaImportParentChildInput {
+            parent: parent.to_string(),
+            child: child.to_string(),
+            relationship_type: "biological".to_string(),
+            parent_role: "FATHER".to_string(),
+            source_citation: Some(" שופטים יג ב ".to_string()),
+        }
+    }
+
+    fn bad_request(result: Result<TanahpediaImportInput, ServiceError>) -> String {
+        match result {
+            Err(ServiceError::BadRequest(message)) => message,
+            other => panic!("expected a bad request, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn normalize_trims_fields() {
+        let mut manoah = entity_input(" manoah ", TanahpediaEntityType::Person, " מנוח ");
+        manoah.names.push(TanahpediaImportPersonNameInput {
+            name: " מנוח ".to_string(),
+            name_type: "MAIN".to_string(),
+        });
+        let input = TanahpediaImportInput {
+            entities: vec![
+                manoah,
+                entity_input("shimshon", TanahpediaEntityType::Person, "שמשון"),
+            ],
+            parent_child: vec![parent_child_input("manoah", " shimshon")],
+            unions: Vec::new(),
+        };
+
+        let document = normalize(input).unwrap();
+
+        assert_eq!(document.entities[0].key, "manoah");
+        assert_eq!(document.entities[0].name, "מנוח");
+        assert_eq!(document.entities[0].names[0].name, "מנוח");
+        assert_eq!(document.parent_child[0].child, "shimshon");
+        assert_eq!(
+            document.parent_child[0].source_citation.as_deref(),
+            Some("שופטים יג ב")
+        );
+    }
+
+    #[test]
+    fn normalize_rejects_bad_keys_and_edges() {
+        let person = |key: &str| entity_input(key, TanahpediaEntityType::Person, key);
+        let place = entity_input("tsora", TanahpediaEntityType::Place, "צרעה");
+
+        let duplicate = TanahpediaImportInput {
+            entities: vec![person("a"), person("a")],
+            ..Default::default()
+        };
+        assert_eq!(
+            bad_request(normalize(duplicate)),
+            "entities[1].key a is defined more than once"
+        );
+
+        let undefined = TanahpediaImportInput {
+            entities: vec![person("a")],
+            parent_child: vec![parent_child_input("a", "b")],
+            ..Default::default()
+        };
+        assert_eq!(
+            bad_request(normalize(undefined)),
+            "parentChild[0].child b is not defined under entities"
+        );
+
+        let not_a_person = TanahpediaImportInput {
+            entities: vec![person("a"), place.clone()],
+            parent_child: vec![parent_child_input("tsora", "a")],
+            ..Default::default()
+        };
+        assert_eq!(
+            bad_request(normalize(not_a_person)),
+            "parentChild[0].parent tsora is not a PERSON entity"
+        );
+
+        let own_parent = TanahpediaImportInput {
+            entities: vec![person("a")],
+            parent_child: vec![parent_child_input("a", "a")],
+            ..Default::default()
+        };
+        assert_eq!(
+            bad_request(normalize(own_parent)),
+            "parentChild[0]: a person cannot be their own parent"
+        );
+
+        let same_name = TanahpediaImportInput {
+            entities: vec![
+                entity_input("a", TanahpediaEntityType::Person, "עדה"),
+                entity_input("b", TanahpediaEntityType::Person, "עדה"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            bad_request(normalize(same_name)),
+            "entities[1]: a has the same type and name"
+        );
+
+        let mut first = person("a");
+        first.entity_id = Some(entity_id("a"));
+        let mut shared_id = person("b");
+        shared_id.entity_id = Some(entity_id("a"));
+        let shared_id = TanahpediaImportInput {
+            entities: vec![first, shared_id],
+            ..Default::default()
+        };
+        assert_eq!(
+            bad_request(normalize(shared_id)),
+            format!("entities[1].entityId {} is also used by a", entity_id("a"))
+        );
+
+        let mut unset_id = person("a");
+        unset_id.entity_id = Some("SHIMSHON-ENTITY-ID".to_string());
+        let unset_id = TanahpediaImportInput {
+            entities: vec![unset_id],
+            ..Default::default()
+        };
+        assert_eq!(
+            bad_request(normalize(unset_id)),
+            "entities[0].entityId must be a UUID"
+        );
+
+        let mut sexed_place = place;
+        sexed_place.sex = Some(TanahpediaSex::Male);
+        let misplaced = TanahpediaImportInput {
+            entities: vec![sexed_place],
+            ..Default::default()
+        };
+        assert_eq!(
+            bad_request(normalize(misplaced)),
+            "entities[0]: sex and names are for PERSON entities only"
+        );
+    }
+
+    #[tokio::test]
+    async fn import_content_dry_run_reports_new_rows() {
+        let mut manoah = entity_input("manoah", TanahpediaEntityType::Person, "מנוח");
+        manoah.sex = Some(TanahpediaSex::Male);
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([Vec::<entity::Model>::new()])
+                .append_query_results([Vec::<person::Model>::new()])
+                .append_query_results([Vec::<person_sex::Model>::new()])
+                .append_exec_results([exec_result(), exec_result(), exec_result()])
+                .into_connection(),
+        );
+
+        let result = import_content(
+            &db,
+            TanahpediaImportInput {
+                entities: vec![manoah],
+                ..Default::default()
+            },
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert!(result.dry_run);
+        assert!(result.entities[0].created);
+        assert!(uuid::Uuid::parse_str(&result.entities[0].entity_id).is_ok());
+        assert_eq!(
+            (result.created, result.updated, result.unchanged),
+            (3, 0, 0)
+        );
+    }
+
+    #[tokio::test]
+    async fn import_content_again_changes_nothing() {
+        let mut manoah = entity_input("manoah", TanahpediaEntityType::Person, "מנוח");
+        manoah.sex = Some(TanahpediaSex::Male);
+        let now = chrono::Utc::now().naive_utc();
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![entity::Model {
+                    id: entity_id("manoah"),
+                    entity_type: EntityType::Person,
+                    name: "מנוח".to_string(),
+                    created_at: now,
+                    updated_at: now,
+                }]])
+                .append_query_results([vec![person::Model {
+                    id: "p-manoah".to_string(),
+                    entity_id: entity_id("manoah"),
+                }]])
+                .append_query_results([vec![person_sex::Model {
+                    id: "s-manoah".to_string(),
+                    person_id: "p-manoah".to_string(),
+                    sex: Sex::Male,
+                    alt_group_id: None,
+                }]])
+                .into_connection(),
+        );
+
+        let result = import_content(
+            &db,
+            TanahpediaImportInput {
+                entities: vec![manoah],
+                ..Default::default()
+            },
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            result.entities,
+            vec![TanahpediaImportedEntity {
+                key: "manoah".to_string(),
+                entity_id: entity_id("manoah"),
+                created: false,
+            }]
+        );
+        assert_eq!(
+            (result.created, result.updated, result.unchanged),
+            (0, 0, 3)
+        );
+    }
+
+    #[tokio::test]
+    async fn import_content_rejects_unknown_ids_and_ambiguous_names() {
+        let now = chrono::Utc::now().naive_utc();
+        let ada = |key: &str| entity::Model {
+            id: entity_id(key),
+            entity_type: EntityType::Person,
+            name: "עדה".to_string(),
+            created_at: now,
+            updated_at: now,
+        };
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([Vec::<entity::Model>::new()])
+                .append_query_results([vec![ada("ada-lemech"), ada("ada-esav")]])
+                .into_connection(),
+        );
+        let message = |result: Result<TanahpediaImportResult, ServiceError>| match result {
+            Err(ServiceError::BadRequest(message)) => message,
+            other => panic!("expected a bad request, got {other:?}"),
+        };
+
+        let mut unknown = entity_input("ada", TanahpediaEntityType::Person, "עדה");
+        unknown.entity_id = Some(entity_id("ada"));
+        let unknown = import_content(
+            &db,
+            TanahpediaImportInput {
+                entities: vec![unknown],
+                ..Default::default()
+            },
+            true,
+        )
+        .await;
+        assert_eq!(
+            message(unknown),
+            format!(
+                "entity ada: entityId {} does not exist; omit it to create the entity",
+                entity_id("ada")
+            )
+        );
+
+        let ambiguous = import_content(
+            &db,
+            TanahpediaImportInput {
+                entities: vec![entity_input("ada", TanahpediaEntityType::Person, "עדה")],
+                ..Default::default()
+            },
+            true,
+        )
+        .await;
+        assert_eq!(
+            message(ambiguous),
+            "entity ada: 2 entities are named עדה; give the entityId of the one to import into"
+        );
+    }
+
+    #[tokio::test]
+    async fn import_content_rejects_an_id_of_another_entity_type() {
+        let now = chrono::Utc::now().naive_utc();
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![entity::Model {
+                    id: entity_id("tsora"),
+                    entity_type: EntityType::Place,
+                    name: "צרעה".to_string(),
+                    created_at: now,
+                    updated_at: now,
+                }]])
+                .into_connection(),
+        );
+
+        let result = import_content(
+            &db,
+            TanahpediaImportInput {
+                entities: vec![TanahpediaImportEntityInput {
+                    entity_id: Some(entity_id("tsora")),
+                    ..entity_input("tsora", TanahpediaEntityType::Person, "צרעה")
+                }],
+                ..Default::default()
+            },
+            true,
+        )
+        .await;
+
+        assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+    }
+}
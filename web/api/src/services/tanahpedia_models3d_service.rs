@@ -0,0 +1,430 @@
+use crate::{
+    common::error_handling::{INTERNAL_SERVER_ERROR, ServiceError},
+    dtos::tanahpedia_model3d::{
+        TanahpediaModel3d, TanahpediaModel3dFormat, UploadTanahpediaModel3dInput,
+    },
+    providers::{Database, Storage},
+    services::tanahpedia_family_service::{db_error, optional, required},
+};
+use entities::tanahpedia::{entity, three_d_model};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder};
+
+/// Upper bound for a single uploaded model.
+pub const MAX_MODEL_BYTES: usize = 50 * 1024 * 1024;
+
+const GLB_HEADER_LEN: usize = 12;
+const ZIP_LOCAL_HEADER_LEN: usize = 30;
+
+fn storage_error(err: anyhow::Error) -> ServiceError {
+    ServiceError::internal_server_error(INTERNAL_SERVER_ERROR, Some(err))
+}
+
+/// Deterministic object key, so re-uploading a model overwrites its asset
+/// instead of leaving the previous one behind.
+pub(crate) fn model_key(entity_id: &str, id: &str, format: TanahpediaModel3dFormat) -> String {
+    format!(
+        "tanahpedia/3d-models/{entity_id}/{id}.{}",
+        format.extension()
+    )
+}
+
+fn le_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn le_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+fn validate_glb(bytes: &[u8]) -> bool {
+    bytes.len() >= GLB_HEADER_LEN
+        && &bytes[..4] == b"glTF"
+        && le_u32(bytes, 4) == 2
+        && le_u32(bytes, 8) as usize == bytes.len()
+}
+
+/// Only self-contained glTF is accepted: every buffer must be embedded as a
+/// data URI, since sibling `.bin` files are never uploaded.
+fn validate_gltf(bytes: &[u8]) -> bool {
+    serde_json::from_slice::<serde_json::Value>(bytes).is_ok_and(|json| {
+        let version_ok = json
+            .pointer("/asset/version")
+            .and_then(|version| version.as_str())
+            .is_some_and(|version| version.starts_with("2."));
+        let buffers_embedded = json
+            .get("buffers")
+            .and_then(|buffers| buffers.as_array())
+            .is_none_or(|buffers| {
+                buffers.iter().all(|buffer| {
+                    buffer
+                        .get("uri")
+                        .and_then(|uri| uri.as_str())
+                        .is_some_and(|uri| uri.starts_with("data:"))
+                })
+            });
+        version_ok && buffers_embedded
+    })
+}
+
+/// USDZ is a zip whose entries are stored uncompressed and whose first entry
+/// is the root USD layer.
+fn validate_usdz(bytes: &[u8]) -> bool {
+    if bytes.len() < ZIP_LOCAL_HEADER_LEN || &bytes[..4] != b"PK\x03\x04" || le_u16(bytes, 8) != 0 {
+        return false;
+    }
+    let name_end = ZIP_LOCAL_HEADER_LEN + le_u16(bytes, 26) as usize;
+    bytes
+        .get(ZIP_LOCAL_HEADER_LEN..name_end)
+        .and_then(|name| std::str::from_utf8(name).ok())
+        .is_some_and(|name| {
+            [".usd", ".usda", ".usdc"]
+                .iter()
+                .any(|ext| name.ends_with(ext))
+        })
+}
+
+/// Checks that `bytes` is a well-formed payload of the declared `format`.
+pub(crate) fn validate_payload(
+    format: TanahpediaModel3dFormat,
+    bytes: &[u8],
+) -> Result<(), ServiceError> {
+    if bytes.is_empty() {
+        return Err(ServiceError::bad_request("file is empty"));
+    }
+    if bytes.len() > MAX_MODEL_BYTES {
+        return Err(ServiceError::bad_request(&format!(
+            "file must be at most {MAX_MODEL_BYTES} bytes"
+        )));
+    }
+    let valid = match format {
+        TanahpediaModel3dFormat::Gltf => validate_gltf(bytes),
+        TanahpediaModel3dFormat::Glb => validate_glb(bytes),
+        TanahpediaModel3dFormat::Usdz => validate_usdz(bytes),
+    };
+    if !valid {
+        return Err(ServiceError::bad_request(&format!(
+            "file is not a valid {} model",
+            format.stored_name()
+        )));
+    }
+    Ok(())
+}
+
+async fn to_dto(
+    storage: &Storage,
+    row: three_d_model::Model,
+) -> Result<TanahpediaModel3d, ServiceError> {
+    Ok(TanahpediaModel3d {
+        url: storage
+            .presigned_get_url(&row.blob_key)
+            .await
+            .map_err(storage_error)?,
+        id: row.id,
+        entity_id: row.entity_id,
+        format: row.format,
+        label: row.label,
+        alt_group_id: row.alt_group_id,
+    })
+}
+
+/// The 3D models attached to an entity, each with a pre-signed download URL.
+pub async fn get_entity_models(
+    db: &Database,
+    storage: &Storage,
+    entity_id: String,
+) -> Result<Vec<TanahpediaModel3d>, ServiceError> {
+    let entity_id = required(entity_id, "entityId", 36)?;
+    let rows = three_d_model::Entity::find()
+        .filter(three_d_model::Column::EntityId.eq(entity_id))
+        .order_by_asc(three_d_model::Column::Id)
+        .all(db.get_connection())
+        .await
+        .map_err(db_error)?;
+
+    let mut models = Vec::with_capacity(rows.len());
+    for row in rows {
+        models.push(to_dto(storage, row).await?);
+    }
+    Ok(models)
+}
+
+/// Validates and stores a model asset under its deterministic key, then
+/// upserts its `tanahpedia_3d_model` row. A model id stays bound to the entity
+/// it was first uploaded for; when a re-upload changes the format, the asset
+/// under the old key is deleted. When the upsert fails, an asset no row
+/// references is deleted again.
+pub async fn upload_model(
+    db: &Database,
+    storage: &Storage,
+    input: UploadTanahpediaModel3dInput,
+    bytes: Vec<u8>,
+) -> Result<TanahpediaModel3d, ServiceError> {
+    let id = required(input.id, "id", 36)?;
+    let entity_id = required(input.entity_id, "entityId", 36)?;
+    let label = optional(input.label, "label", 255)?;
+    let alt_group_id = optional(input.alt_group_id, "altGroupId", 36)?;
+    validate_payload(input.format, &bytes)?;
+
+    let conn = db.get_connection();
+    if entity::Entity::find_by_id(entity_id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+        .is_none()
+    {
+        return Err(ServiceError::not_found("entity not found", None::<&str>));
+    }
+    let previous = three_d_model::Entity::find_by_id(id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?;
+    if previous
+        .as_ref()
+        .is_some_and(|previous| previous.entity_id != entity_id)
+    {
+        return Err(ServiceError::bad_request(
+            "id belongs to a model of another entity",
+        ));
+    }
+
+    let blob_key = model_key(&entity_id, &id, input.format);
+    storage
+        .put_object(&blob_key, bytes, input.format.content_type())
+        .await
+        .map_err(storage_error)?;
+
+    let row = three_d_model::Model {
+        id,
+        entity_id,
+        blob_key,
+        format: input.format.stored_name().to_string(),
+        label,
+        alt_group_id,
+    };
+    let upserted = three_d_model::Entity::insert(row.clone().into_active_model())
+        .on_conflict(
+            OnConflict::column(three_d_model::Column::Id)
+                .update_columns([
+                    three_d_model::Column::BlobKey,
+                    three_d_model::Column::Format,
+                    three_d_model::Column::Label,
+                    three_d_model::Column::AltGroupId,
+                ])
+                .to_owned(),
+        )
+        .exec(conn)
+        .await;
+    if let Err(err) = upserted {
+        // Without the row nothing references a new key, so drop its object.
+        // An object under the previous row's key stays referenced and is kept.
+        // The upsert error is the one to report either way.
+        if previous
+            .as_ref()
+            .is_none_or(|previous| previous.blob_key != row.blob_key)
+            && let Err(cause) = storage.delete_object(&row.blob_key).await
+        {
+            tracing::warn!(key = %row.blob_key, %cause, "Failed to delete an unreferenced 3D model asset");
+        }
+        return Err(db_error(err));
+    }
+
+    if let Some(previous) = previous.filter(|previous| previous.blob_key != row.blob_key) {
+        storage
+            .delete_object(&previous.blob_key)
+            .await
+            .map_err(storage_error)?;
+    }
+
+    to_dto(storage, row).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    fn glb(len_override: Option<u32>) -> Vec<u8> {
+        let mut bytes = b"glTF".to_vec();
+        bytes.extend(2u32.to_le_bytes());
+        bytes.extend(len_override.unwrap_or(20).to_le_bytes());
+        bytes.extend([0u8; 8]);
+        bytes
+    }
+
+    fn usdz(method: u16, name: &str) -> Vec<u8> {
+        let mut bytes = b"PK\x03\x04".to_vec();
+        bytes.extend([0u8; 4]);
+        bytes.extend(method.to_le_bytes());
+        bytes.extend([0u8; 16]);
+        bytes.extend((name.len() as u16).to_le_bytes());
+        bytes.extend([0u8; 2]);
+        bytes.extend(name.as_bytes());
+        bytes
+    }
+
+    fn model_row(id: &str, format: &str) -> three_d_model::Model {
+        three_d_model::Model {
+            id: id.to_string(),
+            entity_id: "entity-1".to_string(),
+            blob_key: format!("tanahpedia/3d-models/entity-1/{id}.glb"),
+            format: format.to_string(),
+            label: None,
+            alt_group_id: None,
+        }
+    }
+
+    fn upload_input(format: TanahpediaModel3dFormat) -> UploadTanahpediaModel3dInput {
+        UploadTanahpediaModel3dInput {
+            id: "model-1".to_string(),
+            entity_id: "entity-1".to_string(),
+            format,
+            label: Some("מזבח".to_string()),
+            alt_group_id: None,
+        }
+    }
+
+    #[test]
+    fn model_key_is_deterministic_per_entity_and_model() {
+        assert_eq!(
+            model_key("entity-1", "model-1", TanahpediaModel3dFormat::Usdz),
+            "tanahpedia/3d-models/entity-1/model-1.usdz"
+        );
+    }
+
+    #[test]
+    fn validate_payload_accepts_well_formed_models() {
+        assert!(validate_payload(TanahpediaModel3dFormat::Glb, &glb(None)).is_ok());
+        assert!(
+            validate_payload(
+                TanahpediaModel3dFormat::Gltf,
+                br#"{"asset":{"version":"2.0"},"meshes":[]}"#
+            )
+            .is_ok()
+        );
+        assert!(
+            validate_payload(
+                TanahpediaModel3dFormat::Gltf,
+                br#"{"asset":{"version":"2.0"},"buffers":[{"uri":"data:application/octet-stream;base64,AAAAAA==","byteLength":4}]}"#
+            )
+            .is_ok()
+        );
+        assert!(validate_payload(TanahpediaModel3dFormat::Usdz, &usdz(0, "model.usdc")).is_ok());
+    }
+
+    #[test]
+    fn validate_payload_rejects_malformed_or_mismatched_models() {
+        for (format, bytes) in [
+            (TanahpediaModel3dFormat::Glb, glb(Some(99))),
+            (TanahpediaModel3dFormat::Glb, usdz(0, "model.usdc")),
+            (
+                TanahpediaModel3dFormat::Gltf,
+                br#"{"asset":{"version":"1.0"}}"#.to_vec(),
+            ),
+            (TanahpediaModel3dFormat::Gltf, glb(None)),
+            (
+                TanahpediaModel3dFormat::Gltf,
+                br#"{"asset":{"version":"2.0"},"buffers":[{"uri":"model.bin","byteLength":4}]}"#
+                    .to_vec(),
+            ),
+            (
+                TanahpediaModel3dFormat::Gltf,
+                br#"{"asset":{"version":"2.0"},"buffers":[{"byteLength":4}]}"#.to_vec(),
+            ),
+            (TanahpediaModel3dFormat::Usdz, usdz(8, "model.usdc")),
+            (TanahpediaModel3dFormat::Usdz, usdz(0, "texture.png")),
+            (TanahpediaModel3dFormat::Usdz, Vec::new()),
+        ] {
+            assert!(
+                matches!(
+                    validate_payload(format, &bytes),
+                    Err(ServiceError::BadRequest(_))
+                ),
+                "{format:?} should reject {bytes:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn get_entity_models_presigns_each_blob_key() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![model_row("model-1", "GLB")]])
+                .into_connection(),
+        );
+
+        let models = get_entity_models(&db, &Storage::for_testing(), "entity-1".to_string())
+            .await
+            .expect("models should load");
+
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].format, "GLB");
+        assert!(models[0].url.starts_with(
+            "http://localhost:4566/bible-on-site-assets-test/tanahpedia/3d-models/entity-1/model-1.glb?"
+        ));
+        assert!(models[0].url.contains("X-Amz-Expires=900"));
+    }
+
+    #[tokio::test]
+    async fn upload_model_rejects_invalid_payload_before_querying() {
+        let db =
+            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
+
+        let result = upload_model(
+            &db,
+            &Storage::for_testing(),
+            upload_input(TanahpediaModel3dFormat::Glb),
+            b"not a model".to_vec(),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn upload_model_requires_existing_entity() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<entity::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let result = upload_model(
+            &db,
+            &Storage::for_testing(),
+            upload_input(TanahpediaModel3dFormat::Glb),
+            glb(None),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ServiceError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn upload_model_rejects_id_of_another_entity() {
+        let mut foreign = model_row("model-1", "GLB");
+        foreign.entity_id = "entity-2".to_string();
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![entity::Model {
+                    id: "entity-1".to_string(),
+                    entity_type: entities::tanahpedia::sea_orm_active_enums::EntityType::Person,
+                    name: "מזבח".to_string(),
+                    created_at: chrono::Utc::now().naive_utc(),
+                    updated_at: chrono::Utc::now().naive_utc(),
+                }]])
+                .append_query_results([vec![foreign]])
+                .into_connection(),
+        );
+
+        let result = upload_model(
+            &db,
+            &Storage::for_testing(),
+            upload_input(TanahpediaModel3dFormat::Glb),
+            glb(None),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,593 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::tanahpedia_naming::{
+        PutTanahpediaNameGiverInput, TanahpediaNameGiver, TanahpediaNameGiverType,
+        TanahpediaNameGiverWriteResult, TanahpediaNaming,
+    },
+    providers::Database,
+    services::{
+        tanahpedia_family_service::{
+            db_error, optional, person_display_names, require_person, required,
+        },
+        tanahpedia_sayings_service::GOD_DISPLAY_NAME,
+    },
+};
+use entities::tanahpedia::{
+    god, lookup_name_type, person_name, person_name_giver_god, person_name_giver_person,
+};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter, TransactionTrait,
+};
+
+/// One giver link row of either table, before display names are resolved.
+struct GiverRow {
+    link_id: String,
+    person_name_id: String,
+    giver_type: TanahpediaNameGiverType,
+    giver_id: String,
+    alt_group_id: Option<String>,
+    source_citation: Option<String>,
+}
+
+impl GiverRow {
+    fn into_giver(self, person_names: &HashMap<String, String>) -> TanahpediaNameGiver {
+        let display_name = match self.giver_type {
+            TanahpediaNameGiverType::Person => person_names
+                .get(&self.giver_id)
+                .cloned()
+                .unwrap_or_default(),
+            TanahpediaNameGiverType::God => GOD_DISPLAY_NAME.to_string(),
+        };
+        TanahpediaNameGiver {
+            link_id: self.link_id,
+            giver_type: self.giver_type,
+            giver_id: self.giver_id,
+            display_name,
+            alt_group_id: self.alt_group_id,
+            source_citation: self.source_citation,
+        }
+    }
+}
+
+impl From<person_name_giver_person::Model> for GiverRow {
+    fn from(row: person_name_giver_person::Model) -> Self {
+        Self {
+            link_id: row.id,
+            person_name_id: row.person_name_id,
+            giver_type: TanahpediaNameGiverType::Person,
+            giver_id: row.giver_person_id,
+            alt_group_id: row.alt_group_id,
+            source_citation: row.source_citation,
+        }
+    }
+}
+
+impl From<person_name_giver_god::Model> for GiverRow {
+    fn from(row: person_name_giver_god::Model) -> Self {
+        Self {
+            link_id: row.id,
+            person_name_id: row.person_name_id,
+            giver_type: TanahpediaNameGiverType::God,
+            giver_id: row.god_id,
+            alt_group_id: row.alt_group_id,
+            source_citation: row.source_citation,
+        }
+    }
+}
+
+/// Maps each `person_name_id` to everyone who gave that name, resolving the
+/// giving persons' display names in one batch.
+pub(crate) async fn name_givers(
+    conn: &sea_orm::DatabaseConnection,
+    person_name_ids: HashSet<String>,
+) -> Result<HashMap<String, Vec<TanahpediaNameGiver>>, ServiceError> {
+    if person_name_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut rows = person_name_giver_person::Entity::find()
+        .filter(person_name_giver_person::Column::PersonNameId.is_in(person_name_ids.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(GiverRow::from)
+        .collect::<Vec<_>>();
+    rows.extend(
+        person_name_giver_god::Entity::find()
+            .filter(person_name_giver_god::Column::PersonNameId.is_in(person_name_ids))
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(GiverRow::from),
+    );
+    let person_names = person_display_names(
+        conn,
+        rows.iter()
+            .filter(|row| row.giver_type == TanahpediaNameGiverType::Person)
+            .map(|row| row.giver_id.clone())
+            .collect(),
+    )
+    .await?;
+
+    let mut givers = HashMap::<String, Vec<TanahpediaNameGiver>>::new();
+    for row in rows {
+        givers
+            .entry(row.person_name_id.clone())
+            .or_default()
+            .push(row.into_giver(&person_names));
+    }
+    Ok(givers)
+}
+
+/// Lists every name given by `giver_id`, which may be a `personId` or the
+/// `godId`, sorted by the named person and then by name.
+pub async fn find_namings(
+    db: &Database,
+    giver_id: String,
+) -> Result<Vec<TanahpediaNaming>, ServiceError> {
+    let giver_id = required(giver_id, "giverId", 36)?;
+    let conn = db.get_connection();
+
+    let mut rows = person_name_giver_person::Entity::find()
+        .filter(person_name_giver_person::Column::GiverPersonId.eq(giver_id.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(GiverRow::from)
+        .collect::<Vec<_>>();
+    rows.extend(
+        person_name_giver_god::Entity::find()
+            .filter(person_name_giver_god::Column::GodId.eq(giver_id))
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(GiverRow::from),
+    );
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let names = person_name::Entity::find()
+        .filter(
+            person_name::Column::Id.is_in(
+                rows.iter()
+                    .map(|row| row.person_name_id.clone())
+                    .collect::<HashSet<_>>(),
+            ),
+        )
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| (row.id.clone(), row))
+        .collect::<HashMap<_, _>>();
+    let name_types = lookup_name_type::Entity::find()
+        .filter(
+            lookup_name_type::Column::Id.is_in(
+                names
+                    .values()
+                    .map(|row| row.name_type_id.clone())
+                    .collect::<HashSet<_>>(),
+            ),
+        )
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| (row.id, row.name))
+        .collect::<HashMap<_, _>>();
+    let person_names = person_display_names(
+        conn,
+        names
+            .values()
+            .map(|row| row.person_id.clone())
+            .chain(
+                rows.iter()
+                    .filter(|row| row.giver_type == TanahpediaNameGiverType::Person)
+                    .map(|row| row.giver_id.clone()),
+            )
+            .collect(),
+    )
+    .await?;
+
+    let mut namings = rows
+        .into_iter()
+        .filter_map(|row| {
+            let name = names.get(&row.person_name_id)?;
+            Some(TanahpediaNaming {
+                person_name_id: name.id.clone(),
+                name: name.name.clone(),
+                name_type: name_types
+                    .get(&name.name_type_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                person_id: name.person_id.clone(),
+                person_display_name: person_names
+                    .get(&name.person_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                giver: row.into_giver(&person_names),
+            })
+        })
+        .collect::<Vec<_>>();
+    namings.sort_by(|a, b| {
+        (&a.person_display_name, &a.name, &a.giver.link_id).cmp(&(
+            &b.person_display_name,
+            &b.name,
+            &b.giver.link_id,
+        ))
+    });
+    Ok(namings)
+}
+
+async fn require_person_name(
+    conn: &sea_orm::DatabaseConnection,
+    person_name_id: &str,
+) -> Result<(), ServiceError> {
+    if person_name::Entity::find_by_id(person_name_id.to_string())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+        .is_none()
+    {
+        return Err(ServiceError::bad_request(&format!(
+            "personNameId {person_name_id} does not reference an existing person name"
+        )));
+    }
+    Ok(())
+}
+
+async fn require_giver(
+    conn: &sea_orm::DatabaseConnection,
+    giver_type: TanahpediaNameGiverType,
+    giver_id: &str,
+) -> Result<(), ServiceError> {
+    match giver_type {
+        TanahpediaNameGiverType::Person => require_person(conn, giver_id).await,
+        TanahpediaNameGiverType::God => {
+            if god::Entity::find_by_id(giver_id.to_string())
+                .one(conn)
+                .await
+                .map_err(db_error)?
+                .is_none()
+            {
+                return Err(ServiceError::bad_request(&format!(
+                    "giverId {giver_id} does not reference an existing GOD"
+                )));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Removes `id` from both giver tables, so re-putting a link with a different
+/// `giverType` never leaves the old row behind. Returns the number of rows
+/// removed.
+async fn delete_giver_links<C: ConnectionTrait>(conn: &C, id: &str) -> Result<u64, ServiceError> {
+    let mut removed = person_name_giver_person::Entity::delete_by_id(id.to_string())
+        .exec(conn)
+        .await
+        .map_err(db_error)?
+        .rows_affected;
+    removed += person_name_giver_god::Entity::delete_by_id(id.to_string())
+        .exec(conn)
+        .await
+        .map_err(db_error)?
+        .rows_affected;
+    Ok(removed)
+}
+
+pub async fn put_name_giver(
+    db: &Database,
+    input: PutTanahpediaNameGiverInput,
+) -> Result<TanahpediaNameGiverWriteResult, ServiceError> {
+    let conn = db.get_connection();
+    let id = required(input.id, "id", 36)?;
+    let person_name_id = required(input.person_name_id, "personNameId", 36)?;
+    let giver_type = input.giver_type;
+    let giver_id = required(input.giver_id, "giverId", 36)?;
+    let alt_group_id = optional(input.alt_group_id, "altGroupId", 36)?;
+    let source_citation = optional(input.source_citation, "sourceCitation", 400)?;
+    require_person_name(conn, &person_name_id).await?;
+    require_giver(conn, giver_type, &giver_id).await?;
+
+    let transaction = conn.begin().await.map_err(db_error)?;
+    delete_giver_links(&transaction, &id).await?;
+    let insert_result = match giver_type {
+        TanahpediaNameGiverType::Person => person_name_giver_person::Entity::insert(
+            person_name_giver_person::Model {
+                id: id.clone(),
+                person_name_id: person_name_id.clone(),
+                giver_person_id: giver_id,
+                alt_group_id,
+                source_citation,
+            }
+            .into_active_model(),
+        )
+        .exec(&transaction)
+        .await
+        .map(|_| ()),
+        TanahpediaNameGiverType::God => person_name_giver_god::Entity::insert(
+            person_name_giver_god::Model {
+                id: id.clone(),
+                person_name_id: person_name_id.clone(),
+                god_id: giver_id,
+                alt_group_id,
+                source_citation,
+            }
+            .into_active_model(),
+        )
+        .exec(&transaction)
+        .await
+        .map(|_| ()),
+    };
+    insert_result.map_err(db_error)?;
+    transaction.commit().await.map_err(db_error)?;
+
+    Ok(TanahpediaNameGiverWriteResult {
+        id,
+        person_name_id,
+        giver_type,
+    })
+}
+
+pub async fn delete_name_giver(
+    db: &Database,
+    id: String,
+) -> Result<TanahpediaNameGiverWriteResult, ServiceError> {
+    let id = required(id, "id", 36)?;
+    let conn = db.get_connection();
+
+    let existing = if let Some(row) = person_name_giver_person::Entity::find_by_id(id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+    {
+        Some(GiverRow::from(row))
+    } else {
+        person_name_giver_god::Entity::find_by_id(id.clone())
+            .one(conn)
+            .await
+            .map_err(db_error)?
+            .map(GiverRow::from)
+    };
+    let Some(existing) = existing else {
+        return Err(ServiceError::not_found(
+            "name giver not found",
+            None::<&str>,
+        ));
+    };
+
+    delete_giver_links(conn, &id).await?;
+
+    Ok(TanahpediaNameGiverWriteResult {
+        id,
+        person_name_id: existing.person_name_id,
+        giver_type: existing.giver_type,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::tanahpedia::{entity, person, sea_orm_active_enums::EntityType};
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    fn giver_input(giver_type: TanahpediaNameGiverType) -> PutTanahpediaNameGiverInput {
+        PutTanahpediaNameGiverInput {
+            id: "giver-1".to_string(),
+            person_name_id: "name-1".to_string(),
+            giver_type,
+            giver_id: "giver".to_string(),
+            alt_group_id: None,
+            source_citation: Some(" בראשית יז ה ".to_string()),
+        }
+    }
+
+    fn name_model(id: &str, person_id: &str, name: &str) -> person_name::Model {
+        person_name::Model {
+            id: id.to_string(),
+            person_id: person_id.to_string(),
+            name: name.to_string(),
+            name_type_id: "nt-birth".to_string(),
+            alt_group_id: None,
+        }
+    }
+
+    fn person_row(id: &str, name: &str) -> (person::Model, entity::Model) {
+        (
+            person::Model {
+                id: id.to_string(),
+                entity_id: format!("entity-{id}"),
+            },
+            entity::Model {
+                id: format!("entity-{id}"),
+                entity_type: EntityType::Person,
+                name: name.to_string(),
+                created_at: chrono::Utc::now().naive_utc(),
+                updated_at: chrono::Utc::now().naive_utc(),
+            },
+        )
+    }
+
+    fn giver_person_model(id: &str, person_name_id: &str) -> person_name_giver_person::Model {
+        person_name_giver_person::Model {
+            id: id.to_string(),
+            person_name_id: person_name_id.to_string(),
+            giver_person_id: "leah".to_string(),
+            alt_group_id: None,
+            source_citation: Some("בראשית כט".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn put_name_giver_rejects_unknown_person_name() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<person_name::Model, Vec<person_name::Model>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let err = put_name_giver(&db, giver_input(TanahpediaNameGiverType::Person))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ServiceError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn put_name_giver_replaces_link_across_giver_tables() {
+        let no_rows = MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 0,
+        };
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<person_name::Model, Vec<person_name::Model>, _>([vec![
+                    name_model("name-1", "avraham", "אברהם"),
+                ]])
+                .append_query_results::<god::Model, Vec<god::Model>, _>([vec![god::Model {
+                    id: "giver".to_string(),
+                }]])
+                .append_exec_results([
+                    no_rows.clone(),
+                    no_rows,
+                    MockExecResult {
+                        last_insert_id: 0,
+                        rows_affected: 1,
+                    },
+                ])
+                .into_connection(),
+        );
+
+        let result = put_name_giver(&db, giver_input(TanahpediaNameGiverType::God))
+            .await
+            .expect("god giver should be stored");
+
+        assert_eq!(result.id, "giver-1");
+        assert_eq!(result.person_name_id, "name-1");
+        assert_eq!(result.giver_type, TanahpediaNameGiverType::God);
+    }
+
+    #[tokio::test]
+    async fn delete_name_giver_returns_not_found_for_unknown_id() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<person_name_giver_person::Model, Vec<_>, _>([vec![]])
+                .append_query_results::<person_name_giver_god::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let err = delete_name_giver(&db, "missing".to_string())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn name_givers_groups_person_and_god_givers_by_name() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![giver_person_model("gp-1", "name-reuven")]])
+                .append_query_results([vec![person_name_giver_god::Model {
+                    id: "gg-1".to_string(),
+                    person_name_id: "name-avraham".to_string(),
+                    god_id: "god".to_string(),
+                    alt_group_id: Some("alt-1".to_string()),
+                    source_citation: None,
+                }]])
+                .append_query_results([vec![person_row("leah", "לאה")]])
+                .into_connection(),
+        );
+
+        let givers = name_givers(
+            db.get_connection(),
+            ["name-reuven", "name-avraham"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        )
+        .await
+        .expect("givers should load");
+
+        assert_eq!(givers["name-reuven"][0].display_name, "לאה");
+        assert_eq!(
+            givers["name-reuven"][0].giver_type,
+            TanahpediaNameGiverType::Person
+        );
+        assert_eq!(
+            givers["name-reuven"][0].source_citation.as_deref(),
+            Some("בראשית כט")
+        );
+        assert_eq!(givers["name-avraham"][0].display_name, GOD_DISPLAY_NAME);
+        assert_eq!(
+            givers["name-avraham"][0].alt_group_id.as_deref(),
+            Some("alt-1")
+        );
+    }
+
+    #[tokio::test]
+    async fn find_namings_lists_names_with_the_named_person() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![
+                    giver_person_model("gp-2", "name-shimon"),
+                    giver_person_model("gp-1", "name-reuven"),
+                ]])
+                .append_query_results::<person_name_giver_god::Model, Vec<_>, _>([vec![]])
+                .append_query_results([vec![
+                    name_model("name-reuven", "reuven", "ראובן"),
+                    name_model("name-shimon", "shimon", "שמעון"),
+                ]])
+                .append_query_results([vec![lookup_name_type::Model {
+                    id: "nt-birth".to_string(),
+                    name: "BIRTH".to_string(),
+                }]])
+                .append_query_results([vec![
+                    person_row("leah", "לאה"),
+                    person_row("reuven", "ראובן"),
+                    person_row("shimon", "שמעון"),
+                ]])
+                .into_connection(),
+        );
+
+        let namings = find_namings(&db, "leah".to_string())
+            .await
+            .expect("namings should load");
+
+        assert_eq!(
+            namings
+                .iter()
+                .map(|naming| naming.person_display_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["ראובן", "שמעון"]
+        );
+        assert_eq!(namings[0].name_type, "BIRTH");
+        assert_eq!(namings[0].giver.display_name, "לאה");
+        assert_eq!(namings[0].giver.link_id, "gp-1");
+    }
+
+    #[tokio::test]
+    async fn find_namings_skips_lookups_when_nothing_was_given() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<person_name_giver_person::Model, Vec<_>, _>([vec![]])
+                .append_query_results::<person_name_giver_god::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        assert!(
+            find_namings(&db, "nobody".to_string())
+                .await
+                .expect("namings should load")
+                .is_empty()
+        );
+    }
+}
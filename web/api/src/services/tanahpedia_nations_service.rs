@@ -0,0 +1,881 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::{
+        tanahpedia_nation::{
+            PutTanahpediaNationSourceInput, PutTanahpediaNationTerritoryInput,
+            PutTanahpediaPersonNationInput, TanahpediaNation, TanahpediaNationLinkWriteResult,
+            TanahpediaNationRelative, TanahpediaNationTerritory, TanahpediaPersonNationLink,
+            TanahpediaTerritoryFeature, TanahpediaTerritoryFeatureCollection,
+            TanahpediaTerritoryFeatureProperties,
+        },
+        tanahpedia_place::TanahpediaPointGeometry,
+    },
+    providers::Database,
+    services::{
+        tanahpedia_family_service::{
+            db_error, nation_display_names, optional, person_display_names, place_display_names,
+            require_person, required,
+        },
+        tanahpedia_places_service::to_f64,
+    },
+};
+use entities::tanahpedia::{
+    entity, lookup_person_nation_role, nation, nation_source_nation, nation_territory,
+    person_nation, place, place_identification,
+};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+};
+
+/// How many generations a lineage walk follows before giving up. Far beyond
+/// any real table of nations; it only bounds the walk if the data is broken.
+const MAX_LINEAGE_DEPTH: i32 = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Lineage {
+    Ancestors,
+    Descendants,
+}
+
+impl Lineage {
+    /// The nation a link leads to, walking in this direction.
+    fn relative(self, row: &nation_source_nation::Model) -> &str {
+        match self {
+            Self::Ancestors => &row.source_nation_id,
+            Self::Descendants => &row.nation_id,
+        }
+    }
+
+    /// The nation a link leads from, walking in this direction.
+    fn via(self, row: &nation_source_nation::Model) -> &str {
+        match self {
+            Self::Ancestors => &row.nation_id,
+            Self::Descendants => &row.source_nation_id,
+        }
+    }
+}
+
+/// Walks `nation_source_nation` from `nation_id` one generation per query,
+/// returning every link with its depth, nearest first. A nation reached at
+/// one depth isn't expanded again, so cyclic data can't loop; competing
+/// opinions that reach the same nation at the same depth are all kept.
+async fn lineage<C: ConnectionTrait>(
+    conn: &C,
+    nation_id: &str,
+    direction: Lineage,
+) -> Result<Vec<(i32, nation_source_nation::Model)>, ServiceError> {
+    let mut seen = HashSet::from([nation_id.to_string()]);
+    let mut frontier = seen.clone();
+    let mut links = Vec::new();
+    let mut depth = 1;
+    while !frontier.is_empty() && depth <= MAX_LINEAGE_DEPTH {
+        let column = match direction {
+            Lineage::Ancestors => nation_source_nation::Column::NationId,
+            Lineage::Descendants => nation_source_nation::Column::SourceNationId,
+        };
+        let rows = nation_source_nation::Entity::find()
+            .filter(column.is_in(frontier))
+            .order_by_asc(nation_source_nation::Column::Id)
+            .all(conn)
+            .await
+            .map_err(db_error)?;
+        let mut next = HashSet::new();
+        for row in rows {
+            let relative = direction.relative(&row);
+            if seen.contains(relative) {
+                continue;
+            }
+            next.insert(relative.to_string());
+            links.push((depth, row));
+        }
+        seen.extend(next.iter().cloned());
+        frontier = next;
+        depth += 1;
+    }
+    Ok(links)
+}
+
+fn relatives(
+    links: Vec<(i32, nation_source_nation::Model)>,
+    direction: Lineage,
+    names: &HashMap<String, String>,
+) -> Vec<TanahpediaNationRelative> {
+    links
+        .into_iter()
+        .map(|(depth, row)| {
+            let nation_id = direction.relative(&row).to_string();
+            TanahpediaNationRelative {
+                display_name: names.get(&nation_id).cloned().unwrap_or_default(),
+                via_nation_id: direction.via(&row).to_string(),
+                link_id: row.id,
+                nation_id,
+                depth,
+                alt_group_id: row.alt_group_id,
+            }
+        })
+        .collect()
+}
+
+fn validate_year(year: Option<i32>) -> Result<Option<i32>, ServiceError> {
+    match year {
+        Some(year) if year < 1 => Err(ServiceError::bad_request(
+            "year must be a Hebrew year of at least 1",
+        )),
+        year => Ok(year),
+    }
+}
+
+/// Whether a territory held from `start_date` to `end_date` (`YYYYMMDD`, open
+/// when missing) was held at some point during Hebrew `year`.
+fn held_in_year(start_date: Option<i32>, end_date: Option<i32>, year: i32) -> bool {
+    start_date.is_none_or(|date| date / 10_000 <= year)
+        && end_date.is_none_or(|date| date / 10_000 >= year)
+}
+
+async fn person_nation_links(
+    conn: &sea_orm::DatabaseConnection,
+    rows: Vec<person_nation::Model>,
+) -> Result<Vec<TanahpediaPersonNationLink>, ServiceError> {
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let roles = lookup_person_nation_role::Entity::find()
+        .filter(
+            lookup_person_nation_role::Column::Id.is_in(
+                rows.iter()
+                    .map(|row| row.role_id.clone())
+                    .collect::<HashSet<_>>(),
+            ),
+        )
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| (row.id, row.name))
+        .collect::<HashMap<_, _>>();
+    let person_names =
+        person_display_names(conn, rows.iter().map(|row| row.person_id.clone()).collect()).await?;
+    let nation_names =
+        nation_display_names(conn, rows.iter().map(|row| row.nation_id.clone()).collect()).await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| TanahpediaPersonNationLink {
+            person_display_name: person_names
+                .get(&row.person_id)
+                .cloned()
+                .unwrap_or_default(),
+            nation_display_name: nation_names
+                .get(&row.nation_id)
+                .cloned()
+                .unwrap_or_default(),
+            role: roles.get(&row.role_id).cloned().unwrap_or_default(),
+            link_id: row.id,
+            person_id: row.person_id,
+            nation_id: row.nation_id,
+            alt_group_id: row.alt_group_id,
+            source_citation: row.source_citation,
+        })
+        .collect())
+}
+
+/// Loads a nation with its ancestors and descendants to any depth, the
+/// territories it held (only those held during `year`, when given) and the
+/// persons linked to it. Errors with `NOT_FOUND` when the nation doesn't
+/// exist.
+pub async fn get_nation(
+    db: &Database,
+    nation_id: String,
+    year: Option<i32>,
+) -> Result<TanahpediaNation, ServiceError> {
+    let nation_id = required(nation_id, "nationId", 36)?;
+    let year = validate_year(year)?;
+    let conn = db.get_connection();
+
+    let Some((nation_row, Some(entity_row))) = nation::Entity::find_by_id(nation_id)
+        .find_also_related(entity::Entity)
+        .one(conn)
+        .await
+        .map_err(db_error)?
+    else {
+        return Err(ServiceError::not_found("nation not found", None::<&str>));
+    };
+
+    let ancestors = lineage(conn, &nation_row.id, Lineage::Ancestors).await?;
+    let descendants = lineage(conn, &nation_row.id, Lineage::Descendants).await?;
+    let territories = nation_territory::Entity::find()
+        .filter(nation_territory::Column::NationId.eq(nation_row.id.clone()))
+        .order_by_asc(nation_territory::Column::StartDate)
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .filter(|row| year.is_none_or(|year| held_in_year(row.start_date, row.end_date, year)))
+        .collect::<Vec<_>>();
+    let persons = person_nation::Entity::find()
+        .filter(person_nation::Column::NationId.eq(nation_row.id.clone()))
+        .order_by_asc(person_nation::Column::Id)
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+
+    let nation_names = nation_display_names(
+        conn,
+        ancestors
+            .iter()
+            .map(|(_, row)| Lineage::Ancestors.relative(row).to_string())
+            .chain(
+                descendants
+                    .iter()
+                    .map(|(_, row)| Lineage::Descendants.relative(row).to_string()),
+            )
+            .collect(),
+    )
+    .await?;
+    let place_names = place_display_names(
+        conn,
+        territories.iter().map(|row| row.place_id.clone()).collect(),
+    )
+    .await?;
+
+    Ok(TanahpediaNation {
+        ancestors: relatives(ancestors, Lineage::Ancestors, &nation_names),
+        descendants: relatives(descendants, Lineage::Descendants, &nation_names),
+        territories: territories
+            .into_iter()
+            .map(|row| TanahpediaNationTerritory {
+                display_name: place_names.get(&row.place_id).cloned().unwrap_or_default(),
+                link_id: row.id,
+                place_id: row.place_id,
+                start_date: row.start_date,
+                end_date: row.end_date,
+                alt_group_id: row.alt_group_id,
+            })
+            .collect(),
+        persons: person_nation_links(conn, persons).await?,
+        nation_id: nation_row.id,
+        entity_id: entity_row.id,
+        display_name: entity_row.name,
+    })
+}
+
+/// Lists the nations `person_id` is linked to, e.g. the nations a person
+/// founded.
+pub async fn find_person_nations(
+    db: &Database,
+    person_id: String,
+) -> Result<Vec<TanahpediaPersonNationLink>, ServiceError> {
+    let person_id = required(person_id, "personId", 36)?;
+    let conn = db.get_connection();
+
+    let rows = person_nation::Entity::find()
+        .filter(person_nation::Column::PersonId.eq(person_id))
+        .order_by_asc(person_nation::Column::Id)
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    person_nation_links(conn, rows).await
+}
+
+/// Builds a GeoJSON feature collection of nation territories for map
+/// overlays: one point per territory and located identification of its
+/// place. Optionally limited to territories held during `year` and/or to the
+/// given nations.
+pub async fn territories_geojson(
+    db: &Database,
+    year: Option<i32>,
+    nation_ids: Option<Vec<String>>,
+) -> Result<TanahpediaTerritoryFeatureCollection, ServiceError> {
+    let year = validate_year(year)?;
+    let nation_ids = nation_ids
+        .map(|ids| {
+            ids.into_iter()
+                .map(|id| required(id, "nationIds", 36))
+                .collect::<Result<HashSet<_>, _>>()
+        })
+        .transpose()?;
+    let conn = db.get_connection();
+
+    let mut territory_query = nation_territory::Entity::find();
+    if let Some(nation_ids) = nation_ids {
+        territory_query =
+            territory_query.filter(nation_territory::Column::NationId.is_in(nation_ids));
+    }
+    let territories = territory_query
+        .order_by_asc(nation_territory::Column::Id)
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .filter(|row| year.is_none_or(|year| held_in_year(row.start_date, row.end_date, year)))
+        .collect::<Vec<_>>();
+    if territories.is_empty() {
+        return Ok(feature_collection(Vec::new()));
+    }
+
+    let place_ids = territories
+        .iter()
+        .map(|row| row.place_id.clone())
+        .collect::<HashSet<_>>();
+    let mut identifications_by_place = HashMap::<String, Vec<place_identification::Model>>::new();
+    for row in place_identification::Entity::find()
+        .filter(place_identification::Column::PlaceId.is_in(place_ids.clone()))
+        .order_by_asc(place_identification::Column::Id)
+        .all(conn)
+        .await
+        .map_err(db_error)?
+    {
+        identifications_by_place
+            .entry(row.place_id.clone())
+            .or_default()
+            .push(row);
+    }
+    let place_names = place_display_names(conn, place_ids).await?;
+    let nation_names = nation_display_names(
+        conn,
+        territories
+            .iter()
+            .map(|row| row.nation_id.clone())
+            .collect(),
+    )
+    .await?;
+
+    let mut features = Vec::new();
+    for territory in territories {
+        for row in identifications_by_place
+            .get(&territory.place_id)
+            .into_iter()
+            .flatten()
+        {
+            let (Some(latitude), Some(longitude)) = (to_f64(row.latitude), to_f64(row.longitude))
+            else {
+                continue;
+            };
+            features.push(TanahpediaTerritoryFeature {
+                kind: "Feature".to_string(),
+                id: format!("{}:{}", territory.id, row.id),
+                geometry: TanahpediaPointGeometry {
+                    kind: "Point".to_string(),
+                    coordinates: vec![longitude, latitude],
+                },
+                properties: TanahpediaTerritoryFeatureProperties {
+                    nation_id: territory.nation_id.clone(),
+                    nation_display_name: nation_names
+                        .get(&territory.nation_id)
+                        .cloned()
+                        .unwrap_or_default(),
+                    territory_id: territory.id.clone(),
+                    place_id: territory.place_id.clone(),
+                    place_display_name: place_names
+                        .get(&territory.place_id)
+                        .cloned()
+                        .unwrap_or_default(),
+                    identification_id: row.id.clone(),
+                    modern_name: row.modern_name.clone(),
+                    start_date: territory.start_date,
+                    end_date: territory.end_date,
+                    alt_group_id: territory.alt_group_id.clone(),
+                    identification_alt_group_id: row.alt_group_id.clone(),
+                },
+            });
+        }
+    }
+
+    Ok(feature_collection(features))
+}
+
+fn feature_collection(
+    features: Vec<TanahpediaTerritoryFeature>,
+) -> TanahpediaTerritoryFeatureCollection {
+    TanahpediaTerritoryFeatureCollection {
+        kind: "FeatureCollection".to_string(),
+        features,
+    }
+}
+
+async fn require_nation(
+    conn: &sea_orm::DatabaseConnection,
+    nation_id: &str,
+    field: &str,
+) -> Result<(), ServiceError> {
+    if nation::Entity::find_by_id(nation_id.to_string())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+        .is_none()
+    {
+        return Err(ServiceError::bad_request(&format!(
+            "{field} {nation_id} does not reference an existing nation"
+        )));
+    }
+    Ok(())
+}
+
+async fn person_nation_role_id(
+    conn: &sea_orm::DatabaseConnection,
+    name: String,
+) -> Result<String, ServiceError> {
+    let name = required(name, "role", 50)?.to_uppercase();
+    lookup_person_nation_role::Entity::find()
+        .filter(lookup_person_nation_role::Column::Name.eq(name.clone()))
+        .one(conn)
+        .await
+        .map_err(db_error)?
+        .map(|row| row.id)
+        .ok_or_else(|| ServiceError::bad_request(&format!("unknown role {name}")))
+}
+
+pub async fn put_nation_source(
+    db: &Database,
+    input: PutTanahpediaNationSourceInput,
+) -> Result<TanahpediaNationLinkWriteResult, ServiceError> {
+    let conn = db.get_connection();
+    let id = required(input.id, "id", 36)?;
+    let nation_id = required(input.nation_id, "nationId", 36)?;
+    let source_nation_id = required(input.source_nation_id, "sourceNationId", 36)?;
+    let alt_group_id = optional(input.alt_group_id, "altGroupId", 36)?;
+    if nation_id == source_nation_id {
+        return Err(ServiceError::bad_request(
+            "a nation cannot descend from itself",
+        ));
+    }
+    require_nation(conn, &nation_id, "nationId").await?;
+    require_nation(conn, &source_nation_id, "sourceNationId").await?;
+    if lineage(conn, &source_nation_id, Lineage::Ancestors)
+        .await?
+        .iter()
+        .any(|(_, row)| Lineage::Ancestors.relative(row) == nation_id)
+    {
+        return Err(ServiceError::bad_request(&format!(
+            "sourceNationId {source_nation_id} already descends from nationId {nation_id}"
+        )));
+    }
+
+    let model = nation_source_nation::Model {
+        id: id.clone(),
+        nation_id: nation_id.clone(),
+        source_nation_id,
+        alt_group_id,
+    };
+    nation_source_nation::Entity::insert(model.into_active_model())
+        .on_conflict(
+            OnConflict::column(nation_source_nation::Column::Id)
+                .update_columns([
+                    nation_source_nation::Column::NationId,
+                    nation_source_nation::Column::SourceNationId,
+                    nation_source_nation::Column::AltGroupId,
+                ])
+                .to_owned(),
+        )
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+
+    Ok(TanahpediaNationLinkWriteResult { id, nation_id })
+}
+
+pub async fn delete_nation_source(
+    db: &Database,
+    id: String,
+) -> Result<TanahpediaNationLinkWriteResult, ServiceError> {
+    let id = required(id, "id", 36)?;
+    let conn = db.get_connection();
+    let Some(existing) = nation_source_nation::Entity::find_by_id(id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+    else {
+        return Err(ServiceError::not_found(
+            "nation source link not found",
+            None::<&str>,
+        ));
+    };
+    nation_source_nation::Entity::delete_by_id(id.clone())
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+    Ok(TanahpediaNationLinkWriteResult {
+        id,
+        nation_id: existing.nation_id,
+    })
+}
+
+pub async fn put_nation_territory(
+    db: &Database,
+    input: PutTanahpediaNationTerritoryInput,
+) -> Result<TanahpediaNationLinkWriteResult, ServiceError> {
+    let conn = db.get_connection();
+    let id = required(input.id, "id", 36)?;
+    let nation_id = required(input.nation_id, "nationId", 36)?;
+    let place_id = required(input.place_id, "placeId", 36)?;
+    let alt_group_id = optional(input.alt_group_id, "altGroupId", 36)?;
+    if let (Some(start_date), Some(end_date)) = (input.start_date, input.end_date)
+        && start_date > end_date
+    {
+        return Err(ServiceError::bad_request(
+            "startDate must not be after endDate",
+        ));
+    }
+    require_nation(conn, &nation_id, "nationId").await?;
+    if place::Entity::find_by_id(place_id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+        .is_none()
+    {
+        return Err(ServiceError::bad_request(&format!(
+            "placeId {place_id} does not reference an existing place"
+        )));
+    }
+
+    let model = nation_territory::Model {
+        id: id.clone(),
+        nation_id: nation_id.clone(),
+        place_id,
+        start_date: input.start_date,
+        end_date: input.end_date,
+        alt_group_id,
+    };
+    nation_territory::Entity::insert(model.into_active_model())
+        .on_conflict(
+            OnConflict::column(nation_territory::Column::Id)
+                .update_columns([
+                    nation_territory::Column::NationId,
+                    nation_territory::Column::PlaceId,
+                    nation_territory::Column::StartDate,
+                    nation_territory::Column::EndDate,
+                    nation_territory::Column::AltGroupId,
+                ])
+                .to_owned(),
+        )
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+
+    Ok(TanahpediaNationLinkWriteResult { id, nation_id })
+}
+
+pub async fn delete_nation_territory(
+    db: &Database,
+    id: String,
+) -> Result<TanahpediaNationLinkWriteResult, ServiceError> {
+    let id = required(id, "id", 36)?;
+    let conn = db.get_connection();
+    let Some(existing) = nation_territory::Entity::find_by_id(id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+    else {
+        return Err(ServiceError::not_found(
+            "nation territory not found",
+            None::<&str>,
+        ));
+    };
+    nation_territory::Entity::delete_by_id(id.clone())
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+    Ok(TanahpediaNationLinkWriteResult {
+        id,
+        nation_id: existing.nation_id,
+    })
+}
+
+pub async fn put_person_nation(
+    db: &Database,
+    input: PutTanahpediaPersonNationInput,
+) -> Result<TanahpediaNationLinkWriteResult, ServiceError> {
+    let conn = db.get_connection();
+    let id = required(input.id, "id", 36)?;
+    let person_id = required(input.person_id, "personId", 36)?;
+    let nation_id = required(input.nation_id, "nationId", 36)?;
+    let role = required(input.role, "role", 50)?;
+    let alt_group_id = optional(input.alt_group_id, "altGroupId", 36)?;
+    let source_citation = optional(input.source_citation, "sourceCitation", 400)?;
+    require_person(conn, &person_id).await?;
+    require_nation(conn, &nation_id, "nationId").await?;
+
+    let model = person_nation::Model {
+        id: id.clone(),
+        person_id,
+        nation_id: nation_id.clone(),
+        role_id: person_nation_role_id(conn, role).await?,
+        alt_group_id,
+        source_citation,
+    };
+    person_nation::Entity::insert(model.into_active_model())
+        .on_conflict(
+            OnConflict::column(person_nation::Column::Id)
+                .update_columns([
+                    person_nation::Column::PersonId,
+                    person_nation::Column::NationId,
+                    person_nation::Column::RoleId,
+                    person_nation::Column::AltGroupId,
+                    person_nation::Column::SourceCitation,
+                ])
+                .to_owned(),
+        )
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+
+    Ok(TanahpediaNationLinkWriteResult { id, nation_id })
+}
+
+pub async fn delete_person_nation(
+    db: &Database,
+    id: String,
+) -> Result<TanahpediaNationLinkWriteResult, ServiceError> {
+    let id = required(id, "id", 36)?;
+    let conn = db.get_connection();
+    let Some(existing) = person_nation::Entity::find_by_id(id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+    else {
+        return Err(ServiceError::not_found(
+            "person-nation link not found",
+            None::<&str>,
+        ));
+    };
+    person_nation::Entity::delete_by_id(id.clone())
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+    Ok(TanahpediaNationLinkWriteResult {
+        id,
+        nation_id: existing.nation_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::tanahpedia::sea_orm_active_enums::EntityType;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    fn source_link(
+        id: &str,
+        nation_id: &str,
+        source_nation_id: &str,
+    ) -> nation_source_nation::Model {
+        nation_source_nation::Model {
+            id: id.to_string(),
+            nation_id: nation_id.to_string(),
+            source_nation_id: source_nation_id.to_string(),
+            alt_group_id: None,
+        }
+    }
+
+    fn nation_row(id: &str, name: &str) -> (nation::Model, entity::Model) {
+        (
+            nation::Model {
+                id: id.to_string(),
+                entity_id: format!("entity-{id}"),
+            },
+            entity::Model {
+                id: format!("entity-{id}"),
+                entity_type: EntityType::Nation,
+                name: name.to_string(),
+                created_at: chrono::Utc::now().naive_utc(),
+                updated_at: chrono::Utc::now().naive_utc(),
+            },
+        )
+    }
+
+    fn source_input(nation_id: &str, source_nation_id: &str) -> PutTanahpediaNationSourceInput {
+        PutTanahpediaNationSourceInput {
+            id: "link-1".to_string(),
+            nation_id: nation_id.to_string(),
+            source_nation_id: source_nation_id.to_string(),
+            alt_group_id: None,
+        }
+    }
+
+    #[test]
+    fn held_in_year_treats_missing_dates_as_open() {
+        assert!(held_in_year(Some(24890101), Some(28890101), 2500));
+        assert!(held_in_year(Some(24890101), Some(28890101), 2489));
+        assert!(held_in_year(Some(24890101), Some(28890101), 2889));
+        assert!(!held_in_year(Some(24890101), Some(28890101), 2488));
+        assert!(!held_in_year(Some(24890101), Some(28890101), 2890));
+        assert!(held_in_year(None, Some(28890101), 1));
+        assert!(held_in_year(Some(24890101), None, 3500));
+        assert!(held_in_year(None, None, 2000));
+    }
+
+    #[test]
+    fn validate_year_rejects_years_before_creation() {
+        assert_eq!(validate_year(None).unwrap(), None);
+        assert_eq!(validate_year(Some(2448)).unwrap(), Some(2448));
+        assert!(validate_year(Some(0)).is_err());
+    }
+
+    #[tokio::test]
+    async fn lineage_walks_generations_and_stops_on_cycles() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![
+                    source_link("l-1", "edom", "esav"),
+                    source_link("l-2", "edom", "seir"),
+                ]])
+                .append_query_results([vec![
+                    source_link("l-3", "esav", "yitzhak"),
+                    source_link("l-4", "seir", "edom"),
+                ]])
+                .append_query_results::<nation_source_nation::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let links = lineage(db.get_connection(), "edom", Lineage::Ancestors)
+            .await
+            .expect("lineage should load");
+
+        assert_eq!(
+            links
+                .iter()
+                .map(|(depth, row)| (*depth, row.id.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(1, "l-1"), (1, "l-2"), (2, "l-3")]
+        );
+    }
+
+    #[tokio::test]
+    async fn get_nation_returns_not_found_for_unknown_nation() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<(nation::Model, entity::Model), Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let err = get_nation(&db, "missing".to_string(), None)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn get_nation_resolves_relatives_and_filters_territories_by_year() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![nation_row("edom", "אדום")]])
+                .append_query_results([vec![source_link("l-1", "edom", "esav")]])
+                .append_query_results::<nation_source_nation::Model, Vec<_>, _>([vec![]])
+                .append_query_results::<nation_source_nation::Model, Vec<_>, _>([vec![]])
+                .append_query_results([vec![
+                    nation_territory::Model {
+                        id: "t-1".to_string(),
+                        nation_id: "edom".to_string(),
+                        place_id: "seir".to_string(),
+                        start_date: Some(22280101),
+                        end_date: None,
+                        alt_group_id: None,
+                    },
+                    nation_territory::Model {
+                        id: "t-2".to_string(),
+                        nation_id: "edom".to_string(),
+                        place_id: "botzra".to_string(),
+                        start_date: Some(34000101),
+                        end_date: None,
+                        alt_group_id: None,
+                    },
+                ]])
+                .append_query_results::<person_nation::Model, Vec<_>, _>([vec![]])
+                .append_query_results([vec![nation_row("esav", "בני עשו")]])
+                .append_query_results([vec![(
+                    place::Model {
+                        id: "seir".to_string(),
+                        entity_id: "entity-seir".to_string(),
+                    },
+                    entity::Model {
+                        id: "entity-seir".to_string(),
+                        entity_type: EntityType::Place,
+                        name: "הר שעיר".to_string(),
+                        created_at: chrono::Utc::now().naive_utc(),
+                        updated_at: chrono::Utc::now().naive_utc(),
+                    },
+                )]])
+                .into_connection(),
+        );
+
+        let nation = get_nation(&db, "edom".to_string(), Some(2500))
+            .await
+            .expect("nation should load");
+
+        assert_eq!(nation.display_name, "אדום");
+        assert_eq!(
+            nation.ancestors,
+            vec![TanahpediaNationRelative {
+                link_id: "l-1".to_string(),
+                nation_id: "esav".to_string(),
+                display_name: "בני עשו".to_string(),
+                depth: 1,
+                via_nation_id: "edom".to_string(),
+                alt_group_id: None,
+            }]
+        );
+        assert!(nation.descendants.is_empty());
+        assert_eq!(nation.territories.len(), 1);
+        assert_eq!(nation.territories[0].display_name, "הר שעיר");
+        assert!(nation.persons.is_empty());
+    }
+
+    #[tokio::test]
+    async fn put_nation_source_rejects_self_links_before_querying() {
+        let db =
+            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
+
+        let err = put_nation_source(&db, source_input("edom", " edom "))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ServiceError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn put_nation_source_rejects_cycles() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![nation_row("esav", "בני עשו").0]])
+                .append_query_results([vec![nation_row("edom", "אדום").0]])
+                .append_query_results([vec![source_link("l-1", "edom", "esav")]])
+                .append_query_results::<nation_source_nation::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let err = put_nation_source(&db, source_input("esav", "edom"))
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(err, ServiceError::BadRequest(message) if message.contains("already descends"))
+        );
+    }
+
+    #[tokio::test]
+    async fn put_nation_territory_rejects_reversed_dates_before_querying() {
+        let db =
+            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
+
+        let err = put_nation_territory(
+            &db,
+            PutTanahpediaNationTerritoryInput {
+                id: "t-1".to_string(),
+                nation_id: "edom".to_string(),
+                place_id: "seir".to_string(),
+                start_date: Some(30000101),
+                end_date: Some(29990101),
+                alt_group_id: None,
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(err, ServiceError::BadRequest(_)));
+    }
+}
@@ -0,0 +1,422 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::tanahpedia_opinion::{
+        PutTanahpediaOpinionInput, TanahpediaOpinion, TanahpediaOpinionGroup,
+        TanahpediaOpinionMetadata, TanahpediaOpinionWriteResult,
+    },
+    providers::Database,
+    services::{
+        tanahpedia_family_service::{db_error, optional, required},
+        tanahpedia_sources_service::{SourcesInput, delete_sources, load_sources, replace_sources},
+    },
+};
+use async_graphql::OutputType;
+use entities::tanahpedia::alt_group;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter, TransactionTrait,
+};
+
+/// `tanahpedia_source_group.target_table` value for opinion sources.
+const OPINION_SOURCE_TABLE: &str = "tanahpedia_alt_group";
+
+/// Opinion metadata for a set of `altGroupId`s, loaded once per detail so every
+/// fact list of that detail can be grouped without further queries.
+#[derive(Debug, Default)]
+pub(crate) struct OpinionCatalog {
+    opinions: HashMap<String, TanahpediaOpinionMetadata>,
+}
+
+impl OpinionCatalog {
+    pub(crate) async fn load<C: ConnectionTrait>(
+        conn: &C,
+        alt_group_ids: HashSet<String>,
+    ) -> Result<Self, ServiceError> {
+        Ok(Self {
+            opinions: load_metadata(conn, alt_group_ids).await?,
+        })
+    }
+
+    /// Buckets `(altGroupId, fact)` rows into opinions, keeping each bucket's
+    /// rows in their incoming order. Ids without metadata still form an
+    /// opinion, just unlabelled and without sources.
+    pub(crate) fn group<T: OutputType>(
+        &self,
+        rows: Vec<(Option<String>, T)>,
+    ) -> TanahpediaOpinionGroup<TanahpediaOpinion<T>>
+    where
+        TanahpediaOpinion<T>: OutputType,
+    {
+        let mut opinions: Vec<TanahpediaOpinion<T>> = Vec::new();
+        for (alt_group_id, fact) in rows {
+            if let Some(opinion) = opinions
+                .iter_mut()
+                .find(|opinion| opinion.alt_group_id == alt_group_id)
+            {
+                opinion.facts.push(fact);
+                continue;
+            }
+            let metadata = alt_group_id.as_ref().and_then(|id| self.opinions.get(id));
+            opinions.push(TanahpediaOpinion {
+                label: metadata.and_then(|m| m.label.clone()),
+                is_preferred: metadata.is_some_and(|m| m.is_preferred),
+                sources: metadata.map(|m| m.sources.clone()).unwrap_or_default(),
+                alt_group_id,
+                facts: vec![fact],
+            });
+        }
+        opinions.sort_by(|a, b| {
+            a.alt_group_id
+                .is_some()
+                .cmp(&b.alt_group_id.is_some())
+                .then(b.is_preferred.cmp(&a.is_preferred))
+                .then(a.label.is_none().cmp(&b.label.is_none()))
+                .then_with(|| a.label.cmp(&b.label))
+                .then_with(|| a.alt_group_id.cmp(&b.alt_group_id))
+        });
+
+        TanahpediaOpinionGroup {
+            preferred_alt_group_id: opinions
+                .iter()
+                .find(|opinion| opinion.is_preferred)
+                .and_then(|opinion| opinion.alt_group_id.clone()),
+            is_disputed: opinions.len() > 1,
+            opinions,
+        }
+    }
+}
+
+/// Collects the non-null `altGroupId`s of fact rows for [`OpinionCatalog::load`].
+pub(crate) fn alt_group_ids<'a>(
+    ids: impl IntoIterator<Item = &'a Option<String>>,
+) -> HashSet<String> {
+    ids.into_iter().flatten().cloned().collect()
+}
+
+async fn load_metadata<C: ConnectionTrait>(
+    conn: &C,
+    alt_group_ids: HashSet<String>,
+) -> Result<HashMap<String, TanahpediaOpinionMetadata>, ServiceError> {
+    if alt_group_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows = alt_group::Entity::find()
+        .filter(alt_group::Column::Id.is_in(alt_group_ids))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let mut sources = load_sources(
+        conn,
+        OPINION_SOURCE_TABLE,
+        rows.iter().map(|row| row.id.clone()).collect(),
+    )
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let metadata = TanahpediaOpinionMetadata {
+                sources: sources.remove(&row.id).unwrap_or_default(),
+                alt_group_id: row.id.clone(),
+                label: row.label,
+                is_preferred: row.is_preferred,
+                dispute_id: row.dispute_id,
+            };
+            (row.id, metadata)
+        })
+        .collect())
+}
+
+/// Metadata and sources for each requested opinion that has any, in request
+/// order.
+pub async fn get_opinions(
+    db: &Database,
+    alt_group_ids: Vec<String>,
+) -> Result<Vec<TanahpediaOpinionMetadata>, ServiceError> {
+    let alt_group_ids = alt_group_ids
+        .into_iter()
+        .map(|id| required(id, "altGroupIds", 36))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut metadata =
+        load_metadata(db.get_connection(), alt_group_ids.iter().cloned().collect()).await?;
+    Ok(alt_group_ids
+        .iter()
+        .filter_map(|id| metadata.remove(id))
+        .collect())
+}
+
+/// Upserts an opinion's metadata and sources. Marking it preferred clears the
+/// flag on the other opinions of its dispute in the same transaction, so each
+/// dispute has at most one preferred opinion.
+pub async fn put_opinion(
+    db: &Database,
+    input: PutTanahpediaOpinionInput,
+) -> Result<TanahpediaOpinionWriteResult, ServiceError> {
+    let alt_group_id = required(input.alt_group_id, "altGroupId", 36)?;
+    let label = optional(input.label, "label", 200)?;
+    let dispute_id = optional(input.dispute_id, "disputeId", 36)?;
+    if input.is_preferred && dispute_id.is_none() {
+        return Err(ServiceError::bad_request("isPreferred requires disputeId"));
+    }
+    let sources = SourcesInput::parse(input.tanah_sources, input.non_tanah_sources)?;
+
+    let transaction = db.get_connection().begin().await.map_err(db_error)?;
+    if let Some(dispute_id) = dispute_id.as_ref().filter(|_| input.is_preferred) {
+        alt_group::Entity::update_many()
+            .col_expr(alt_group::Column::IsPreferred, Expr::value(false))
+            .filter(alt_group::Column::DisputeId.eq(dispute_id.clone()))
+            .filter(alt_group::Column::Id.ne(alt_group_id.clone()))
+            .exec(&transaction)
+            .await
+            .map_err(db_error)?;
+    }
+    alt_group::Entity::insert(
+        alt_group::Model {
+            id: alt_group_id.clone(),
+            label: label.clone(),
+            is_preferred: input.is_preferred,
+            dispute_id: dispute_id.clone(),
+        }
+        .into_active_model(),
+    )
+    .on_conflict(
+        OnConflict::column(alt_group::Column::Id)
+            .update_columns([
+                alt_group::Column::Label,
+                alt_group::Column::IsPreferred,
+                alt_group::Column::DisputeId,
+            ])
+            .to_owned(),
+    )
+    .exec(&transaction)
+    .await
+    .map_err(db_error)?;
+    replace_sources(&transaction, OPINION_SOURCE_TABLE, &alt_group_id, sources).await?;
+    transaction.commit().await.map_err(db_error)?;
+
+    Ok(TanahpediaOpinionWriteResult {
+        alt_group_id,
+        label,
+        is_preferred: input.is_preferred,
+        dispute_id,
+    })
+}
+
+/// Removes an opinion's label, preferred flag and sources. The fact rows
+/// carrying the `altGroupId` are left alone.
+pub async fn delete_opinion(
+    db: &Database,
+    alt_group_id: String,
+) -> Result<TanahpediaOpinionWriteResult, ServiceError> {
+    let alt_group_id = required(alt_group_id, "altGroupId", 36)?;
+    let conn = db.get_connection();
+    let Some(row) = alt_group::Entity::find_by_id(alt_group_id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+    else {
+        return Err(ServiceError::not_found("opinion not found", None::<&str>));
+    };
+
+    let transaction = conn.begin().await.map_err(db_error)?;
+    delete_sources(&transaction, OPINION_SOURCE_TABLE, &alt_group_id).await?;
+    alt_group::Entity::delete_by_id(alt_group_id.clone())
+        .exec(&transaction)
+        .await
+        .map_err(db_error)?;
+    transaction.commit().await.map_err(db_error)?;
+
+    Ok(TanahpediaOpinionWriteResult {
+        alt_group_id,
+        label: row.label,
+        is_preferred: row.is_preferred,
+        dispute_id: row.dispute_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::tanahpedia_source::TanahpediaSources;
+    use entities::tanahpedia::source_group;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    fn catalog(entries: &[(&str, Option<&str>, bool)]) -> OpinionCatalog {
+        OpinionCatalog {
+            opinions: entries
+                .iter()
+                .map(|(id, label, is_preferred)| {
+                    (
+                        id.to_string(),
+                        TanahpediaOpinionMetadata {
+                            alt_group_id: id.to_string(),
+                            label: label.map(str::to_string),
+                            is_preferred: *is_preferred,
+                            dispute_id: None,
+                            sources: TanahpediaSources::default(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn alt(id: &str) -> Option<String> {
+        Some(id.to_string())
+    }
+
+    #[test]
+    fn group_puts_undisputed_then_preferred_then_labelled_opinions() {
+        let catalog = catalog(&[
+            ("alt-rashi", Some("רש\"י"), false),
+            ("alt-ramban", Some("רמב\"ן"), true),
+        ]);
+
+        let group = catalog.group(vec![
+            (alt("alt-unlabelled"), 4),
+            (alt("alt-rashi"), 1),
+            (None, 0),
+            (alt("alt-ramban"), 2),
+            (alt("alt-rashi"), 3),
+        ]);
+
+        assert_eq!(
+            group
+                .opinions
+                .iter()
+                .map(|opinion| (opinion.alt_group_id.as_deref(), opinion.facts.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (None, vec![0]),
+                (Some("alt-ramban"), vec![2]),
+                (Some("alt-rashi"), vec![1, 3]),
+                (Some("alt-unlabelled"), vec![4]),
+            ]
+        );
+        assert_eq!(group.preferred_alt_group_id.as_deref(), Some("alt-ramban"));
+        assert!(group.is_disputed);
+        assert_eq!(group.opinions[2].label.as_deref(), Some("רש\"י"));
+    }
+
+    #[test]
+    fn group_of_undisputed_rows_is_not_disputed() {
+        let group = OpinionCatalog::default().group(vec![(None, "MALE".to_string())]);
+
+        assert!(!group.is_disputed);
+        assert_eq!(group.preferred_alt_group_id, None);
+        assert_eq!(group.opinions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn get_opinions_keeps_request_order_and_skips_unknown_ids() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![
+                    alt_group::Model {
+                        id: "alt-1".to_string(),
+                        label: Some("רש\"י".to_string()),
+                        is_preferred: true,
+                        dispute_id: Some("dispute-1".to_string()),
+                    },
+                    alt_group::Model {
+                        id: "alt-2".to_string(),
+                        label: None,
+                        is_preferred: false,
+                        dispute_id: Some("dispute-1".to_string()),
+                    },
+                ]])
+                .append_query_results::<source_group::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let opinions = get_opinions(
+            &db,
+            vec![
+                "alt-2".to_string(),
+                "missing".to_string(),
+                "alt-1".to_string(),
+            ],
+        )
+        .await
+        .expect("opinions should load");
+
+        assert_eq!(
+            opinions
+                .iter()
+                .map(|opinion| opinion.alt_group_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["alt-2", "alt-1"]
+        );
+        assert!(opinions[1].is_preferred);
+    }
+
+    #[tokio::test]
+    async fn delete_opinion_reports_missing_metadata() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<alt_group::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let err = delete_opinion(&db, "missing".to_string())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+
+    fn put_input(is_preferred: bool, dispute_id: Option<&str>) -> PutTanahpediaOpinionInput {
+        PutTanahpediaOpinionInput {
+            alt_group_id: "alt-ramban".to_string(),
+            label: Some("רמב\"ן".to_string()),
+            is_preferred,
+            dispute_id: dispute_id.map(str::to_string),
+            tanah_sources: vec![],
+            non_tanah_sources: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn put_opinion_requires_dispute_to_mark_preferred() {
+        let db =
+            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
+
+        let err = put_opinion(&db, put_input(true, None)).await.unwrap_err();
+
+        assert!(
+            matches!(err, ServiceError::BadRequest(message) if message == "isPreferred requires disputeId")
+        );
+    }
+
+    #[tokio::test]
+    async fn put_opinion_clears_other_preferred_opinions_of_its_dispute() {
+        let exec_result = MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        };
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_exec_results([exec_result.clone(), exec_result.clone(), exec_result])
+                .into_connection(),
+        );
+
+        let result = put_opinion(&db, put_input(true, Some("dispute-1")))
+            .await
+            .expect("opinion should be written");
+
+        assert!(result.is_preferred);
+        assert_eq!(result.dispute_id.as_deref(), Some("dispute-1"));
+        let transaction_log = db.get_connection().clone().into_transaction_log();
+        assert_eq!(transaction_log.len(), 1);
+        let executed_sql = format!("{:?}", transaction_log[0]);
+        let cleared = executed_sql
+            .find("UPDATE `tanahpedia_alt_group` SET `is_preferred`")
+            .expect("other opinions of the dispute should be cleared");
+        let upserted = executed_sql
+            .find("INSERT INTO `tanahpedia_alt_group`")
+            .expect("opinion should be upserted");
+        assert!(cleared < upserted);
+    }
+}
@@ -0,0 +1,542 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::tanahpedia_place::{
+        TanahpediaPlaceBirthLink, TanahpediaPlaceEventLink, TanahpediaPlaceFeature,
+        TanahpediaPlaceFeatureCollection, TanahpediaPlaceFeatureProperties,
+        TanahpediaPlaceIdentification, TanahpediaPlaceNear, TanahpediaPointGeometry,
+    },
+    providers::Database,
+    services::{
+        tanahpedia_family_service::{
+            db_error, entity_display_names, person_display_names, required,
+        },
+        tanahpedia_opinions_service::{OpinionCatalog, alt_group_ids},
+    },
+};
+use entities::tanahpedia::{event, event_place, person_birth_place, place, place_identification};
+use sea_orm::prelude::Decimal;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+/// Mean Earth radius used by the haversine distance, in kilometres.
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// Upper bound for `radiusKm`; half the Earth's circumference covers every
+/// point, so anything larger is a client mistake.
+const MAX_RADIUS_KM: f64 = 20_038.0;
+
+/// A GeoJSON bounding box: `[minLongitude, minLatitude, maxLongitude, maxLatitude]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct BoundingBox {
+    min_lon: f64,
+    min_lat: f64,
+    max_lon: f64,
+    max_lat: f64,
+}
+
+impl BoundingBox {
+    fn parse(values: Vec<f64>) -> Result<Self, ServiceError> {
+        let [min_lon, min_lat, max_lon, max_lat] = values[..] else {
+            return Err(ServiceError::bad_request(
+                "bbox must be [minLongitude, minLatitude, maxLongitude, maxLatitude]",
+            ));
+        };
+        validate_latitude(min_lat, "bbox")?;
+        validate_latitude(max_lat, "bbox")?;
+        validate_longitude(min_lon, "bbox")?;
+        validate_longitude(max_lon, "bbox")?;
+        if min_lon > max_lon || min_lat > max_lat {
+            return Err(ServiceError::bad_request(
+                "bbox minimums must not exceed its maximums",
+            ));
+        }
+        Ok(Self {
+            min_lon,
+            min_lat,
+            max_lon,
+            max_lat,
+        })
+    }
+
+    fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        (self.min_lat..=self.max_lat).contains(&latitude)
+            && (self.min_lon..=self.max_lon).contains(&longitude)
+    }
+}
+
+pub(crate) fn validate_latitude(value: f64, field: &str) -> Result<(), ServiceError> {
+    if !(-90.0..=90.0).contains(&value) {
+        return Err(ServiceError::bad_request(&format!(
+            "{field} latitude must be between -90 and 90"
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_longitude(value: f64, field: &str) -> Result<(), ServiceError> {
+    if !(-180.0..=180.0).contains(&value) {
+        return Err(ServiceError::bad_request(&format!(
+            "{field} longitude must be between -180 and 180"
+        )));
+    }
+    Ok(())
+}
+
+/// Great-circle distance between two WGS84 points, in kilometres.
+fn haversine_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lon = (lon2 - lon1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+pub(crate) fn to_f64(value: Option<Decimal>) -> Option<f64> {
+    value.and_then(|value| f64::try_from(value).ok())
+}
+
+fn identification(row: &place_identification::Model) -> TanahpediaPlaceIdentification {
+    TanahpediaPlaceIdentification {
+        id: row.id.clone(),
+        modern_name: row.modern_name.clone(),
+        latitude: to_f64(row.latitude),
+        longitude: to_f64(row.longitude),
+        alt_group_id: row.alt_group_id.clone(),
+    }
+}
+
+/// Maps each `eventId` to the name of its backing entity.
+async fn event_display_names(
+    conn: &sea_orm::DatabaseConnection,
+    event_ids: HashSet<String>,
+) -> Result<HashMap<String, String>, ServiceError> {
+    if event_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows = event::Entity::find()
+        .filter(event::Column::Id.is_in(event_ids))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let names =
+        entity_display_names(conn, rows.iter().map(|r| r.entity_id.clone()).collect()).await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|r| names.get(&r.entity_id).map(|name| (r.id, name.clone())))
+        .collect())
+}
+
+/// Builds a GeoJSON feature collection of every located place identification,
+/// optionally limited to a bounding box and/or to the places of the given
+/// entities. Each feature carries all alternative identifications of its
+/// place plus the events held and persons born there.
+pub async fn places_geojson(
+    db: &Database,
+    bbox: Option<Vec<f64>>,
+    entity_ids: Option<Vec<String>>,
+) -> Result<TanahpediaPlaceFeatureCollection, ServiceError> {
+    let bbox = bbox.map(BoundingBox::parse).transpose()?;
+    let entity_ids = entity_ids
+        .map(|ids| {
+            ids.into_iter()
+                .map(|id| required(id, "entityIds", 36))
+                .collect::<Result<HashSet<_>, _>>()
+        })
+        .transpose()?;
+    let conn = db.get_connection();
+
+    let mut place_query = place::Entity::find();
+    if let Some(entity_ids) = entity_ids {
+        place_query = place_query.filter(place::Column::EntityId.is_in(entity_ids));
+    }
+    let places = place_query
+        .order_by_asc(place::Column::Id)
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    if places.is_empty() {
+        return Ok(feature_collection(Vec::new()));
+    }
+
+    let identifications = place_identification::Entity::find()
+        .filter(
+            place_identification::Column::PlaceId.is_in(places.iter().map(|row| row.id.clone())),
+        )
+        .order_by_asc(place_identification::Column::Id)
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let mut identifications_by_place = HashMap::<String, Vec<place_identification::Model>>::new();
+    for row in identifications {
+        identifications_by_place
+            .entry(row.place_id.clone())
+            .or_default()
+            .push(row);
+    }
+
+    let located_place_ids = identifications_by_place
+        .iter()
+        .filter(|(_, rows)| {
+            rows.iter()
+                .any(|row| match (to_f64(row.latitude), to_f64(row.longitude)) {
+                    (Some(lat), Some(lon)) => bbox.is_none_or(|bbox| bbox.contains(lat, lon)),
+                    _ => false,
+                })
+        })
+        .map(|(place_id, _)| place_id.clone())
+        .collect::<HashSet<_>>();
+    if located_place_ids.is_empty() {
+        return Ok(feature_collection(Vec::new()));
+    }
+
+    let events = event_place::Entity::find()
+        .filter(event_place::Column::PlaceId.is_in(located_place_ids.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let births = person_birth_place::Entity::find()
+        .filter(person_birth_place::Column::PlaceId.is_in(located_place_ids.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let place_names = entity_display_names(
+        conn,
+        places
+            .iter()
+            .filter(|row| located_place_ids.contains(&row.id))
+            .map(|row| row.entity_id.clone())
+            .collect(),
+    )
+    .await?;
+    let event_names =
+        event_display_names(conn, events.iter().map(|r| r.event_id.clone()).collect()).await?;
+    let person_names =
+        person_display_names(conn, births.iter().map(|r| r.person_id.clone()).collect()).await?;
+    let catalog = OpinionCatalog::load(
+        conn,
+        alt_group_ids(
+            located_place_ids
+                .iter()
+                .filter_map(|place_id| identifications_by_place.get(place_id))
+                .flatten()
+                .map(|row| &row.alt_group_id),
+        ),
+    )
+    .await?;
+
+    let mut events_by_place = HashMap::<String, Vec<TanahpediaPlaceEventLink>>::new();
+    for row in events {
+        events_by_place
+            .entry(row.place_id)
+            .or_default()
+            .push(TanahpediaPlaceEventLink {
+                display_name: event_names.get(&row.event_id).cloned().unwrap_or_default(),
+                link_id: row.id,
+                event_id: row.event_id,
+                alt_group_id: row.alt_group_id,
+            });
+    }
+    let mut births_by_place = HashMap::<String, Vec<TanahpediaPlaceBirthLink>>::new();
+    for row in births {
+        births_by_place
+            .entry(row.place_id)
+            .or_default()
+            .push(TanahpediaPlaceBirthLink {
+                display_name: person_names
+                    .get(&row.person_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                link_id: row.id,
+                person_id: row.person_id,
+                alt_group_id: row.alt_group_id,
+            });
+    }
+
+    let mut features = Vec::new();
+    for place_row in places {
+        if !located_place_ids.contains(&place_row.id) {
+            continue;
+        }
+        let rows = identifications_by_place
+            .remove(&place_row.id)
+            .unwrap_or_default();
+        let alternatives = rows.iter().map(identification).collect::<Vec<_>>();
+        let opinions = catalog.group(
+            rows.iter()
+                .map(|row| (row.alt_group_id.clone(), identification(row)))
+                .collect(),
+        );
+        let display_name = place_names
+            .get(&place_row.entity_id)
+            .cloned()
+            .unwrap_or_default();
+        let events = events_by_place.remove(&place_row.id).unwrap_or_default();
+        let births = births_by_place.remove(&place_row.id).unwrap_or_default();
+
+        for row in &rows {
+            let (Some(latitude), Some(longitude)) = (to_f64(row.latitude), to_f64(row.longitude))
+            else {
+                continue;
+            };
+            if bbox.is_some_and(|bbox| !bbox.contains(latitude, longitude)) {
+                continue;
+            }
+            features.push(TanahpediaPlaceFeature {
+                kind: "Feature".to_string(),
+                id: row.id.clone(),
+                geometry: TanahpediaPointGeometry {
+                    kind: "Point".to_string(),
+                    coordinates: vec![longitude, latitude],
+                },
+                properties: TanahpediaPlaceFeatureProperties {
+                    place_id: place_row.id.clone(),
+                    entity_id: place_row.entity_id.clone(),
+                    display_name: display_name.clone(),
+                    identification_id: row.id.clone(),
+                    modern_name: row.modern_name.clone(),
+                    alt_group_id: row.alt_group_id.clone(),
+                    identifications: alternatives.clone(),
+                    identification_opinions: opinions.clone(),
+                    events: events.clone(),
+                    births: births.clone(),
+                },
+            });
+        }
+    }
+
+    Ok(feature_collection(features))
+}
+
+fn feature_collection(features: Vec<TanahpediaPlaceFeature>) -> TanahpediaPlaceFeatureCollection {
+    TanahpediaPlaceFeatureCollection {
+        kind: "FeatureCollection".to_string(),
+        features,
+    }
+}
+
+/// Lists every located place identification within `radius_km` of the given
+/// point, nearest first. Each alternative identification is a separate hit,
+/// so a place may appear more than once.
+pub async fn places_near(
+    db: &Database,
+    latitude: f64,
+    longitude: f64,
+    radius_km: f64,
+) -> Result<Vec<TanahpediaPlaceNear>, ServiceError> {
+    validate_latitude(latitude, "lat")?;
+    validate_longitude(longitude, "lon")?;
+    if !(radius_km > 0.0 && radius_km <= MAX_RADIUS_KM) {
+        return Err(ServiceError::bad_request(&format!(
+            "radiusKm must be greater than 0 and at most {MAX_RADIUS_KM}"
+        )));
+    }
+    let conn = db.get_connection();
+
+    let mut hits = place_identification::Entity::find()
+        .filter(place_identification::Column::Latitude.is_not_null())
+        .filter(place_identification::Column::Longitude.is_not_null())
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .filter_map(|row| {
+            let lat = to_f64(row.latitude)?;
+            let lon = to_f64(row.longitude)?;
+            let distance_km = haversine_km(latitude, longitude, lat, lon);
+            (distance_km <= radius_km).then_some((row, lat, lon, distance_km))
+        })
+        .collect::<Vec<_>>();
+    if hits.is_empty() {
+        return Ok(Vec::new());
+    }
+    hits.sort_by(|a, b| a.3.total_cmp(&b.3).then_with(|| a.0.id.cmp(&b.0.id)));
+
+    let places = place::Entity::find()
+        .filter(place::Column::Id.is_in(hits.iter().map(|(row, ..)| row.place_id.clone())))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| (row.id, row.entity_id))
+        .collect::<HashMap<_, _>>();
+    let names = entity_display_names(conn, places.values().cloned().collect()).await?;
+
+    Ok(hits
+        .into_iter()
+        .filter_map(|(row, lat, lon, distance_km)| {
+            let entity_id = places.get(&row.place_id)?.clone();
+            Some(TanahpediaPlaceNear {
+                display_name: names.get(&entity_id).cloned().unwrap_or_default(),
+                place_id: row.place_id,
+                entity_id,
+                identification_id: row.id,
+                modern_name: row.modern_name,
+                latitude: lat,
+                longitude: lon,
+                alt_group_id: row.alt_group_id,
+                distance_km,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::tanahpedia::{alt_group, entity, sea_orm_active_enums::EntityType, source_group};
+    use sea_orm::{DatabaseBackend, MockDatabase};
+    use std::str::FromStr;
+
+    fn place_model(id: &str, entity_id: &str) -> place::Model {
+        place::Model {
+            id: id.to_string(),
+            entity_id: entity_id.to_string(),
+        }
+    }
+
+    fn identification_model(
+        id: &str,
+        place_id: &str,
+        coordinates: Option<(&str, &str)>,
+        alt_group_id: Option<&str>,
+    ) -> place_identification::Model {
+        place_identification::Model {
+            id: id.to_string(),
+            place_id: place_id.to_string(),
+            modern_name: Some(format!("modern {id}")),
+            latitude: coordinates.map(|(lat, _)| Decimal::from_str(lat).unwrap()),
+            longitude: coordinates.map(|(_, lon)| Decimal::from_str(lon).unwrap()),
+            alt_group_id: alt_group_id.map(str::to_string),
+        }
+    }
+
+    fn entity_model(id: &str, name: &str) -> entity::Model {
+        entity::Model {
+            id: id.to_string(),
+            entity_type: EntityType::Place,
+            name: name.to_string(),
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    #[test]
+    fn bounding_box_requires_four_ordered_values() {
+        assert!(BoundingBox::parse(vec![34.0, 31.0, 36.0]).is_err());
+        assert!(BoundingBox::parse(vec![36.0, 31.0, 34.0, 33.0]).is_err());
+        assert!(BoundingBox::parse(vec![34.0, 91.0, 36.0, 92.0]).is_err());
+
+        let bbox = BoundingBox::parse(vec![34.0, 31.0, 36.0, 33.0]).unwrap();
+        assert!(bbox.contains(31.7683, 35.2137));
+        assert!(!bbox.contains(30.0, 35.0));
+    }
+
+    #[test]
+    fn haversine_matches_known_distance() {
+        // Jerusalem to Hebron is about 28 km as the crow flies.
+        let distance = haversine_km(31.7683, 35.2137, 31.5326, 35.0998);
+        assert!((distance - 28.2).abs() < 1.0, "{distance}");
+        assert_eq!(haversine_km(31.0, 35.0, 31.0, 35.0), 0.0);
+    }
+
+    #[tokio::test]
+    async fn places_near_rejects_invalid_radius_before_querying() {
+        let db =
+            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
+
+        let err = places_near(&db, 31.77, 35.21, 0.0).await.unwrap_err();
+
+        assert!(matches!(err, ServiceError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn places_near_orders_alternative_identifications_by_distance() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![
+                    identification_model(
+                        "ident-far",
+                        "place-1",
+                        Some(("31.53260000", "35.09980000")),
+                        Some("alt-b"),
+                    ),
+                    identification_model(
+                        "ident-near",
+                        "place-1",
+                        Some(("31.77000000", "35.21000000")),
+                        Some("alt-a"),
+                    ),
+                    identification_model(
+                        "ident-out",
+                        "place-2",
+                        Some(("33.00000000", "35.50000000")),
+                        None,
+                    ),
+                ]])
+                .append_query_results([vec![place_model("place-1", "entity-1")]])
+                .append_query_results([vec![entity_model("entity-1", "חברון")]])
+                .into_connection(),
+        );
+
+        let hits = places_near(&db, 31.7683, 35.2137, 50.0)
+            .await
+            .expect("nearby places should load");
+
+        assert_eq!(
+            hits.iter()
+                .map(|hit| hit.identification_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["ident-near", "ident-far"]
+        );
+        assert_eq!(hits[0].display_name, "חברון");
+        assert_eq!(hits[1].alt_group_id.as_deref(), Some("alt-b"));
+    }
+
+    #[tokio::test]
+    async fn places_geojson_emits_one_feature_per_located_identification() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![place_model("place-1", "entity-1")]])
+                .append_query_results([vec![
+                    identification_model(
+                        "ident-a",
+                        "place-1",
+                        Some(("31.77000000", "35.21000000")),
+                        Some("alt-a"),
+                    ),
+                    identification_model("ident-b", "place-1", None, Some("alt-b")),
+                ]])
+                .append_query_results::<event_place::Model, Vec<_>, _>([vec![]])
+                .append_query_results::<person_birth_place::Model, Vec<_>, _>([vec![]])
+                .append_query_results([vec![entity_model("entity-1", "ירושלים")]])
+                .append_query_results([vec![alt_group::Model {
+                    id: "alt-b".to_string(),
+                    label: Some("אבן שמואל".to_string()),
+                    is_preferred: true,
+                    dispute_id: Some("dispute-1".to_string()),
+                }]])
+                .append_query_results::<source_group::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let collection = places_geojson(&db, None, None)
+            .await
+            .expect("geojson should build");
+
+        assert_eq!(collection.kind, "FeatureCollection");
+        assert_eq!(collection.features.len(), 1);
+        let feature = &collection.features[0];
+        let [lon, lat] = feature.geometry.coordinates[..] else {
+            panic!("point should have two coordinates");
+        };
+        assert!((lon - 35.21).abs() < 1e-9 && (lat - 31.77).abs() < 1e-9);
+        assert_eq!(feature.properties.display_name, "ירושלים");
+        assert_eq!(feature.properties.identifications.len(), 2);
+        let opinions = &feature.properties.identification_opinions;
+        assert!(opinions.is_disputed);
+        assert_eq!(opinions.preferred_alt_group_id.as_deref(), Some("alt-b"));
+        assert_eq!(opinions.opinions[0].facts[0].id, "ident-b");
+    }
+}
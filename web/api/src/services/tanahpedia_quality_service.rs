@@ -0,0 +1,521 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::{
+        tanahpedia_entry::TanahpediaEntryReference,
+        tanahpedia_family::TanahpediaEntitySummary,
+        tanahpedia_quality::{
+            TanahpediaEntityFindings, TanahpediaEntryFindings, TanahpediaIncompletePerson,
+            TanahpediaIncompletePersonFindings, TanahpediaLookup, TanahpediaQualityReport,
+            TanahpediaRelationshipKind, TanahpediaUncitedRelationship,
+            TanahpediaUncitedRelationshipFindings, TanahpediaUnrecognizedNonTanahSource,
+            TanahpediaUnrecognizedNonTanahSourceFindings, TanahpediaUnusedLookupValue,
+            TanahpediaUnusedLookupValueFindings,
+        },
+    },
+    providers::Database,
+    services::{
+        tanahpedia_chazal_service::parse_reference,
+        tanahpedia_family_service::{ENTITY_DEPENDENCY_SQL, db_error},
+    },
+};
+use entities::tanahpedia::{
+    entity, entity_tanah_source, entry, entry_entity, lookup_name_type, lookup_parent_child_type,
+    lookup_parent_role, lookup_person_nation_role, lookup_union_end_reason, lookup_union_type,
+    non_tanah_source, person, person_name, person_nation, person_parent_child, person_sex,
+    person_union, source_group,
+};
+use sea_orm::sea_query::{Expr, IntoColumnRef, IntoTableRef, Query, SelectStatement};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, EntityTrait, ExprTrait, QueryFilter,
+    QueryOrder, Statement,
+};
+
+/// The non-null values of `column` in `table`, for `NOT IN` filters. A NULL
+/// in the subquery would make every `NOT IN` false.
+fn used_values<T, C>(table: T, column: C) -> SelectStatement
+where
+    T: IntoTableRef,
+    C: IntoColumnRef + Copy,
+{
+    Query::select()
+        .column(column)
+        .from(table)
+        .and_where(Expr::col(column).is_not_null())
+        .to_owned()
+}
+
+fn entity_summary(entity: entity::Model) -> TanahpediaEntitySummary {
+    TanahpediaEntitySummary {
+        entity_id: entity.id,
+        entity_type: entity.entity_type.into(),
+        display_name: entity.name,
+    }
+}
+
+fn entity_findings(entities: Vec<entity::Model>) -> TanahpediaEntityFindings {
+    TanahpediaEntityFindings {
+        count: entities.len() as i32,
+        entities: entities.into_iter().map(entity_summary).collect(),
+    }
+}
+
+/// `ENTITY_DEPENDENCY_SQL` evaluated for every entity at once, keeping those
+/// with no dependencies.
+fn orphan_entities_sql() -> String {
+    format!(
+        "SELECT e.* FROM tanahpedia_entity e WHERE ({}) = 0 ORDER BY e.name",
+        ENTITY_DEPENDENCY_SQL.replace('?', "e.id")
+    )
+}
+
+async fn persons_missing<C: ConnectionTrait>(
+    conn: &C,
+    subquery: SelectStatement,
+) -> Result<Vec<(person::Model, Option<entity::Model>)>, ServiceError> {
+    person::Entity::find()
+        .find_also_related(entity::Entity)
+        .filter(person::Column::Id.not_in_subquery(subquery))
+        .all(conn)
+        .await
+        .map_err(db_error)
+}
+
+async fn incomplete_persons<C: ConnectionTrait>(
+    conn: &C,
+) -> Result<TanahpediaIncompletePersonFindings, ServiceError> {
+    let without_sex = persons_missing(
+        conn,
+        used_values(person_sex::Entity, person_sex::Column::PersonId),
+    )
+    .await?;
+    let without_names = persons_missing(
+        conn,
+        used_values(person_name::Entity, person_name::Column::PersonId),
+    )
+    .await?;
+
+    let mut persons = BTreeMap::new();
+    for (missing_sex, rows) in [(true, without_sex), (false, without_names)] {
+        for (person, entity) in rows {
+            let finding =
+                persons
+                    .entry(person.id.clone())
+                    .or_insert_with(|| TanahpediaIncompletePerson {
+                        person_id: person.id,
+                        entity_id: person.entity_id,
+                        display_name: entity.map(|entity| entity.name).unwrap_or_default(),
+                        missing_sex: false,
+                        missing_names: false,
+                    });
+            if missing_sex {
+                finding.missing_sex = true;
+            } else {
+                finding.missing_names = true;
+            }
+        }
+    }
+
+    let mut persons: Vec<_> = persons.into_values().collect();
+    persons.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+    Ok(TanahpediaIncompletePersonFindings {
+        count: persons.len() as i32,
+        persons,
+    })
+}
+
+async fn uncited_relationships<C: ConnectionTrait>(
+    conn: &C,
+) -> Result<TanahpediaUncitedRelationshipFindings, ServiceError> {
+    let parent_child = person_parent_child::Entity::find()
+        .filter(
+            Condition::any()
+                .add(person_parent_child::Column::SourceCitation.is_null())
+                .add(person_parent_child::Column::SourceCitation.eq("")),
+        )
+        .order_by_asc(person_parent_child::Column::Id)
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let unions = person_union::Entity::find()
+        .filter(
+            Condition::any()
+                .add(person_union::Column::SourceCitation.is_null())
+                .add(person_union::Column::SourceCitation.eq("")),
+        )
+        .order_by_asc(person_union::Column::Id)
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+
+    let relationships: Vec<_> = parent_child
+        .into_iter()
+        .map(|link| TanahpediaUncitedRelationship {
+            id: link.id,
+            kind: TanahpediaRelationshipKind::ParentChild,
+            person1_id: link.parent_id,
+            person2_id: link.child_id,
+        })
+        .chain(
+            unions
+                .into_iter()
+                .map(|union| TanahpediaUncitedRelationship {
+                    id: union.id,
+                    kind: TanahpediaRelationshipKind::Union,
+                    person1_id: union.person1_id,
+                    person2_id: union.person2_id,
+                }),
+        )
+        .collect();
+    Ok(TanahpediaUncitedRelationshipFindings {
+        count: relationships.len() as i32,
+        relationships,
+    })
+}
+
+fn unused(lookup: TanahpediaLookup) -> impl Fn((String, String)) -> TanahpediaUnusedLookupValue {
+    move |(id, name)| TanahpediaUnusedLookupValue { lookup, id, name }
+}
+
+async fn unused_lookup_values<C: ConnectionTrait>(
+    conn: &C,
+) -> Result<TanahpediaUnusedLookupValueFindings, ServiceError> {
+    let mut values = Vec::new();
+
+    values.extend(
+        lookup_name_type::Entity::find()
+            .filter(lookup_name_type::Column::Id.not_in_subquery(used_values(
+                person_name::Entity,
+                person_name::Column::NameTypeId,
+            )))
+            .order_by_asc(lookup_name_type::Column::Name)
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|value| (value.id, value.name))
+            .map(unused(TanahpediaLookup::NameType)),
+    );
+    values.extend(
+        lookup_parent_child_type::Entity::find()
+            .filter(
+                lookup_parent_child_type::Column::Id.not_in_subquery(used_values(
+                    person_parent_child::Entity,
+                    person_parent_child::Column::RelationshipTypeId,
+                )),
+            )
+            .order_by_asc(lookup_parent_child_type::Column::Name)
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|value| (value.id, value.name))
+            .map(unused(TanahpediaLookup::ParentChildType)),
+    );
+    values.extend(
+        lookup_parent_role::Entity::find()
+            .filter(lookup_parent_role::Column::Id.not_in_subquery(used_values(
+                person_parent_child::Entity,
+                person_parent_child::Column::ParentRoleId,
+            )))
+            .order_by_asc(lookup_parent_role::Column::Name)
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|value| (value.id, value.name))
+            .map(unused(TanahpediaLookup::ParentRole)),
+    );
+    values.extend(
+        lookup_union_type::Entity::find()
+            .filter(lookup_union_type::Column::Id.not_in_subquery(used_values(
+                person_union::Entity,
+                person_union::Column::UnionTypeId,
+            )))
+            .order_by_asc(lookup_union_type::Column::Name)
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|value| (value.id, value.name))
+            .map(unused(TanahpediaLookup::UnionType)),
+    );
+    values.extend(
+        lookup_union_end_reason::Entity::find()
+            .filter(
+                lookup_union_end_reason::Column::Id.not_in_subquery(used_values(
+                    person_union::Entity,
+                    person_union::Column::EndReasonId,
+                )),
+            )
+            .order_by_asc(lookup_union_end_reason::Column::Name)
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|value| (value.id, value.name))
+            .map(unused(TanahpediaLookup::UnionEndReason)),
+    );
+    values.extend(
+        lookup_person_nation_role::Entity::find()
+            .filter(
+                lookup_person_nation_role::Column::Id.not_in_subquery(used_values(
+                    person_nation::Entity,
+                    person_nation::Column::RoleId,
+                )),
+            )
+            .order_by_asc(lookup_person_nation_role::Column::Name)
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|value| (value.id, value.name))
+            .map(unused(TanahpediaLookup::PersonNationRole)),
+    );
+
+    Ok(TanahpediaUnusedLookupValueFindings {
+        count: values.len() as i32,
+        values,
+    })
+}
+
+/// Rows the parser left without a reference. The reason comes from parsing
+/// the text again, so it reflects the current parser even before
+/// `normalizeTanahpediaNonTanahSources` has run.
+async fn unrecognized_non_tanah_sources<C: ConnectionTrait>(
+    conn: &C,
+) -> Result<TanahpediaUnrecognizedNonTanahSourceFindings, ServiceError> {
+    let sources: Vec<_> = non_tanah_source::Entity::find()
+        .find_also_related(source_group::Entity)
+        .filter(non_tanah_source::Column::RefCorpus.is_null())
+        .order_by_asc(non_tanah_source::Column::SourceText)
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|(source, group)| {
+            let (target_table, target_id) = group
+                .map(|group| (group.target_table, group.target_id))
+                .unwrap_or_default();
+            TanahpediaUnrecognizedNonTanahSource {
+                reason: parse_reference(&source.source_text)
+                    .err()
+                    .unwrap_or_else(|| "not normalized yet".to_string()),
+                id: source.id,
+                source_text: source.source_text,
+                target_table,
+                target_id,
+            }
+        })
+        .collect();
+    Ok(TanahpediaUnrecognizedNonTanahSourceFindings {
+        count: sources.len() as i32,
+        sources,
+    })
+}
+
+/// Counts and lists the gaps in Tanahpedia's data, each with the ids of the
+/// rows to fix.
+pub async fn get_quality_report(db: &Database) -> Result<TanahpediaQualityReport, ServiceError> {
+    tracing::info_span!("tanahpedia_quality_service::get_quality_report");
+    let conn = db.get_connection();
+
+    let entities_without_sources = entity::Entity::find()
+        .filter(entity::Column::Id.not_in_subquery(used_values(
+            entity_tanah_source::Entity,
+            entity_tanah_source::Column::EntityId,
+        )))
+        .order_by_asc(entity::Column::Name)
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+
+    let incomplete_persons = incomplete_persons(conn).await?;
+
+    let entries_without_entities: Vec<TanahpediaEntryReference> = entry::Entity::find()
+        .filter(entry::Column::Id.not_in_subquery(used_values(
+            entry_entity::Entity,
+            entry_entity::Column::EntryId,
+        )))
+        .order_by_asc(entry::Column::Title)
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    let orphan_entities = entity::Entity::find()
+        .from_raw_sql(Statement::from_string(
+            DatabaseBackend::MySql,
+            orphan_entities_sql(),
+        ))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+
+    Ok(TanahpediaQualityReport {
+        entities_without_sources: entity_findings(entities_without_sources),
+        incomplete_persons,
+        entries_without_entities: TanahpediaEntryFindings {
+            count: entries_without_entities.len() as i32,
+            entries: entries_without_entities,
+        },
+        orphan_entities: entity_findings(orphan_entities),
+        uncited_relationships: uncited_relationships(conn).await?,
+        unused_lookup_values: unused_lookup_values(conn).await?,
+        unrecognized_non_tanah_sources: unrecognized_non_tanah_sources(conn).await?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::tanahpedia::sea_orm_active_enums::EntityType;
+    use sea_orm::MockDatabase;
+
+    fn entity_model(id: &str, name: &str) -> entity::Model {
+        entity::Model {
+            id: id.to_string(),
+            entity_type: EntityType::Person,
+            name: name.to_string(),
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    fn person_model(id: &str, entity_id: &str) -> person::Model {
+        person::Model {
+            id: id.to_string(),
+            entity_id: entity_id.to_string(),
+        }
+    }
+
+    fn lookup_model(id: &str, name: &str) -> lookup_union_end_reason::Model {
+        lookup_union_end_reason::Model {
+            id: id.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn orphan_entities_sql_binds_every_dependency_to_the_row() {
+        let sql = orphan_entities_sql();
+        assert!(!sql.contains('?'));
+        assert_eq!(
+            sql.matches("= e.id").count(),
+            ENTITY_DEPENDENCY_SQL.matches('?').count()
+        );
+    }
+
+    #[tokio::test]
+    async fn get_quality_report_collects_every_finding() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![entity_model("ent-1", "אבימלך")]])
+                .append_query_results([vec![
+                    (
+                        person_model("p-1", "ent-1"),
+                        entity_model("ent-1", "אבימלך"),
+                    ),
+                    (person_model("p-2", "ent-2"), entity_model("ent-2", "אבגיל")),
+                ]])
+                .append_query_results([vec![(
+                    person_model("p-1", "ent-1"),
+                    entity_model("ent-1", "אבימלך"),
+                )]])
+                .append_query_results([Vec::<entry::Model>::new()])
+                .append_query_results([vec![entity_model("ent-3", "גלית")]])
+                .append_query_results([vec![person_parent_child::Model {
+                    id: "pc-1".to_string(),
+                    parent_id: "p-1".to_string(),
+                    child_id: "p-2".to_string(),
+                    relationship_type_id: "bio".to_string(),
+                    parent_role_id: "father".to_string(),
+                    alt_group_id: None,
+                    source_citation: Some(String::new()),
+                }]])
+                .append_query_results([Vec::<person_union::Model>::new()])
+                .append_query_results([Vec::<lookup_name_type::Model>::new()])
+                .append_query_results([Vec::<lookup_parent_child_type::Model>::new()])
+                .append_query_results([Vec::<lookup_parent_role::Model>::new()])
+                .append_query_results([Vec::<lookup_union_type::Model>::new()])
+                .append_query_results([vec![lookup_model("divorce", "גירושין")]])
+                .append_query_results([Vec::<lookup_person_nation_role::Model>::new()])
+                .append_query_results([vec![(
+                    non_tanah_source::Model {
+                        id: "nts-1".to_string(),
+                        source_group_id: "group-1".to_string(),
+                        source_text: "זוהר חלק א".to_string(),
+                        ref_corpus: None,
+                        ref_work: None,
+                        ref_section: None,
+                        ref_subsection: None,
+                    },
+                    source_group::Model {
+                        id: "group-1".to_string(),
+                        target_table: "tanahpedia_animal_purity".to_string(),
+                        target_id: "purity-1".to_string(),
+                    },
+                )]])
+                .into_connection(),
+        );
+
+        let report = get_quality_report(&db).await.unwrap();
+
+        assert_eq!(report.entities_without_sources.count, 1);
+        assert_eq!(
+            report.entities_without_sources.entities[0].entity_id,
+            "ent-1"
+        );
+        assert_eq!(
+            report.incomplete_persons.persons,
+            vec![
+                TanahpediaIncompletePerson {
+                    person_id: "p-2".to_string(),
+                    entity_id: "ent-2".to_string(),
+                    display_name: "אבגיל".to_string(),
+                    missing_sex: true,
+                    missing_names: false,
+                },
+                TanahpediaIncompletePerson {
+                    person_id: "p-1".to_string(),
+                    entity_id: "ent-1".to_string(),
+                    display_name: "אבימלך".to_string(),
+                    missing_sex: true,
+                    missing_names: true,
+                },
+            ]
+        );
+        assert_eq!(report.entries_without_entities.count, 0);
+        assert_eq!(report.orphan_entities.entities[0].entity_id, "ent-3");
+        assert_eq!(
+            report.uncited_relationships.relationships,
+            vec![TanahpediaUncitedRelationship {
+                id: "pc-1".to_string(),
+                kind: TanahpediaRelationshipKind::ParentChild,
+                person1_id: "p-1".to_string(),
+                person2_id: "p-2".to_string(),
+            }]
+        );
+        assert_eq!(
+            report.unused_lookup_values.values,
+            vec![TanahpediaUnusedLookupValue {
+                lookup: TanahpediaLookup::UnionEndReason,
+                id: "divorce".to_string(),
+                name: "גירושין".to_string(),
+            }]
+        );
+        assert_eq!(
+            report.unrecognized_non_tanah_sources.sources,
+            vec![TanahpediaUnrecognizedNonTanahSource {
+                id: "nts-1".to_string(),
+                source_text: "זוהר חלק א".to_string(),
+                target_table: "tanahpedia_animal_purity".to_string(),
+                target_id: "purity-1".to_string(),
+                reason: "unknown tractate or midrash \"זוהר\"".to_string(),
+            }]
+        );
+    }
+}
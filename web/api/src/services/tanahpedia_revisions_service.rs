@@ -1,14 +1,14 @@
 use crate::{
     common::error_handling::{INTERNAL_SERVER_ERROR, ServiceError},
-    dtos::tanahpedia_entry_revision::SubmitEntryRevisionInput,
+    dtos::tanahpedia_entry_revision::{SubmitEntryRevisionInput, TanahpediaRevisionStatus},
     providers::Database,
+    services::tanahpedia_entries_service,
 };
-use entities::tanahpedia::{entry, entry_revision};
+use entities::tanahpedia::{entry, entry_revision, sea_orm_active_enums::RevisionStatus};
 use sea_orm::sea_query::Expr;
-use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder};
-
-const REVISION_STATUS_PENDING: &str = "PENDING";
-const REVISION_STATUS_APPLIED: &str = "APPLIED";
+use sea_orm::{
+    ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, TransactionTrait,
+};
 
 /// Normalizes an optional, possibly-blank string into `Some(trimmed)` or `None`.
 fn normalize(value: Option<String>) -> Option<String> {
@@ -72,7 +72,7 @@ pub async fn create_revision(
         proposed_content,
         source,
         notes,
-        status: REVISION_STATUS_PENDING.to_string(),
+        status: RevisionStatus::Pending,
         created_at: now,
         updated_at: now,
     };
@@ -92,14 +92,14 @@ pub async fn create_revision(
 /// targeted entry. Intended for the admin triage view.
 pub async fn find_revisions(
     db: &Database,
-    status: Option<String>,
+    status: Option<TanahpediaRevisionStatus>,
     entry_id: Option<String>,
 ) -> Result<Vec<entry_revision::Model>, ServiceError> {
     tracing::info_span!("tanahpedia_revisions_service::find_revisions");
 
     let mut query = entry_revision::Entity::find();
-    if let Some(status) = normalize(status) {
-        query = query.filter(entry_revision::Column::Status.eq(status));
+    if let Some(status) = status {
+        query = query.filter(entry_revision::Column::Status.eq(RevisionStatus::from(status)));
     }
     if let Some(entry_id) = normalize(entry_id) {
         query = query.filter(entry_revision::Column::EntryId.eq(entry_id));
@@ -128,6 +128,10 @@ pub async fn find_revisions(
 ///   requires both `proposed_unique_name` and `proposed_title` (the entry's
 ///   non-null columns) — and the revision is linked to the new entry.
 ///
+/// The entry's internal links are re-extracted whenever the revision carries
+/// content. When it renames an existing entry, links to the old unique name in
+/// other entries are rewritten to the new one. All writes share a transaction.
+///
 /// Re-applying an already-`APPLIED` revision is rejected.
 pub async fn apply_revision(
     db: &Database,
@@ -143,18 +147,21 @@ pub async fn apply_revision(
         .map_err(|db_err| ServiceError::internal_server_error(INTERNAL_SERVER_ERROR, Some(db_err)))?
         .ok_or_else(|| ServiceError::not_found("revision not found", Option::<String>::None))?;
 
-    if revision.status == REVISION_STATUS_APPLIED {
+    if revision.status == RevisionStatus::Applied {
         return Err(ServiceError::bad_request(
             "revision has already been applied",
         ));
     }
 
     let now = chrono::Utc::now().naive_utc();
+    let transaction = conn.begin().await.map_err(|db_err| {
+        ServiceError::internal_server_error(INTERNAL_SERVER_ERROR, Some(db_err))
+    })?;
 
     let target_entry_id = match revision.entry_id.clone() {
         Some(entry_id) => {
-            entry::Entity::find_by_id(entry_id.clone())
-                .one(conn)
+            let existing = entry::Entity::find_by_id(entry_id.clone())
+                .one(&transaction)
                 .await
                 .map_err(|db_err| {
                     ServiceError::internal_server_error(INTERNAL_SERVER_ERROR, Some(db_err))
@@ -178,10 +185,32 @@ pub async fn apply_revision(
                 update = update.col_expr(entry::Column::Content, Expr::value(content));
             }
             update = update.col_expr(entry::Column::UpdatedAt, Expr::value(now));
-            update.exec(conn).await.map_err(|db_err| {
+            update.exec(&transaction).await.map_err(|db_err| {
                 ServiceError::internal_server_error(INTERNAL_SERVER_ERROR, Some(db_err))
             })?;
 
+            if revision.proposed_content.is_some() {
+                tanahpedia_entries_service::sync_entry_links(
+                    &transaction,
+                    &entry_id,
+                    revision.proposed_content.as_deref(),
+                )
+                .await?;
+            }
+            if let Some(unique_name) = revision
+                .proposed_unique_name
+                .as_deref()
+                .filter(|unique_name| *unique_name != existing.unique_name)
+            {
+                tanahpedia_entries_service::rewrite_links_to(
+                    &transaction,
+                    &existing.unique_name,
+                    unique_name,
+                    now,
+                )
+                .await?;
+            }
+
             entry_id
         }
         None => {
@@ -204,12 +233,21 @@ pub async fn apply_revision(
             };
             let new_id = new_entry.id.clone();
             entry::Entity::insert(new_entry.into_active_model())
-                .exec(conn)
+                .exec(&transaction)
                 .await
                 .map_err(|db_err| {
                     ServiceError::internal_server_error(INTERNAL_SERVER_ERROR, Some(db_err))
                 })?;
 
+            if revision.proposed_content.is_some() {
+                tanahpedia_entries_service::sync_entry_links(
+                    &transaction,
+                    &new_id,
+                    revision.proposed_content.as_deref(),
+                )
+                .await?;
+            }
+
             new_id
         }
     };
@@ -217,7 +255,7 @@ pub async fn apply_revision(
     entry_revision::Entity::update_many()
         .col_expr(
             entry_revision::Column::Status,
-            Expr::value(REVISION_STATUS_APPLIED.to_string()),
+            Expr::value(RevisionStatus::Applied),
         )
         .col_expr(
             entry_revision::Column::EntryId,
@@ -225,14 +263,17 @@ pub async fn apply_revision(
         )
         .col_expr(entry_revision::Column::UpdatedAt, Expr::value(now))
         .filter(entry_revision::Column::Id.eq(revision.id.clone()))
-        .exec(conn)
+        .exec(&transaction)
         .await
         .map_err(|db_err| {
             ServiceError::internal_server_error(INTERNAL_SERVER_ERROR, Some(db_err))
         })?;
+    transaction.commit().await.map_err(|db_err| {
+        ServiceError::internal_server_error(INTERNAL_SERVER_ERROR, Some(db_err))
+    })?;
 
     let mut applied = revision;
-    applied.status = REVISION_STATUS_APPLIED.to_string();
+    applied.status = RevisionStatus::Applied;
     applied.entry_id = Some(target_entry_id);
     applied.updated_at = now;
 
@@ -243,6 +284,7 @@ pub async fn apply_revision(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use entities::tanahpedia::entry_link;
     use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
 
     fn input_with_title(title: &str) -> SubmitEntryRevisionInput {
@@ -324,7 +366,7 @@ mod tests {
             .expect("revision should be created");
 
         assert_eq!(revision.source, "gpt-4o");
-        assert_eq!(revision.status, "PENDING");
+        assert_eq!(revision.status, RevisionStatus::Pending);
         assert_eq!(revision.proposed_title.as_deref(), Some("ערך חדש"));
         assert!(revision.entry_id.is_none());
         assert!(!revision.id.is_empty());
@@ -380,7 +422,7 @@ mod tests {
             proposed_content: None,
             source: "gpt-4o".to_string(),
             notes: None,
-            status: "PENDING".to_string(),
+            status: RevisionStatus::Pending,
             created_at: chrono::Utc::now().naive_utc(),
             updated_at: chrono::Utc::now().naive_utc(),
         };
@@ -391,7 +433,7 @@ mod tests {
             .into_connection();
         let db = Database::from_connection(mock_db);
 
-        let revisions = find_revisions(&db, Some("PENDING".to_string()), None)
+        let revisions = find_revisions(&db, Some(TanahpediaRevisionStatus::Pending), None)
             .await
             .expect("should query");
         assert_eq!(revisions.len(), 1);
@@ -409,7 +451,7 @@ mod tests {
         assert!(matches!(err, ServiceError::InternalServerError(_)));
     }
 
-    fn revision_model(entry_id: Option<&str>, status: &str) -> entry_revision::Model {
+    fn revision_model(entry_id: Option<&str>, status: RevisionStatus) -> entry_revision::Model {
         entry_revision::Model {
             id: "rev-1".to_string(),
             entry_id: entry_id.map(|s| s.to_string()),
@@ -418,7 +460,7 @@ mod tests {
             proposed_content: Some("<p>תוכן</p>".to_string()),
             source: "gpt-4o".to_string(),
             notes: None,
-            status: status.to_string(),
+            status,
             created_at: chrono::Utc::now().naive_utc(),
             updated_at: chrono::Utc::now().naive_utc(),
         }
@@ -441,7 +483,7 @@ mod tests {
     async fn apply_revision_rejects_already_applied() {
         let mock_db = MockDatabase::new(DatabaseBackend::MySql)
             .append_query_results::<entry_revision::Model, Vec<entry_revision::Model>, _>([vec![
-                revision_model(Some("entry-1"), "APPLIED"),
+                revision_model(Some("entry-1"), RevisionStatus::Applied),
             ]])
             .into_connection();
         let db = Database::from_connection(mock_db);
@@ -452,7 +494,7 @@ mod tests {
 
     #[tokio::test]
     async fn apply_revision_new_entry_requires_unique_name() {
-        let mut revision = revision_model(None, "PENDING");
+        let mut revision = revision_model(None, RevisionStatus::Pending);
         revision.proposed_unique_name = None;
         let mock_db = MockDatabase::new(DatabaseBackend::MySql)
             .append_query_results::<entry_revision::Model, Vec<entry_revision::Model>, _>([vec![
@@ -469,7 +511,7 @@ mod tests {
     async fn apply_revision_creates_new_entry() {
         let mock_db = MockDatabase::new(DatabaseBackend::MySql)
             .append_query_results::<entry_revision::Model, Vec<entry_revision::Model>, _>([vec![
-                revision_model(None, "PENDING"),
+                revision_model(None, RevisionStatus::Pending),
             ]])
             .append_exec_results([
                 MockExecResult {
@@ -480,6 +522,11 @@ mod tests {
                     last_insert_id: 0,
                     rows_affected: 1,
                 },
+                // clears the new entry's links; its content has none
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 0,
+                },
             ])
             .into_connection();
         let db = Database::from_connection(mock_db);
@@ -488,7 +535,7 @@ mod tests {
             .await
             .expect("revision should apply");
 
-        assert_eq!(applied.status, "APPLIED");
+        assert_eq!(applied.status, RevisionStatus::Applied);
         assert!(applied.entry_id.is_some());
         assert!(!applied.entry_id.unwrap().is_empty());
     }
@@ -497,7 +544,7 @@ mod tests {
     async fn apply_revision_updates_existing_entry() {
         let mock_db = MockDatabase::new(DatabaseBackend::MySql)
             .append_query_results::<entry_revision::Model, Vec<entry_revision::Model>, _>([vec![
-                revision_model(Some("entry-1"), "PENDING"),
+                revision_model(Some("entry-1"), RevisionStatus::Pending),
             ]])
             .append_query_results::<entry::Model, Vec<entry::Model>, _>([vec![entry_model(
                 "entry-1",
@@ -511,6 +558,10 @@ mod tests {
                     last_insert_id: 0,
                     rows_affected: 1,
                 },
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 0,
+                },
             ])
             .into_connection();
         let db = Database::from_connection(mock_db);
@@ -519,7 +570,48 @@ mod tests {
             .await
             .expect("revision should apply");
 
-        assert_eq!(applied.status, "APPLIED");
+        assert_eq!(applied.status, RevisionStatus::Applied);
+        assert_eq!(applied.entry_id.as_deref(), Some("entry-1"));
+    }
+
+    #[tokio::test]
+    async fn apply_revision_rewrites_links_to_a_renamed_entry() {
+        let mut revision = revision_model(Some("entry-1"), RevisionStatus::Pending);
+        revision.proposed_unique_name = Some("avraham-avinu".to_string());
+        let mut linking = entry_model("entry-2");
+        linking.unique_name = "yitzhak".to_string();
+        linking.content = Some(r#"<p>בן <a href="/pedia/avraham">אברהם</a></p>"#.to_string());
+        let mock_db = MockDatabase::new(DatabaseBackend::MySql)
+            .append_query_results::<entry_revision::Model, Vec<entry_revision::Model>, _>([vec![
+                revision,
+            ]])
+            .append_query_results::<entry::Model, Vec<entry::Model>, _>([vec![entry_model(
+                "entry-1",
+            )]])
+            .append_query_results::<entry_link::Model, Vec<entry_link::Model>, _>([vec![
+                entry_link::Model {
+                    id: "link-1".to_string(),
+                    source_entry_id: "entry-2".to_string(),
+                    target_unique_name: "avraham".to_string(),
+                },
+            ]])
+            .append_query_results::<entry::Model, Vec<entry::Model>, _>([vec![linking]])
+            .append_exec_results(
+                // entry update, own links cleared, linking entry rewritten,
+                // its links cleared and re-inserted, revision marked applied
+                (0..6).map(|_| MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                }),
+            )
+            .into_connection();
+        let db = Database::from_connection(mock_db);
+
+        let applied = apply_revision(&db, "rev-1".to_string())
+            .await
+            .expect("revision should apply");
+
+        assert_eq!(applied.status, RevisionStatus::Applied);
         assert_eq!(applied.entry_id.as_deref(), Some("entry-1"));
     }
 
@@ -527,7 +619,7 @@ mod tests {
     async fn apply_revision_rejects_when_target_entry_missing() {
         let mock_db = MockDatabase::new(DatabaseBackend::MySql)
             .append_query_results::<entry_revision::Model, Vec<entry_revision::Model>, _>([vec![
-                revision_model(Some("gone"), "PENDING"),
+                revision_model(Some("gone"), RevisionStatus::Pending),
             ]])
             .append_query_results::<entry::Model, Vec<entry::Model>, _>([vec![]])
             .into_connection();
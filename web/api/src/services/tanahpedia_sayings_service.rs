@@ -0,0 +1,958 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::tanahpedia_saying::{
+        PutTanahpediaSayingPartyInput, TanahpediaConversationPartner, TanahpediaSaying,
+        TanahpediaSayingLocation, TanahpediaSayingOpinions, TanahpediaSayingParty,
+        TanahpediaSayingPartyType, TanahpediaSayingPartyWriteResult,
+    },
+    providers::Database,
+    services::{
+        tanahpedia_family_service::{
+            db_error, entity_display_names, nation_display_names, optional, person_display_names,
+            place_display_names, require_person, required,
+        },
+        tanahpedia_opinions_service::{OpinionCatalog, alt_group_ids},
+    },
+};
+use entities::tanahpedia::{
+    god, nation, saying, saying_audience_nation, saying_audience_person, saying_location,
+    saying_speaker_god, saying_speaker_nation, saying_speaker_person,
+};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+    TransactionTrait,
+};
+
+/// Display name used for `GOD` speakers; `tanahpedia_god` is a singleton row
+/// with no name column.
+pub(crate) const GOD_DISPLAY_NAME: &str = "ה'";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SayingRole {
+    Speaker,
+    Audience,
+}
+
+const AUDIENCE_PARTY_TYPES: &str = "audience partyType must be PERSON or NATION";
+
+/// Rejects party types the role does not accept: an audience is never `GOD`.
+fn check_party_type(
+    role: SayingRole,
+    party_type: TanahpediaSayingPartyType,
+) -> Result<(), ServiceError> {
+    match (role, party_type) {
+        (SayingRole::Audience, TanahpediaSayingPartyType::God) => {
+            Err(ServiceError::bad_request(AUDIENCE_PARTY_TYPES))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn party(
+    link_id: String,
+    party_type: TanahpediaSayingPartyType,
+    party_id: String,
+    names: &HashMap<String, String>,
+    alt_group_id: Option<String>,
+) -> TanahpediaSayingParty {
+    let display_name = match party_type {
+        TanahpediaSayingPartyType::God => GOD_DISPLAY_NAME.to_string(),
+        _ => names.get(&party_id).cloned().unwrap_or_default(),
+    };
+    TanahpediaSayingParty {
+        link_id,
+        party_type,
+        party_id,
+        display_name,
+        alt_group_id,
+    }
+}
+
+/// Loads the given sayings with every speaker, audience member and location,
+/// resolving display names in batches rather than one lookup per link.
+async fn load_sayings(
+    conn: &sea_orm::DatabaseConnection,
+    saying_ids: HashSet<String>,
+) -> Result<Vec<TanahpediaSaying>, ServiceError> {
+    if saying_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let sayings = saying::Entity::find()
+        .filter(saying::Column::Id.is_in(saying_ids.clone()))
+        .order_by_asc(saying::Column::Id)
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let speaker_persons = saying_speaker_person::Entity::find()
+        .filter(saying_speaker_person::Column::SayingId.is_in(saying_ids.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let speaker_nations = saying_speaker_nation::Entity::find()
+        .filter(saying_speaker_nation::Column::SayingId.is_in(saying_ids.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let speaker_gods = saying_speaker_god::Entity::find()
+        .filter(saying_speaker_god::Column::SayingId.is_in(saying_ids.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let audience_persons = saying_audience_person::Entity::find()
+        .filter(saying_audience_person::Column::SayingId.is_in(saying_ids.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let audience_nations = saying_audience_nation::Entity::find()
+        .filter(saying_audience_nation::Column::SayingId.is_in(saying_ids.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let locations = saying_location::Entity::find()
+        .filter(saying_location::Column::SayingId.is_in(saying_ids))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+
+    let person_names = person_display_names(
+        conn,
+        speaker_persons
+            .iter()
+            .map(|row| row.person_id.clone())
+            .chain(audience_persons.iter().map(|row| row.person_id.clone()))
+            .collect(),
+    )
+    .await?;
+    let nation_names = nation_display_names(
+        conn,
+        speaker_nations
+            .iter()
+            .map(|row| row.nation_id.clone())
+            .chain(audience_nations.iter().map(|row| row.nation_id.clone()))
+            .collect(),
+    )
+    .await?;
+    let place_names = place_display_names(
+        conn,
+        locations.iter().map(|row| row.place_id.clone()).collect(),
+    )
+    .await?;
+    let saying_names = entity_display_names(
+        conn,
+        sayings.iter().map(|row| row.entity_id.clone()).collect(),
+    )
+    .await?;
+
+    let mut by_id = sayings
+        .into_iter()
+        .map(|row| {
+            let display_name = saying_names
+                .get(&row.entity_id)
+                .cloned()
+                .unwrap_or_default();
+            (
+                row.id.clone(),
+                TanahpediaSaying {
+                    saying_id: row.id,
+                    entity_id: row.entity_id,
+                    display_name,
+                    content: row.content,
+                    speakers: Vec::new(),
+                    audience: Vec::new(),
+                    locations: Vec::new(),
+                },
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    for row in speaker_persons {
+        if let Some(saying) = by_id.get_mut(&row.saying_id) {
+            saying.speakers.push(party(
+                row.id,
+                TanahpediaSayingPartyType::Person,
+                row.person_id,
+                &person_names,
+                row.alt_group_id,
+            ));
+        }
+    }
+    for row in speaker_nations {
+        if let Some(saying) = by_id.get_mut(&row.saying_id) {
+            saying.speakers.push(party(
+                row.id,
+                TanahpediaSayingPartyType::Nation,
+                row.nation_id,
+                &nation_names,
+                row.alt_group_id,
+            ));
+        }
+    }
+    for row in speaker_gods {
+        if let Some(saying) = by_id.get_mut(&row.saying_id) {
+            saying.speakers.push(party(
+                row.id,
+                TanahpediaSayingPartyType::God,
+                row.god_id,
+                &HashMap::new(),
+                row.alt_group_id,
+            ));
+        }
+    }
+    for row in audience_persons {
+        if let Some(saying) = by_id.get_mut(&row.saying_id) {
+            saying.audience.push(party(
+                row.id,
+                TanahpediaSayingPartyType::Person,
+                row.person_id,
+                &person_names,
+                row.alt_group_id,
+            ));
+        }
+    }
+    for row in audience_nations {
+        if let Some(saying) = by_id.get_mut(&row.saying_id) {
+            saying.audience.push(party(
+                row.id,
+                TanahpediaSayingPartyType::Nation,
+                row.nation_id,
+                &nation_names,
+                row.alt_group_id,
+            ));
+        }
+    }
+    for row in locations {
+        if let Some(saying) = by_id.get_mut(&row.saying_id) {
+            saying.locations.push(TanahpediaSayingLocation {
+                display_name: place_names.get(&row.place_id).cloned().unwrap_or_default(),
+                link_id: row.id,
+                place_id: row.place_id,
+                alt_group_id: row.alt_group_id,
+            });
+        }
+    }
+
+    Ok(by_id.into_values().collect())
+}
+
+/// Lists every saying spoken by `speaker_id`, which may be a `personId`, a
+/// `nationId` or the `godId`.
+pub async fn find_sayings_by_speaker(
+    db: &Database,
+    speaker_id: String,
+) -> Result<Vec<TanahpediaSaying>, ServiceError> {
+    let speaker_id = required(speaker_id, "speakerId", 36)?;
+    let conn = db.get_connection();
+
+    let mut saying_ids = saying_speaker_person::Entity::find()
+        .filter(saying_speaker_person::Column::PersonId.eq(speaker_id.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| row.saying_id)
+        .collect::<HashSet<_>>();
+    saying_ids.extend(
+        saying_speaker_nation::Entity::find()
+            .filter(saying_speaker_nation::Column::NationId.eq(speaker_id.clone()))
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|row| row.saying_id),
+    );
+    saying_ids.extend(
+        saying_speaker_god::Entity::find()
+            .filter(saying_speaker_god::Column::GodId.eq(speaker_id))
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|row| row.saying_id),
+    );
+
+    load_sayings(conn, saying_ids).await
+}
+
+/// Lists every saying addressed to `audience_id`, which may be a `personId`
+/// or a `nationId`.
+pub async fn find_sayings_by_audience(
+    db: &Database,
+    audience_id: String,
+) -> Result<Vec<TanahpediaSaying>, ServiceError> {
+    let audience_id = required(audience_id, "audienceId", 36)?;
+    let conn = db.get_connection();
+
+    let mut saying_ids = saying_audience_person::Entity::find()
+        .filter(saying_audience_person::Column::PersonId.eq(audience_id.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| row.saying_id)
+        .collect::<HashSet<_>>();
+    saying_ids.extend(
+        saying_audience_nation::Entity::find()
+            .filter(saying_audience_nation::Column::NationId.eq(audience_id))
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|row| row.saying_id),
+    );
+
+    load_sayings(conn, saying_ids).await
+}
+
+/// Lists every saying located at `place_id`.
+pub async fn find_sayings_at_place(
+    db: &Database,
+    place_id: String,
+) -> Result<Vec<TanahpediaSaying>, ServiceError> {
+    let place_id = required(place_id, "placeId", 36)?;
+    let conn = db.get_connection();
+
+    let saying_ids = saying_location::Entity::find()
+        .filter(saying_location::Column::PlaceId.eq(place_id))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| row.saying_id)
+        .collect();
+
+    load_sayings(conn, saying_ids).await
+}
+
+/// Folds `(saying_id, partner_id)` pairs into per-partner counts. A saying is
+/// counted once per partner even if the link is duplicated across alternative
+/// opinions. Sorted by total descending, then by partner id for stable output.
+fn tally_partners(
+    spoke_to: Vec<(String, String)>,
+    heard_from: Vec<(String, String)>,
+) -> Vec<(String, i32, i32)> {
+    let spoke_to = spoke_to.into_iter().collect::<HashSet<_>>();
+    let heard_from = heard_from.into_iter().collect::<HashSet<_>>();
+    let mut counts = BTreeMap::<String, (i32, i32)>::new();
+    for (_, partner) in spoke_to {
+        counts.entry(partner).or_default().0 += 1;
+    }
+    for (_, partner) in heard_from {
+        counts.entry(partner).or_default().1 += 1;
+    }
+
+    let mut tallies = counts
+        .into_iter()
+        .map(|(partner, (spoke, heard))| (partner, spoke, heard))
+        .collect::<Vec<_>>();
+    tallies.sort_by(|a, b| (b.1 + b.2).cmp(&(a.1 + a.2)).then_with(|| a.0.cmp(&b.0)));
+    tallies
+}
+
+/// Aggregates the persons `person_id` has spoken to or heard from, with the
+/// number of sayings in each direction.
+pub async fn get_conversation_partners(
+    db: &Database,
+    person_id: String,
+) -> Result<Vec<TanahpediaConversationPartner>, ServiceError> {
+    let person_id = required(person_id, "personId", 36)?;
+    let conn = db.get_connection();
+
+    let spoken_ids = saying_speaker_person::Entity::find()
+        .filter(saying_speaker_person::Column::PersonId.eq(person_id.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| row.saying_id)
+        .collect::<HashSet<_>>();
+    let heard_ids = saying_audience_person::Entity::find()
+        .filter(saying_audience_person::Column::PersonId.eq(person_id.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| row.saying_id)
+        .collect::<HashSet<_>>();
+
+    let spoke_to = if spoken_ids.is_empty() {
+        Vec::new()
+    } else {
+        saying_audience_person::Entity::find()
+            .filter(saying_audience_person::Column::SayingId.is_in(spoken_ids))
+            .filter(saying_audience_person::Column::PersonId.ne(person_id.clone()))
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|row| (row.saying_id, row.person_id))
+            .collect()
+    };
+    let heard_from = if heard_ids.is_empty() {
+        Vec::new()
+    } else {
+        saying_speaker_person::Entity::find()
+            .filter(saying_speaker_person::Column::SayingId.is_in(heard_ids))
+            .filter(saying_speaker_person::Column::PersonId.ne(person_id))
+            .all(conn)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|row| (row.saying_id, row.person_id))
+            .collect()
+    };
+
+    let tallies = tally_partners(spoke_to, heard_from);
+    let names = person_display_names(
+        conn,
+        tallies
+            .iter()
+            .map(|(partner, _, _)| partner.clone())
+            .collect(),
+    )
+    .await?;
+
+    Ok(tallies
+        .into_iter()
+        .map(
+            |(person_id, spoke_to_count, heard_from_count)| TanahpediaConversationPartner {
+                display_name: names.get(&person_id).cloned().unwrap_or_default(),
+                person_id,
+                spoke_to_count,
+                heard_from_count,
+                total_count: spoke_to_count + heard_from_count,
+            },
+        )
+        .collect())
+}
+
+async fn require_saying<C: ConnectionTrait>(conn: &C, saying_id: &str) -> Result<(), ServiceError> {
+    if saying::Entity::find_by_id(saying_id.to_string())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+        .is_none()
+    {
+        return Err(ServiceError::bad_request(&format!(
+            "sayingId {saying_id} does not reference an existing saying"
+        )));
+    }
+    Ok(())
+}
+
+async fn require_party<C: ConnectionTrait>(
+    conn: &C,
+    party_type: TanahpediaSayingPartyType,
+    party_id: &str,
+) -> Result<(), ServiceError> {
+    let (exists, kind) = match party_type {
+        TanahpediaSayingPartyType::Person => return require_person(conn, party_id).await,
+        TanahpediaSayingPartyType::Nation => (
+            nation::Entity::find_by_id(party_id.to_string())
+                .one(conn)
+                .await
+                .map_err(db_error)?
+                .is_some(),
+            "nation",
+        ),
+        TanahpediaSayingPartyType::God => (
+            god::Entity::find_by_id(party_id.to_string())
+                .one(conn)
+                .await
+                .map_err(db_error)?
+                .is_some(),
+            "god",
+        ),
+    };
+    if !exists {
+        return Err(ServiceError::bad_request(&format!(
+            "partyId {party_id} does not reference an existing {kind}"
+        )));
+    }
+    Ok(())
+}
+
+/// Removes `id` from every link table of `role`, so re-putting a link with a
+/// different `partyType` never leaves the old row behind. Returns the number
+/// of rows removed.
+async fn delete_party_links<C: ConnectionTrait>(
+    conn: &C,
+    role: SayingRole,
+    id: &str,
+) -> Result<u64, ServiceError> {
+    let mut removed = 0;
+    match role {
+        SayingRole::Speaker => {
+            removed += saying_speaker_person::Entity::delete_by_id(id.to_string())
+                .exec(conn)
+                .await
+                .map_err(db_error)?
+                .rows_affected;
+            removed += saying_speaker_nation::Entity::delete_by_id(id.to_string())
+                .exec(conn)
+                .await
+                .map_err(db_error)?
+                .rows_affected;
+            removed += saying_speaker_god::Entity::delete_by_id(id.to_string())
+                .exec(conn)
+                .await
+                .map_err(db_error)?
+                .rows_affected;
+        }
+        SayingRole::Audience => {
+            removed += saying_audience_person::Entity::delete_by_id(id.to_string())
+                .exec(conn)
+                .await
+                .map_err(db_error)?
+                .rows_affected;
+            removed += saying_audience_nation::Entity::delete_by_id(id.to_string())
+                .exec(conn)
+                .await
+                .map_err(db_error)?
+                .rows_affected;
+        }
+    }
+    Ok(removed)
+}
+
+async fn put_saying_party(
+    db: &Database,
+    input: PutTanahpediaSayingPartyInput,
+    role: SayingRole,
+) -> Result<TanahpediaSayingPartyWriteResult, ServiceError> {
+    let conn = db.get_connection();
+    let id = required(input.id, "id", 36)?;
+    let saying_id = required(input.saying_id, "sayingId", 36)?;
+    let party_type = input.party_type;
+    check_party_type(role, party_type)?;
+    let party_id = required(input.party_id, "partyId", 36)?;
+    let alt_group_id = optional(input.alt_group_id, "altGroupId", 36)?;
+
+    let transaction = conn.begin().await.map_err(db_error)?;
+    require_saying(&transaction, &saying_id).await?;
+    require_party(&transaction, party_type, &party_id).await?;
+    delete_party_links(&transaction, role, &id).await?;
+    let insert_result = match (role, party_type) {
+        (SayingRole::Speaker, TanahpediaSayingPartyType::Person) => {
+            saying_speaker_person::Entity::insert(
+                saying_speaker_person::Model {
+                    id: id.clone(),
+                    saying_id: saying_id.clone(),
+                    person_id: party_id,
+                    alt_group_id,
+                }
+                .into_active_model(),
+            )
+            .exec(&transaction)
+            .await
+            .map(|_| ())
+        }
+        (SayingRole::Speaker, TanahpediaSayingPartyType::Nation) => {
+            saying_speaker_nation::Entity::insert(
+                saying_speaker_nation::Model {
+                    id: id.clone(),
+                    saying_id: saying_id.clone(),
+                    nation_id: party_id,
+                    alt_group_id,
+                }
+                .into_active_model(),
+            )
+            .exec(&transaction)
+            .await
+            .map(|_| ())
+        }
+        (SayingRole::Speaker, TanahpediaSayingPartyType::God) => {
+            saying_speaker_god::Entity::insert(
+                saying_speaker_god::Model {
+                    id: id.clone(),
+                    saying_id: saying_id.clone(),
+                    god_id: party_id,
+                    alt_group_id,
+                }
+                .into_active_model(),
+            )
+            .exec(&transaction)
+            .await
+            .map(|_| ())
+        }
+        (SayingRole::Audience, TanahpediaSayingPartyType::Person) => {
+            saying_audience_person::Entity::insert(
+                saying_audience_person::Model {
+                    id: id.clone(),
+                    saying_id: saying_id.clone(),
+                    person_id: party_id,
+                    alt_group_id,
+                }
+                .into_active_model(),
+            )
+            .exec(&transaction)
+            .await
+            .map(|_| ())
+        }
+        (SayingRole::Audience, TanahpediaSayingPartyType::Nation) => {
+            saying_audience_nation::Entity::insert(
+                saying_audience_nation::Model {
+                    id: id.clone(),
+                    saying_id: saying_id.clone(),
+                    nation_id: party_id,
+                    alt_group_id,
+                }
+                .into_active_model(),
+            )
+            .exec(&transaction)
+            .await
+            .map(|_| ())
+        }
+        (SayingRole::Audience, TanahpediaSayingPartyType::God) => {
+            return Err(ServiceError::bad_request(AUDIENCE_PARTY_TYPES));
+        }
+    };
+    insert_result.map_err(db_error)?;
+    transaction.commit().await.map_err(db_error)?;
+
+    Ok(TanahpediaSayingPartyWriteResult {
+        id,
+        saying_id,
+        party_type,
+    })
+}
+
+async fn delete_saying_party(
+    db: &Database,
+    id: String,
+    role: SayingRole,
+) -> Result<TanahpediaSayingPartyWriteResult, ServiceError> {
+    let id = required(id, "id", 36)?;
+    let transaction = db.get_connection().begin().await.map_err(db_error)?;
+
+    let existing = match role {
+        SayingRole::Speaker => {
+            if let Some(row) = saying_speaker_person::Entity::find_by_id(id.clone())
+                .one(&transaction)
+                .await
+                .map_err(db_error)?
+            {
+                Some((row.saying_id, TanahpediaSayingPartyType::Person))
+            } else if let Some(row) = saying_speaker_nation::Entity::find_by_id(id.clone())
+                .one(&transaction)
+                .await
+                .map_err(db_error)?
+            {
+                Some((row.saying_id, TanahpediaSayingPartyType::Nation))
+            } else {
+                saying_speaker_god::Entity::find_by_id(id.clone())
+                    .one(&transaction)
+                    .await
+                    .map_err(db_error)?
+                    .map(|row| (row.saying_id, TanahpediaSayingPartyType::God))
+            }
+        }
+        SayingRole::Audience => {
+            if let Some(row) = saying_audience_person::Entity::find_by_id(id.clone())
+                .one(&transaction)
+                .await
+                .map_err(db_error)?
+            {
+                Some((row.saying_id, TanahpediaSayingPartyType::Person))
+            } else {
+                saying_audience_nation::Entity::find_by_id(id.clone())
+                    .one(&transaction)
+                    .await
+                    .map_err(db_error)?
+                    .map(|row| (row.saying_id, TanahpediaSayingPartyType::Nation))
+            }
+        }
+    };
+    let Some((saying_id, party_type)) = existing else {
+        return Err(ServiceError::not_found(
+            "saying link not found",
+            None::<&str>,
+        ));
+    };
+
+    delete_party_links(&transaction, role, &id).await?;
+    transaction.commit().await.map_err(db_error)?;
+
+    Ok(TanahpediaSayingPartyWriteResult {
+        id,
+        saying_id,
+        party_type,
+    })
+}
+
+pub async fn put_saying_speaker(
+    db: &Database,
+    input: PutTanahpediaSayingPartyInput,
+) -> Result<TanahpediaSayingPartyWriteResult, ServiceError> {
+    put_saying_party(db, input, SayingRole::Speaker).await
+}
+
+pub async fn delete_saying_speaker(
+    db: &Database,
+    id: String,
+) -> Result<TanahpediaSayingPartyWriteResult, ServiceError> {
+    delete_saying_party(db, id, SayingRole::Speaker).await
+}
+
+pub async fn put_saying_audience(
+    db: &Database,
+    input: PutTanahpediaSayingPartyInput,
+) -> Result<TanahpediaSayingPartyWriteResult, ServiceError> {
+    put_saying_party(db, input, SayingRole::Audience).await
+}
+
+pub async fn delete_saying_audience(
+    db: &Database,
+    id: String,
+) -> Result<TanahpediaSayingPartyWriteResult, ServiceError> {
+    delete_saying_party(db, id, SayingRole::Audience).await
+}
+
+/// Groups an already-loaded saying by opinion; only the opinion metadata is
+/// queried.
+pub async fn get_saying_opinions(
+    db: &Database,
+    saying: &TanahpediaSaying,
+) -> Result<TanahpediaSayingOpinions, ServiceError> {
+    let catalog = OpinionCatalog::load(
+        db.get_connection(),
+        alt_group_ids(
+            saying
+                .speakers
+                .iter()
+                .chain(&saying.audience)
+                .map(|party| &party.alt_group_id)
+                .chain(
+                    saying
+                        .locations
+                        .iter()
+                        .map(|location| &location.alt_group_id),
+                ),
+        ),
+    )
+    .await?;
+    let parties = |parties: &[TanahpediaSayingParty]| -> Vec<_> {
+        parties
+            .iter()
+            .map(|party| (party.alt_group_id.clone(), party.clone()))
+            .collect()
+    };
+
+    Ok(TanahpediaSayingOpinions {
+        speakers: catalog.group(parties(&saying.speakers)),
+        audience: catalog.group(parties(&saying.audience)),
+        locations: catalog.group(
+            saying
+                .locations
+                .iter()
+                .map(|location| (location.alt_group_id.clone(), location.clone()))
+                .collect(),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::tanahpedia::{entity, person, sea_orm_active_enums::EntityType};
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    fn pair(saying_id: &str, partner: &str) -> (String, String) {
+        (saying_id.to_string(), partner.to_string())
+    }
+
+    fn party_input(party_type: TanahpediaSayingPartyType) -> PutTanahpediaSayingPartyInput {
+        PutTanahpediaSayingPartyInput {
+            id: "speaker-1".to_string(),
+            saying_id: "saying-1".to_string(),
+            party_type,
+            party_id: "party-1".to_string(),
+            alt_group_id: None,
+        }
+    }
+
+    fn saying_model(id: &str, entity_id: &str) -> saying::Model {
+        saying::Model {
+            id: id.to_string(),
+            entity_id: entity_id.to_string(),
+            content: Some("לך לך".to_string()),
+        }
+    }
+
+    fn entity_model(id: &str, entity_type: EntityType, name: &str) -> entity::Model {
+        entity::Model {
+            id: id.to_string(),
+            entity_type,
+            name: name.to_string(),
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    #[test]
+    fn audiences_accept_every_party_type_but_god() {
+        assert!(check_party_type(SayingRole::Speaker, TanahpediaSayingPartyType::God).is_ok());
+        assert!(check_party_type(SayingRole::Audience, TanahpediaSayingPartyType::Nation).is_ok());
+        assert!(check_party_type(SayingRole::Audience, TanahpediaSayingPartyType::God).is_err());
+    }
+
+    #[test]
+    fn tally_partners_counts_each_saying_once_per_direction() {
+        let tallies = tally_partners(
+            vec![
+                pair("s1", "sarah"),
+                pair("s1", "sarah"),
+                pair("s2", "sarah"),
+                pair("s3", "lot"),
+            ],
+            vec![pair("s4", "sarah"), pair("s5", "avimelech")],
+        );
+
+        assert_eq!(
+            tallies,
+            vec![
+                ("sarah".to_string(), 2, 1),
+                ("avimelech".to_string(), 0, 1),
+                ("lot".to_string(), 1, 0),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn put_saying_audience_rejects_god_before_querying() {
+        let db =
+            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
+
+        let err = put_saying_audience(&db, party_input(TanahpediaSayingPartyType::God))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ServiceError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn put_saying_speaker_rejects_unknown_saying() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<saying::Model, Vec<saying::Model>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let err = put_saying_speaker(&db, party_input(TanahpediaSayingPartyType::Person))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ServiceError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn put_saying_speaker_replaces_link_across_party_tables() {
+        let no_rows = MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 0,
+        };
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<saying::Model, Vec<saying::Model>, _>([vec![saying_model(
+                    "saying-1",
+                    "entity-saying",
+                )]])
+                .append_query_results::<god::Model, Vec<god::Model>, _>([vec![god::Model {
+                    id: "party-1".to_string(),
+                }]])
+                .append_exec_results([
+                    no_rows.clone(),
+                    no_rows.clone(),
+                    no_rows,
+                    MockExecResult {
+                        last_insert_id: 0,
+                        rows_affected: 1,
+                    },
+                ])
+                .into_connection(),
+        );
+
+        let result = put_saying_speaker(&db, party_input(TanahpediaSayingPartyType::God))
+            .await
+            .expect("god speaker should be stored");
+
+        assert_eq!(result.id, "speaker-1");
+        assert_eq!(result.party_type, TanahpediaSayingPartyType::God);
+    }
+
+    #[tokio::test]
+    async fn find_sayings_by_speaker_groups_links_and_names() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<saying_speaker_person::Model, Vec<saying_speaker_person::Model>, _>([vec![
+                    saying_speaker_person::Model {
+                        id: "sp-1".to_string(),
+                        saying_id: "saying-1".to_string(),
+                        person_id: "person-avraham".to_string(),
+                        alt_group_id: None,
+                    },
+                ]])
+                .append_query_results::<saying_speaker_nation::Model, Vec<saying_speaker_nation::Model>, _>([vec![]])
+                .append_query_results::<saying_speaker_god::Model, Vec<saying_speaker_god::Model>, _>([vec![]])
+                .append_query_results::<saying::Model, Vec<saying::Model>, _>([vec![
+                    saying_model("saying-1", "entity-saying"),
+                ]])
+                .append_query_results::<saying_speaker_person::Model, Vec<saying_speaker_person::Model>, _>([vec![
+                    saying_speaker_person::Model {
+                        id: "sp-1".to_string(),
+                        saying_id: "saying-1".to_string(),
+                        person_id: "person-avraham".to_string(),
+                        alt_group_id: None,
+                    },
+                ]])
+                .append_query_results::<saying_speaker_nation::Model, Vec<saying_speaker_nation::Model>, _>([vec![]])
+                .append_query_results::<saying_speaker_god::Model, Vec<saying_speaker_god::Model>, _>([vec![]])
+                .append_query_results::<saying_audience_person::Model, Vec<saying_audience_person::Model>, _>([vec![
+                    saying_audience_person::Model {
+                        id: "ap-1".to_string(),
+                        saying_id: "saying-1".to_string(),
+                        person_id: "person-sarah".to_string(),
+                        alt_group_id: Some("alt-1".to_string()),
+                    },
+                ]])
+                .append_query_results::<saying_audience_nation::Model, Vec<saying_audience_nation::Model>, _>([vec![]])
+                .append_query_results::<saying_location::Model, Vec<saying_location::Model>, _>([vec![]])
+                .append_query_results([vec![
+                    (
+                        person::Model {
+                            id: "person-avraham".to_string(),
+                            entity_id: "entity-avraham".to_string(),
+                        },
+                        entity_model("entity-avraham", EntityType::Person, "אברהם"),
+                    ),
+                    (
+                        person::Model {
+                            id: "person-sarah".to_string(),
+                            entity_id: "entity-sarah".to_string(),
+                        },
+                        entity_model("entity-sarah", EntityType::Person, "שרה"),
+                    ),
+                ]])
+                .append_query_results::<entity::Model, Vec<entity::Model>, _>([vec![
+                    entity_model("entity-saying", EntityType::Saying, "אמרי נא אחתי את"),
+                ]])
+                .into_connection(),
+        );
+
+        let sayings = find_sayings_by_speaker(&db, "person-avraham".to_string())
+            .await
+            .expect("sayings should load");
+
+        assert_eq!(sayings.len(), 1);
+        assert_eq!(sayings[0].display_name, "אמרי נא אחתי את");
+        assert_eq!(sayings[0].speakers[0].display_name, "אברהם");
+        assert_eq!(sayings[0].audience[0].display_name, "שרה");
+        assert_eq!(
+            sayings[0].audience[0].alt_group_id.as_deref(),
+            Some("alt-1")
+        );
+        assert!(sayings[0].locations.is_empty());
+    }
+}
@@ -0,0 +1,660 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::tanahpedia_source::{
+        TanahpediaNonTanahSource, TanahpediaPerushNote, TanahpediaSources, TanahpediaTanahSource,
+        TanahpediaTanahSourceCommentary, TanahpediaTanahSourceInput,
+    },
+    providers::Database,
+    services::{
+        tanahpedia_chazal_service::{ChazalReference, reference_columns},
+        tanahpedia_family_service::{db_error, format_citation, required},
+    },
+};
+use async_graphql::dataloader::{DataLoader, Loader};
+use entities::perek;
+use entities::perushim::{note, parshan, perush};
+use entities::tanahpedia::{non_tanah_source, source_group, tanah_source};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+};
+
+/// Validated sources ready to be stored by [`replace_sources`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SourcesInput {
+    tanah: Vec<TanahpediaTanahSourceInput>,
+    non_tanah: Vec<String>,
+}
+
+impl SourcesInput {
+    /// Every Tanah source must name a perek (a pasuk number alone is
+    /// meaningless) and pasuk numbers are 1-based.
+    pub(crate) fn parse(
+        tanah: Vec<TanahpediaTanahSourceInput>,
+        non_tanah: Vec<String>,
+    ) -> Result<Self, ServiceError> {
+        for source in &tanah {
+            if source.perek_id.is_none() {
+                return Err(ServiceError::bad_request(
+                    "tanahSources entries require perekId",
+                ));
+            }
+            if source.pasuk_number.is_some_and(|pasuk| pasuk < 1) {
+                return Err(ServiceError::bad_request(
+                    "tanahSources pasukNumber must be at least 1",
+                ));
+            }
+        }
+        let non_tanah = non_tanah
+            .into_iter()
+            .map(|text| required(text, "nonTanahSources", 500))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { tanah, non_tanah })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tanah.is_empty() && self.non_tanah.is_empty()
+    }
+}
+
+/// Loads the source group of every `targetIds` row of `target_table`. Rows
+/// without a group are absent from the map.
+pub(crate) async fn load_sources<C: ConnectionTrait>(
+    conn: &C,
+    target_table: &str,
+    target_ids: HashSet<String>,
+) -> Result<HashMap<String, TanahpediaSources>, ServiceError> {
+    if target_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let groups = source_group::Entity::find()
+        .filter(source_group::Column::TargetTable.eq(target_table))
+        .filter(source_group::Column::TargetId.is_in(target_ids))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    if groups.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let target_by_group: HashMap<String, String> = groups
+        .into_iter()
+        .map(|group| (group.id, group.target_id))
+        .collect();
+
+    let mut tanah = tanah_source::Entity::find()
+        .filter(tanah_source::Column::SourceGroupId.is_in(target_by_group.keys().cloned()))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    tanah.sort_by_key(|row| (row.perek_id, row.pasuk_number, row.perush_id));
+    let mut non_tanah = non_tanah_source::Entity::find()
+        .filter(non_tanah_source::Column::SourceGroupId.is_in(target_by_group.keys().cloned()))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    non_tanah.sort_by(|a, b| a.source_text.cmp(&b.source_text));
+
+    let mut sources: HashMap<String, TanahpediaSources> = HashMap::new();
+    for row in tanah {
+        if let Some(target_id) = target_by_group.get(&row.source_group_id) {
+            sources
+                .entry(target_id.clone())
+                .or_default()
+                .tanah
+                .push(TanahpediaTanahSource {
+                    id: row.id,
+                    perush_id: row.perush_id,
+                    perek_id: row.perek_id,
+                    pasuk_number: row.pasuk_number,
+                });
+        }
+    }
+    for row in non_tanah {
+        if let Some(target_id) = target_by_group.get(&row.source_group_id) {
+            sources
+                .entry(target_id.clone())
+                .or_default()
+                .non_tanah
+                .push(TanahpediaNonTanahSource {
+                    reference: ChazalReference::from_columns(
+                        row.ref_corpus.as_deref(),
+                        row.ref_work.as_deref(),
+                        row.ref_section,
+                        row.ref_subsection,
+                    )
+                    .map(|reference| (&reference).into()),
+                    id: row.id,
+                    source_text: row.source_text,
+                });
+        }
+    }
+    Ok(sources)
+}
+
+/// Rejects Tanah sources naming a perush that doesn't exist, before the
+/// foreign key turns it into a database error. No query when no source names
+/// a perush.
+async fn require_perushim<C: ConnectionTrait>(
+    conn: &C,
+    tanah: &[TanahpediaTanahSourceInput],
+) -> Result<(), ServiceError> {
+    let ids: HashSet<i16> = tanah.iter().filter_map(|source| source.perush_id).collect();
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let found: HashSet<i16> = perush::Entity::find()
+        .filter(perush::Column::Id.is_in(ids.iter().copied()))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| row.id)
+        .collect();
+    let mut missing: Vec<i16> = ids.difference(&found).copied().collect();
+    missing.sort_unstable();
+    match missing.first() {
+        Some(id) => Err(ServiceError::bad_request(&format!(
+            "tanahSources perushId {id} does not exist"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Batches the perek, perush and note lookups behind a Tanah source's
+/// `citation` and `commentary`, so a page of sources costs one query per
+/// table instead of one per source.
+pub struct TanahSourceLoader {
+    db: Database,
+}
+
+impl TanahSourceLoader {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+/// Perakim by id.
+impl Loader<i32> for TanahSourceLoader {
+    type Value = perek::Model;
+    type Error = ServiceError;
+
+    async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Self::Value>, Self::Error> {
+        Ok(perek::Entity::find()
+            .filter(perek::Column::Id.is_in(keys.iter().copied()))
+            .all(self.db.get_connection())
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|row| (row.id, row))
+            .collect())
+    }
+}
+
+/// Perushim by id, with their parshan.
+impl Loader<i16> for TanahSourceLoader {
+    type Value = (perush::Model, Option<parshan::Model>);
+    type Error = ServiceError;
+
+    async fn load(&self, keys: &[i16]) -> Result<HashMap<i16, Self::Value>, Self::Error> {
+        Ok(perush::Entity::find()
+            .find_also_related(parshan::Entity)
+            .filter(perush::Column::Id.is_in(keys.iter().copied()))
+            .all(self.db.get_connection())
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|(perush, parshan)| (perush.id, (perush, parshan)))
+            .collect())
+    }
+}
+
+/// The notes of a perush on a perek, keyed by `(perushId, perekId)`, in pasuk
+/// and note order.
+impl Loader<(i16, i32)> for TanahSourceLoader {
+    type Value = Vec<note::Model>;
+    type Error = ServiceError;
+
+    async fn load(
+        &self,
+        keys: &[(i16, i32)],
+    ) -> Result<HashMap<(i16, i32), Self::Value>, Self::Error> {
+        let condition = keys
+            .iter()
+            .fold(Condition::any(), |condition, &(perush_id, perek_id)| {
+                condition.add(
+                    Condition::all()
+                        .add(note::Column::PerushId.eq(perush_id))
+                        .add(note::Column::PerekId.eq(perek_id)),
+                )
+            });
+        let mut notes: HashMap<(i16, i32), Self::Value> = HashMap::new();
+        for row in note::Entity::find()
+            .filter(condition)
+            .order_by_asc(note::Column::Pasuk)
+            .order_by_asc(note::Column::NoteIdx)
+            .all(self.db.get_connection())
+            .await
+            .map_err(db_error)?
+        {
+            notes
+                .entry((row.perush_id, row.perek_id.into()))
+                .or_default()
+                .push(row);
+        }
+        Ok(notes)
+    }
+}
+
+/// Formats a Tanah source as `רש"י על בראשית ל' ד'`, or as the bare pasuk
+/// citation when it names no perush.
+pub async fn get_tanah_source_citation(
+    loader: &DataLoader<TanahSourceLoader>,
+    source: &TanahpediaTanahSource,
+) -> Result<String, ServiceError> {
+    let Some(perek_id) = source.perek_id else {
+        return Ok(String::new());
+    };
+    let citation = loader
+        .load_one(perek_id)
+        .await?
+        .map(|row| format_citation(&row, source.pasuk_number))
+        .unwrap_or_default();
+    let Some(perush_id) = source.perush_id else {
+        return Ok(citation);
+    };
+    Ok(match loader.load_one(perush_id).await? {
+        Some((perush, _)) => format!("{} על {}", perush.name, citation),
+        None => citation,
+    })
+}
+
+/// Resolves `perushId` to the perush, its parshan and the `note` rows on the
+/// cited pasuk (every pasuk of the perek when the source cites a whole
+/// perek), in pasuk and note order.
+pub async fn get_tanah_source_commentary(
+    loader: &DataLoader<TanahSourceLoader>,
+    source: &TanahpediaTanahSource,
+) -> Result<Option<TanahpediaTanahSourceCommentary>, ServiceError> {
+    let (Some(perush_id), Some(perek_id)) = (source.perush_id, source.perek_id) else {
+        return Ok(None);
+    };
+    let Some((perush, parshan)) = loader.load_one(perush_id).await? else {
+        return Ok(None);
+    };
+
+    let notes = loader
+        .load_one((perush_id, perek_id))
+        .await?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|note| {
+            source
+                .pasuk_number
+                .is_none_or(|pasuk| i32::from(note.pasuk) == pasuk)
+        })
+        .map(|note| TanahpediaPerushNote {
+            pasuk: note.pasuk.into(),
+            note_idx: note.note_idx.into(),
+            content: note.note_content,
+        })
+        .collect();
+
+    Ok(Some(TanahpediaTanahSourceCommentary {
+        perush_id,
+        perush_name: perush.name,
+        parshan_name: parshan.map(|parshan| parshan.name),
+        notes,
+    }))
+}
+
+/// Drops the source group of a row; its citations go with it
+/// (`ON DELETE CASCADE`).
+pub(crate) async fn delete_sources<C: ConnectionTrait>(
+    conn: &C,
+    target_table: &str,
+    target_id: &str,
+) -> Result<(), ServiceError> {
+    source_group::Entity::delete_many()
+        .filter(source_group::Column::TargetTable.eq(target_table))
+        .filter(source_group::Column::TargetId.eq(target_id))
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+    Ok(())
+}
+
+/// Replaces the source group of a row with `sources`. An empty input leaves the
+/// row without a group. Call inside the transaction that writes the row.
+pub(crate) async fn replace_sources<C: ConnectionTrait>(
+    conn: &C,
+    target_table: &str,
+    target_id: &str,
+    sources: SourcesInput,
+) -> Result<(), ServiceError> {
+    require_perushim(conn, &sources.tanah).await?;
+    delete_sources(conn, target_table, target_id).await?;
+    if sources.is_empty() {
+        return Ok(());
+    }
+
+    let group_id = uuid::Uuid::new_v4().to_string();
+    source_group::Entity::insert(
+        source_group::Model {
+            id: group_id.clone(),
+            target_table: target_table.to_string(),
+            target_id: target_id.to_string(),
+        }
+        .into_active_model(),
+    )
+    .exec(conn)
+    .await
+    .map_err(db_error)?;
+
+    if !sources.tanah.is_empty() {
+        tanah_source::Entity::insert_many(sources.tanah.into_iter().map(|source| {
+            tanah_source::Model {
+                id: uuid::Uuid::new_v4().to_string(),
+                source_group_id: group_id.clone(),
+                perush_id: source.perush_id,
+                perek_id: source.perek_id,
+                pasuk_number: source.pasuk_number,
+            }
+            .into_active_model()
+        }))
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+    }
+    if !sources.non_tanah.is_empty() {
+        non_tanah_source::Entity::insert_many(sources.non_tanah.into_iter().map(|source_text| {
+            let (ref_corpus, ref_work, ref_section, ref_subsection) =
+                reference_columns(&source_text);
+            non_tanah_source::Model {
+                id: uuid::Uuid::new_v4().to_string(),
+                source_group_id: group_id.clone(),
+                source_text,
+                ref_corpus,
+                ref_work,
+                ref_section,
+                ref_subsection,
+            }
+            .into_active_model()
+        }))
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    fn rashi() -> perush::Model {
+        perush::Model {
+            id: 1,
+            name: "רש\"י".to_string(),
+            parshan_id: 1,
+            comp_date: None,
+            pub_date: None,
+            priority: 100,
+        }
+    }
+
+    fn rashi_on(pasuk_number: Option<i32>) -> TanahpediaTanahSource {
+        TanahpediaTanahSource {
+            id: "tanah-1".to_string(),
+            perush_id: Some(1),
+            perek_id: Some(30),
+            pasuk_number,
+        }
+    }
+
+    fn tanah_input(perek_id: Option<i32>, pasuk_number: Option<i32>) -> TanahpediaTanahSourceInput {
+        TanahpediaTanahSourceInput {
+            perush_id: None,
+            perek_id,
+            pasuk_number,
+        }
+    }
+
+    #[test]
+    fn sources_input_requires_perek_and_positive_pasuk() {
+        assert!(SourcesInput::parse(vec![tanah_input(None, Some(3))], vec![]).is_err());
+        assert!(SourcesInput::parse(vec![tanah_input(Some(90), Some(0))], vec![]).is_err());
+        assert!(SourcesInput::parse(vec![], vec!["  ".to_string()]).is_err());
+
+        let parsed = SourcesInput::parse(
+            vec![tanah_input(Some(90), None)],
+            vec![" חולין נט ע\"א ".to_string()],
+        )
+        .unwrap();
+        assert_eq!(parsed.non_tanah, vec!["חולין נט ע\"א".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn load_sources_groups_citations_by_target_row() {
+        let conn = MockDatabase::new(DatabaseBackend::MySql)
+            .append_query_results([vec![
+                source_group::Model {
+                    id: "group-1".to_string(),
+                    target_table: "tanahpedia_animal_purity".to_string(),
+                    target_id: "purity-1".to_string(),
+                },
+                source_group::Model {
+                    id: "group-2".to_string(),
+                    target_table: "tanahpedia_animal_purity".to_string(),
+                    target_id: "purity-2".to_string(),
+                },
+            ]])
+            .append_query_results([vec![
+                tanah_source::Model {
+                    id: "tanah-2".to_string(),
+                    source_group_id: "group-1".to_string(),
+                    perush_id: None,
+                    perek_id: Some(90),
+                    pasuk_number: Some(6),
+                },
+                tanah_source::Model {
+                    id: "tanah-1".to_string(),
+                    source_group_id: "group-1".to_string(),
+                    perush_id: None,
+                    perek_id: Some(90),
+                    pasuk_number: Some(4),
+                },
+            ]])
+            .append_query_results([vec![non_tanah_source::Model {
+                id: "non-tanah-1".to_string(),
+                source_group_id: "group-2".to_string(),
+                source_text: "חולין סג ע\"א".to_string(),
+                ref_corpus: Some("BAVLI".to_string()),
+                ref_work: Some("Chullin".to_string()),
+                ref_section: Some(63),
+                ref_subsection: Some(1),
+            }]])
+            .into_connection();
+
+        let sources = load_sources(
+            &conn,
+            "tanahpedia_animal_purity",
+            HashSet::from(["purity-1".to_string(), "purity-2".to_string()]),
+        )
+        .await
+        .expect("sources should load");
+
+        assert_eq!(
+            sources["purity-1"]
+                .tanah
+                .iter()
+                .map(|source| source.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["tanah-1", "tanah-2"]
+        );
+        assert!(sources["purity-1"].non_tanah.is_empty());
+        assert_eq!(
+            sources["purity-2"].non_tanah[0].source_text,
+            "חולין סג ע\"א"
+        );
+        assert_eq!(
+            sources["purity-2"].non_tanah[0]
+                .reference
+                .as_ref()
+                .map(|reference| reference.english_citation.as_str()),
+            Some("Bavli Chullin 63a")
+        );
+    }
+
+    #[tokio::test]
+    async fn replace_sources_rejects_unknown_perush_ids() {
+        let conn = MockDatabase::new(DatabaseBackend::MySql)
+            .append_query_results([vec![rashi()]])
+            .into_connection();
+        let sources = SourcesInput::parse(
+            vec![
+                TanahpediaTanahSourceInput {
+                    perush_id: Some(1),
+                    perek_id: Some(30),
+                    pasuk_number: Some(4),
+                },
+                TanahpediaTanahSourceInput {
+                    perush_id: Some(999),
+                    perek_id: Some(30),
+                    pasuk_number: Some(4),
+                },
+            ],
+            vec![],
+        )
+        .unwrap();
+
+        let error = replace_sources(&conn, "tanahpedia_animal_kind", "kind-1", sources)
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(error, ServiceError::BadRequest(message) if message == "tanahSources perushId 999 does not exist")
+        );
+    }
+
+    #[tokio::test]
+    async fn get_tanah_source_citation_names_the_perush() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![perek::Model {
+                    id: 30,
+                    perek_id: Some(30),
+                    sefer_id: Some(1),
+                    sefer_name: Some("בראשית".to_string()),
+                    additional: None,
+                    additional_letter: None,
+                    perek: Some(30),
+                    perek_in_context: Some(30),
+                    date: None,
+                    hebdate: None,
+                    tseit: None,
+                    header: None,
+                }]])
+                .append_query_results([vec![(rashi(), None::<parshan::Model>)]])
+                .into_connection(),
+        );
+
+        let loader = DataLoader::new(TanahSourceLoader::new(db), tokio::spawn);
+
+        let citation = get_tanah_source_citation(&loader, &rashi_on(Some(4)))
+            .await
+            .unwrap();
+
+        assert_eq!(citation, "רש\"י על בראשית ל' ד'");
+    }
+
+    #[tokio::test]
+    async fn citations_of_sibling_sources_are_loaded_in_one_query_per_table() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![perek::Model {
+                    id: 30,
+                    perek_id: Some(30),
+                    sefer_id: Some(1),
+                    sefer_name: Some("בראשית".to_string()),
+                    additional: None,
+                    additional_letter: None,
+                    perek: Some(30),
+                    perek_in_context: Some(30),
+                    date: None,
+                    hebdate: None,
+                    tseit: None,
+                    header: None,
+                }]])
+                .append_query_results([vec![(rashi(), None::<parshan::Model>)]])
+                .into_connection(),
+        );
+        let loader = DataLoader::new(TanahSourceLoader::new(db.clone()), tokio::spawn);
+
+        let (pasuk4, pasuk5) = (rashi_on(Some(4)), rashi_on(Some(5)));
+
+        let (first, second) = tokio::join!(
+            get_tanah_source_citation(&loader, &pasuk4),
+            get_tanah_source_citation(&loader, &pasuk5),
+        );
+
+        assert_eq!(first.unwrap(), "רש\"י על בראשית ל' ד'");
+        assert_eq!(second.unwrap(), "רש\"י על בראשית ל' ה'");
+        assert_eq!(db.get_connection().clone().into_transaction_log().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn get_tanah_source_commentary_returns_the_pasuk_notes() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![(
+                    rashi(),
+                    parshan::Model {
+                        id: 1,
+                        name: "רש\"י".to_string(),
+                        birth_year: Some(1040),
+                        has_pic: false,
+                    },
+                )]])
+                .append_query_results([vec![note::Model {
+                    perush_id: 1,
+                    perek_id: 30,
+                    pasuk: 4,
+                    note_idx: 0,
+                    note_content: "ותתן לו את בלהה שפחתה לאשה".to_string(),
+                }]])
+                .into_connection(),
+        );
+
+        let loader = DataLoader::new(TanahSourceLoader::new(db), tokio::spawn);
+
+        let commentary = get_tanah_source_commentary(&loader, &rashi_on(Some(4)))
+            .await
+            .unwrap()
+            .expect("perush should resolve");
+
+        assert_eq!(commentary.perush_name, "רש\"י");
+        assert_eq!(
+            commentary.notes,
+            vec![TanahpediaPerushNote {
+                pasuk: 4,
+                note_idx: 0,
+                content: "ותתן לו את בלהה שפחתה לאשה".to_string(),
+            }]
+        );
+        assert_eq!(
+            get_tanah_source_commentary(
+                &loader,
+                &TanahpediaTanahSource {
+                    perush_id: None,
+                    ..rashi_on(Some(4))
+                }
+            )
+            .await
+            .unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,758 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::error_handling::ServiceError,
+    dtos::tanahpedia_war::{
+        PutTanahpediaWarParticipantInput, PutTanahpediaWarSideInput, TanahpediaParticipantWar,
+        TanahpediaWar, TanahpediaWarDateRange, TanahpediaWarOpinions, TanahpediaWarParticipant,
+        TanahpediaWarParticipantType, TanahpediaWarParticipantWriteResult, TanahpediaWarPlace,
+        TanahpediaWarSide, TanahpediaWarSideOpinions, TanahpediaWarSideWriteResult,
+    },
+    providers::Database,
+    services::{
+        tanahpedia_family_service::{
+            db_error, entity_display_names, nation_display_names, optional, person_display_names,
+            place_display_names, require_person, required,
+        },
+        tanahpedia_opinions_service::{OpinionCatalog, alt_group_ids},
+    },
+};
+use entities::tanahpedia::{
+    event_date_range, event_place, nation, war, war_side, war_side_participant_nation,
+    war_side_participant_person,
+};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, SqlErr,
+    TransactionTrait,
+};
+
+/// Loads a war with its event date ranges, event places and every side with
+/// its person and nation participants. Errors with `NOT_FOUND` when the war
+/// doesn't exist.
+pub async fn get_war(db: &Database, war_id: String) -> Result<TanahpediaWar, ServiceError> {
+    let war_id = required(war_id, "warId", 36)?;
+    let conn = db.get_connection();
+
+    let Some(war_row) = war::Entity::find_by_id(war_id)
+        .one(conn)
+        .await
+        .map_err(db_error)?
+    else {
+        return Err(ServiceError::not_found("war not found", None::<&str>));
+    };
+
+    let date_ranges = event_date_range::Entity::find()
+        .filter(event_date_range::Column::EventId.eq(war_row.event_id.clone()))
+        .order_by_asc(event_date_range::Column::StartDate)
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let places = event_place::Entity::find()
+        .filter(event_place::Column::EventId.eq(war_row.event_id.clone()))
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let sides = war_side::Entity::find()
+        .filter(war_side::Column::WarId.eq(war_row.id.clone()))
+        .order_by_asc(war_side::Column::SideNumber)
+        .all(conn)
+        .await
+        .map_err(db_error)?;
+    let side_ids = sides.iter().map(|side| side.id.clone()).collect::<Vec<_>>();
+    let (person_links, nation_links) = if side_ids.is_empty() {
+        (Vec::new(), Vec::new())
+    } else {
+        (
+            war_side_participant_person::Entity::find()
+                .filter(war_side_participant_person::Column::WarSideId.is_in(side_ids.clone()))
+                .all(conn)
+                .await
+                .map_err(db_error)?,
+            war_side_participant_nation::Entity::find()
+                .filter(war_side_participant_nation::Column::WarSideId.is_in(side_ids))
+                .all(conn)
+                .await
+                .map_err(db_error)?,
+        )
+    };
+
+    let place_names = place_display_names(
+        conn,
+        places.iter().map(|row| row.place_id.clone()).collect(),
+    )
+    .await?;
+    let person_names = person_display_names(
+        conn,
+        person_links
+            .iter()
+            .map(|row| row.person_id.clone())
+            .collect(),
+    )
+    .await?;
+    let nation_names = nation_display_names(
+        conn,
+        nation_links
+            .iter()
+            .map(|row| row.nation_id.clone())
+            .collect(),
+    )
+    .await?;
+    let war_names = entity_display_names(conn, [war_row.entity_id.clone()].into()).await?;
+
+    let mut participants_by_side = HashMap::<String, Vec<TanahpediaWarParticipant>>::new();
+    for row in person_links {
+        participants_by_side
+            .entry(row.war_side_id)
+            .or_default()
+            .push(TanahpediaWarParticipant {
+                display_name: person_names
+                    .get(&row.person_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                link_id: row.id,
+                participant_type: TanahpediaWarParticipantType::Person,
+                participant_id: row.person_id,
+                alt_group_id: row.alt_group_id,
+            });
+    }
+    for row in nation_links {
+        participants_by_side
+            .entry(row.war_side_id)
+            .or_default()
+            .push(TanahpediaWarParticipant {
+                display_name: nation_names
+                    .get(&row.nation_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                link_id: row.id,
+                participant_type: TanahpediaWarParticipantType::Nation,
+                participant_id: row.nation_id,
+                alt_group_id: row.alt_group_id,
+            });
+    }
+
+    Ok(TanahpediaWar {
+        display_name: war_names
+            .get(&war_row.entity_id)
+            .cloned()
+            .unwrap_or_default(),
+        war_id: war_row.id,
+        entity_id: war_row.entity_id,
+        event_id: war_row.event_id,
+        date_ranges: date_ranges
+            .into_iter()
+            .map(|row| TanahpediaWarDateRange {
+                id: row.id,
+                start_date: row.start_date,
+                end_date: row.end_date,
+                alt_group_id: row.alt_group_id,
+            })
+            .collect(),
+        places: places
+            .into_iter()
+            .map(|row| TanahpediaWarPlace {
+                display_name: place_names.get(&row.place_id).cloned().unwrap_or_default(),
+                link_id: row.id,
+                place_id: row.place_id,
+                alt_group_id: row.alt_group_id,
+            })
+            .collect(),
+        sides: sides
+            .into_iter()
+            .map(|side| TanahpediaWarSide {
+                participants: participants_by_side.remove(&side.id).unwrap_or_default(),
+                side_id: side.id,
+                side_number: side.side_number,
+            })
+            .collect(),
+    })
+}
+
+/// Resolves `(link_id, war_side_id, alt_group_id)` participant links to the
+/// wars they belong to, ordered by war name and then side number.
+async fn participant_wars(
+    conn: &sea_orm::DatabaseConnection,
+    links: Vec<(String, String, Option<String>)>,
+) -> Result<Vec<TanahpediaParticipantWar>, ServiceError> {
+    if links.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let sides = war_side::Entity::find()
+        .filter(war_side::Column::Id.is_in(links.iter().map(|(_, side_id, _)| side_id.clone())))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|side| (side.id.clone(), side))
+        .collect::<HashMap<_, _>>();
+    let wars = war::Entity::find()
+        .filter(war::Column::Id.is_in(sides.values().map(|side| side.war_id.clone())))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| (row.id.clone(), row))
+        .collect::<HashMap<_, _>>();
+    let names = entity_display_names(
+        conn,
+        wars.values().map(|row| row.entity_id.clone()).collect(),
+    )
+    .await?;
+
+    let mut result = links
+        .into_iter()
+        .filter_map(|(link_id, side_id, alt_group_id)| {
+            let side = sides.get(&side_id)?;
+            let war_row = wars.get(&side.war_id)?;
+            Some(TanahpediaParticipantWar {
+                war_id: war_row.id.clone(),
+                display_name: names.get(&war_row.entity_id).cloned().unwrap_or_default(),
+                side_id,
+                side_number: side.side_number,
+                link_id,
+                alt_group_id,
+            })
+        })
+        .collect::<Vec<_>>();
+    result.sort_by(|a, b| {
+        a.display_name
+            .cmp(&b.display_name)
+            .then(a.side_number.cmp(&b.side_number))
+            .then_with(|| a.link_id.cmp(&b.link_id))
+    });
+    Ok(result)
+}
+
+/// Lists the wars `person_id` fought in, with the side for each.
+pub async fn find_person_wars(
+    db: &Database,
+    person_id: String,
+) -> Result<Vec<TanahpediaParticipantWar>, ServiceError> {
+    let person_id = required(person_id, "personId", 36)?;
+    let conn = db.get_connection();
+
+    let links = war_side_participant_person::Entity::find()
+        .filter(war_side_participant_person::Column::PersonId.eq(person_id))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| (row.id, row.war_side_id, row.alt_group_id))
+        .collect();
+
+    participant_wars(conn, links).await
+}
+
+/// Lists the wars `nation_id` fought in, with the side for each.
+pub async fn find_nation_wars(
+    db: &Database,
+    nation_id: String,
+) -> Result<Vec<TanahpediaParticipantWar>, ServiceError> {
+    let nation_id = required(nation_id, "nationId", 36)?;
+    let conn = db.get_connection();
+
+    let links = war_side_participant_nation::Entity::find()
+        .filter(war_side_participant_nation::Column::NationId.eq(nation_id))
+        .all(conn)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| (row.id, row.war_side_id, row.alt_group_id))
+        .collect();
+
+    participant_wars(conn, links).await
+}
+
+/// Groups an already-loaded war by opinion; only the opinion metadata is
+/// queried.
+pub async fn get_war_opinions(
+    db: &Database,
+    war: &TanahpediaWar,
+) -> Result<TanahpediaWarOpinions, ServiceError> {
+    let catalog = OpinionCatalog::load(
+        db.get_connection(),
+        alt_group_ids(
+            war.date_ranges
+                .iter()
+                .map(|range| &range.alt_group_id)
+                .chain(war.places.iter().map(|place| &place.alt_group_id))
+                .chain(
+                    war.sides
+                        .iter()
+                        .flat_map(|side| &side.participants)
+                        .map(|participant| &participant.alt_group_id),
+                ),
+        ),
+    )
+    .await?;
+
+    Ok(TanahpediaWarOpinions {
+        date_ranges: catalog.group(
+            war.date_ranges
+                .iter()
+                .map(|range| (range.alt_group_id.clone(), range.clone()))
+                .collect(),
+        ),
+        places: catalog.group(
+            war.places
+                .iter()
+                .map(|place| (place.alt_group_id.clone(), place.clone()))
+                .collect(),
+        ),
+        sides: war
+            .sides
+            .iter()
+            .map(|side| TanahpediaWarSideOpinions {
+                side_id: side.side_id.clone(),
+                side_number: side.side_number,
+                participants: catalog.group(
+                    side.participants
+                        .iter()
+                        .map(|participant| (participant.alt_group_id.clone(), participant.clone()))
+                        .collect(),
+                ),
+            })
+            .collect(),
+    })
+}
+
+pub async fn put_war_side(
+    db: &Database,
+    input: PutTanahpediaWarSideInput,
+) -> Result<TanahpediaWarSideWriteResult, ServiceError> {
+    let conn = db.get_connection();
+    let id = required(input.id, "id", 36)?;
+    let war_id = required(input.war_id, "warId", 36)?;
+    if input.side_number < 1 {
+        return Err(ServiceError::bad_request("sideNumber must be at least 1"));
+    }
+    if war::Entity::find_by_id(war_id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+        .is_none()
+    {
+        return Err(ServiceError::bad_request(&format!(
+            "warId {war_id} does not reference an existing war"
+        )));
+    }
+    let side_number_taken = || {
+        ServiceError::bad_request(&format!(
+            "war {war_id} already has a side number {}",
+            input.side_number
+        ))
+    };
+    let taken = war_side::Entity::find()
+        .filter(war_side::Column::WarId.eq(war_id.clone()))
+        .filter(war_side::Column::SideNumber.eq(input.side_number))
+        .filter(war_side::Column::Id.ne(id.clone()))
+        .one(conn)
+        .await
+        .map_err(db_error)?;
+    if taken.is_some() {
+        return Err(side_number_taken());
+    }
+    if let Some(existing) = war_side::Entity::find_by_id(id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+        && existing.war_id != war_id
+    {
+        return Err(ServiceError::bad_request(&format!(
+            "war side {id} belongs to war {}",
+            existing.war_id
+        )));
+    }
+
+    let model = war_side::Model {
+        id: id.clone(),
+        war_id: war_id.clone(),
+        side_number: input.side_number,
+    };
+
+    // A side never moves to another war, so only its number is updated.
+    // `uk_war_side_number` catches a concurrent put that takes the same number
+    // after the check above.
+    war_side::Entity::insert(model.into_active_model())
+        .on_conflict(
+            OnConflict::column(war_side::Column::Id)
+                .update_column(war_side::Column::SideNumber)
+                .to_owned(),
+        )
+        .exec(conn)
+        .await
+        .map_err(|err| match err.sql_err() {
+            Some(SqlErr::UniqueConstraintViolation(_)) => side_number_taken(),
+            _ => db_error(err),
+        })?;
+
+    Ok(TanahpediaWarSideWriteResult {
+        id,
+        war_id,
+        side_number: input.side_number,
+    })
+}
+
+/// Deletes a war side. Its participant links go with it (`ON DELETE CASCADE`).
+pub async fn delete_war_side(
+    db: &Database,
+    id: String,
+) -> Result<TanahpediaWarSideWriteResult, ServiceError> {
+    let id = required(id, "id", 36)?;
+    let conn = db.get_connection();
+    let Some(side) = war_side::Entity::find_by_id(id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+    else {
+        return Err(ServiceError::not_found("war side not found", None::<&str>));
+    };
+
+    war_side::Entity::delete_by_id(id.clone())
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+
+    Ok(TanahpediaWarSideWriteResult {
+        id,
+        war_id: side.war_id,
+        side_number: side.side_number,
+    })
+}
+
+/// Removes `id` from both participant tables, so re-putting a link with a
+/// different `participantType` never leaves the old row behind.
+async fn delete_participant_links<C: ConnectionTrait>(
+    conn: &C,
+    id: &str,
+) -> Result<u64, ServiceError> {
+    let person = war_side_participant_person::Entity::delete_by_id(id.to_string())
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+    let nation = war_side_participant_nation::Entity::delete_by_id(id.to_string())
+        .exec(conn)
+        .await
+        .map_err(db_error)?;
+    Ok(person.rows_affected + nation.rows_affected)
+}
+
+pub async fn put_war_participant(
+    db: &Database,
+    input: PutTanahpediaWarParticipantInput,
+) -> Result<TanahpediaWarParticipantWriteResult, ServiceError> {
+    let conn = db.get_connection();
+    let id = required(input.id, "id", 36)?;
+    let war_side_id = required(input.war_side_id, "warSideId", 36)?;
+    let participant_type = input.participant_type;
+    let participant_id = required(input.participant_id, "participantId", 36)?;
+    let alt_group_id = optional(input.alt_group_id, "altGroupId", 36)?;
+    if war_side::Entity::find_by_id(war_side_id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+        .is_none()
+    {
+        return Err(ServiceError::bad_request(&format!(
+            "warSideId {war_side_id} does not reference an existing war side"
+        )));
+    }
+    match participant_type {
+        TanahpediaWarParticipantType::Person => require_person(conn, &participant_id).await?,
+        TanahpediaWarParticipantType::Nation => {
+            if nation::Entity::find_by_id(participant_id.clone())
+                .one(conn)
+                .await
+                .map_err(db_error)?
+                .is_none()
+            {
+                return Err(ServiceError::bad_request(&format!(
+                    "participantId {participant_id} does not reference an existing nation"
+                )));
+            }
+        }
+    }
+
+    let transaction = conn.begin().await.map_err(db_error)?;
+    delete_participant_links(&transaction, &id).await?;
+    match participant_type {
+        TanahpediaWarParticipantType::Person => {
+            war_side_participant_person::Entity::insert(
+                war_side_participant_person::Model {
+                    id: id.clone(),
+                    war_side_id: war_side_id.clone(),
+                    person_id: participant_id,
+                    alt_group_id,
+                }
+                .into_active_model(),
+            )
+            .exec(&transaction)
+            .await
+            .map_err(db_error)?;
+        }
+        TanahpediaWarParticipantType::Nation => {
+            war_side_participant_nation::Entity::insert(
+                war_side_participant_nation::Model {
+                    id: id.clone(),
+                    war_side_id: war_side_id.clone(),
+                    nation_id: participant_id,
+                    alt_group_id,
+                }
+                .into_active_model(),
+            )
+            .exec(&transaction)
+            .await
+            .map_err(db_error)?;
+        }
+    }
+    transaction.commit().await.map_err(db_error)?;
+
+    Ok(TanahpediaWarParticipantWriteResult {
+        id,
+        war_side_id,
+        participant_type,
+    })
+}
+
+pub async fn delete_war_participant(
+    db: &Database,
+    id: String,
+) -> Result<TanahpediaWarParticipantWriteResult, ServiceError> {
+    let id = required(id, "id", 36)?;
+    let conn = db.get_connection();
+
+    let existing = if let Some(row) = war_side_participant_person::Entity::find_by_id(id.clone())
+        .one(conn)
+        .await
+        .map_err(db_error)?
+    {
+        Some((row.war_side_id, TanahpediaWarParticipantType::Person))
+    } else {
+        war_side_participant_nation::Entity::find_by_id(id.clone())
+            .one(conn)
+            .await
+            .map_err(db_error)?
+            .map(|row| (row.war_side_id, TanahpediaWarParticipantType::Nation))
+    };
+    let Some((war_side_id, participant_type)) = existing else {
+        return Err(ServiceError::not_found(
+            "war participant not found",
+            None::<&str>,
+        ));
+    };
+
+    delete_participant_links(conn, &id).await?;
+
+    Ok(TanahpediaWarParticipantWriteResult {
+        id,
+        war_side_id,
+        participant_type,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::tanahpedia::{entity, sea_orm_active_enums::EntityType};
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    fn side_input(side_number: i32) -> PutTanahpediaWarSideInput {
+        PutTanahpediaWarSideInput {
+            id: "side-1".to_string(),
+            war_id: "war-1".to_string(),
+            side_number,
+        }
+    }
+
+    fn war_model() -> war::Model {
+        war::Model {
+            id: "war-1".to_string(),
+            event_id: "event-1".to_string(),
+            entity_id: "entity-war".to_string(),
+        }
+    }
+
+    fn side_model(id: &str, side_number: i32) -> war_side::Model {
+        war_side::Model {
+            id: id.to_string(),
+            war_id: "war-1".to_string(),
+            side_number,
+        }
+    }
+
+    fn entity_model(id: &str, name: &str) -> entity::Model {
+        entity::Model {
+            id: id.to_string(),
+            entity_type: EntityType::War,
+            name: name.to_string(),
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    #[tokio::test]
+    async fn put_war_side_rejects_non_positive_side_number_before_querying() {
+        let db =
+            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
+
+        let err = put_war_side(&db, side_input(0)).await.unwrap_err();
+
+        assert!(matches!(err, ServiceError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn put_war_side_rejects_side_number_taken_by_another_side() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![war_model()]])
+                .append_query_results([vec![side_model("side-other", 1)]])
+                .into_connection(),
+        );
+
+        let err = put_war_side(&db, side_input(1)).await.unwrap_err();
+
+        assert!(matches!(err, ServiceError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn put_war_side_rejects_moving_a_side_to_another_war() {
+        let mut other_war_side = side_model("side-1", 1);
+        other_war_side.war_id = "war-2".to_string();
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![war_model()]])
+                .append_query_results::<war_side::Model, Vec<_>, _>([vec![], vec![other_war_side]])
+                .into_connection(),
+        );
+
+        let err = put_war_side(&db, side_input(1)).await.unwrap_err();
+
+        assert!(matches!(err, ServiceError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn put_war_side_updates_only_the_side_number() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![war_model()]])
+                .append_query_results::<war_side::Model, Vec<_>, _>([
+                    vec![],
+                    vec![side_model("side-1", 2)],
+                ])
+                .append_exec_results([MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 2,
+                }])
+                .into_connection(),
+        );
+
+        put_war_side(&db, side_input(1))
+            .await
+            .expect("side should be renumbered");
+
+        let transaction_log = db.get_connection().clone().into_transaction_log();
+        let upsert = format!("{:?}", transaction_log.last().unwrap());
+        assert!(upsert.contains("ON DUPLICATE KEY UPDATE `side_number` = VALUES(`side_number`)"));
+        assert!(!upsert.contains("`war_id` = VALUES(`war_id`)"));
+    }
+
+    #[tokio::test]
+    async fn delete_war_participant_reports_missing_link() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results::<war_side_participant_person::Model, Vec<_>, _>([vec![]])
+                .append_query_results::<war_side_participant_nation::Model, Vec<_>, _>([vec![]])
+                .into_connection(),
+        );
+
+        let err = delete_war_participant(&db, "missing".to_string())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ServiceError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn put_war_participant_replaces_link_across_participant_tables() {
+        let no_rows = MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 0,
+        };
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![side_model("side-1", 1)]])
+                .append_query_results([vec![nation::Model {
+                    id: "nation-1".to_string(),
+                    entity_id: "entity-nation".to_string(),
+                }]])
+                .append_exec_results([
+                    no_rows.clone(),
+                    no_rows,
+                    MockExecResult {
+                        last_insert_id: 0,
+                        rows_affected: 1,
+                    },
+                ])
+                .into_connection(),
+        );
+
+        let result = put_war_participant(
+            &db,
+            PutTanahpediaWarParticipantInput {
+                id: "participant-1".to_string(),
+                war_side_id: "side-1".to_string(),
+                participant_type: TanahpediaWarParticipantType::Nation,
+                participant_id: "nation-1".to_string(),
+                alt_group_id: None,
+            },
+        )
+        .await
+        .expect("nation participant should be stored");
+
+        assert_eq!(
+            result.participant_type,
+            TanahpediaWarParticipantType::Nation
+        );
+        assert_eq!(result.war_side_id, "side-1");
+    }
+
+    #[tokio::test]
+    async fn find_person_wars_orders_by_war_name_then_side() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![
+                    war_side_participant_person::Model {
+                        id: "link-2".to_string(),
+                        war_side_id: "side-2".to_string(),
+                        person_id: "person-1".to_string(),
+                        alt_group_id: Some("alt-1".to_string()),
+                    },
+                    war_side_participant_person::Model {
+                        id: "link-1".to_string(),
+                        war_side_id: "side-1".to_string(),
+                        person_id: "person-1".to_string(),
+                        alt_group_id: None,
+                    },
+                ]])
+                .append_query_results([vec![side_model("side-1", 1), side_model("side-2", 2)]])
+                .append_query_results([vec![war_model()]])
+                .append_query_results([vec![entity_model("entity-war", "מלחמת המלכים")]])
+                .into_connection(),
+        );
+
+        let wars = find_person_wars(&db, "person-1".to_string())
+            .await
+            .expect("person wars should load");
+
+        assert_eq!(
+            wars.iter()
+                .map(|war| (war.link_id.as_str(), war.side_number))
+                .collect::<Vec<_>>(),
+            vec![("link-1", 1), ("link-2", 2)]
+        );
+        assert_eq!(wars[0].display_name, "מלחמת המלכים");
+        assert_eq!(wars[1].alt_group_id.as_deref(), Some("alt-1"));
+    }
+}
@@ -9,13 +9,17 @@ use std::{
 
 use actix_web::guard;
 use actix_web::middleware::Compress;
-use actix_web::{App, HttpResponse, HttpServer, dev::Server, web};
+use actix_web::{App, HttpRequest, HttpResponse, HttpServer, dev::Server, http::header, web};
 use anyhow::Error;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tracing_actix_web::TracingLogger;
 
-use crate::providers::Database;
+use crate::common::error_handling::ServiceError;
+use crate::common::http_cache;
+use crate::providers::{Database, PersistedQueries, StaticCache, Storage};
+use crate::services::tanahpedia_graph_service::{self, GraphFormat};
 
+use super::rest_api;
 use super::schema_builder::{build_schema, graphql_playground, graphql_request};
 use tokio::time::Duration;
 
@@ -45,6 +49,10 @@ impl ActixApp {
         let listener = TcpListener::bind(format!("{}:{}", &host, &port))?;
         let port = listener.local_addr().unwrap().port();
         let db: Database = Database::new().await?;
+        let storage = Storage::new().await;
+        // Shared by every worker so a mutation on one invalidates all of them.
+        let cache = StaticCache::new();
+        let persisted_queries = PersistedQueries::new()?;
         let shutdown_signal = Arc::new(AtomicBool::new(false));
         let server = HttpServer::new({
             let shutdown_signal = shutdown_signal.clone();
@@ -52,7 +60,13 @@ impl ActixApp {
                 App::new()
                     .wrap(Compress::default())
                     .wrap(TracingLogger::default())
-                    .configure(Self::build_app_config(&db, shutdown_signal.clone()))
+                    .configure(Self::build_app_config(
+                        &db,
+                        &storage,
+                        &cache,
+                        &persisted_queries,
+                        shutdown_signal.clone(),
+                    ))
             }
         })
         .listen_auto_h2c(listener)?
@@ -82,12 +96,35 @@ impl ActixApp {
 
     pub fn build_app_config(
         db: &Database,
+        storage: &Storage,
+        cache: &StaticCache,
+        persisted_queries: &PersistedQueries,
         shutdown_signal: Arc<AtomicBool>,
     ) -> impl Fn(&mut web::ServiceConfig) {
         let db = db.clone();
+        let storage = storage.clone();
+        let cache = cache.clone();
+        let persisted_queries = persisted_queries.clone();
         move |cfg: &mut web::ServiceConfig| {
-            cfg.app_data(web::Data::new(build_schema(&db)))
+            let schema = build_schema(&db, &storage, &cache, &persisted_queries);
+            cfg.app_data(web::Data::new(schema))
+                .app_data(web::Data::new(db.clone()))
+                .app_data(web::Data::new(cache.clone()))
                 .service(web::resource("/").guard(guard::Post()).to(graphql_request))
+                // `GET /?query=...` (or a persisted-query hash) so CDNs and
+                // browsers can cache and revalidate queries; bare `GET /`
+                // stays the playground.
+                .service(
+                    web::resource("/")
+                        .guard(guard::Get())
+                        .guard(guard::fn_guard(|ctx| {
+                            ctx.head()
+                                .uri
+                                .query()
+                                .is_some_and(|query| !query.is_empty())
+                        }))
+                        .to(graphql_request),
+                )
                 .service(
                     web::resource("/")
                         .guard(guard::Get())
@@ -97,7 +134,13 @@ impl ActixApp {
                     web::resource("/health")
                         .guard(guard::Get())
                         .to(health_check),
-                );
+                )
+                .service(
+                    web::resource("/tanahpedia/graph")
+                        .guard(guard::Get())
+                        .to(tanahpedia_graph),
+                )
+                .configure(rest_api::configure);
 
             if env::var("PROFILE").unwrap_or_default() != "prod" {
                 cfg.service(web::resource("/api/shutdown").guard(guard::Post()).to({
@@ -134,6 +177,51 @@ async fn health_check() -> HttpResponse {
     })
 }
 
+#[derive(Deserialize)]
+struct GraphExportQuery {
+    format: Option<String>,
+}
+
+/// GET /tanahpedia/graph?format=jsonld|turtle — the Tanahpedia knowledge
+/// graph as schema.org JSON-LD (default) or RDF Turtle, for triple stores and
+/// the `graph-exporter` CLI. The serialized graph is kept in the
+/// [`StaticCache`], so it is rebuilt at most once per TTL or mutation, and
+/// carries an ETag.
+async fn tanahpedia_graph(
+    req: HttpRequest,
+    db: web::Data<Database>,
+    cache: web::Data<StaticCache>,
+    query: web::Query<GraphExportQuery>,
+) -> Result<HttpResponse, ServiceError> {
+    let format = match query.format.as_deref() {
+        None | Some("jsonld") => GraphFormat::JsonLd,
+        Some("turtle" | "ttl") => GraphFormat::Turtle,
+        Some(_) => {
+            return Err(ServiceError::bad_request("format must be jsonld or turtle"));
+        }
+    };
+    let body: String = cache
+        .get_or_load(format!("tanahpediaGraph:{format:?}"), || async {
+            let graph = tanahpedia_graph_service::load_graph(&db).await?;
+            Ok::<_, ServiceError>(match format {
+                GraphFormat::JsonLd => graph.to_json_ld().to_string(),
+                GraphFormat::Turtle => graph.to_turtle(),
+            })
+        })
+        .await?;
+
+    let etag = http_cache::etag(body.as_bytes());
+    if http_cache::is_not_modified(&req, &etag) {
+        return Ok(HttpResponse::NotModified()
+            .insert_header((header::ETAG, etag))
+            .finish());
+    }
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag))
+        .content_type(format.content_type())
+        .body(body))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,12 +257,16 @@ mod tests {
     }
 
     #[actix_web::test]
-    async fn build_app_config_wires_health_playground_and_graphql_post() {
+    async fn build_app_config_wires_health_playground_and_graphql_post_and_get() {
         let db = mock_database();
         let shutdown_signal = Arc::new(AtomicBool::new(false));
-        let app = test::init_service(
-            App::new().configure(ActixApp::build_app_config(&db, shutdown_signal)),
-        )
+        let app = test::init_service(App::new().configure(ActixApp::build_app_config(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+            shutdown_signal,
+        )))
         .await;
 
         let health_response =
@@ -202,5 +294,91 @@ mod tests {
         )
         .await;
         assert_eq!(graphql_response.status(), StatusCode::OK);
+
+        let graphql_get_response = test::call_service(
+            &app,
+            test::TestRequest::get()
+                .uri("/?query=%7B%20__typename%20%7D")
+                .to_request(),
+        )
+        .await;
+        assert_eq!(graphql_get_response.status(), StatusCode::OK);
+        assert_eq!(
+            graphql_get_response
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok()),
+            Some("application/graphql-response+json")
+        );
+
+        let graph_response = test::call_service(
+            &app,
+            test::TestRequest::get()
+                .uri("/tanahpedia/graph?format=rdfxml")
+                .to_request(),
+        )
+        .await;
+        assert_eq!(graph_response.status(), StatusCode::BAD_REQUEST);
+
+        let openapi_response = test::call_service(
+            &app,
+            test::TestRequest::get()
+                .uri("/v1/openapi.json")
+                .to_request(),
+        )
+        .await;
+        assert_eq!(openapi_response.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn tanahpedia_graph_is_served_from_the_static_cache_with_an_etag() {
+        // No mock results: a database read would fail the request.
+        let db =
+            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
+        let cache = StaticCache::default();
+        let _: Result<String, ServiceError> = cache
+            .get_or_load("tanahpediaGraph:Turtle".to_string(), || async {
+                Ok("@prefix tp: <x> .".to_string())
+            })
+            .await;
+        let app = test::init_service(App::new().configure(ActixApp::build_app_config(
+            &db,
+            &Storage::for_testing(),
+            &cache,
+            &PersistedQueries::default(),
+            Arc::new(AtomicBool::new(false)),
+        )))
+        .await;
+
+        let first = test::call_service(
+            &app,
+            test::TestRequest::get()
+                .uri("/tanahpedia/graph?format=turtle")
+                .to_request(),
+        )
+        .await;
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = first.headers().get(header::ETAG).unwrap().clone();
+        let body = to_bytes(first.into_body()).await.unwrap();
+        assert_eq!(body.as_ref(), b"@prefix tp: <x> .");
+
+        let second = test::call_service(
+            &app,
+            test::TestRequest::get()
+                .uri("/tanahpedia/graph?format=ttl")
+                .insert_header((header::IF_NONE_MATCH, etag))
+                .to_request(),
+        )
+        .await;
+        assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+
+        let uncached = test::call_service(
+            &app,
+            test::TestRequest::get()
+                .uri("/tanahpedia/graph")
+                .to_request(),
+        )
+        .await;
+        assert_eq!(uncached.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
 }
@@ -1,4 +1,5 @@
 pub mod app;
+pub mod rest_api;
 pub mod schema_builder;
 pub mod telemetry;
 pub use app::ActixApp;
@@ -0,0 +1,326 @@
+use actix_web::{HttpRequest, HttpResponse, guard, web};
+use utoipa::OpenApi;
+
+use crate::common::error_handling::{ServiceError, ServiceErrorBody};
+use crate::common::http_cache::{STATIC_CACHE_CONTROL, json_response};
+use crate::dtos::{
+    article::Article, author::Author, perek::Perek, sefer::Sefer, tanahpedia_entry::TanahpediaEntry,
+};
+use crate::providers::{Database, StaticCache};
+use crate::services::{
+    articles_service, authors_service, perakim_service, sefarim_service, tanahpedia_entries_service,
+};
+
+/// The read-only REST facade over the GraphQL services, for clients that
+/// prefer plain cacheable GETs. Served at `/v1/openapi.json`. Every response
+/// carries an ETag and honours `If-None-Match`.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Bible on Site API",
+        description = "Read-only REST endpoints. Everything here is also available through GraphQL at `POST /`."
+    ),
+    paths(perek, sefarim, article, author, tanahpedia_entry),
+    components(schemas(Perek, Sefer, Article, Author, TanahpediaEntry, ServiceErrorBody))
+)]
+pub struct RestApiDoc;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/v1/openapi.json")
+            .guard(guard::Get())
+            .to(openapi_json),
+    )
+    .service(
+        web::resource("/v1/perakim/{id}")
+            .guard(guard::Get())
+            .to(perek),
+    )
+    .service(web::resource("/v1/sefarim").guard(guard::Get()).to(sefarim))
+    .service(
+        web::resource("/v1/articles/{id}")
+            .guard(guard::Get())
+            .to(article),
+    )
+    .service(
+        web::resource("/v1/authors/{id}")
+            .guard(guard::Get())
+            .to(author),
+    )
+    .service(
+        web::resource("/v1/tanahpedia/entries/{uniqueName}")
+            .guard(guard::Get())
+            .to(tanahpedia_entry),
+    );
+}
+
+/// GET /v1/openapi.json — the OpenAPI 3 document for the `/v1` endpoints.
+async fn openapi_json() -> HttpResponse {
+    HttpResponse::Ok().json(RestApiDoc::openapi())
+}
+
+/// A perek by its 929 perek id (1-929).
+#[utoipa::path(
+    get,
+    path = "/v1/perakim/{id}",
+    tag = "perakim",
+    params(("id" = i32, Path, description = "Perek id (1-929)")),
+    responses(
+        (status = 200, body = Perek),
+        (status = 404, body = ServiceErrorBody),
+    )
+)]
+async fn perek(
+    req: HttpRequest,
+    db: web::Data<Database>,
+    cache: web::Data<StaticCache>,
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ServiceError> {
+    let perek_id = id.into_inner();
+    let perek: Perek = cache
+        .get_or_load(format!("perekByPerekId:{perek_id}"), || async {
+            Ok::<_, ServiceError>(
+                perakim_service::find_one_by_perek_id(&db, perek_id)
+                    .await?
+                    .into(),
+            )
+        })
+        .await?;
+    Ok(json_response(&req, &perek, Some(STATIC_CACHE_CONTROL)))
+}
+
+/// Every sefer.
+#[utoipa::path(
+    get,
+    path = "/v1/sefarim",
+    tag = "sefarim",
+    responses((status = 200, body = Vec<Sefer>))
+)]
+async fn sefarim(
+    req: HttpRequest,
+    db: web::Data<Database>,
+    cache: web::Data<StaticCache>,
+) -> Result<HttpResponse, ServiceError> {
+    let sefarim: Vec<Sefer> = cache
+        .get_or_load("sefarim".to_string(), || async {
+            Ok::<_, ServiceError>(
+                sefarim_service::find_all(&db)
+                    .await?
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+            )
+        })
+        .await?;
+    Ok(json_response(&req, &sefarim, Some(STATIC_CACHE_CONTROL)))
+}
+
+/// An article by id, with its content.
+#[utoipa::path(
+    get,
+    path = "/v1/articles/{id}",
+    tag = "articles",
+    params(("id" = i32, Path, description = "Article id")),
+    responses(
+        (status = 200, body = Article),
+        (status = 404, body = ServiceErrorBody),
+    )
+)]
+async fn article(
+    req: HttpRequest,
+    db: web::Data<Database>,
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ServiceError> {
+    let article: Article = articles_service::find_one_by_id(&db, id.into_inner())
+        .await?
+        .into();
+    Ok(json_response(&req, &article, None))
+}
+
+/// An author by id, with the number of articles they wrote.
+#[utoipa::path(
+    get,
+    path = "/v1/authors/{id}",
+    tag = "authors",
+    params(("id" = i32, Path, description = "Author id")),
+    responses(
+        (status = 200, body = Author),
+        (status = 404, body = ServiceErrorBody),
+    )
+)]
+async fn author(
+    req: HttpRequest,
+    db: web::Data<Database>,
+    id: web::Path<i32>,
+) -> Result<HttpResponse, ServiceError> {
+    let model = authors_service::find_one_by_id(&db, id.into_inner()).await?;
+    let count = articles_service::count_by_author_id(&db, model.id).await?;
+    Ok(json_response(
+        &req,
+        &Author::with_articles_count(model, count),
+        None,
+    ))
+}
+
+/// The Tanahpedia entry at `/pedia/<uniqueName>`.
+#[utoipa::path(
+    get,
+    path = "/v1/tanahpedia/entries/{uniqueName}",
+    tag = "tanahpedia",
+    params(("uniqueName" = String, Path, description = "Entry unique name")),
+    responses(
+        (status = 200, body = TanahpediaEntry),
+        (status = 404, body = ServiceErrorBody),
+    )
+)]
+async fn tanahpedia_entry(
+    req: HttpRequest,
+    db: web::Data<Database>,
+    unique_name: web::Path<String>,
+) -> Result<HttpResponse, ServiceError> {
+    match tanahpedia_entries_service::get_entry(&db, unique_name.into_inner()).await? {
+        Some(entry) => Ok(json_response(&req, &entry, None)),
+        None => Err(ServiceError::not_found(
+            "Tanahpedia entry not found",
+            None::<&str>,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{
+        App,
+        http::{StatusCode, header},
+        test,
+    };
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    fn sefer_model(id: i32, name: &str) -> entities::sefer::Model {
+        entities::sefer::Model {
+            id,
+            name: Some(name.to_string()),
+            tanach_us_name: None,
+            perek_id_from: 1,
+            perek_id_to: 50,
+        }
+    }
+
+    #[actix_web::test]
+    async fn openapi_document_lists_every_v1_path() {
+        let document = serde_json::to_value(RestApiDoc::openapi()).unwrap();
+
+        assert!(
+            document["openapi"]
+                .as_str()
+                .is_some_and(|version| version.starts_with("3."))
+        );
+        for path in [
+            "/v1/perakim/{id}",
+            "/v1/sefarim",
+            "/v1/articles/{id}",
+            "/v1/authors/{id}",
+            "/v1/tanahpedia/entries/{uniqueName}",
+        ] {
+            assert!(document["paths"][path]["get"].is_object(), "{path}");
+        }
+        assert!(
+            document["components"]["schemas"]["Perek"]["properties"]["compiledSource"].is_object()
+        );
+    }
+
+    #[actix_web::test]
+    async fn sefarim_returns_camel_case_json() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![sefer_model(1, "בראשית")]])
+                .into_connection(),
+        );
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(db))
+                .app_data(web::Data::new(StaticCache::default()))
+                .configure(configure),
+        )
+        .await;
+
+        let body: serde_json::Value = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::get().uri("/v1/sefarim").to_request(),
+        )
+        .await;
+
+        assert_eq!(
+            body,
+            serde_json::json!([{ "id": 1, "name": "בראשית", "tanachUsName": null }])
+        );
+    }
+
+    #[actix_web::test]
+    async fn sefarim_are_cached_and_revalidated_by_etag() {
+        // A single mock result: the second request must come from the cache.
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![sefer_model(1, "בראשית")]])
+                .into_connection(),
+        );
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(db))
+                .app_data(web::Data::new(StaticCache::default()))
+                .configure(configure),
+        )
+        .await;
+
+        let first = test::call_service(
+            &app,
+            test::TestRequest::get().uri("/v1/sefarim").to_request(),
+        )
+        .await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(
+            first.headers().get(header::CACHE_CONTROL).unwrap(),
+            STATIC_CACHE_CONTROL
+        );
+        let etag = first.headers().get(header::ETAG).unwrap().clone();
+
+        let second = test::call_service(
+            &app,
+            test::TestRequest::get()
+                .uri("/v1/sefarim")
+                .insert_header((header::IF_NONE_MATCH, etag))
+                .to_request(),
+        )
+        .await;
+        assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+    }
+
+    #[actix_web::test]
+    async fn missing_entry_returns_not_found_body() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([Vec::<entities::tanahpedia::entry::Model>::new()])
+                .into_connection(),
+        );
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(db))
+                .app_data(web::Data::new(StaticCache::default()))
+                .configure(configure),
+        )
+        .await;
+
+        let response = test::call_service(
+            &app,
+            test::TestRequest::get()
+                .uri("/v1/tanahpedia/entries/missing")
+                .to_request(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "NOT_FOUND");
+    }
+}
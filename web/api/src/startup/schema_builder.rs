@@ -1,19 +1,42 @@
-use actix_web::{HttpRequest, HttpResponse, Result, web::Data};
+use actix_web::{
+    HttpRequest, HttpResponse, Responder, Result,
+    http::header::{self, HeaderValue},
+    web::Data,
+};
 use async_graphql::{
     EmptySubscription, MergedObject, Schema,
+    dataloader::DataLoader,
     http::{GraphQLPlaygroundConfig, playground_source},
 };
 use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse};
 
 use crate::common::auth::ApiAuth;
-use crate::providers::Database;
+use crate::common::http_cache;
+use crate::providers::static_cache::{ReadOnlyRequest, StaticCacheInvalidation};
+use crate::providers::{Database, PersistedQueries, StaticCache, Storage};
 use crate::resolvers::articles_resolver;
 use crate::resolvers::authors_resolver;
 use crate::resolvers::perakim_resolver;
 use crate::resolvers::sefarim_resolver;
 use crate::resolvers::starter_resolver;
+use crate::resolvers::tanahpedia_animals_resolver;
+use crate::resolvers::tanahpedia_auto_linker_resolver;
+use crate::resolvers::tanahpedia_category_homepages_resolver;
+use crate::resolvers::tanahpedia_chazal_resolver;
+use crate::resolvers::tanahpedia_creation_days_resolver;
+use crate::resolvers::tanahpedia_entries_resolver;
 use crate::resolvers::tanahpedia_family_resolver;
+use crate::resolvers::tanahpedia_import_resolver;
+use crate::resolvers::tanahpedia_models3d_resolver;
+use crate::resolvers::tanahpedia_namings_resolver;
+use crate::resolvers::tanahpedia_nations_resolver;
+use crate::resolvers::tanahpedia_opinions_resolver;
+use crate::resolvers::tanahpedia_places_resolver;
+use crate::resolvers::tanahpedia_quality_resolver;
 use crate::resolvers::tanahpedia_revisions_resolver;
+use crate::resolvers::tanahpedia_sayings_resolver;
+use crate::resolvers::tanahpedia_wars_resolver;
+use crate::services::tanahpedia_sources_service::TanahSourceLoader;
 
 #[derive(MergedObject, Default)]
 pub struct QueryRoot(
@@ -22,23 +45,72 @@ pub struct QueryRoot(
     perakim_resolver::PerakimQuery,
     sefarim_resolver::SefarimQuery,
     starter_resolver::StarterQuery,
+    tanahpedia_animals_resolver::TanahpediaAnimalsQuery,
+    tanahpedia_category_homepages_resolver::TanahpediaCategoryHomepagesQuery,
+    tanahpedia_chazal_resolver::TanahpediaChazalQuery,
+    tanahpedia_creation_days_resolver::TanahpediaCreationDaysQuery,
+    tanahpedia_entries_resolver::TanahpediaEntriesQuery,
     tanahpedia_family_resolver::TanahpediaFamilyQuery,
+    tanahpedia_namings_resolver::TanahpediaNamingsQuery,
+    tanahpedia_nations_resolver::TanahpediaNationsQuery,
+    tanahpedia_opinions_resolver::TanahpediaOpinionsQuery,
+    tanahpedia_places_resolver::TanahpediaPlacesQuery,
+    tanahpedia_quality_resolver::TanahpediaQualityQuery,
     tanahpedia_revisions_resolver::TanahpediaRevisionsQuery,
+    tanahpedia_sayings_resolver::TanahpediaSayingsQuery,
+    tanahpedia_wars_resolver::TanahpediaWarsQuery,
 );
 
 #[derive(MergedObject, Default)]
 pub struct MutationRoot(
+    tanahpedia_animals_resolver::TanahpediaAnimalsMutation,
+    tanahpedia_auto_linker_resolver::TanahpediaAutoLinkerMutation,
+    tanahpedia_category_homepages_resolver::TanahpediaCategoryHomepagesMutation,
+    tanahpedia_chazal_resolver::TanahpediaChazalMutation,
+    tanahpedia_creation_days_resolver::TanahpediaCreationDaysMutation,
+    tanahpedia_entries_resolver::TanahpediaEntriesMutation,
     tanahpedia_family_resolver::TanahpediaFamilyMutation,
+    tanahpedia_import_resolver::TanahpediaImportMutation,
+    tanahpedia_models3d_resolver::TanahpediaModels3dMutation,
+    tanahpedia_namings_resolver::TanahpediaNamingsMutation,
+    tanahpedia_nations_resolver::TanahpediaNationsMutation,
+    tanahpedia_opinions_resolver::TanahpediaOpinionsMutation,
     tanahpedia_revisions_resolver::TanahpediaRevisionsMutation,
+    tanahpedia_sayings_resolver::TanahpediaSayingsMutation,
+    tanahpedia_wars_resolver::TanahpediaWarsMutation,
 );
 
-pub fn build_schema(database: &Database) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+/// Deepest selection accepted. The playground's introspection query nests 13
+/// levels, the site's own queries fewer than 10.
+pub const MAX_QUERY_DEPTH: usize = 16;
+/// Each field costs 1 unless it carries a `complexity` hint: a list costs 5
+/// times its selection, and full-table reads and graph walks (`perakim`,
+/// `starter`, reports, GeoJSON, nation lineage, imports) cost 100 plus their
+/// selection.
+pub const MAX_QUERY_COMPLEXITY: usize = 500;
+
+pub fn build_schema(
+    database: &Database,
+    storage: &Storage,
+    cache: &StaticCache,
+    persisted_queries: &PersistedQueries,
+) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
     Schema::build(
         QueryRoot::default(),
         MutationRoot::default(),
         EmptySubscription,
     )
     .data(database.to_owned())
+    .data(DataLoader::new(
+        TanahSourceLoader::new(database.to_owned()),
+        tokio::spawn,
+    ))
+    .data(storage.to_owned())
+    .data(cache.to_owned())
+    .extension(StaticCacheInvalidation::new(cache.to_owned()))
+    .extension(persisted_queries.to_owned())
+    .limit_depth(MAX_QUERY_DEPTH)
+    .limit_complexity(MAX_QUERY_COMPLEXITY)
     .finish()
 }
 
@@ -56,9 +128,42 @@ pub async fn graphql_request(
     schema: Data<Schema<QueryRoot, MutationRoot, EmptySubscription>>,
     req: HttpRequest,
     gql_req: GraphQLRequest,
-) -> GraphQLResponse {
+) -> HttpResponse {
     let auth = ApiAuth::new(extract_bearer(&req));
-    schema.execute(gql_req.into_inner().data(auth)).await.into()
+    let mut request = gql_req.into_inner().data(auth);
+    if req.method() == actix_web::http::Method::GET {
+        request = request.data(ReadOnlyRequest);
+    }
+    let response = schema.execute(request).await;
+
+    // Only successful, cacheable responses get an ETag; mutations and queries
+    // with an unhinted root field are marked no-cache by
+    // `StaticCacheInvalidation`.
+    let etag = if response.is_ok() && response.cache_control.max_age != -1 {
+        serde_json::to_vec(&response.data)
+            .ok()
+            .map(|body| http_cache::etag(&body))
+    } else {
+        None
+    };
+    if let Some(etag) = &etag
+        && http_cache::is_not_modified(&req, etag)
+    {
+        let mut not_modified = HttpResponse::NotModified();
+        not_modified.insert_header((header::ETAG, etag.as_str()));
+        if let Some(cache_control) = response.cache_control.value() {
+            not_modified.insert_header((header::CACHE_CONTROL, cache_control));
+        }
+        return not_modified.finish();
+    }
+
+    let mut http_response = GraphQLResponse::from(response).respond_to(&req);
+    if let Some(etag) = etag
+        && let Ok(value) = HeaderValue::from_str(&etag)
+    {
+        http_response.headers_mut().insert(header::ETAG, value);
+    }
+    http_response
 }
 
 pub async fn graphql_playground() -> Result<HttpResponse> {
@@ -72,6 +177,7 @@ pub async fn graphql_playground() -> Result<HttpResponse> {
 mod tests {
     use super::*;
     use async_graphql::Request;
+    use entities::tanahpedia::sea_orm_active_enums::{EntityType, RevisionStatus, Sex};
     use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
     use std::collections::BTreeMap;
 
@@ -128,7 +234,7 @@ mod tests {
             proposed_content: Some("<p>Body</p>".to_string()),
             source: "gpt-test".to_string(),
             notes: Some("review note".to_string()),
-            status: "PENDING".to_string(),
+            status: RevisionStatus::Pending,
             created_at: now,
             updated_at: now,
         }
@@ -155,7 +261,12 @@ mod tests {
                 )
                 .into_connection(),
         );
-        let schema = build_schema(&db);
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
 
         let by_id = schema
             .execute(Request::new(
@@ -190,7 +301,12 @@ mod tests {
                 ])
                 .into_connection(),
         );
-        let schema = build_schema(&db);
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
 
         let one = schema
             .execute(Request::new(
@@ -239,7 +355,12 @@ mod tests {
                 ])
                 .into_connection(),
         );
-        let schema = build_schema(&db);
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
 
         let response = schema
             .execute(Request::new(
@@ -274,11 +395,16 @@ mod tests {
                 >([vec![revision_model("rev-1", Some("entry-1"))]])
                 .into_connection(),
         );
-        let schema = build_schema(&db);
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
 
         let response = schema
             .execute(Request::new(
-                r#"{ tanahpediaEntryRevisions(status: "PENDING", entryId: "entry-1") { id entryId proposedTitle source notes status createdAt } }"#,
+                r#"{ tanahpediaEntryRevisions(status: PENDING, entryId: "entry-1") { id entryId proposedTitle source notes status createdAt } }"#,
             ))
             .await;
 
@@ -296,7 +422,12 @@ mod tests {
     async fn schema_executes_all_tanahpedia_family_resolvers() {
         let db =
             Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
-        let schema = build_schema(&db);
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
 
         let response = schema
             .execute(
@@ -332,7 +463,12 @@ mod tests {
     async fn schema_rejects_revision_mutations_without_api_auth() {
         let db =
             Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
-        let schema = build_schema(&db);
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
 
         let submit = schema
             .execute(
@@ -357,17 +493,51 @@ mod tests {
     async fn schema_rejects_family_mutations_without_api_auth() {
         let db =
             Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
-        let schema = build_schema(&db);
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
         let operations = [
             r#"mutation { putTanahpediaEntryEntityLink(input: { id: "ee", entryUniqueName: "entry", entityId: "e" }) { id } }"#,
             r#"mutation { deleteTanahpediaEntryEntityLink(id: "ee") { id } }"#,
-            r#"mutation { putTanahpediaPersonNode(input: { entityId: "e", personId: "p", displayName: "Name", sexId: "s", sex: "MALE" }) { personId } }"#,
+            r#"mutation { putTanahpediaPersonNode(input: { entityId: "e", personId: "p", displayName: "Name", sexId: "s", sex: MALE }) { personId } }"#,
             r#"mutation { deleteTanahpediaOrphanPersonNode(input: { entityId: "e", personId: "p", sexId: "s" }) { personId } }"#,
-            r#"mutation { deleteTanahpediaOrphanEntity(input: { entityId: "e", entityType: "PERSON", displayName: "Name" }) { entityId } }"#,
+            r#"mutation { deleteTanahpediaOrphanEntity(input: { entityId: "e", entityType: PERSON, displayName: "Name" }) { entityId } }"#,
             r#"mutation { putTanahpediaParentChildLink(input: { id: "pc", parentPersonId: "p", childPersonId: "c", relationshipType: "BIOLOGICAL", parentRole: "FATHER" }) { id } }"#,
             r#"mutation { deleteTanahpediaParentChildLink(id: "pc") { id } }"#,
             r#"mutation { putTanahpediaPersonUnion(input: { id: "u", person1Id: "p1", person2Id: "p2", unionType: "MARRIAGE" }) { id } }"#,
             r#"mutation { deleteTanahpediaPersonUnion(id: "u") { id } }"#,
+            r#"mutation { putTanahpediaSayingSpeaker(input: { id: "sp", sayingId: "s", partyType: PERSON, partyId: "p" }) { id } }"#,
+            r#"mutation { deleteTanahpediaSayingSpeaker(id: "sp") { id } }"#,
+            r#"mutation { putTanahpediaSayingAudience(input: { id: "sa", sayingId: "s", partyType: PERSON, partyId: "p" }) { id } }"#,
+            r#"mutation { deleteTanahpediaSayingAudience(id: "sa") { id } }"#,
+            r#"mutation { putTanahpediaWarSide(input: { id: "ws", warId: "w", sideNumber: 1 }) { id } }"#,
+            r#"mutation { deleteTanahpediaWarSide(id: "ws") { id } }"#,
+            r#"mutation { putTanahpediaWarParticipant(input: { id: "wp", warSideId: "ws", participantType: PERSON, participantId: "p" }) { id } }"#,
+            r#"mutation { deleteTanahpediaWarParticipant(id: "wp") { id } }"#,
+            r#"mutation { putTanahpediaAnimalKind(input: { id: "ak", animalId: "a", kind: BEHEMA }) { id } }"#,
+            r#"mutation { deleteTanahpediaAnimalKind(id: "ak") { id } }"#,
+            r#"mutation { putTanahpediaAnimalPurity(input: { id: "ap", animalId: "a", purity: TAHOR }) { id } }"#,
+            r#"mutation { deleteTanahpediaAnimalPurity(id: "ap") { id } }"#,
+            r#"mutation { putTanahpediaOpinion(input: { altGroupId: "ag", label: "Rashi" }) { altGroupId } }"#,
+            r#"mutation { deleteTanahpediaOpinion(altGroupId: "ag") { altGroupId } }"#,
+            r#"mutation { putTanahpediaCategoryHomepage(input: { entityType: PLACE, layoutType: LIST }) { id } }"#,
+            r#"mutation { rebuildTanahpediaEntryLinks { entries links } }"#,
+            r#"mutation { proposeTanahpediaAutoLinks(entryId: "e") { scanned } }"#,
+            r#"mutation { importTanahpediaContent(input: {}, dryRun: true) { created } }"#,
+            r#"mutation { putTanahpediaNameGiver(input: { id: "g", personNameId: "n", giverType: GOD, giverId: "god" }) { id } }"#,
+            r#"mutation { deleteTanahpediaNameGiver(id: "g") { id } }"#,
+            r#"mutation { putTanahpediaNationSource(input: { id: "l", nationId: "n", sourceNationId: "s" }) { id } }"#,
+            r#"mutation { deleteTanahpediaNationSource(id: "l") { id } }"#,
+            r#"mutation { putTanahpediaNationTerritory(input: { id: "t", nationId: "n", placeId: "p" }) { id } }"#,
+            r#"mutation { deleteTanahpediaNationTerritory(id: "t") { id } }"#,
+            r#"mutation { putTanahpediaPersonNation(input: { id: "l", personId: "p", nationId: "n", role: "FOUNDER" }) { id } }"#,
+            r#"mutation { deleteTanahpediaPersonNation(id: "l") { id } }"#,
+            r#"mutation { putTanahpediaCreationDay(input: { id: "d", itemType: "PLANT", itemId: "p", creationDay: 3 }) { id } }"#,
+            r#"mutation { deleteTanahpediaCreationDay(id: "d") { id } }"#,
+            r#"mutation { normalizeTanahpediaNonTanahSources { updated } }"#,
         ];
 
         for operation in operations {
@@ -379,6 +549,54 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn schema_resolves_entity_models3d_with_presigned_urls() {
+        use entities::tanahpedia::{entity, three_d_model};
+
+        let now = chrono::Utc::now().naive_utc();
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![entity::Model {
+                    id: "entity-1".to_string(),
+                    entity_type: EntityType::Object,
+                    name: "מזבח".to_string(),
+                    created_at: now,
+                    updated_at: now,
+                }]])
+                .append_query_results([vec![three_d_model::Model {
+                    id: "model-1".to_string(),
+                    entity_id: "entity-1".to_string(),
+                    blob_key: "tanahpedia/3d-models/entity-1/model-1.glb".to_string(),
+                    format: "GLB".to_string(),
+                    label: None,
+                    alt_group_id: None,
+                }]])
+                .into_connection(),
+        );
+
+        let response = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        )
+            .execute(Request::new(
+                r#"{ tanahpediaEntity(entityId: "entity-1") { entityType models3d { id format url } } }"#,
+            ))
+            .await;
+
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let json = response.data.into_json().unwrap();
+        assert_eq!(json["tanahpediaEntity"]["entityType"], "OBJECT");
+        assert_eq!(json["tanahpediaEntity"]["models3d"][0]["format"], "GLB");
+        assert!(
+            json["tanahpediaEntity"]["models3d"][0]["url"]
+                .as_str()
+                .unwrap()
+                .contains("/tanahpedia/3d-models/entity-1/model-1.glb?")
+        );
+    }
+
     #[tokio::test]
     async fn schema_executes_authorized_entry_entity_link_mutation() {
         use entities::tanahpedia::{entity, entry, entry_entity};
@@ -397,7 +615,7 @@ mod tests {
                 .append_query_results::<entity::Model, Vec<entity::Model>, _>([vec![
                     entity::Model {
                         id: "entity-1".to_string(),
-                        entity_type: "PERSON".to_string(),
+                        entity_type: EntityType::Person,
                         name: "שמשון".to_string(),
                         created_at: now,
                         updated_at: now,
@@ -411,7 +629,12 @@ mod tests {
                 }])
                 .into_connection(),
         );
-        let response = build_schema(&db)
+        let response = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        )
             .execute(
                 Request::new(
                     r#"mutation { putTanahpediaEntryEntityLink(input: { id: "entry-entity-1", entryUniqueName: "שמשון", entityId: "entity-1" }) { id entryId entityId } }"#,
@@ -449,11 +672,16 @@ mod tests {
                 ])
                 .into_connection(),
         );
-        let schema = build_schema(&db);
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
         let response = schema
             .execute(
                 Request::new(
-                    r#"mutation { putTanahpediaPersonNode(input: { entityId: "entity-1", personId: "person-1", displayName: "שמשון", sexId: "sex-1", sex: "MALE" }) { entityId personId sexId } }"#,
+                    r#"mutation { putTanahpediaPersonNode(input: { entityId: "entity-1", personId: "person-1", displayName: "שמשון", sexId: "sex-1", sex: MALE }) { entityId personId sexId } }"#,
                 )
                 .data(crate::common::auth::ApiAuth::with_revision_api_key(
                     Some("family-test-key".to_string()),
@@ -486,7 +714,7 @@ mod tests {
                 .append_query_results::<entity::Model, Vec<entity::Model>, _>([vec![
                     entity::Model {
                         id: "entity-duplicate".to_string(),
-                        entity_type: "PERSON".to_string(),
+                        entity_type: EntityType::Person,
                         name: "שמשון".to_string(),
                         created_at: now,
                         updated_at: now,
@@ -502,7 +730,7 @@ mod tests {
                     person_sex::Model {
                         id: "sex-duplicate".to_string(),
                         person_id: "person-duplicate".to_string(),
-                        sex: "MALE".to_string(),
+                        sex: Sex::Male,
                         alt_group_id: None,
                     },
                 ]])
@@ -515,7 +743,7 @@ mod tests {
                 .append_query_results::<entity::Model, Vec<entity::Model>, _>([vec![
                     entity::Model {
                         id: "entity-duplicate".to_string(),
-                        entity_type: "PERSON".to_string(),
+                        entity_type: EntityType::Person,
                         name: "שמשון".to_string(),
                         created_at: now,
                         updated_at: now,
@@ -535,7 +763,12 @@ mod tests {
                 ])
                 .into_connection(),
         );
-        let schema = build_schema(&db);
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
         let auth = || {
             crate::common::auth::ApiAuth::with_revision_api_key(
                 Some("family-test-key".to_string()),
@@ -570,7 +803,7 @@ mod tests {
         let delete_entity = schema
             .execute(
                 Request::new(
-                    r#"mutation { deleteTanahpediaOrphanEntity(input: { entityId: "entity-duplicate", entityType: "PERSON", displayName: "שמשון" }) { entityId entityType displayName } }"#,
+                    r#"mutation { deleteTanahpediaOrphanEntity(input: { entityId: "entity-duplicate", entityType: PERSON, displayName: "שמשון" }) { entityId entityType displayName } }"#,
                 )
                 .data(auth()),
             )
@@ -632,7 +865,12 @@ mod tests {
                 .append_exec_results([exec_result.clone(), exec_result.clone(), exec_result])
                 .into_connection(),
         );
-        let schema = build_schema(&db);
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
         let auth = || {
             crate::common::auth::ApiAuth::with_revision_api_key(
                 Some("family-test-key".to_string()),
@@ -651,4 +889,242 @@ mod tests {
             assert!(response.errors.is_empty(), "{:?}", response.errors);
         }
     }
+
+    fn sefer_model(id: i32, name: &str) -> entities::sefer::Model {
+        entities::sefer::Model {
+            id,
+            name: Some(name.to_string()),
+            tanach_us_name: None,
+            perek_id_from: 1,
+            perek_id_to: 50,
+        }
+    }
+
+    #[actix_web::test]
+    async fn graphql_request_sets_etag_and_answers_not_modified_from_cache() {
+        use actix_web::{App, http::StatusCode, test, web};
+
+        // A single mock result: the second request must come from the cache.
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![sefer_model(1, "בראשית")]])
+                .into_connection(),
+        );
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(schema))
+                .route("/", web::post().to(graphql_request))
+                .route("/", web::get().to(graphql_request)),
+        )
+        .await;
+        let request = || {
+            test::TestRequest::post()
+                .uri("/")
+                .insert_header((header::CONTENT_TYPE, "application/json"))
+                .set_payload(r#"{"query":"{ sefarim { id name } }"}"#)
+        };
+
+        let first = test::call_service(&app, request().to_request()).await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(
+            first.headers().get(header::CACHE_CONTROL).unwrap(),
+            "max-age=3600"
+        );
+        let etag = first.headers().get(header::ETAG).unwrap().clone();
+
+        let second = test::call_service(
+            &app,
+            request()
+                .insert_header((header::IF_NONE_MATCH, etag.clone()))
+                .to_request(),
+        )
+        .await;
+        assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(second.headers().get(header::ETAG).unwrap(), etag);
+
+        // The same query over GET shares the ETag, so CDNs can revalidate it.
+        let over_get = test::call_service(
+            &app,
+            test::TestRequest::get()
+                .uri("/?query=%7B%20sefarim%20%7B%20id%20name%20%7D%20%7D")
+                .insert_header((header::IF_NONE_MATCH, etag.clone()))
+                .to_request(),
+        )
+        .await;
+        assert_eq!(over_get.status(), StatusCode::NOT_MODIFIED);
+
+        let mutation_over_get: serde_json::Value = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::get()
+                .uri("/?query=mutation%20%7B%20rebuildTanahpediaEntryLinks%20%7B%20entries%20%7D%20%7D")
+                .to_request(),
+        )
+        .await;
+        assert_eq!(
+            mutation_over_get["errors"][0]["message"],
+            "Mutations are not allowed over GET"
+        );
+    }
+
+    #[tokio::test]
+    async fn queries_with_an_unhinted_root_field_are_not_cacheable() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![sefer_model(1, "בראשית")]])
+                .into_connection(),
+        );
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
+
+        let hinted = schema
+            .execute(
+                "query { ...Sefarim __typename } fragment Sefarim on QueryRoot { sefarim { id } }",
+            )
+            .await;
+        assert!(hinted.errors.is_empty(), "{:?}", hinted.errors);
+        assert_eq!(
+            hinted.cache_control.value().as_deref(),
+            Some("max-age=3600")
+        );
+
+        let mixed = schema
+            .execute("{ sefarim { id } __schema { queryType { name } } }")
+            .await;
+        assert!(mixed.errors.is_empty(), "{:?}", mixed.errors);
+        assert_eq!(
+            mixed.cache_control.value().as_deref(),
+            Some("no-cache, private")
+        );
+    }
+
+    #[tokio::test]
+    async fn mutations_are_not_cacheable_and_clear_the_static_cache() {
+        let db = Database::from_connection(
+            MockDatabase::new(DatabaseBackend::MySql)
+                .append_query_results([vec![sefer_model(1, "בראשית")]])
+                .append_query_results([Vec::<entities::tanahpedia::non_tanah_source::Model>::new()])
+                .append_query_results([vec![sefer_model(1, "Genesis")]])
+                .into_connection(),
+        );
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
+        let sefer_name = |response: async_graphql::Response| {
+            assert!(response.errors.is_empty(), "{:?}", response.errors);
+            response.data.into_json().unwrap()["sefarim"][0]["name"].clone()
+        };
+
+        let before = schema.execute("{ sefarim { name } }").await;
+        assert_eq!(before.cache_control.max_age, 3600);
+        assert_eq!(sefer_name(before), "בראשית");
+
+        let mutation = schema
+            .execute(
+                Request::new("mutation { normalizeTanahpediaNonTanahSources { updated } }").data(
+                    crate::common::auth::ApiAuth::with_revision_api_key(
+                        Some("cache-test-key".to_string()),
+                        Some("cache-test-key".to_string()),
+                    ),
+                ),
+            )
+            .await;
+        assert!(mutation.errors.is_empty(), "{:?}", mutation.errors);
+        assert_eq!(
+            mutation.cache_control.value().as_deref(),
+            Some("no-cache, private")
+        );
+
+        let after = schema.execute("{ sefarim { name } }").await;
+        assert_eq!(sefer_name(after), "Genesis");
+    }
+
+    #[tokio::test]
+    async fn schema_rejects_too_deep_and_too_complex_queries() {
+        let db =
+            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
+
+        let deep = format!(
+            "{{ __schema {{ types {{ {}name{} }} }} }}",
+            "ofType { ".repeat(MAX_QUERY_DEPTH),
+            " }".repeat(MAX_QUERY_DEPTH)
+        );
+        let response = schema.execute(deep).await;
+        assert!(
+            response
+                .errors
+                .iter()
+                .any(|error| error.message.contains("nested too deep")),
+            "{:?}",
+            response.errors
+        );
+
+        let wide = (0..=MAX_QUERY_COMPLEXITY / 100)
+            .map(|i| format!("p{i}: perakim {{ id }}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let response = schema.execute(format!("{{ {wide} }}")).await;
+        assert!(
+            response
+                .errors
+                .iter()
+                .any(|error| error.message.contains("too complex")),
+            "{:?}",
+            response.errors
+        );
+    }
+
+    #[tokio::test]
+    async fn schema_prices_lists_by_their_selection_and_lineage_walks_up_front() {
+        let db =
+            Database::from_connection(MockDatabase::new(DatabaseBackend::MySql).into_connection());
+        let schema = build_schema(
+            &db,
+            &Storage::for_testing(),
+            &StaticCache::default(),
+            &PersistedQueries::default(),
+        );
+        let too_complex = |response: async_graphql::Response| {
+            response
+                .errors
+                .iter()
+                .any(|error| error.message.contains("too complex"))
+        };
+
+        let selection = (0..=MAX_QUERY_COMPLEXITY / 5)
+            .map(|i| format!("w{i}: warId"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let wide_list = format!("{{ tanahpediaPersonWars(personId: \"p\") {{ {selection} }} }}");
+        assert!(too_complex(schema.execute(wide_list).await));
+
+        let lineages = (0..MAX_QUERY_COMPLEXITY / 100)
+            .map(|i| format!("n{i}: tanahpediaNation(id: \"n\") {{ nationId }}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert!(too_complex(
+            schema.execute(format!("{{ {lineages} }}")).await
+        ));
+
+        let narrow = "{ tanahpediaNation(id: \"n\") { nationId displayName } }";
+        assert!(!too_complex(schema.execute(narrow).await));
+    }
 }